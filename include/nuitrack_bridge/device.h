@@ -3,10 +3,14 @@
 #include <memory>
 
 #include "nuitrack/types/NuitrackDevice.h"
+#include "nuitrack-rs/src/nuitrack_bridge/types/video_mode.rs.h"
 
 namespace nuitrack_bridge::device {
     using Device = tdv::nuitrack::device::NuitrackDevice;
     using DeviceInfoType = tdv::nuitrack::device::DeviceInfoType;
+    using ActivationStatus = tdv::nuitrack::device::ActivationStatus;
+    using StreamType = tdv::nuitrack::device::StreamType;
+    using VideoMode = nuitrack_bridge::video_mode::VideoMode;
     using SharedPtrDevice = std::shared_ptr<Device>;
 
     std::shared_ptr<Device> unwrapSharedPtrDevice(const SharedPtrDevice& spd);
@@ -22,4 +26,14 @@ namespace nuitrack_bridge::device {
         const std::shared_ptr<Device>& device_shared_ptr // This is const std::shared_ptr<Device>&
     );
 
+    ActivationStatus getActivationStatus(const std::shared_ptr<Device>& device_shared_ptr);
+
+    void activate(const std::shared_ptr<Device>& device_shared_ptr, rust::Str activation_key);
+
+    rust::Vec<VideoMode> getAvailableVideoModes(const std::shared_ptr<Device>& device_shared_ptr, StreamType stream_type);
+
+    VideoMode getSelectedVideoMode(const std::shared_ptr<Device>& device_shared_ptr, StreamType stream_type);
+
+    void setVideoMode(const std::shared_ptr<Device>& device_shared_ptr, StreamType stream_type, VideoMode mode);
+
 }
\ No newline at end of file
@@ -0,0 +1,5 @@
+#pragma once
+
+// This header includes the C++ definition of the VideoMode struct
+// that is generated by CXX from the corresponding Rust definition.
+#include "nuitrack-rs/src/nuitrack_bridge/types/video_mode.rs.h"
\ No newline at end of file
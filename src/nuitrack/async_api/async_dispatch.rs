@@ -17,6 +17,10 @@ where
 {
     use crate::nuitrack::shared_types::error::NuitrackError;
 
+    // Callers of the blocking API may not be inside a runtime.
+    if tokio::runtime::Handle::try_current().is_err() {
+        return blocking::unblock(func).await;
+    }
     match tokio::task::spawn_blocking(func).await {
         Ok(res) => res,
         Err(join_error) => Err(NuitrackError::OperationFailed(format!(
@@ -25,3 +29,48 @@ where
         ))),
     }
 }
+
+/// Drives a future to completion on the current thread, parking between wakeups.
+///
+/// Used where no async runtime can be assumed, such as recorder worker threads.
+pub(crate) fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    match block_on_until(future, None) {
+        Some(output) => output,
+        None => unreachable!("block_on without a deadline cannot time out"),
+    }
+}
+
+/// Like [`block_on`], but gives up and drops the future once `deadline` has passed.
+pub(crate) fn block_on_until<F: std::future::Future>(future: F, deadline: Option<std::time::Instant>) -> Option<F::Output> {
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+    use std::time::Instant;
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let mut future = std::pin::pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return Some(output);
+        }
+        match deadline {
+            None => thread::park(),
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return None;
+                }
+                thread::park_timeout(deadline - now);
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
+use crate::nuitrack::backend::virtual_device::stream_keys;
 use tracing::warn;
 use crate::nuitrack_bridge::modules::color_sensor::ffi as color_sensor_ffi;
 use crate::nuitrack_bridge::types::output_mode::ffi::OutputMode as OutputModeFfi;
+use crate::nuitrack::shared_types::camera_model::CameraModel;
 use crate::nuitrack::shared_types::rgb_frame::RGBFrame;
 
 
@@ -30,7 +32,12 @@ impl AsyncColorSensor {
     /// The `OutputModeFfi` struct contains information like FPS, resolution, and field of view.
     #[instrument(skip(self))]
     pub async fn output_mode(&self) -> NuitrackResult<OutputModeFfi> {
-        let ptr = self.get_ffi_ptr_clone(); // Assuming this method exists from the macro
+        if let Some(device) = self.virtual_device() {
+            return device.descriptor().color_mode.ok_or_else(|| {
+                NuitrackError::Backend("Virtual device has no color output mode".to_string())
+            });
+        }
+        let ptr = self.get_ffi_ptr_clone()?; // Assuming this method exists from the macro
         trace_span!("ffi", function="color_sensor_ffi::output_mode").in_scope(|| {
             run_blocking(move || { // Assuming run_blocking helper
                 color_sensor_ffi::output_mode(&ptr) // Assuming correct FFI function name is output_mode
@@ -39,12 +46,23 @@ impl AsyncColorSensor {
         }).await
     }
 
+    /// A [`CameraModel`] of the color image, e.g. for drawing skeletons onto it.
+    #[instrument(skip(self))]
+    pub async fn camera_model(&self) -> NuitrackResult<CameraModel> {
+        CameraModel::new(&self.output_mode().await?)
+    }
+
     /// Gets the last available color frame synchronously from the sensor.
     ///
     /// This provides direct access to an `RgbFrame` without using the asynchronous stream.
     #[instrument(skip(self))]
     pub async fn latest_color_frame_sync(&self) -> NuitrackResult<RGBFrame> {
-        let ptr = self.get_ffi_ptr_clone();
+        if let Some(device) = self.virtual_device() {
+            return device.latest(stream_keys::RGB_FRAMES).ok_or_else(|| {
+                NuitrackError::OperationFailed("Backend has not produced a color frame yet".to_string())
+            });
+        }
+        let ptr = self.get_ffi_ptr_clone()?;
         let ffi_rgb_frame_ptr = trace_span!("ffi", function="color_sensor_ffi::color_frame").in_scope(|| {
             run_blocking(move || {
                 color_sensor_ffi::color_frame(&ptr) // Assuming correct FFI function name is color_frame
@@ -62,7 +80,10 @@ impl AsyncColorSensor {
     /// Gets the timestamp of the last processed data by the color sensor in microseconds.
     #[instrument(skip(self))]
     pub async fn sensor_timestamp(&self) -> NuitrackResult<u64> {
-        let ptr = self.get_ffi_ptr_clone();
+        if let Some(device) = self.virtual_device() {
+            return Ok(device.last_timestamp());
+        }
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="color_sensor_ffi::timestamp").in_scope(|| {
             run_blocking(move || {
                 color_sensor_ffi::timestamp(&ptr) // Assuming correct FFI function name is timestamp
@@ -74,7 +95,7 @@ impl AsyncColorSensor {
     /// Checks if the Nuitrack color sensor module can update (i.e., if it's running and providing data).
     #[instrument(skip(self))] 
     pub async fn can_update(&self) -> NuitrackResult<bool> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="color_sensor_ffi::can_update").in_scope(|| {
             run_blocking(move || {
                 color_sensor_ffi::can_update(&ptr)
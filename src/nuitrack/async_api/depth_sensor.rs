@@ -1,8 +1,10 @@
+use crate::nuitrack::backend::virtual_device::stream_keys;
+use crate::nuitrack::shared_types::camera_model::CameraModel;
 use crate::nuitrack::shared_types::depth_frame::DepthFrame;
 use crate::nuitrack_bridge::modules::depth_sensor::ffi as depth_sensor_ffi;
 use tracing::warn;
 
-pub use crate::nuitrack_bridge::types::output_mode::ffi::OutputMode;
+pub use crate::nuitrack_bridge::types::output_mode::ffi::{Intrinsics, OutputMode};
 pub use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
 // This macro likely generates the boilerplate for creating the sensor,
 // handling callbacks, and creating a data stream.
@@ -29,7 +31,12 @@ impl AsyncDepthSensor {
     /// Gets the current output mode of the depth sensor.
     #[instrument(skip(self))]
     pub async fn output_mode(&self) -> NuitrackResult<OutputMode> {
-        let ptr = self.get_ffi_ptr_clone();
+        if let Some(device) = self.virtual_device() {
+            return device.descriptor().depth_mode.ok_or_else(|| {
+                NuitrackError::Backend("Virtual device has no depth output mode".to_string())
+            });
+        }
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function = "depth_sensor_ffi::output_mode").in_scope(
             || {
                 run_blocking(move || {
@@ -42,10 +49,26 @@ impl AsyncDepthSensor {
         .await
     }
 
+    /// A [`CameraModel`] for converting coordinates without an SDK call per point.
+    /// Reflects the current output mode and mirror setting; rebuild it after changing either.
+    #[instrument(skip(self))]
+    pub async fn camera_model(&self) -> NuitrackResult<CameraModel> {
+        let model = CameraModel::new(&self.output_mode().await?)?;
+        if self.virtual_device().is_some() {
+            return Ok(model);
+        }
+        Ok(model.with_mirror(self.is_mirror().await?))
+    }
+
     /// Gets the last available depth frame synchronously from the sensor.
     #[instrument(skip(self))]
     pub async fn latest_depth_frame_sync(&self) -> NuitrackResult<DepthFrame> {
-        let ptr = self.get_ffi_ptr_clone();
+        if let Some(device) = self.virtual_device() {
+            return device.latest(stream_keys::DEPTH_FRAMES).ok_or_else(|| {
+                NuitrackError::OperationFailed("Backend has not produced a depth frame yet".to_string())
+            });
+        }
+        let ptr = self.get_ffi_ptr_clone()?;
         let ffi_depth_frame_ptr = trace_span!("ffi", function = "depth_sensor_ffi::depth_frame")
             .in_scope(|| {
                 run_blocking(move || {
@@ -70,7 +93,7 @@ impl AsyncDepthSensor {
     /// Checks if mirror mode is enabled.
     #[instrument(skip(self))]
     pub async fn is_mirror(&self) -> NuitrackResult<bool> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function = "depth_sensor_ffi::is_mirror").in_scope(
             || {
                 run_blocking(move || {
@@ -86,7 +109,7 @@ impl AsyncDepthSensor {
     /// Sets the mirror mode.
     #[instrument(skip(self))]
     pub async fn set_mirror(&self, mirror: bool) -> NuitrackResult<()> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function = "depth_sensor_ffi::set_mirror").in_scope(
             || {
                 run_blocking(move || {
@@ -105,7 +128,7 @@ impl AsyncDepthSensor {
         &self,
         point: Vector3,
     ) -> NuitrackResult<Vector3> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function = "depth_sensor_ffi::convert_proj_to_real_coords")
             .in_scope(move || {
                 run_blocking(move || {
@@ -126,7 +149,7 @@ impl AsyncDepthSensor {
         &self,
         point: Vector3,
     ) -> NuitrackResult<Vector3> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function = "depth_sensor_ffi::convert_real_to_proj_coords")
             .in_scope(move || {
                 run_blocking(move || {
@@ -144,7 +167,10 @@ impl AsyncDepthSensor {
     /// Gets the timestamp of the last processed data by the depth sensor in microseconds.
     #[instrument(skip(self))]
     pub async fn sensor_timestamp(&self) -> NuitrackResult<u64> {
-        let ptr = self.get_ffi_ptr_clone();
+        if let Some(device) = self.virtual_device() {
+            return Ok(device.last_timestamp());
+        }
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function = "depth_sensor_ffi::timestamp").in_scope(
             || {
                 run_blocking(move || {
@@ -160,7 +186,7 @@ impl AsyncDepthSensor {
     /// Checks if the Nuitrack depth sensor module can update.
     #[instrument(skip(self))]
     pub async fn can_update(&self) -> NuitrackResult<bool> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function = "depth_sensor_ffi::can_update").in_scope(
             || {
                 run_blocking(move || {
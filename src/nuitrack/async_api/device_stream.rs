@@ -0,0 +1,120 @@
+//! Device-tagged streams for sessions with more than one sensor.
+//!
+//! Every tracker stream belongs to exactly one device, but nothing in its items says
+//! which. [`DeviceTaggedStream`] attaches the device's index and serial number to each
+//! item, and [`MergedDeviceStream`] interleaves the same kind of stream from several
+//! devices into one.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use pin_project::pin_project;
+use tracing::debug;
+
+/// An item from one device's stream.
+#[derive(Debug, Clone)]
+pub struct DeviceTagged<T> {
+    /// Position of the device in `NuitrackSession::active_devices`.
+    pub device_index: usize,
+    pub serial_number: Arc<str>,
+    pub item: T,
+}
+
+impl<T> DeviceTagged<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> DeviceTagged<U> {
+        DeviceTagged { device_index: self.device_index, serial_number: self.serial_number, item: f(self.item) }
+    }
+}
+
+/// Wraps a stream so each item carries the device it came from.
+/// Created by [`ActiveDeviceContext::tag`](super::session::ActiveDeviceContext::tag).
+#[pin_project]
+pub struct DeviceTaggedStream<S> {
+    #[pin]
+    stream: S,
+    device_index: usize,
+    serial_number: Arc<str>,
+}
+
+impl<S> DeviceTaggedStream<S> {
+    pub(crate) fn new(stream: S, device_index: usize, serial_number: Arc<str>) -> Self {
+        Self { stream, device_index, serial_number }
+    }
+
+    pub fn device_index(&self) -> usize {
+        self.device_index
+    }
+
+    pub fn serial_number(&self) -> &str {
+        &self.serial_number
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: Stream> Stream for DeviceTaggedStream<S> {
+    type Item = DeviceTagged<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.stream.poll_next(cx).map(|item| {
+            item.map(|item| DeviceTagged {
+                device_index: *this.device_index,
+                serial_number: Arc::clone(this.serial_number),
+                item,
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+/// The same kind of stream from several devices, polled round-robin so a busy
+/// device cannot starve the others. Ends once every device's stream has ended.
+/// Created by [`NuitrackSession::merged_streams`](super::session::NuitrackSession::merged_streams).
+pub struct MergedDeviceStream<S> {
+    streams: Vec<Option<Pin<Box<DeviceTaggedStream<S>>>>>,
+    next: usize,
+}
+
+impl<S> MergedDeviceStream<S> {
+    pub fn new(streams: Vec<DeviceTaggedStream<S>>) -> Self {
+        Self { streams: streams.into_iter().map(|s| Some(Box::pin(s))).collect(), next: 0 }
+    }
+
+    /// Number of device streams that have not ended yet.
+    pub fn active_devices(&self) -> usize {
+        self.streams.iter().filter(|s| s.is_some()).count()
+    }
+}
+
+impl<S: Stream> Stream for MergedDeviceStream<S> {
+    type Item = DeviceTagged<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let len = this.streams.len();
+        for offset in 0..len {
+            let index = (this.next + offset) % len;
+            let Some(stream) = this.streams[index].as_mut() else { continue };
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.next = (index + 1) % len;
+                    return Poll::Ready(Some(item));
+                }
+                Poll::Ready(None) => {
+                    debug!(device_index = stream.device_index(), "Device stream ended.");
+                    this.streams[index] = None;
+                }
+                Poll::Pending => {}
+            }
+        }
+        if this.streams.iter().all(Option::is_none) { Poll::Ready(None) } else { Poll::Pending }
+    }
+}
@@ -0,0 +1,67 @@
+//! Stream adapter that smooths skeleton frames as they arrive.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use pin_project::pin_project;
+
+use crate::nuitrack::shared_types::{
+    error::Result as NuitrackResult,
+    skeleton_filter::{SkeletonFilter, SkeletonFilterConfig},
+    skeleton_frame::SkeletonFrame,
+};
+
+/// Applies a [`SkeletonFilter`] to every frame of a skeleton stream, such as
+/// `AsyncSkeletonTracker::skeleton_frames_stream`. Errors pass through unchanged.
+///
+/// ```no_run
+/// # use nuitrack_rs::nuitrack::async_api::skeleton_tracker::AsyncSkeletonTracker;
+/// # use nuitrack_rs::nuitrack::async_api::filtered_skeleton_stream::FilteredSkeletonStream;
+/// # use nuitrack_rs::nuitrack::shared_types::skeleton_filter::{FilterConfig, SkeletonFilterConfig};
+/// # fn example(tracker: &mut AsyncSkeletonTracker) -> nuitrack_rs::nuitrack::shared_types::error::Result<()> {
+/// let config = SkeletonFilterConfig::new(FilterConfig::one_euro());
+/// let smoothed = FilteredSkeletonStream::with_config(tracker.skeleton_frames_stream()?, config);
+/// # Ok(())
+/// # }
+/// ```
+#[pin_project]
+pub struct FilteredSkeletonStream<S> {
+    #[pin]
+    stream: S,
+    filter: SkeletonFilter,
+}
+
+impl<S> FilteredSkeletonStream<S> {
+    pub fn new(stream: S, filter: SkeletonFilter) -> Self {
+        Self { stream, filter }
+    }
+
+    pub fn with_config(stream: S, config: SkeletonFilterConfig) -> Self {
+        Self::new(stream, SkeletonFilter::new(config))
+    }
+
+    /// The filter, e.g. to change its configuration while streaming.
+    pub fn filter_mut(&mut self) -> &mut SkeletonFilter {
+        &mut self.filter
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: Stream<Item = NuitrackResult<SkeletonFrame>>> Stream for FilteredSkeletonStream<S> {
+    type Item = NuitrackResult<SkeletonFrame>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.stream
+            .poll_next(cx)
+            .map(|item| item.map(|frame| frame.and_then(|frame| this.filter.filter_frame(&frame))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
@@ -0,0 +1,353 @@
+//! Aligns the frame streams of one device into timestamped bundles.
+//!
+//! Nuitrack stamps every module updated in the same cycle with the same timestamp,
+//! but each stream is delivered independently and any of them may drop a frame.
+//! [`FrameBundleStream`] buffers a few frames per stream and pairs them up by
+//! timestamp, so consumers see one [`FrameBundle`] per update instead of having to
+//! `join!` streams that can drift apart.
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use pin_project::pin_project;
+use tracing::{debug, instrument, trace, warn};
+
+use crate::nuitrack::backend::BackendFrame;
+use crate::nuitrack::shared_types::{
+    depth_frame::DepthFrame,
+    error::{NuitrackError, Result as NuitrackResult},
+    hand_frame::HandFrame,
+    rgb_frame::RGBFrame,
+    rgbd_frame::{RgbdFrame, Registration},
+    skeleton_frame::SkeletonFrame,
+    user_frame::UserFrame,
+};
+
+use super::session::ActiveDeviceContext;
+
+/// Frames of one device that share a timestamp (within the configured tolerance).
+///
+/// Members whose stream was not attached are always `None`. Attached members are
+/// `None` only when allowed by the [`MissingFramePolicy`].
+#[derive(Clone, Default)]
+pub struct FrameBundle {
+    /// Timestamp of the earliest member, in microseconds.
+    pub timestamp: u64,
+    pub depth: Option<DepthFrame>,
+    pub color: Option<RGBFrame>,
+    pub user: Option<UserFrame>,
+    pub skeleton: Option<SkeletonFrame>,
+    pub hand: Option<HandFrame>,
+}
+
+impl FrameBundle {
+    fn set(&mut self, frame: BackendFrame) {
+        match frame {
+            BackendFrame::Depth(f) => self.depth = Some(f),
+            BackendFrame::Color(f) => self.color = Some(f),
+            BackendFrame::User(f) => self.user = Some(f),
+            BackendFrame::Skeleton(f) => self.skeleton = Some(f),
+            BackendFrame::Hand(f) => self.hand = Some(f),
+            _ => {}
+        }
+    }
+
+    /// Registers the depth member to the color member. `None` unless the bundle has both.
+    pub fn to_rgbd(&self, registration: &Registration) -> NuitrackResult<Option<RgbdFrame>> {
+        match (&self.depth, &self.color) {
+            (Some(depth), Some(color)) => registration.align(depth, color).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// What to do when an attached stream has no frame for a bundle's timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingFramePolicy {
+    /// Drop the bundle; only complete bundles are yielded.
+    #[default]
+    Skip,
+    /// Yield the bundle with the missing members set to `None`.
+    Partial,
+    /// Fill missing members with the last frame that stream contributed, if any.
+    HoldLast,
+}
+
+pub(super) type MemberStream = Pin<Box<dyn Stream<Item = NuitrackResult<BackendFrame>> + Send>>;
+
+/// Boxes a typed tracker stream, wrapping each item with `wrap`.
+pub(super) fn member_stream<S, T>(stream: S, wrap: fn(T) -> BackendFrame) -> MemberStream
+where
+    S: Stream<Item = NuitrackResult<T>> + Send + 'static,
+    T: 'static,
+{
+    Box::pin(Member { stream, wrap })
+}
+
+/// Wraps a typed tracker stream so all members can be buffered uniformly.
+#[pin_project]
+struct Member<S, T> {
+    #[pin]
+    stream: S,
+    wrap: fn(T) -> BackendFrame,
+}
+
+impl<S, T> Stream for Member<S, T>
+where
+    S: Stream<Item = NuitrackResult<T>>,
+{
+    type Item = NuitrackResult<BackendFrame>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let wrap = *this.wrap;
+        this.stream.poll_next(cx).map(|item| item.map(|result| result.map(wrap)))
+    }
+}
+
+struct Slot {
+    name: &'static str,
+    stream: MemberStream,
+    queue: VecDeque<(u64, BackendFrame)>,
+    ended: bool,
+    last: Option<BackendFrame>,
+}
+
+/// Collects the streams to synchronize. Created by [`FrameBundleStream::builder`].
+pub struct FrameBundleBuilder {
+    slots: Vec<Slot>,
+    tolerance: Duration,
+    policy: MissingFramePolicy,
+    max_pending: usize,
+}
+
+impl FrameBundleBuilder {
+    fn attach<S, T>(mut self, name: &'static str, stream: S, wrap: fn(T) -> BackendFrame) -> Self
+    where
+        S: Stream<Item = NuitrackResult<T>> + Send + 'static,
+        T: 'static,
+    {
+        self.slots.retain(|slot| slot.name != name);
+        self.slots.push(Slot {
+            name,
+            stream: member_stream(stream, wrap),
+            queue: VecDeque::new(),
+            ended: false,
+            last: None,
+        });
+        self
+    }
+
+    pub fn depth(self, stream: impl Stream<Item = NuitrackResult<DepthFrame>> + Send + 'static) -> Self {
+        self.attach("depth", stream, BackendFrame::Depth)
+    }
+
+    pub fn color(self, stream: impl Stream<Item = NuitrackResult<RGBFrame>> + Send + 'static) -> Self {
+        self.attach("color", stream, BackendFrame::Color)
+    }
+
+    pub fn user(self, stream: impl Stream<Item = NuitrackResult<UserFrame>> + Send + 'static) -> Self {
+        self.attach("user", stream, BackendFrame::User)
+    }
+
+    pub fn skeleton(self, stream: impl Stream<Item = NuitrackResult<SkeletonFrame>> + Send + 'static) -> Self {
+        self.attach("skeleton", stream, BackendFrame::Skeleton)
+    }
+
+    pub fn hand(self, stream: impl Stream<Item = NuitrackResult<HandFrame>> + Send + 'static) -> Self {
+        self.attach("hand", stream, BackendFrame::Hand)
+    }
+
+    /// Maximum timestamp difference between members of one bundle. Defaults to zero,
+    /// which matches Nuitrack's behaviour of stamping one update cycle identically.
+    pub fn tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn missing_frame_policy(mut self, policy: MissingFramePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Frames buffered per stream before a missing member is given up on. Defaults to 8.
+    pub fn max_pending(mut self, frames: usize) -> Self {
+        self.max_pending = frames.max(1);
+        self
+    }
+
+    pub fn build(self) -> NuitrackResult<FrameBundleStream> {
+        if self.slots.is_empty() {
+            return Err(NuitrackError::OperationFailed(
+                "FrameBundleStream needs at least one stream".to_string(),
+            ));
+        }
+        debug!(
+            members = ?self.slots.iter().map(|s| s.name).collect::<Vec<_>>(),
+            tolerance_us = self.tolerance.as_micros() as u64,
+            policy = ?self.policy,
+            "Building frame bundle stream."
+        );
+        Ok(FrameBundleStream {
+            slots: self.slots,
+            tolerance_us: self.tolerance.as_micros() as u64,
+            policy: self.policy,
+            max_pending: self.max_pending,
+        })
+    }
+}
+
+/// A stream of [`FrameBundle`]s built from any subset of one device's frame streams.
+///
+/// The stream ends once every member stream has ended and all buffered frames
+/// have been bundled. Errors from member streams are passed through.
+///
+/// ```no_run
+/// # fn example(session: &mut nuitrack_rs::nuitrack::async_api::session::NuitrackSession)
+/// #     -> nuitrack_rs::nuitrack::shared_types::error::Result<()> {
+/// use nuitrack_rs::nuitrack::async_api::frame_bundle::{FrameBundleStream, MissingFramePolicy};
+///
+/// let device = &mut session.active_devices[0];
+/// let bundles = FrameBundleStream::builder()
+///     .depth(device.depth_sensor.as_mut().unwrap().depth_frames_stream()?)
+///     .skeleton(device.skeleton_tracker.as_mut().unwrap().skeleton_frames_stream()?)
+///     .missing_frame_policy(MissingFramePolicy::Partial)
+///     .build()?;
+/// # Ok(()) }
+/// ```
+pub struct FrameBundleStream {
+    slots: Vec<Slot>,
+    tolerance_us: u64,
+    policy: MissingFramePolicy,
+    max_pending: usize,
+}
+
+impl FrameBundleStream {
+    pub fn builder() -> FrameBundleBuilder {
+        FrameBundleBuilder {
+            slots: Vec::new(),
+            tolerance: Duration::ZERO,
+            policy: MissingFramePolicy::default(),
+            max_pending: 8,
+        }
+    }
+
+    /// Starts a builder with every frame stream the device has a module for.
+    ///
+    /// This takes each module's stream, just like calling its `*_stream()` method.
+    #[instrument(skip(device), fields(serial = %device.info.serial_number))]
+    pub fn for_device(device: &mut ActiveDeviceContext) -> NuitrackResult<FrameBundleBuilder> {
+        let mut builder = Self::builder();
+        if let Some(sensor) = device.depth_sensor.as_mut() {
+            builder = builder.depth(sensor.depth_frames_stream()?);
+        }
+        if let Some(sensor) = device.color_sensor.as_mut() {
+            builder = builder.color(sensor.rgb_frames_stream()?);
+        }
+        if let Some(tracker) = device.user_tracker.as_mut() {
+            builder = builder.user(tracker.user_frames_stream()?);
+        }
+        if let Some(tracker) = device.skeleton_tracker.as_mut() {
+            builder = builder.skeleton(tracker.skeleton_frames_stream()?);
+        }
+        if let Some(tracker) = device.hand_tracker.as_mut() {
+            builder = builder.hand(tracker.hand_frames_stream()?);
+        }
+        Ok(builder)
+    }
+
+    /// Moves every ready frame into its slot's queue. Returns the first stream error.
+    fn fill_queues(&mut self, cx: &mut Context<'_>) -> Option<NuitrackError> {
+        for slot in self.slots.iter_mut().filter(|slot| !slot.ended) {
+            loop {
+                match slot.stream.as_mut().poll_next(cx) {
+                    Poll::Pending => break,
+                    Poll::Ready(None) => {
+                        debug!(member = slot.name, "Member stream ended.");
+                        slot.ended = true;
+                        break;
+                    }
+                    Poll::Ready(Some(Err(e))) => return Some(e),
+                    Poll::Ready(Some(Ok(frame))) => {
+                        let timestamp = match frame.timestamp() {
+                            Ok(ts) => ts.unwrap_or_default(),
+                            Err(e) => return Some(e),
+                        };
+                        if slot.queue.len() >= self.max_pending {
+                            warn!(member = slot.name, "Frame bundle buffer full; dropping oldest frame.");
+                            slot.queue.pop_front();
+                        }
+                        slot.queue.push_back((timestamp, frame));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Tries to assemble the next bundle from the queued frames.
+    ///
+    /// Returns `Ready(None)` when every stream has ended and nothing is queued, and
+    /// `Pending` while a member could still deliver a frame for the oldest timestamp.
+    fn next_bundle(&mut self) -> Poll<Option<FrameBundle>> {
+        loop {
+            let Some(anchor) = self.slots.iter().filter_map(|s| s.queue.front().map(|f| f.0)).min() else {
+                return if self.slots.iter().all(|s| s.ended) { Poll::Ready(None) } else { Poll::Pending };
+            };
+            let limit = anchor.saturating_add(self.tolerance_us);
+            let backlog = self.slots.iter().any(|s| s.queue.len() >= self.max_pending);
+
+            let mut complete = true;
+            for slot in &self.slots {
+                let matched = slot.queue.front().is_some_and(|f| f.0 <= limit);
+                if matched {
+                    continue;
+                }
+                complete = false;
+                // Timestamps only grow, so a later frame or an ended stream means no match will come.
+                let given_up = slot.ended || !slot.queue.is_empty() || backlog;
+                if !given_up {
+                    return Poll::Pending;
+                }
+            }
+
+            let mut bundle = FrameBundle { timestamp: anchor, ..Default::default() };
+            for slot in &mut self.slots {
+                match slot.queue.front() {
+                    Some(&(ts, _)) if ts <= limit => {
+                        let (_, frame) = slot.queue.pop_front().expect("front exists");
+                        slot.last = Some(frame.clone());
+                        bundle.set(frame);
+                    }
+                    _ if self.policy == MissingFramePolicy::HoldLast => {
+                        if let Some(last) = slot.last.clone() {
+                            bundle.set(last);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if complete || self.policy != MissingFramePolicy::Skip {
+                trace!(timestamp = anchor, complete, "Yielding frame bundle.");
+                return Poll::Ready(Some(bundle));
+            }
+            debug!(timestamp = anchor, "Skipping incomplete frame bundle.");
+        }
+    }
+}
+
+impl Stream for FrameBundleStream {
+    type Item = NuitrackResult<FrameBundle>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if let Some(e) = this.fill_queues(cx) {
+            return Poll::Ready(Some(Err(e)));
+        }
+        this.next_bundle().map(|bundle| bundle.map(Ok))
+    }
+}
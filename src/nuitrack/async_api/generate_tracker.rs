@@ -1,5 +1,12 @@
 
 
+/// A tracker stream whose channel can stop accepting items while the items
+/// already queued remain readable.
+pub(crate) trait ClosableStream: futures_core::Stream {
+    /// Stops new items from being queued. The stream ends once the queue is drained.
+    fn close(self: std::pin::Pin<&mut Self>);
+}
+
 macro_rules! generate_async_tracker {
     // ========================================================================================
     // Rule 1: PUBLIC ENTRY POINT - CORRECTED
@@ -64,6 +71,8 @@ macro_rules! generate_async_tracker {
                     {
                         stream_struct_name: [< $item_base_name_pascal Stream >],
                         stream_method_name: [< $item_base_name_snake s_stream >],
+                        tap_method_name: [< tap_ $item_base_name_snake s >],
+                        connect_method_name: [< connect_ $item_base_name_snake s >],
                         sender_type_alias: [< $item_base_name_pascal Sender >],
                         handler_id_field: [< id_for_on_ $item_base_name_snake _handler >],
                         raw_sender_field: [< raw_ $item_base_name_snake _sender >],
@@ -141,6 +150,8 @@ macro_rules! generate_async_tracker {
             $({
                 stream_struct_name: $stream_struct_name:ident,
                 stream_method_name: $stream_method_name:ident,
+                tap_method_name: $tap_method_name:ident,
+                connect_method_name: $connect_method_name:ident,
                 sender_type_alias: $sender_type_alias:ident,
                 handler_id_field: $handler_id_field:ident,
                 raw_sender_field: $raw_sender_field:ident,
@@ -164,6 +175,7 @@ macro_rules! generate_async_tracker {
         use super::async_dispatch::run_blocking;
         use tracing::{debug, error, instrument, trace_span};
         use std::sync::{Arc, Mutex};
+        use crate::nuitrack::backend::virtual_device::VirtualDevice;
 
         $(
             type $sender_type_alias = UnboundedSender<NuitrackResult<$rust_item_type>>;
@@ -173,7 +185,6 @@ macro_rules! generate_async_tracker {
                 #[pin]
                 rx: UnboundedReceiver<NuitrackResult<$rust_item_type>>,
                 active_state: Arc<Mutex<Option<StreamActiveState<$rust_item_type>>>>,
-                tracker_ptr: SharedPtr<$ffi_tracker_type>,
             }
             
 
@@ -184,16 +195,29 @@ macro_rules! generate_async_tracker {
                 }
             }
 
+            impl $crate::nuitrack::async_api::generate_tracker::ClosableStream for $stream_struct_name {
+                fn close(self: Pin<&mut Self>) {
+                    self.project().rx.close();
+                }
+            }
+
             #[pinned_drop]
             impl PinnedDrop for $stream_struct_name {
                 fn drop(self: Pin<&mut Self>) {
                     let mut state_guard = self.active_state.lock().unwrap();
-                    if let Some(active_state) = state_guard.take() {
-                        debug!(stream = stringify!($stream_struct_name), "Dropping stream and disconnecting callback.");
-                        if let Err(e) = $ffi_disconnect_stream_fn(&self.tracker_ptr, active_state.handler_id) {
-                            error!(error = %e, "FFI disconnect error during PinnedDrop");
+                    match state_guard.take() {
+                        Some(StreamActiveState::Ffi { handler_id, raw_sender_ptr, tracker_ptr, .. }) => {
+                            debug!(stream = stringify!($stream_struct_name), "Dropping stream and disconnecting callback.");
+                            if let Err(e) = $ffi_disconnect_stream_fn(&tracker_ptr, handler_id) {
+                                error!(error = %e, "FFI disconnect error during PinnedDrop");
+                            }
+                            unsafe { let _ = Box::from_raw(raw_sender_ptr as *mut $sender_type_alias); };
+                        }
+                        Some(StreamActiveState::Virtual { device, key, subscription_id }) => {
+                            debug!(stream = stringify!($stream_struct_name), "Dropping stream and unsubscribing from virtual device.");
+                            device.unsubscribe(key, subscription_id);
                         }
-                        unsafe { let _ = Box::from_raw(active_state.raw_sender_ptr as *mut $sender_type_alias); };
+                        None => {}
                     }
                 }
             }
@@ -207,16 +231,29 @@ macro_rules! generate_async_tracker {
             );
         )*
 
-        struct StreamActiveState<T> {
-            handler_id: u64,
-            raw_sender_ptr: *mut $c_void_type,
-            _phantom: std::marker::PhantomData<T>,
+        enum StreamActiveState<T> {
+            /// Callback registered with the SDK; owns the boxed sender behind `raw_sender_ptr`.
+            Ffi {
+                /// Module the callback is connected to; changes when the stream is re-attached.
+                tracker_ptr: SharedPtr<$ffi_tracker_type>,
+                handler_id: u64,
+                raw_sender_ptr: *mut $c_void_type,
+                _phantom: std::marker::PhantomData<T>,
+            },
+            /// Subscription on a backend-driven virtual device.
+            Virtual {
+                device: Arc<VirtualDevice>,
+                key: &'static str,
+                subscription_id: u64,
+            },
         }
 
         unsafe impl<T: Send> Send for StreamActiveState<T> {}
 
         pub struct $tracker_name {
-            ptr: SharedPtr<$ffi_tracker_type>,
+            /// Shared with [`ModuleLink`] so session recovery can swap in a new SDK module.
+            ptr: Arc<Mutex<SharedPtr<$ffi_tracker_type>>>,
+            virtual_device: Option<Arc<VirtualDevice>>,
             $(
                 $handler_id_field: Arc<Mutex<Option<StreamActiveState<$rust_item_type>>>>,
             )*
@@ -226,6 +263,7 @@ macro_rules! generate_async_tracker {
             fn clone(&self) -> Self {
                 Self {
                     ptr: self.ptr.clone(),
+                    virtual_device: self.virtual_device.clone(),
                     $(
                         $handler_id_field: Arc::new(Mutex::new(None)),
                     )*
@@ -249,7 +287,8 @@ macro_rules! generate_async_tracker {
                     })
                 }).await?;
                 Ok(Self {
-                    ptr: tracker_ptr,
+                    ptr: Arc::new(Mutex::new(tracker_ptr)),
+                    virtual_device: None,
                     // $($handler_id_field: None, $raw_sender_field: None,)*
                     $(
                         $handler_id_field: Arc::new(Mutex::new(None)),
@@ -257,14 +296,68 @@ macro_rules! generate_async_tracker {
                 })
             }
 
-            pub(crate) fn get_ffi_ptr_clone(&self) -> SharedPtr<$ffi_tracker_type> {
-                self.ptr.clone()
+            /// Creates a tracker fed by a backend's virtual device rather than the SDK.
+            pub(crate) fn new_virtual(device: Arc<VirtualDevice>) -> Self {
+                Self {
+                    ptr: Arc::new(Mutex::new(SharedPtr::null())),
+                    virtual_device: Some(device),
+                    $(
+                        $handler_id_field: Arc::new(Mutex::new(None)),
+                    )*
+                }
+            }
+
+            /// Returns the SDK module pointer, or an error if this tracker is backed by a virtual device.
+            pub(crate) fn get_ffi_ptr_clone(&self) -> NuitrackResult<SharedPtr<$ffi_tracker_type>> {
+                match &self.virtual_device {
+                    None => Ok(self.ptr.lock().unwrap().clone()),
+                    Some(device) => Err(NuitrackError::Backend(format!(
+                        "{} on virtual device '{}' does not support SDK calls.",
+                        stringify!($tracker_name), device.descriptor().serial_number
+                    ))),
+                }
+            }
+
+            pub(crate) fn virtual_device(&self) -> Option<&Arc<VirtualDevice>> {
+                self.virtual_device.as_ref()
+            }
+
+            /// Returns the handle session recovery uses to re-attach this tracker's
+            /// streams, or `None` for trackers backed by a virtual device.
+            #[cfg(feature = "tokio_runtime")]
+            pub(crate) fn module_link(&self) -> Option<ModuleLink> {
+                if self.virtual_device.is_some() {
+                    return None;
+                }
+                Some(ModuleLink {
+                    ptr: self.ptr.clone(),
+                    $(
+                        $handler_id_field: self.$handler_id_field.clone(),
+                    )*
+                })
             }
 
             $(
                 #[instrument(skip(self), name = "get_stream")]
                 pub fn $stream_method_name(&mut self) -> NuitrackResult<$stream_struct_name> {
-                    let mut state_guard = self.$handler_id_field.lock().unwrap();
+                    self.$connect_method_name(self.$handler_id_field.clone())
+                }
+
+                /// Opens an additional, independent subscription to the same data.
+                ///
+                /// Unlike the public stream method this does not occupy the tracker's
+                /// stream slot, so observers such as the session recorder can run
+                /// alongside the application's own stream.
+                #[instrument(skip(self), name = "get_tap")]
+                pub(crate) fn $tap_method_name(&self) -> NuitrackResult<$stream_struct_name> {
+                    self.$connect_method_name(Arc::new(Mutex::new(None)))
+                }
+
+                fn $connect_method_name(
+                    &self,
+                    slot: Arc<Mutex<Option<StreamActiveState<$rust_item_type>>>>,
+                ) -> NuitrackResult<$stream_struct_name> {
+                    let mut state_guard = slot.lock().unwrap();
 
                     if state_guard.is_some() {
                         return Err(NuitrackError::OperationFailed(
@@ -272,27 +365,44 @@ macro_rules! generate_async_tracker {
                         ));
                     }
                     let (tx, rx) = unbounded::<NuitrackResult<$rust_item_type>>();
+
+                    if let Some(device) = &self.virtual_device {
+                        let key = concat!(stringify!($base_module_name_snake), "::", stringify!($stream_method_name));
+                        let subscription_id = device.subscribe(key, tx);
+                        *state_guard = Some(StreamActiveState::Virtual {
+                            device: device.clone(),
+                            key,
+                            subscription_id,
+                        });
+                        drop(state_guard);
+                        return Ok($stream_struct_name {
+                            rx,
+                            active_state: slot,
+                        });
+                    }
                     
                     let sender_boxed = Box::new(tx);
                     let sender_raw_ptr = Box::into_raw(sender_boxed) as *mut $c_void_type;
                     //self.$raw_sender_field = Some(sender_raw_ptr);
 
+                    let tracker_ptr = self.ptr.lock().unwrap().clone();
                     let handler_id = unsafe {
-                        $ffi_connect_stream_fn(&self.ptr, sender_raw_ptr)
+                        $ffi_connect_stream_fn(&tracker_ptr, sender_raw_ptr)
                     }.map_err(|e| NuitrackError::OperationFailed(
                         format!("FFI connect call {} failed: {}", stringify!($ffi_connect_stream_fn), e)
                     ))?;
 
-                    *state_guard = Some(StreamActiveState {
+                    *state_guard = Some(StreamActiveState::Ffi {
+                        tracker_ptr,
                         handler_id,
                         raw_sender_ptr: sender_raw_ptr,
                         _phantom: std::marker::PhantomData,
                     });
                     //self.$handler_id_field = Some(handler_id);
+                    drop(state_guard);
                     Ok($stream_struct_name {
                         rx,
-                        active_state: self.$handler_id_field.clone(),
-                        tracker_ptr: self.ptr.clone(),
+                        active_state: slot,
                     })
                 }
             )*
@@ -303,11 +413,119 @@ macro_rules! generate_async_tracker {
                 debug!(tracker = stringify!($tracker_name), "Dropping tracker and disconnecting streams.");
                 $(
                     let mut state = self.$handler_id_field.lock().unwrap();
-                    if let Some(active_state) = state.take() {
-                        if let Err(e) = $ffi_disconnect_stream_fn(&self.ptr, active_state.handler_id) {
-                            error!(error = %e, "FFI disconnect error during Tracker Drop");
+                    match state.take() {
+                        Some(StreamActiveState::Ffi { handler_id, raw_sender_ptr, tracker_ptr, .. }) => {
+                            if let Err(e) = $ffi_disconnect_stream_fn(&tracker_ptr, handler_id) {
+                                error!(error = %e, "FFI disconnect error during Tracker Drop");
+                            }
+                            unsafe { let _ = Box::from_raw(raw_sender_ptr as *mut $sender_type_alias); };
+                        }
+                        Some(StreamActiveState::Virtual { device, key, subscription_id }) => {
+                            device.unsubscribe(key, subscription_id);
+                        }
+                        None => {}
+                    }
+                )*
+            }
+        }
+
+        /// Lets session recovery move a tracker, and its open streams, onto a new SDK module
+        /// after the runtime was re-initialized. Taps are not tracked and end instead.
+        #[cfg(feature = "tokio_runtime")]
+        #[derive(Clone)]
+        pub(crate) struct ModuleLink {
+            ptr: Arc<Mutex<SharedPtr<$ffi_tracker_type>>>,
+            $(
+                $handler_id_field: Arc<Mutex<Option<StreamActiveState<$rust_item_type>>>>,
+            )*
+        }
+
+        #[cfg(feature = "tokio_runtime")]
+        unsafe impl Send for ModuleLink {}
+        #[cfg(feature = "tokio_runtime")]
+        unsafe impl Sync for ModuleLink {}
+
+        /// A new module with every open stream connected to it, not yet used by the tracker.
+        /// Dropping it without [`commit`](Self::commit) disconnects the streams from it again.
+        #[cfg(feature = "tokio_runtime")]
+        pub(crate) struct PreparedModule {
+            link: ModuleLink,
+            new_ptr: SharedPtr<$ffi_tracker_type>,
+            $(
+                $handler_id_field: Option<u64>,
+            )*
+        }
+
+        #[cfg(feature = "tokio_runtime")]
+        unsafe impl Send for PreparedModule {}
+
+        #[cfg(feature = "tokio_runtime")]
+        impl ModuleLink {
+            /// Creates a new module and connects every open stream's sender to it, leaving
+            /// the tracker on its old module. Must run on a blocking thread, with the global
+            /// API lock held and the owning device selected.
+            pub(crate) fn prepare_reattach_blocking(&self) -> NuitrackResult<PreparedModule> {
+                let new_ptr = $ffi_create_function()
+                    .map_err(|e| NuitrackError::ModuleCreationFailed(format!("{}: {}", $module_creation_error_context, e)))?;
+                let mut prepared = PreparedModule {
+                    link: self.clone(),
+                    new_ptr,
+                    $(
+                        $handler_id_field: None,
+                    )*
+                };
+                $(
+                    if let Some(StreamActiveState::Ffi { raw_sender_ptr, .. }) = self.$handler_id_field.lock().unwrap().as_ref() {
+                        // On failure `prepared` is dropped, which disconnects the streams connected so far.
+                        prepared.$handler_id_field = Some(unsafe { $ffi_connect_stream_fn(&prepared.new_ptr, *raw_sender_ptr) }
+                            .map_err(|e| NuitrackError::OperationFailed(
+                                format!("FFI connect call {} failed: {}", stringify!($ffi_connect_stream_fn), e)
+                            ))?);
+                    }
+                )*
+                Ok(prepared)
+            }
+
+            pub(crate) fn ffi_ptr(&self) -> SharedPtr<$ffi_tracker_type> {
+                self.ptr.lock().unwrap().clone()
+            }
+        }
+
+        #[cfg(feature = "tokio_runtime")]
+        impl PreparedModule {
+            /// Moves the tracker and its open streams onto the new module.
+            pub(crate) fn commit(mut self) {
+                $(
+                    if let Some(new_handler_id) = self.$handler_id_field.take() {
+                        match self.link.$handler_id_field.lock().unwrap().as_mut() {
+                            Some(StreamActiveState::Ffi { tracker_ptr, handler_id, .. }) => {
+                                // The old module belongs to the released runtime and will not call back again,
+                                // so the sender can move over without being freed.
+                                *handler_id = new_handler_id;
+                                *tracker_ptr = self.new_ptr.clone();
+                                debug!(stream = stringify!($stream_struct_name), "Stream re-attached.");
+                            }
+                            // The stream was closed after it was prepared.
+                            _ => {
+                                if let Err(e) = $ffi_disconnect_stream_fn(&self.new_ptr, new_handler_id) {
+                                    error!(error = %e, "FFI disconnect error while committing a re-attached module");
+                                }
+                            }
+                        }
+                    }
+                )*
+                *self.link.ptr.lock().unwrap() = self.new_ptr.clone();
+            }
+        }
+
+        #[cfg(feature = "tokio_runtime")]
+        impl Drop for PreparedModule {
+            fn drop(&mut self) {
+                $(
+                    if let Some(handler_id) = self.$handler_id_field.take() {
+                        if let Err(e) = $ffi_disconnect_stream_fn(&self.new_ptr, handler_id) {
+                            error!(error = %e, "FFI disconnect error while discarding a re-attached module");
                         }
-                        unsafe { let _ = Box::from_raw(active_state.raw_sender_ptr as *mut $sender_type_alias); };
                     }
                 )*
             }
@@ -0,0 +1,110 @@
+//! Stream adapter that turns gesture recognizer updates into gesture lifecycle events.
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use pin_project::pin_project;
+
+use crate::nuitrack::shared_types::{
+    error::Result as NuitrackResult,
+    gesture_frame::{GestureFrame, UserGesturesFrame},
+    gesture_progress::{GestureEvent, GestureProgressTracker},
+};
+
+/// Merges the `update` and `completed_gestures_frame` streams of an
+/// `AsyncGestureRecognizer` through a [`GestureProgressTracker`] and yields its
+/// events. Errors from either stream pass through unchanged; the stream ends when
+/// both inputs have ended.
+///
+/// ```no_run
+/// # use nuitrack_rs::nuitrack::async_api::gesture_recognizer::AsyncGestureRecognizer;
+/// # use nuitrack_rs::nuitrack::async_api::gesture_event_stream::GestureEventStream;
+/// # use nuitrack_rs::nuitrack::shared_types::gesture_progress::GestureProgressTracker;
+/// # fn example(recognizer: &mut AsyncGestureRecognizer) -> nuitrack_rs::nuitrack::shared_types::error::Result<()> {
+/// let events = GestureEventStream::new(
+///     recognizer.updates_stream()?,
+///     recognizer.completed_gestures_frames_stream()?,
+///     GestureProgressTracker::default(),
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[pin_project]
+pub struct GestureEventStream<U, C> {
+    #[pin]
+    updates: U,
+    #[pin]
+    completed: C,
+    updates_done: bool,
+    completed_done: bool,
+    tracker: GestureProgressTracker,
+    pending: VecDeque<GestureEvent>,
+}
+
+impl<U, C> GestureEventStream<U, C> {
+    pub fn new(updates: U, completed: C, tracker: GestureProgressTracker) -> Self {
+        Self { updates, completed, updates_done: false, completed_done: false, tracker, pending: VecDeque::new() }
+    }
+
+    /// The tracker, e.g. to check which gestures are under way.
+    pub fn tracker_mut(&mut self) -> &mut GestureProgressTracker {
+        &mut self.tracker
+    }
+
+    pub fn into_inner(self) -> (U, C) {
+        (self.updates, self.completed)
+    }
+}
+
+impl<U, C> Stream for GestureEventStream<U, C>
+where
+    U: Stream<Item = NuitrackResult<UserGesturesFrame>>,
+    C: Stream<Item = NuitrackResult<GestureFrame>>,
+{
+    type Item = NuitrackResult<GestureEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(event) = this.pending.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            // Completions first, so a completion is not mistaken for a cancellation when
+            // both arrive together.
+            if !*this.completed_done {
+                match this.completed.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(Ok(frame))) => {
+                        match this.tracker.process_completed(&frame) {
+                            Ok(events) => this.pending.extend(events),
+                            Err(e) => return Poll::Ready(Some(Err(e))),
+                        }
+                        continue;
+                    }
+                    Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                    Poll::Ready(None) => *this.completed_done = true,
+                    Poll::Pending => {}
+                }
+            }
+            if !*this.updates_done {
+                match this.updates.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(Ok(frame))) => {
+                        match this.tracker.process_update(&frame) {
+                            Ok(events) => this.pending.extend(events),
+                            Err(e) => return Poll::Ready(Some(Err(e))),
+                        }
+                        continue;
+                    }
+                    Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                    Poll::Ready(None) => *this.updates_done = true,
+                    Poll::Pending => {}
+                }
+            }
+            if *this.updates_done && *this.completed_done {
+                return Poll::Ready(None);
+            }
+            return Poll::Pending;
+        }
+    }
+}
@@ -52,7 +52,7 @@ impl AsyncGestureRecognizer {
     /// Enables or disables the recognition of control gestures (e.g., 'Push', 'Swipe').
     #[instrument(skip(self))]
     pub async fn set_control_gestures_status(&self, status: bool) -> NuitrackResult<()> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="gr_ffi::set_control_gestures_status").in_scope(|| {
             run_blocking(move || {
                 gr_ffi::set_control_gestures_status(&ptr, status)
@@ -64,7 +64,7 @@ impl AsyncGestureRecognizer {
     /// Gets the last gesture recognition time in milliseconds.
     #[instrument(skip(self))]
     pub async fn processing_time(&self) -> NuitrackResult<f32> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="gr_ffi::processing_time").in_scope(|| {
             run_blocking(move || {
                 gr_ffi::processing_time(&ptr)
@@ -76,7 +76,10 @@ impl AsyncGestureRecognizer {
     /// Gets the timestamp of the last processed data by the recognizer in microseconds.
     #[instrument(skip(self))]
     pub async fn recognizer_timestamp(&self) -> NuitrackResult<u64> {
-        let ptr = self.get_ffi_ptr_clone();
+        if let Some(device) = self.virtual_device() {
+            return Ok(device.last_timestamp());
+        }
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="gr_ffi::recognizer_timestamp").in_scope(|| {
             run_blocking(move || {
                 gr_ffi::recognizer_timestamp(&ptr)
@@ -88,7 +91,7 @@ impl AsyncGestureRecognizer {
     /// Checks if the Nuitrack module can update.
     #[instrument(skip(self))]
     pub async fn can_update(&self) -> NuitrackResult<bool> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="gr_ffi::can_update").in_scope(|| {
             run_blocking(move || {
                 gr_ffi::can_update(&ptr)
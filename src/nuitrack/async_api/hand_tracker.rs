@@ -1,3 +1,4 @@
+use crate::nuitrack::backend::virtual_device::stream_keys;
 use tracing::warn;
 use crate::nuitrack_bridge::modules::hand_tracker::ffi as ht_ffi;
 use crate::nuitrack::shared_types::hand_frame::HandFrame;
@@ -28,7 +29,12 @@ impl AsyncHandTracker {
     /// A `NuitrackResult` containing a `HandFrame` if successful, or an error.
     #[instrument(skip(self))]
     pub async fn latest_hand_frame_sync(&self) -> NuitrackResult<HandFrame> {
-        let tracker_ptr = self.get_ffi_ptr_clone(); // Clone the SharedPtr for the blocking task
+        if let Some(device) = self.virtual_device() {
+            return device.latest(stream_keys::HAND_FRAMES).ok_or_else(|| {
+                NuitrackError::OperationFailed("Backend has not produced a hand frame yet".to_string())
+            });
+        }
+        let tracker_ptr = self.get_ffi_ptr_clone()?; // Clone the SharedPtr for the blocking task
         let ffi_hand_data_ptr = trace_span!("ffi", function="ht_ffi::data").in_scope(|| {
             run_blocking(move || {
                 ht_ffi::data(&tracker_ptr) // Call the FFI get_data function
@@ -51,7 +57,7 @@ impl AsyncHandTracker {
     /// A `NuitrackResult` containing the processing time as an `f32`, or an error.
     #[instrument(skip(self))]
     pub async fn processing_time(&self) -> NuitrackResult<f32> {
-        let tracker_ptr = self.get_ffi_ptr_clone();
+        let tracker_ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="ht_ffi::processing_time").in_scope(|| {
             run_blocking(move || {
                 ht_ffi::processing_time(&tracker_ptr)
@@ -67,7 +73,10 @@ impl AsyncHandTracker {
     /// A `NuitrackResult` containing the timestamp as a `u64`, or an error.
     #[instrument(skip(self))]
     pub async fn tracker_timestamp(&self) -> NuitrackResult<u64> {
-        let tracker_ptr = self.get_ffi_ptr_clone();
+        if let Some(device) = self.virtual_device() {
+            return Ok(device.last_timestamp());
+        }
+        let tracker_ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="ht_ffi::tracker_timestamp").in_scope(|| {
             run_blocking(move || {
                 ht_ffi::tracker_timestamp(&tracker_ptr)
@@ -83,7 +92,7 @@ impl AsyncHandTracker {
     /// A `NuitrackResult` containing `true` if the module can update, `false` otherwise, or an error.
     #[instrument(skip(self))] 
     pub async fn can_update(&self) -> NuitrackResult<bool> {
-        let tracker_ptr = self.get_ffi_ptr_clone();
+        let tracker_ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="ht_ffi::can_update").in_scope(|| {
             run_blocking(move || {
             // Assuming the typo `SharedTracker` in hand_tracker.rs FFI was corrected to `SharedPtr<HandTracker>`
@@ -0,0 +1,259 @@
+//! Device health monitoring and session events.
+//!
+//! Every device in a session is watched for updates. A device that stops updating
+//! is first reported as [`SessionEvent::Stalled`] and, if it stays silent or its
+//! `waitUpdate` calls keep failing, as [`SessionEvent::DeviceLost`]. When updates
+//! resume, [`SessionEvent::DeviceRecovered`] follows. With
+//! [`RecoveryPolicy::Reinitialize`], hardware sessions additionally restart the
+//! Nuitrack runtime once a lost device is plugged back in and re-attach the
+//! existing stream handles to it. A backend-driven session whose backend fails
+//! reports [`SessionEvent::BackendFailed`] and ends every stream.
+
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_core::Stream;
+use pin_project::pin_project;
+use tracing::{debug, info, warn};
+
+use crate::nuitrack::shared_types::error::NuitrackError;
+
+/// A change in the health of one of the session's devices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionEvent {
+    /// The device has not updated for at least [`HealthConfig::stall_timeout`].
+    Stalled { serial_number: String, idle: Duration },
+    /// The device is considered disconnected.
+    DeviceLost { serial_number: String, reason: String },
+    /// A stalled or lost device is updating again.
+    DeviceRecovered { serial_number: String },
+    /// The session's backend returned an error. No more updates follow.
+    BackendFailed { reason: String },
+}
+
+/// Current health of a device, as last reported through [`SessionEvent`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceHealth {
+    Healthy,
+    Stalled,
+    Lost,
+}
+
+/// What the session does after a device was lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecoveryPolicy {
+    /// Only report the loss; streams resume by themselves if the SDK recovers.
+    #[default]
+    Disabled,
+    /// Periodically look for the lost device and, once it is back, restart the
+    /// runtime and re-attach every stream handle. Only applies to hardware sessions
+    /// with the `tokio_runtime` internal update loop.
+    Reinitialize {
+        retry_interval: Duration,
+        /// Gives up after this many restarts that did not find the device; `None` retries forever.
+        max_attempts: Option<u32>,
+    },
+}
+
+/// Thresholds for stall and disconnect detection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthConfig {
+    /// Time without updates after which a device is reported as stalled. Defaults to 1 s.
+    pub stall_timeout: Duration,
+    /// Time without updates after which a device is reported as lost. Defaults to 5 s.
+    pub lost_timeout: Duration,
+    /// Consecutive `waitUpdate` errors after which a device is reported as lost. Defaults to 3.
+    pub max_consecutive_errors: u32,
+    pub recovery: RecoveryPolicy,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            stall_timeout: Duration::from_secs(1),
+            lost_timeout: Duration::from_secs(5),
+            max_consecutive_errors: 3,
+            recovery: RecoveryPolicy::default(),
+        }
+    }
+}
+
+/// Stream of [`SessionEvent`]s. Created by `NuitrackSession::events_stream`.
+///
+/// Ends when the session is closed.
+#[pin_project]
+pub struct SessionEventStream {
+    #[pin]
+    rx: UnboundedReceiver<SessionEvent>,
+}
+
+impl Stream for SessionEventStream {
+    type Item = SessionEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().rx.poll_next(cx)
+    }
+}
+
+struct DeviceState {
+    serial_number: String,
+    health: DeviceHealth,
+    last_update: Instant,
+    consecutive_errors: u32,
+}
+
+/// Tracks every device's health and fans events out to subscribers.
+pub(crate) struct HealthMonitor {
+    config: HealthConfig,
+    devices: Mutex<Vec<DeviceState>>,
+    subscribers: Mutex<Vec<UnboundedSender<SessionEvent>>>,
+    /// Set once no more updates are expected, e.g. a backend ran out of data.
+    finished: AtomicBool,
+}
+
+impl HealthMonitor {
+    pub(crate) fn new(config: HealthConfig, serial_numbers: impl IntoIterator<Item = String>) -> Self {
+        let now = Instant::now();
+        let devices = serial_numbers
+            .into_iter()
+            .map(|serial_number| DeviceState {
+                serial_number,
+                health: DeviceHealth::Healthy,
+                last_update: now,
+                consecutive_errors: 0,
+            })
+            .collect();
+        Self {
+            config,
+            devices: Mutex::new(devices),
+            subscribers: Mutex::new(Vec::new()),
+            finished: AtomicBool::new(false),
+        }
+    }
+
+    #[cfg(feature = "tokio_runtime")]
+    pub(crate) fn config(&self) -> &HealthConfig {
+        &self.config
+    }
+
+    pub(crate) fn subscribe(&self) -> SessionEventStream {
+        let (tx, rx) = unbounded();
+        if !self.finished.load(Ordering::SeqCst) {
+            self.subscribers.lock().unwrap().push(tx);
+        }
+        SessionEventStream { rx }
+    }
+
+    /// Stops stall detection and ends every open event stream.
+    pub(crate) fn finish(&self) {
+        self.finished.store(true, Ordering::SeqCst);
+        self.subscribers.lock().unwrap().clear();
+    }
+
+    /// Reports a failed backend, then finishes like [`Self::finish`].
+    pub(crate) fn fail(&self, error: &NuitrackError) {
+        self.publish(SessionEvent::BackendFailed { reason: error.to_string() });
+        self.finish();
+    }
+
+    pub(crate) fn health(&self, serial_number: &str) -> Option<DeviceHealth> {
+        self.devices.lock().unwrap().iter().find(|d| d.serial_number == serial_number).map(|d| d.health)
+    }
+
+    #[cfg(feature = "tokio_runtime")]
+    pub(crate) fn lost_devices(&self) -> Vec<String> {
+        self.devices
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|d| d.health == DeviceHealth::Lost)
+            .map(|d| d.serial_number.clone())
+            .collect()
+    }
+
+    fn publish(&self, event: SessionEvent) {
+        match &event {
+            SessionEvent::DeviceRecovered { .. } => info!(?event, "Session event."),
+            _ => warn!(?event, "Session event."),
+        }
+        self.subscribers.lock().unwrap().retain(|tx| tx.unbounded_send(event.clone()).is_ok());
+    }
+
+    /// Records that the device produced an update.
+    pub(crate) fn record_update(&self, serial_number: &str) {
+        let event = {
+            let mut devices = self.devices.lock().unwrap();
+            let Some(device) = devices.iter_mut().find(|d| d.serial_number == serial_number) else { return };
+            device.last_update = Instant::now();
+            device.consecutive_errors = 0;
+            if device.health == DeviceHealth::Healthy {
+                return;
+            }
+            device.health = DeviceHealth::Healthy;
+            SessionEvent::DeviceRecovered { serial_number: device.serial_number.clone() }
+        };
+        self.publish(event);
+    }
+
+    /// Records a failed `waitUpdate` on the device.
+    pub(crate) fn record_error(&self, serial_number: &str, error: &NuitrackError) {
+        let event = {
+            let mut devices = self.devices.lock().unwrap();
+            let Some(device) = devices.iter_mut().find(|d| d.serial_number == serial_number) else { return };
+            device.consecutive_errors += 1;
+            debug!(serial = %device.serial_number, errors = device.consecutive_errors, "Device update failed.");
+            if device.health == DeviceHealth::Lost || device.consecutive_errors < self.config.max_consecutive_errors {
+                return;
+            }
+            device.health = DeviceHealth::Lost;
+            SessionEvent::DeviceLost {
+                serial_number: device.serial_number.clone(),
+                reason: format!("{} consecutive update errors, last: {}", device.consecutive_errors, error),
+            }
+        };
+        self.publish(event);
+    }
+
+    /// Reports devices whose last update is older than the stall or lost timeout.
+    pub(crate) fn check_stalls(&self) {
+        if self.finished.load(Ordering::SeqCst) {
+            return;
+        }
+        let now = Instant::now();
+        let events = {
+            let mut devices = self.devices.lock().unwrap();
+            devices
+                .iter_mut()
+                .filter_map(|device| {
+                    let idle = now.saturating_duration_since(device.last_update);
+                    match device.health {
+                        DeviceHealth::Healthy | DeviceHealth::Stalled if idle >= self.config.lost_timeout => {
+                            device.health = DeviceHealth::Lost;
+                            Some(SessionEvent::DeviceLost {
+                                serial_number: device.serial_number.clone(),
+                                reason: format!("no updates for {:?}", idle),
+                            })
+                        }
+                        DeviceHealth::Healthy if idle >= self.config.stall_timeout => {
+                            device.health = DeviceHealth::Stalled;
+                            Some(SessionEvent::Stalled { serial_number: device.serial_number.clone(), idle })
+                        }
+                        _ => None,
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        events.into_iter().for_each(|event| self.publish(event));
+    }
+
+    /// Restarts the idle clocks, e.g. after processing (re)started.
+    #[cfg(feature = "tokio_runtime")]
+    pub(crate) fn reset_clocks(&self) {
+        let now = Instant::now();
+        self.devices.lock().unwrap().iter_mut().for_each(|d| d.last_update = now);
+    }
+}
@@ -1,12 +1,73 @@
+/// Builds a session and takes the streams of the listed modules.
+///
+/// With a plain module list, one device (index 0) is configured and the macro resolves
+/// to `(stream_1, ..., stream_n, session)`:
+///
+/// ```ignore
+/// let (skeletons, hands, session) = setup_nuitrack_streams!(SkeletonTracker, HandTracker).await?;
+/// ```
+///
+/// With `devices: [...]`, each selector gets its own module set and the macro resolves
+/// to one tuple of streams per device, in the listed order, followed by the session:
+///
+/// ```ignore
+/// let ((front_skeletons,), (side_skeletons, side_depth), session) = setup_nuitrack_streams!(
+///     devices: [
+///         DeviceSelector::BySerialNumber("front".into()) => [SkeletonTracker],
+///         DeviceSelector::BySerialNumber("side".into()) => [SkeletonTracker, DepthSensor],
+///     ]
+/// ).await?;
+/// ```
 #[macro_export]
 macro_rules! setup_nuitrack_streams {
+    (
+        devices: [ $( $selector:expr => [ $( $device_module:ident ),+ $(,)? ] ),+ $(,)? ]
+        $( ; $( $key:expr => $value:expr ),* )? $(,)?
+    ) => {
+        async {
+            use $crate::nuitrack::{
+                async_api::{session_builder::NuitrackSessionBuilder, session::NuitrackSession},
+                shared_types::session_config::{ModuleType, DeviceConfig, DeviceSelector},
+                shared_types::error::Result as NuitrackResult,
+            };
+
+            let mut builder = NuitrackSessionBuilder::new();
+
+            $(
+                $(
+                    builder = builder.with_config_value($key, $value);
+                )*
+            )?
+
+            $(
+                builder = builder.with_device_config(DeviceConfig::new($selector, vec![ $( ModuleType::$device_module ),+ ]));
+            )+
+
+            let mut session: NuitrackSession = builder.init_session().await?;
+
+            // Active devices are created in configuration order.
+            #[allow(unused_assignments)]
+            let mut device_index = 0usize;
+            let result: NuitrackResult<_> = Ok((
+                $(
+                    {
+                        let index = device_index;
+                        device_index += 1;
+                        ( $( $crate::__nuitrack_device_stream!(session, index, $device_module)?, )+ )
+                    },
+                )+
+                session
+            ));
+            result
+        }
+    };
     ( $( $module_token:ident ),+ $( ; $( $key:expr => $value:expr ),* )? $(,)? ) => { // Match one or more tokens, with optional trailing comma
         async {
             use $crate::nuitrack::{
                 async_api::{session_builder::NuitrackSessionBuilder,
                 session::NuitrackSession}, // For the return type hint
                 shared_types::session_config::{ModuleType, DeviceConfig, DeviceSelector}, // Assuming this is the correct path
-                shared_types::error::{NuitrackError, Result as NuitrackResult},
+                shared_types::error::Result as NuitrackResult,
             };
 
             let mut builder = NuitrackSessionBuilder::new();
@@ -21,95 +82,18 @@ macro_rules! setup_nuitrack_streams {
                 $( ModuleType::$module_token ),*
             ];
 
-            let device_config = DeviceConfig {
-                selector: DeviceSelector::ByIndex(0),
-                modules_to_create,
-            };
+            let device_config = DeviceConfig::new(DeviceSelector::ByIndex(0), modules_to_create);
 
             let mut session: NuitrackSession = builder
                 .with_device_config(device_config)
                 .init_session()
                 .await?; // Use `?` directly as it returns NuitrackResult
 
-            // 3. Helper macro to extract a specific stream
-            macro_rules! get_stream_for_module {
-                ($session_ref:expr, HandTracker) => {
-                    {
-                        let tracker = $session_ref.active_devices
-                            .get_mut(0)
-                            .and_then(|device_context| device_context.hand_tracker.as_mut())
-                            .ok_or_else(|| NuitrackError::ModuleCreationFailed("AsyncHandTracker not found for the configured device.".to_string()))?;
-                        tracker.hand_frames_stream()
-                            .map_err(|e_text| NuitrackError::OperationFailed(format!("Failed to get HandTracker stream: {}", e_text)))
-                    }
-                };
-                ($session_ref:expr, SkeletonTracker) => {
-                    {
-                        let tracker = $session_ref.active_devices
-                            .get_mut(0)
-                            .and_then(|device_context| device_context.skeleton_tracker.as_mut())
-                            .ok_or_else(|| NuitrackError::ModuleCreationFailed("AsyncSkeletonTracker not found for the configured device.".to_string()))?;
-                        tracker.skeleton_frames_stream()
-                            .map_err(|e_text| NuitrackError::OperationFailed(format!("Failed to get SkeletonTracker stream: {}", e_text)))
-                    }
-                };
-                ($session_ref:expr, ColorSensor) => {
-                    {
-                        let tracker = $session_ref.active_devices
-                            .get_mut(0)
-                            .and_then(|device_context| device_context.color_sensor.as_mut())
-                            .ok_or_else(|| NuitrackError::ModuleCreationFailed("AsyncColorSensor not found for the configured device.".to_string()))?;
-                        tracker.rgb_frames_stream() // Method name is rgb_frames_stream
-                            .map_err(|e_text| NuitrackError::OperationFailed(format!("Failed to get ColorSensor stream: {}", e_text)))
-                    }
-                };
-                ($session_ref:expr, DepthSensor) => {
-                    {
-                        let sensor = $session_ref.active_devices
-                            .get_mut(0)
-                            .and_then(|device_context| device_context.depth_sensor.as_mut())
-                            .ok_or_else(|| NuitrackError::ModuleCreationFailed("AsyncDepthSensor not found for the configured device.".to_string()))?;
-                        sensor.depth_frames_stream() // Method name from generate_async_tracker!
-                            .map_err(|e_text| NuitrackError::OperationFailed(format!("Failed to get DepthSensor stream: {}", e_text)))
-                    }
-                };
-                ($session_ref:expr, UserTracker) => {
-                    {
-                        let tracker = $session_ref.active_devices
-                            .get_mut(0)
-                            .and_then(|device_context| device_context.user_tracker.as_mut())
-                            .ok_or_else(|| NuitrackError::ModuleCreationFailed("AsyncUserTracker not found for the configured device.".to_string()))?;
-                        tracker.user_frames_stream()
-                            .map_err(|e_text| NuitrackError::OperationFailed(format!("Failed to get UserTracker stream: {}", e_text)))
-                    }
-                };
-                ($session_ref:expr, GestureRecognizer) => {
-                    {
-                        let tracker = $session_ref.active_devices
-                            .get_mut(0)
-                            .and_then(|device_context| device_context.gesture_recognizer.as_mut())
-                            .ok_or_else(|| NuitrackError::ModuleCreationFailed("AsyncGestureRecognizer not found for the configured device.".to_string()))?;
-                        tracker.completed_gestures_frames_stream()
-                            .map_err(|e_text| NuitrackError::OperationFailed(format!("Failed to get GestureRecognizer stream: {}", e_text)))
-                    }
-                };
-                // To satisfy type checking for the Result in the compile_error! case,
-                // we need a concrete error type. Since the macro is generic over stream types,
-                // this fallback error type doesn't perfectly align with the stream's Ok type.
-                // However, it won't compile anyway due to compile_error!.
-                ($session_ref:expr, $unsupported_token:ident) => { {
-                    compile_error!(concat!("Unsupported ModuleType token in setup_nuitrack_streams macro: ", stringify!($unsupported_token)));
-                    // Provide a valid expression for type inference that results in NuitrackResult<_, NuitrackError>
-                    // This part will not actually be executed due to compile_error!
-                    Err(NuitrackError::OperationFailed(format!("Unsupported module: {}", stringify!($unsupported_token))))
-                } };
-            }
-
             // 4. Get each stream and package into a Result<Tuple, NuitrackError>
             // The `?` operator is used after each stream retrieval.
             let result: NuitrackResult<_> = Ok((
                 $(
-                    get_stream_for_module!(&mut session, $module_token)?
+                    $crate::__nuitrack_device_stream!(session, 0, $module_token)?
                 ),*
                 ,
                 session
@@ -117,4 +101,79 @@ macro_rules! setup_nuitrack_streams {
             result
         }
     };
-}
\ No newline at end of file
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __nuitrack_device_stream {
+    ($session_ref:expr, $device_index:expr, HandTracker) => {
+        {
+            let tracker = $session_ref.active_devices
+                .get_mut($device_index)
+                .and_then(|device_context| device_context.hand_tracker.as_mut())
+                .ok_or_else(|| $crate::nuitrack::shared_types::error::NuitrackError::ModuleCreationFailed(format!("AsyncHandTracker not found for device {}.", $device_index)))?;
+            tracker.hand_frames_stream()
+                .map_err(|e_text| $crate::nuitrack::shared_types::error::NuitrackError::OperationFailed(format!("Failed to get HandTracker stream: {}", e_text)))
+        }
+    };
+    ($session_ref:expr, $device_index:expr, SkeletonTracker) => {
+        {
+            let tracker = $session_ref.active_devices
+                .get_mut($device_index)
+                .and_then(|device_context| device_context.skeleton_tracker.as_mut())
+                .ok_or_else(|| $crate::nuitrack::shared_types::error::NuitrackError::ModuleCreationFailed(format!("AsyncSkeletonTracker not found for device {}.", $device_index)))?;
+            tracker.skeleton_frames_stream()
+                .map_err(|e_text| $crate::nuitrack::shared_types::error::NuitrackError::OperationFailed(format!("Failed to get SkeletonTracker stream: {}", e_text)))
+        }
+    };
+    ($session_ref:expr, $device_index:expr, ColorSensor) => {
+        {
+            let tracker = $session_ref.active_devices
+                .get_mut($device_index)
+                .and_then(|device_context| device_context.color_sensor.as_mut())
+                .ok_or_else(|| $crate::nuitrack::shared_types::error::NuitrackError::ModuleCreationFailed(format!("AsyncColorSensor not found for device {}.", $device_index)))?;
+            tracker.rgb_frames_stream() // Method name is rgb_frames_stream
+                .map_err(|e_text| $crate::nuitrack::shared_types::error::NuitrackError::OperationFailed(format!("Failed to get ColorSensor stream: {}", e_text)))
+        }
+    };
+    ($session_ref:expr, $device_index:expr, DepthSensor) => {
+        {
+            let sensor = $session_ref.active_devices
+                .get_mut($device_index)
+                .and_then(|device_context| device_context.depth_sensor.as_mut())
+                .ok_or_else(|| $crate::nuitrack::shared_types::error::NuitrackError::ModuleCreationFailed(format!("AsyncDepthSensor not found for device {}.", $device_index)))?;
+            sensor.depth_frames_stream() // Method name from generate_async_tracker!
+                .map_err(|e_text| $crate::nuitrack::shared_types::error::NuitrackError::OperationFailed(format!("Failed to get DepthSensor stream: {}", e_text)))
+        }
+    };
+    ($session_ref:expr, $device_index:expr, UserTracker) => {
+        {
+            let tracker = $session_ref.active_devices
+                .get_mut($device_index)
+                .and_then(|device_context| device_context.user_tracker.as_mut())
+                .ok_or_else(|| $crate::nuitrack::shared_types::error::NuitrackError::ModuleCreationFailed(format!("AsyncUserTracker not found for device {}.", $device_index)))?;
+            tracker.user_frames_stream()
+                .map_err(|e_text| $crate::nuitrack::shared_types::error::NuitrackError::OperationFailed(format!("Failed to get UserTracker stream: {}", e_text)))
+        }
+    };
+    ($session_ref:expr, $device_index:expr, GestureRecognizer) => {
+        {
+            let tracker = $session_ref.active_devices
+                .get_mut($device_index)
+                .and_then(|device_context| device_context.gesture_recognizer.as_mut())
+                .ok_or_else(|| $crate::nuitrack::shared_types::error::NuitrackError::ModuleCreationFailed(format!("AsyncGestureRecognizer not found for device {}.", $device_index)))?;
+            tracker.completed_gestures_frames_stream()
+                .map_err(|e_text| $crate::nuitrack::shared_types::error::NuitrackError::OperationFailed(format!("Failed to get GestureRecognizer stream: {}", e_text)))
+        }
+    };
+    // To satisfy type checking for the Result in the compile_error! case,
+    // we need a concrete error type. Since the macro is generic over stream types,
+    // this fallback error type doesn't perfectly align with the stream's Ok type.
+    // However, it won't compile anyway due to compile_error!.
+    ($session_ref:expr, $device_index:expr, $unsupported_token:ident) => { {
+        compile_error!(concat!("Unsupported ModuleType token in setup_nuitrack_streams macro: ", stringify!($unsupported_token)));
+        // Provide a valid expression for type inference that results in NuitrackResult<_, NuitrackError>
+        // This part will not actually be executed due to compile_error!
+        Err($crate::nuitrack::shared_types::error::NuitrackError::OperationFailed(format!("Unsupported module: {}", stringify!($unsupported_token))))
+    } };
+}
@@ -7,11 +7,20 @@ pub mod macros;
 pub mod async_dispatch;
 pub mod color_sensor;
 pub mod depth_sensor;
+pub mod device_stream;
+pub mod filtered_skeleton_stream;
+pub mod frame_bundle;
 // generate_tracker (if in order)
+pub mod gesture_event_stream;
 pub mod gesture_recognizer;
 pub mod hand_tracker;
+pub mod health;
+pub mod processed_stream;
 // macros
+#[cfg(feature = "tokio_runtime")]
+mod recovery;
 pub mod session_builder;
 pub mod session;
 pub mod skeleton_tracker;
+pub mod user_event_stream;
 pub mod user_tracker;
\ No newline at end of file
@@ -0,0 +1,121 @@
+//! Stream adapter that turns frames into events with a Rust-side processor.
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use pin_project::pin_project;
+
+use crate::nuitrack::shared_types::{
+    custom_gesture::{CustomGestureEvent, CustomGestureRecognizer},
+    error::Result as NuitrackResult,
+    hand_cursor::{CursorEvent, HandCursorTracker},
+    hand_frame::HandFrame,
+    pose::{PoseClassifier, PoseEvent},
+    skeleton_frame::SkeletonFrame,
+};
+
+/// Something that consumes frames one at a time and emits zero or more events for
+/// each, such as a [`CustomGestureRecognizer`], a [`PoseClassifier`] or a
+/// [`HandCursorTracker`].
+pub trait FrameProcessor {
+    type Frame;
+    type Event;
+
+    fn process_frame(&mut self, frame: &Self::Frame) -> NuitrackResult<Vec<Self::Event>>;
+}
+
+impl FrameProcessor for CustomGestureRecognizer {
+    type Frame = SkeletonFrame;
+    type Event = CustomGestureEvent;
+
+    fn process_frame(&mut self, frame: &SkeletonFrame) -> NuitrackResult<Vec<CustomGestureEvent>> {
+        CustomGestureRecognizer::process_frame(self, frame)
+    }
+}
+
+impl FrameProcessor for PoseClassifier {
+    type Frame = SkeletonFrame;
+    type Event = PoseEvent;
+
+    fn process_frame(&mut self, frame: &SkeletonFrame) -> NuitrackResult<Vec<PoseEvent>> {
+        PoseClassifier::process_frame(self, frame)
+    }
+}
+
+impl FrameProcessor for HandCursorTracker {
+    type Frame = HandFrame;
+    type Event = CursorEvent;
+
+    fn process_frame(&mut self, frame: &HandFrame) -> NuitrackResult<Vec<CursorEvent>> {
+        HandCursorTracker::process_frame(self, frame)
+    }
+}
+
+/// Feeds every frame of a stream to a [`FrameProcessor`] and yields its events.
+/// Errors pass through unchanged.
+///
+/// ```no_run
+/// # use nuitrack_rs::nuitrack::async_api::skeleton_tracker::AsyncSkeletonTracker;
+/// # use nuitrack_rs::nuitrack::async_api::processed_stream::ProcessedStream;
+/// # use nuitrack_rs::nuitrack::shared_types::custom_gesture::{CustomGestureRecognizer, GestureTemplate};
+/// # fn example(tracker: &mut AsyncSkeletonTracker, raise: GestureTemplate) -> nuitrack_rs::nuitrack::shared_types::error::Result<()> {
+/// let recognizer = CustomGestureRecognizer::default().with_template(raise)?;
+/// let gestures = ProcessedStream::new(tracker.skeleton_frames_stream()?, recognizer);
+/// # Ok(())
+/// # }
+/// ```
+#[pin_project]
+pub struct ProcessedStream<S, P: FrameProcessor> {
+    #[pin]
+    stream: S,
+    processor: P,
+    pending: VecDeque<P::Event>,
+}
+
+impl<S, P: FrameProcessor> ProcessedStream<S, P> {
+    pub fn new(stream: S, processor: P) -> Self {
+        Self { stream, processor, pending: VecDeque::new() }
+    }
+
+    pub fn processor(&self) -> &P {
+        &self.processor
+    }
+
+    /// The processor, e.g. to add gesture templates or calibrate cursor regions while
+    /// streaming.
+    pub fn processor_mut(&mut self) -> &mut P {
+        &mut self.processor
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S, P> Stream for ProcessedStream<S, P>
+where
+    S: Stream<Item = NuitrackResult<P::Frame>>,
+    P: FrameProcessor,
+{
+    type Item = NuitrackResult<P::Event>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(event) = this.pending.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(frame))) => match this.processor.process_frame(&frame) {
+                    Ok(events) => this.pending.extend(events),
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                },
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
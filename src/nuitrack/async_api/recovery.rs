@@ -0,0 +1,185 @@
+//! Runtime re-initialization for [`RecoveryPolicy::Reinitialize`](super::health::RecoveryPolicy::Reinitialize).
+//!
+//! Nuitrack cannot re-open a single device, so recovering one means restarting the
+//! whole runtime: release, init with the session's configuration, select every
+//! device again and recreate its modules. Open streams survive because each
+//! tracker's [`ModuleLink`](super::skeleton_tracker::ModuleLink) moves their
+//! callbacks onto the new modules. Trackers only move once every module has been
+//! recreated, so a failed attempt leaves all streams together on the old runtime.
+
+use std::collections::HashMap;
+
+use cxx::SharedPtr;
+use tracing::{debug, info, instrument, warn};
+
+use crate::nuitrack::shared_types::error::{NuitrackError, Result as NuitrackResult};
+use crate::nuitrack::shared_types::session_config::{VideoMode, VideoStream};
+use crate::nuitrack_bridge::core::ffi as core_ffi;
+use crate::nuitrack_bridge::device::ffi as device_ffi;
+
+use super::session::{ActiveDeviceContext, DeviceUpdateModule, RuntimeConfig, WaitableModuleFFIVariant, NUITRACK_GLOBAL_API_LOCK};
+
+type FFIDevice = crate::nuitrack_bridge::device::ffi::Device;
+
+/// A tracker that can be moved onto a freshly created SDK module.
+pub(crate) trait ReattachableModule: Send + Sync {
+    fn prepare_reattach_blocking(&self) -> NuitrackResult<Box<dyn PreparedReattach>>;
+    fn update_module(&self) -> WaitableModuleFFIVariant;
+}
+
+/// A recreated module the tracker has not moved onto yet. Dropping it undoes the preparation.
+pub(crate) trait PreparedReattach: Send {
+    fn commit(self: Box<Self>);
+}
+
+macro_rules! impl_reattachable_module {
+    ($($module:ident => $variant:ident),+ $(,)?) => {
+        $(
+            impl ReattachableModule for super::$module::ModuleLink {
+                fn prepare_reattach_blocking(&self) -> NuitrackResult<Box<dyn PreparedReattach>> {
+                    Ok(Box::new(super::$module::ModuleLink::prepare_reattach_blocking(self)?))
+                }
+
+                fn update_module(&self) -> WaitableModuleFFIVariant {
+                    WaitableModuleFFIVariant::$variant(self.ffi_ptr())
+                }
+            }
+
+            impl PreparedReattach for super::$module::PreparedModule {
+                fn commit(self: Box<Self>) {
+                    super::$module::PreparedModule::commit(*self)
+                }
+            }
+        )+
+    };
+}
+
+impl_reattachable_module!(
+    color_sensor => ColorSensor,
+    skeleton_tracker => Skeleton,
+    hand_tracker => Hand,
+    depth_sensor => DepthSensor,
+    user_tracker => UserTracker,
+    gesture_recognizer => GestureRecognizer,
+);
+
+struct RecoverableDevice {
+    serial_number: String,
+    video_modes: Vec<(VideoStream, VideoMode)>,
+    /// In `ActiveDeviceContext::update_module` priority order, so the first one is waited on.
+    modules: Vec<Box<dyn ReattachableModule>>,
+}
+
+/// Everything needed to rebuild a hardware session's SDK state.
+pub(crate) struct Recovery {
+    runtime: RuntimeConfig,
+    devices: Vec<RecoverableDevice>,
+}
+
+impl Recovery {
+    pub(crate) fn new(runtime: RuntimeConfig, active_devices: &[ActiveDeviceContext]) -> Self {
+        let devices = active_devices
+            .iter()
+            .map(|device| {
+                let links: [Option<Box<dyn ReattachableModule>>; 6] = [
+                    device.color_sensor.as_ref().and_then(|m| m.module_link()).map(|l| Box::new(l) as _),
+                    device.skeleton_tracker.as_ref().and_then(|m| m.module_link()).map(|l| Box::new(l) as _),
+                    device.hand_tracker.as_ref().and_then(|m| m.module_link()).map(|l| Box::new(l) as _),
+                    device.depth_sensor.as_ref().and_then(|m| m.module_link()).map(|l| Box::new(l) as _),
+                    device.user_tracker.as_ref().and_then(|m| m.module_link()).map(|l| Box::new(l) as _),
+                    device.gesture_recognizer.as_ref().and_then(|m| m.module_link()).map(|l| Box::new(l) as _),
+                ];
+                RecoverableDevice {
+                    serial_number: device.info.serial_number.clone(),
+                    video_modes: device.video_modes.clone(),
+                    modules: links.into_iter().flatten().collect(),
+                }
+            })
+            .collect();
+        Self { runtime, devices }
+    }
+
+    /// Looks up every session device in the SDK's current device list.
+    /// Must be called with the global API lock held.
+    fn find_devices(&self) -> NuitrackResult<Option<Vec<SharedPtr<FFIDevice>>>> {
+        let list = device_ffi::devices().map_err(|e| NuitrackError::DeviceError(format!("FFI GetDeviceList: {}", e)))?;
+        let available: HashMap<String, SharedPtr<FFIDevice>> = list
+            .iter()
+            .map(|wrapped| {
+                let device = device_ffi::unwrap_shared_ptr_device(wrapped);
+                let serial = device_ffi::device_info(&device, device_ffi::DeviceInfoType::SERIAL_NUMBER).unwrap_or_default();
+                (serial, device)
+            })
+            .collect();
+        Ok(self.devices.iter().map(|d| available.get(&d.serial_number).cloned()).collect())
+    }
+
+    /// Whether every session device is listed by the SDK. A failing probe counts as
+    /// present, since only a restart can tell.
+    #[instrument(skip(self))]
+    pub(crate) fn devices_present_blocking(&self) -> NuitrackResult<bool> {
+        let _g_lock = NUITRACK_GLOBAL_API_LOCK
+            .lock()
+            .map_err(|_| NuitrackError::OperationFailed("Global API lock poisoned for device probe".into()))?;
+        match self.find_devices() {
+            Ok(found) => Ok(found.is_some()),
+            Err(e) => {
+                debug!(error = %e, "Device probe failed; a runtime restart will be attempted.");
+                Ok(true)
+            }
+        }
+    }
+
+    /// Restarts the runtime and re-attaches every module. Returns the modules the
+    /// new update tasks should wait on.
+    ///
+    /// The caller must make sure no update task is still inside `waitUpdate`. On
+    /// failure the runtime may be left released; the trackers stay on their old modules.
+    #[instrument(skip(self))]
+    pub(crate) fn reinitialize_blocking(&self) -> NuitrackResult<Vec<DeviceUpdateModule>> {
+        let _g_lock = NUITRACK_GLOBAL_API_LOCK
+            .lock()
+            .map_err(|_| NuitrackError::OperationFailed("Global API lock poisoned for runtime re-initialization".into()))?;
+
+        if let Err(e) = core_ffi::release() {
+            warn!(error = %e, "Nuitrack::release failed before re-initialization.");
+        }
+        core_ffi::init(&self.runtime.config_path)
+            .map_err(|e| NuitrackError::InitFailed(format!("FFI init_nuitrack: {}", e)))?;
+        for (key, value) in &self.runtime.config_values {
+            core_ffi::set_config_value(key, value)
+                .map_err(|e| NuitrackError::InitFailed(format!("FFI set_config_value for key '{}': {}", key, e)))?;
+        }
+
+        let ptrs = self.find_devices()?.ok_or_else(|| {
+            NuitrackError::DeviceError("A session device disappeared during runtime re-initialization.".into())
+        })?;
+        let mut prepared = Vec::new();
+        for (device, ptr) in self.devices.iter().zip(&ptrs) {
+            device_ffi::set_device(ptr)
+                .map_err(|e| NuitrackError::DeviceError(format!("FFI Nuitrack::setDevice failed: {}", e)))?;
+            for &(stream, mode) in &device.video_modes {
+                device_ffi::set_video_mode(ptr, stream.into(), mode)
+                    .map_err(|e| NuitrackError::DeviceError(format!("FFI setVideoMode ({:?}) failed: {}", stream, e)))?;
+            }
+            for module in &device.modules {
+                prepared.push(module.prepare_reattach_blocking()?);
+            }
+            debug!(serial = %device.serial_number, modules = device.modules.len(), "Device modules recreated.");
+        }
+        prepared.into_iter().for_each(|module| module.commit());
+        core_ffi::run().map_err(|e| NuitrackError::OperationFailed(format!("FFI Nuitrack::run: {}", e)))?;
+        info!("Nuitrack runtime re-initialized.");
+
+        Ok(self
+            .devices
+            .iter()
+            .filter_map(|device| {
+                device.modules.first().map(|module| DeviceUpdateModule {
+                    serial_number: device.serial_number.clone(),
+                    module: module.update_module(),
+                })
+            })
+            .collect())
+    }
+}
@@ -2,18 +2,22 @@
 use tokio::{task::JoinHandle, sync::Mutex as TokioMutex};
 #[cfg(feature = "tokio_runtime")] // This import is only needed if tokio_runtime is active
 use tokio_util::sync::CancellationToken;
-#[cfg(feature = "tokio_runtime")] // Arc is from std, but used with Tokio types here for clarity
-use std::sync::Arc;
-
 use tracing::{debug, error, info, trace, info_span, instrument, trace_span, warn, Instrument};
-use std::{collections::HashMap, sync::atomic::{AtomicBool, Ordering}};
+use std::{collections::HashMap, sync::{Arc, atomic::{AtomicBool, Ordering}}};
 use cxx::SharedPtr; // Used by WaitableModuleFfiVariant
 
+use futures_core::Stream;
+
+use super::device_stream::{DeviceTaggedStream, MergedDeviceStream};
+use super::health::{DeviceHealth, HealthConfig, HealthMonitor, SessionEventStream};
+#[cfg(feature = "tokio_runtime")]
+use super::{health::RecoveryPolicy, recovery::Recovery};
 use super::{async_dispatch::run_blocking, hand_tracker::AsyncHandTracker, skeleton_tracker::AsyncSkeletonTracker, color_sensor::AsyncColorSensor, depth_sensor::AsyncDepthSensor};
 
-use crate::nuitrack::{async_api::{gesture_recognizer::AsyncGestureRecognizer, user_tracker::AsyncUserTracker}, shared_types::{
+use crate::nuitrack::{async_api::{gesture_recognizer::AsyncGestureRecognizer, user_tracker::AsyncUserTracker}, backend::virtual_device::BackendDriver, shared_types::{
     error::{NuitrackError, Result as NuitrackResult}, 
-    session_config::DiscoveredDeviceInfo
+    rgbd_frame::Registration,
+    session_config::{DiscoveredDeviceInfo, VideoMode, VideoStream}
 }};
 use crate::nuitrack_bridge::core::ffi as core_ffi;
 
@@ -26,6 +30,14 @@ type FFIDepthSensor = crate::nuitrack_bridge::modules::depth_sensor::ffi::DepthS
 type FFIUserTracker = crate::nuitrack_bridge::modules::user_tracker::ffi::UserTracker;
 type FFIGestureRecognizer = crate::nuitrack_bridge::modules::gesture_recognizer::ffi::GestureRecognizer;
 
+/// Pause after a failed `waitUpdate` so a disconnected device does not spin its update task.
+#[cfg(feature = "tokio_runtime")]
+const UPDATE_ERROR_BACKOFF: std::time::Duration = std::time::Duration::from_millis(100);
+/// How long recovery waits for cancelled update tasks to return from `waitUpdate`
+/// before postponing the restart to the next attempt.
+#[cfg(feature = "tokio_runtime")]
+const UPDATE_TASK_JOIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
 // --- Make these crate-visible ---
 pub(crate) static IS_NUITRACK_RUNTIME_INITIALIZED: AtomicBool = AtomicBool::new(false);
 pub(crate) static NUITRACK_GLOBAL_API_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
@@ -40,9 +52,59 @@ pub struct ActiveDeviceContext {
     pub depth_sensor: Option<AsyncDepthSensor>,
     pub user_tracker: Option<AsyncUserTracker>,
     pub gesture_recognizer: Option<AsyncGestureRecognizer>,
+    /// Position of this device in `NuitrackSession::active_devices`.
+    pub(crate) device_index: usize,
+    /// Modes selected through the device's `DeviceConfig`, re-applied on recovery.
+    pub(crate) video_modes: Vec<(VideoStream, VideoMode)>,
     // pub other_tracker: Option<AsyncOtherTracker>, // Example for your second tracker
 }
 
+impl ActiveDeviceContext {
+    pub fn serial_number(&self) -> &str {
+        &self.info.serial_number
+    }
+
+    /// The mode selected for `stream` by the device's `DeviceConfig`, if it requested one.
+    /// Use the sensor's `output_mode()` for the mode actually in effect.
+    pub fn video_mode(&self, stream: VideoStream) -> Option<VideoMode> {
+        self.video_modes.iter().find(|(s, _)| *s == stream).map(|(_, mode)| *mode)
+    }
+
+    /// A [`Registration`] from the depth and color sensors' current output modes, or
+    /// `None` if the device lacks either sensor.
+    pub async fn registration(&self) -> NuitrackResult<Option<Registration>> {
+        let (Some(depth), Some(color)) = (&self.depth_sensor, &self.color_sensor) else {
+            return Ok(None);
+        };
+        Ok(Some(Registration::new(depth.output_mode().await?, color.output_mode().await?)))
+    }
+
+    /// Tags every item of `stream` with this device's index and serial number.
+    pub fn tag<S: Stream>(&self, stream: S) -> DeviceTaggedStream<S> {
+        DeviceTaggedStream::new(stream, self.device_index, Arc::from(self.info.serial_number.as_str()))
+    }
+
+    /// The module waited on to detect this device's updates, in priority order
+    /// color > skeleton > hand > depth > user > gesture.
+    pub(crate) fn update_module(&self) -> NuitrackResult<Option<WaitableModuleFFIVariant>> {
+        Ok(if let Some(cs) = &self.color_sensor {
+            Some(WaitableModuleFFIVariant::ColorSensor(cs.get_ffi_ptr_clone()?))
+        } else if let Some(st) = &self.skeleton_tracker {
+            Some(WaitableModuleFFIVariant::Skeleton(st.get_ffi_ptr_clone()?))
+        } else if let Some(ht) = &self.hand_tracker {
+            Some(WaitableModuleFFIVariant::Hand(ht.get_ffi_ptr_clone()?))
+        } else if let Some(ds) = &self.depth_sensor {
+            Some(WaitableModuleFFIVariant::DepthSensor(ds.get_ffi_ptr_clone()?))
+        } else if let Some(ut) = &self.user_tracker {
+            Some(WaitableModuleFFIVariant::UserTracker(ut.get_ffi_ptr_clone()?))
+        } else if let Some(gr) = &self.gesture_recognizer {
+            Some(WaitableModuleFFIVariant::GestureRecognizer(gr.get_ffi_ptr_clone()?))
+        } else {
+            None
+        })
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct NuitrackRuntimeGuard(());
 
@@ -137,51 +199,116 @@ pub(crate) enum WaitableModuleFFIVariant {
     // Add other waitable module FFI types here
 }
 
+impl WaitableModuleFFIVariant {
+    /// Blocks (on the blocking pool) until this module's next update.
+    pub(crate) async fn wait_update(&self) -> NuitrackResult<()> {
+        macro_rules! wait_on {
+            ($ptr:expr, $function:ident) => {{
+                let ptr_clone = $ptr.clone();
+                trace_span!("ffi", function = stringify!($function)).in_scope(|| {
+                    run_blocking(move || {
+                        core_ffi::$function(&ptr_clone)
+                            .map_err(|e| NuitrackError::OperationFailed(format!("FFI {}: {}", stringify!($function), e)))
+                    })
+                }).await
+            }};
+        }
+        match self {
+            WaitableModuleFFIVariant::ColorSensor(ptr) => wait_on!(ptr, wait_update_color_sensor),
+            WaitableModuleFFIVariant::Hand(ptr) => wait_on!(ptr, wait_update_hand_tracker),
+            WaitableModuleFFIVariant::Skeleton(ptr) => wait_on!(ptr, wait_update_skeleton_tracker),
+            WaitableModuleFFIVariant::DepthSensor(ptr) => wait_on!(ptr, wait_update_depth_sensor),
+            WaitableModuleFFIVariant::UserTracker(ptr) => wait_on!(ptr, wait_update_user_tracker),
+            WaitableModuleFFIVariant::GestureRecognizer(ptr) => wait_on!(ptr, wait_update_gesture_recognizer),
+        }
+    }
+}
+
+/// Settings the Nuitrack runtime is initialized with; kept so it can be restarted.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RuntimeConfig {
+    pub(crate) config_path: String,
+    pub(crate) config_values: HashMap<String, String>,
+}
+
+/// The module an internal update task waits on, tagged with the device it belongs to.
+#[derive(Clone)]
+pub(crate) struct DeviceUpdateModule {
+    pub(crate) serial_number: String,
+    pub(crate) module: WaitableModuleFFIVariant,
+}
+
 
 pub struct NuitrackSession {
-    pub(crate) guard: NuitrackRuntimeGuard, // Make pub(crate) if builder is in different file but same module tree
+    pub(crate) guard: Option<NuitrackRuntimeGuard>, // None when the session is driven by a backend
     pub active_devices: Vec<ActiveDeviceContext>,
+    backend: Option<Arc<BackendDriver>>,
     run_internal_update_loop: bool,
+    health: Arc<HealthMonitor>,
+    #[cfg(feature = "tokio_runtime")]
+    recovery: Option<Arc<Recovery>>,
     
     // Store the FFI pointers for the internal loop directly
     #[cfg(feature = "tokio_runtime")]
-    modules_for_internal_loop: Vec<WaitableModuleFFIVariant>, // New field
+    modules_for_internal_loop: Vec<DeviceUpdateModule>,
 
     #[cfg(feature = "tokio_runtime")]
     cancellation_token: Option<Arc<CancellationToken>>,
     #[cfg(feature = "tokio_runtime")]
-    update_task_handle: Option<Arc<TokioMutex<Option<JoinHandle<()>>>>>,
+    update_task_handle: Option<Arc<TokioMutex<Vec<JoinHandle<()>>>>>,
 }
 
 impl NuitrackSession {
-    #[instrument(skip(guard, active_devices, modules_for_update_loop))]
+    #[instrument(skip(guard, backend, active_devices, modules_for_update_loop, runtime_config))]
     pub(crate) fn new(
-        guard: NuitrackRuntimeGuard,
+        guard: Option<NuitrackRuntimeGuard>,
+        backend: Option<Arc<BackendDriver>>,
         active_devices: Vec<ActiveDeviceContext>,
-        modules_for_update_loop: Vec<WaitableModuleFFIVariant>,
+        modules_for_update_loop: Vec<DeviceUpdateModule>,
         run_internal_update_loop: bool,
+        health_config: HealthConfig,
+        runtime_config: Option<RuntimeConfig>,
     ) -> NuitrackResult<Self> {
         debug!(
             num_devices = active_devices.len(),
             num_update_modules = modules_for_update_loop.len(),
             internal_loop_enabled = run_internal_update_loop,
+            backend_driven = backend.is_some(),
             "Creating new NuitrackSession."
         );
+        let health = Arc::new(HealthMonitor::new(
+            health_config,
+            active_devices.iter().map(|d| d.info.serial_number.clone()),
+        ));
+        #[cfg(feature = "tokio_runtime")]
+        let recovery = match (&health.config().recovery, runtime_config) {
+            (RecoveryPolicy::Reinitialize { .. }, Some(runtime)) => Some(Arc::new(Recovery::new(runtime, &active_devices))),
+            _ => None,
+        };
+        #[cfg(not(feature = "tokio_runtime"))]
+        let _ = runtime_config;
         Ok(Self {
             guard,
             active_devices,
+            backend,
             run_internal_update_loop,
+            health,
+            #[cfg(feature = "tokio_runtime")]
+            recovery,
             #[cfg(feature = "tokio_runtime")]
             modules_for_internal_loop: if run_internal_update_loop { modules_for_update_loop } else { Vec::new() },
             #[cfg(feature = "tokio_runtime")]
             cancellation_token: if run_internal_update_loop { Some(Arc::new(CancellationToken::new())) } else { None },
             #[cfg(feature = "tokio_runtime")]
-            update_task_handle: if run_internal_update_loop { Some(Arc::new(TokioMutex::new(None))) } else { None },
+            update_task_handle: if run_internal_update_loop { Some(Arc::new(TokioMutex::new(Vec::new()))) } else { None },
         })
     }
 
     #[instrument(skip(self), name = "nuitrack_start_processing")]
     pub async fn start_processing(&self) -> NuitrackResult<()> {
+        if let Some(driver) = &self.backend {
+            return self.start_backend_processing(driver).await;
+        }
         {
             trace_span!("ffi", function = "Nuitrack::run").in_scope(|| {
                     run_blocking(|| {
@@ -197,127 +324,321 @@ impl NuitrackSession {
         {
             debug!("Tokio runtime detected.");
             if self.run_internal_update_loop {
-                if let (Some(token_arc), Some(task_handle_mutex_arc)) =
+                if let (Some(token_arc), Some(task_handles_arc)) =
                     (&self.cancellation_token, &self.update_task_handle)
                 {
+                    let mut handles = task_handles_arc.lock().await;
+                    if self.modules_for_internal_loop.is_empty() {
+                        if !self.active_devices.is_empty() {
+                            warn!("Internal update loop started but no specific modules collected for waitUpdate. Loop will use global Nuitrack::update().");
+                        }
+                        handles.push(tokio::spawn(
+                            Self::global_update_loop(Arc::clone(token_arc), !self.active_devices.is_empty())
+                                .instrument(info_span!("nuitrack_internal_update_loop")),
+                        ));
+                    } else {
+                        // One task per device, so a slow or stalled sensor does not hold back the others.
+                        // They run under a child token that recovery cancels before restarting the runtime,
+                        // and the watchdog owns their handles so it can wait for them first.
+                        let generation = token_arc.child_token();
+                        let device_tasks = Self::spawn_device_update_tasks(
+                            token_arc,
+                            &generation,
+                            &self.modules_for_internal_loop,
+                            &self.health,
+                        );
+                        self.health.reset_clocks();
+                        handles.push(tokio::spawn(
+                            Self::watchdog_loop(
+                                Arc::clone(token_arc),
+                                Arc::clone(&self.health),
+                                self.recovery.clone(),
+                                generation,
+                                device_tasks,
+                            )
+                            .instrument(info_span!("nuitrack_health_watchdog")),
+                        ));
+                    }
+                    debug!(tasks = handles.len(), "Internal update tasks spawned.");
+                } else {
+                    error!("Internal logic error: update loop components were missing when run_internal_update_loop was true.");
+                }
+            }
+        }
+        #[cfg(not(feature = "tokio_runtime"))]
+        {
+            if self.run_internal_update_loop {
+                warn!("Internal update loop was requested, but 'tokio_runtime' feature is not enabled. Manual updates via drive_update_cycle() are required.");
+            }
+        }
+        Ok(())
+    }
 
-                    let token = Arc::clone(token_arc);
-                    let task_handle_mutex = Arc::clone(task_handle_mutex_arc);
-                    
-                    let active_devices_are_present = !self.active_devices.is_empty();
-                    // modules_for_internal_loop is moved into the spawned task
-                    let modules_to_wait_on = self.modules_for_internal_loop.clone(); 
-
-                    if modules_to_wait_on.is_empty() && !self.active_devices.is_empty() {
-                        warn!("Internal update loop started but no specific modules collected for waitUpdate. Loop will use global Nuitrack::update().");
+    #[cfg(feature = "tokio_runtime")]
+    fn spawn_device_update_tasks(
+        root: &Arc<CancellationToken>,
+        generation: &CancellationToken,
+        modules: &[DeviceUpdateModule],
+        health: &Arc<HealthMonitor>,
+    ) -> Vec<JoinHandle<()>> {
+        modules
+            .iter()
+            .map(|device_module| {
+                tokio::spawn(
+                    Self::device_update_loop(
+                        Arc::clone(root),
+                        generation.clone(),
+                        device_module.clone(),
+                        Arc::clone(health),
+                    )
+                    .instrument(info_span!("nuitrack_internal_update_loop", serial = %device_module.serial_number)),
+                )
+            })
+            .collect()
+    }
+
+    /// Waits on one device's representative module until cancelled or a fatal error occurs.
+    /// A fatal error cancels the root token, stopping the other devices' tasks as well;
+    /// other errors count towards the device being reported as lost.
+    ///
+    /// Cancellation is only observed between calls: an in-flight `waitUpdate` keeps running
+    /// on the blocking pool even if its future is dropped, so the task waits for it to
+    /// return. Once the task has finished, no SDK call made on its behalf is still running.
+    #[cfg(feature = "tokio_runtime")]
+    async fn device_update_loop(
+        root: Arc<CancellationToken>,
+        generation: CancellationToken,
+        device_module: DeviceUpdateModule,
+        health: Arc<HealthMonitor>,
+    ) {
+        debug!("Task started.");
+        loop {
+            if generation.is_cancelled() {
+                info!("Cancellation received.");
+                break;
+            }
+            let pause = match device_module.module.wait_update().await {
+                Ok(()) => {
+                    health.record_update(&device_module.serial_number);
+                    // Paces the loop
+                    std::time::Duration::from_millis(1)
+                }
+                Err(e) => {
+                    error!(error = %e, "Error in module waitUpdate");
+                    if NuitrackSession::is_fatal_error(&e) { root.cancel(); break; }
+                    health.record_error(&device_module.serial_number, &e);
+                    UPDATE_ERROR_BACKOFF
+                }
+            };
+            tokio::select! {
+                biased;
+                _ = generation.cancelled() => {}
+                _ = tokio::time::sleep(pause) => {}
+            }
+        }
+        debug!("Task stopped.");
+    }
+
+    /// Waits for cancelled update tasks to finish. Returns `false` if some are still
+    /// inside `waitUpdate` after `UPDATE_TASK_JOIN_TIMEOUT`; those stay in `tasks`.
+    #[cfg(feature = "tokio_runtime")]
+    async fn join_update_tasks(tasks: &mut Vec<JoinHandle<()>>) -> bool {
+        let deadline = tokio::time::Instant::now() + UPDATE_TASK_JOIN_TIMEOUT;
+        while let Some(handle) = tasks.last_mut() {
+            match tokio::time::timeout_at(deadline, handle).await {
+                Ok(Err(e)) => error!(join_error = ?e, "Internal update task panicked or was cancelled."),
+                Ok(Ok(())) => {}
+                Err(_) => return false,
+            }
+            tasks.pop();
+        }
+        true
+    }
+
+    /// Periodically checks for stalled devices and, with `RecoveryPolicy::Reinitialize`,
+    /// restarts the runtime once every lost device is listed by the SDK again.
+    ///
+    /// The watchdog owns the device update tasks: it stops and joins them before each
+    /// restart, and joins them when the session shuts down.
+    #[cfg(feature = "tokio_runtime")]
+    async fn watchdog_loop(
+        root: Arc<CancellationToken>,
+        health: Arc<HealthMonitor>,
+        recovery: Option<Arc<Recovery>>,
+        mut generation: CancellationToken,
+        mut device_tasks: Vec<JoinHandle<()>>,
+    ) {
+        let config = health.config().clone();
+        let tick = (config.stall_timeout / 4).clamp(std::time::Duration::from_millis(10), std::time::Duration::from_millis(250));
+        let mut attempts = 0u32;
+        let mut last_attempt: Option<std::time::Instant> = None;
+        // Set while a failed restart may have left the runtime released.
+        let mut runtime_down = false;
+        debug!(?tick, "Task started.");
+        loop {
+            tokio::select! {
+                biased;
+                _ = root.cancelled() => break,
+                _ = tokio::time::sleep(tick) => {}
+            }
+            health.check_stalls();
+
+            let (Some(recovery), RecoveryPolicy::Reinitialize { retry_interval, max_attempts }) = (&recovery, config.recovery) else {
+                continue;
+            };
+            let lost = health.lost_devices();
+            if lost.is_empty() && !runtime_down {
+                attempts = 0;
+                continue;
+            }
+            if last_attempt.is_some_and(|at| at.elapsed() < retry_interval) || max_attempts.is_some_and(|max| attempts >= max) {
+                continue;
+            }
+            last_attempt = Some(std::time::Instant::now());
+
+            // The SDK can only be probed while the runtime is up; after a failed restart,
+            // go straight to the next one.
+            if !runtime_down {
+                let probe = Arc::clone(recovery);
+                match run_blocking(move || probe.devices_present_blocking()).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        debug!(?lost, "Lost devices are not back yet.");
+                        continue;
+                    }
+                    Err(e) => {
+                        error!(error = %e, "Device probe failed.");
+                        continue;
+                    }
+                }
+            }
+
+            generation.cancel();
+            if !Self::join_update_tasks(&mut device_tasks).await {
+                warn!(tasks = device_tasks.len(), "Update tasks are still inside waitUpdate; postponing the runtime restart.");
+                continue;
+            }
+
+            attempts += 1;
+            info!(attempt = attempts, ?lost, "Re-initializing Nuitrack runtime.");
+            let reinit = Arc::clone(recovery);
+            let modules = match run_blocking(move || reinit.reinitialize_blocking()).await {
+                Ok(modules) => modules,
+                Err(e) => {
+                    // The old modules may belong to a released runtime, so no task may wait on them.
+                    error!(error = %e, "Runtime re-initialization failed; retrying at the next interval.");
+                    runtime_down = true;
+                    continue;
+                }
+            };
+            runtime_down = false;
+            if root.is_cancelled() {
+                break;
+            }
+            generation = root.child_token();
+            device_tasks = Self::spawn_device_update_tasks(&root, &generation, &modules, &health);
+        }
+        generation.cancel();
+        if !Self::join_update_tasks(&mut device_tasks).await {
+            warn!(tasks = device_tasks.len(), "Update tasks did not return from waitUpdate before shutdown.");
+        }
+        debug!("Task stopped.");
+    }
+
+    /// Fallback loop used when no device contributed a module to wait on.
+    #[cfg(feature = "tokio_runtime")]
+    async fn global_update_loop(token: Arc<CancellationToken>, active_devices_are_present: bool) {
+        debug!("Task started.");
+        loop {
+            tokio::select! {
+                biased;
+                _ = token.cancelled() => {
+                    info!("Cancellation received.");
+                    break;
+                }
+                _ = tokio::time::sleep(std::time::Duration::from_millis(1)) => {
+                    if !active_devices_are_present {
+                        continue;
+                    }
+                    if let Err(e) = trace_span!("ffi", function="Nuitrack::update").in_scope(|| {
+                        run_blocking(|| {
+                            core_ffi::update()
+                                .map_err(|cxx_e| NuitrackError::OperationFailed(format!("FFI Nuitrack::update in internal loop: {}", cxx_e)))
+                        })
+                    }).await {
+                        error!(error = %e, "Error in global Nuitrack::update");
+                        if NuitrackSession::is_fatal_error(&e) { token.cancel(); break; }
                     }
+                }
+            }
+        }
+        debug!("Task stopped.");
+    }
 
+    /// Backend counterpart of `start_processing`: there is no SDK thread to start,
+    /// so the internal loop (if enabled) simply keeps pulling batches from the backend.
+    async fn start_backend_processing(&self, driver: &Arc<BackendDriver>) -> NuitrackResult<()> {
+        info!("Backend-driven session started.");
+        if driver.manual_stepping() {
+            info!("Backend is stepped manually; call drive_update_cycle() to advance it.");
+            return Ok(());
+        }
+        #[cfg(feature = "tokio_runtime")]
+        {
+            if self.run_internal_update_loop {
+                if let (Some(token_arc), Some(task_handle_mutex_arc)) =
+                    (&self.cancellation_token, &self.update_task_handle)
+                {
+                    let token = Arc::clone(token_arc);
+                    let driver = Arc::clone(driver);
+                    let health = Arc::clone(&self.health);
                     let update_task = tokio::spawn(
                         async move {
                             debug!("Task started.");
-                            'update_loop: loop {
+                            loop {
+                                let driver_for_step = Arc::clone(&driver);
                                 tokio::select! {
                                     biased;
                                     _ = token.cancelled() => {
                                         info!("Cancellation received.");
-                                        break 'update_loop;
+                                        break;
                                     }
-                                    _ = tokio::time::sleep(std::time::Duration::from_millis(1)) => { // Paces the loop
-                                        if !modules_to_wait_on.is_empty() {
-                                            for module_variant in &modules_to_wait_on {
-                                                if token.is_cancelled() { break 'update_loop; }
-
-                                                // TODO: trace!(module = ?module_variant, "Calling module-specific waitUpdate.");
-
-                                                let wait_result = match module_variant {
-                                                    WaitableModuleFFIVariant::ColorSensor(ptr) => {
-                                                        let ptr_clone = ptr.clone();
-                                                        trace_span!("ffi", function="wait_update_color_sensor").in_scope(|| {
-                                                            run_blocking(move || {
-                                                                core_ffi::wait_update_color_sensor(&ptr_clone)
-                                                                    .map_err(|e_inner| NuitrackError::OperationFailed(format!("FFI wait_update_hand_tracker: {}", e_inner)))
-                                                            })
-                                                        }).await
-                                                    }
-                                                    WaitableModuleFFIVariant::Hand(ptr) => {
-                                                        let ptr_clone = ptr.clone();
-                                                        trace_span!("ffi", function="wait_update_hand_tracker").in_scope(|| {
-                                                            run_blocking(move || {
-                                                                core_ffi::wait_update_hand_tracker(&ptr_clone)
-                                                                    .map_err(|e_inner| NuitrackError::OperationFailed(format!("FFI wait_update_hand_tracker: {}", e_inner)))
-                                                            })
-                                                        }).await
-                                                    }
-                                                    WaitableModuleFFIVariant::Skeleton(ptr) => {
-                                                        let ptr_clone = ptr.clone();
-                                                        trace_span!("ffi", function="wait_update_skeleton_tracker").in_scope(|| {
-                                                            run_blocking(move || {
-                                                                // Ensure you have this FFI function bridged:
-                                                                core_ffi::wait_update_skeleton_tracker(&ptr_clone)
-                                                                    .map_err(|e_inner| NuitrackError::OperationFailed(format!("FFI wait_update_skeleton_tracker: {}", e_inner)))
-                                                            })
-                                                        }).await
-                                                    }
-                                                    WaitableModuleFFIVariant::DepthSensor(ptr) => {
-                                                        let ptr_clone = ptr.clone();
-                                                        trace_span!("ffi", function="wait_update_depth_sensor").in_scope(|| {
-                                                            run_blocking(move || {
-                                                                // Assuming you have this FFI function bridged:
-                                                                core_ffi::wait_update_depth_sensor(&ptr_clone)
-                                                                    .map_err(|e_inner| NuitrackError::OperationFailed(format!("FFI wait_update_depth_sensor: {}", e_inner)))
-                                                            })
-                                                        }).await
-                                                    }
-                                                    WaitableModuleFFIVariant::UserTracker(ptr) => { // ADD THIS BLOCK
-                                                        let ptr_clone = ptr.clone();
-                                                        trace_span!("ffi", function="wait_update_user_tracker").in_scope(|| {
-                                                            run_blocking(move || {
-                                                                // Ensure you have this FFI function bridged:
-                                                                core_ffi::wait_update_user_tracker(&ptr_clone)
-                                                                    .map_err(|e_inner| NuitrackError::OperationFailed(format!("FFI wait_update_user_tracker: {}", e_inner)))
-                                                            })
-                                                        }).await
-                                                    }
-                                                    WaitableModuleFFIVariant::GestureRecognizer(ptr) => {
-                                                        let ptr_clone = ptr.clone();
-                                                        trace_span!("ffi", function="wait_update_gesture_recognizer").in_scope(|| {
-                                                            run_blocking(move || {
-                                                                core_ffi::wait_update_gesture_recognizer(&ptr_clone)
-                                                                    .map_err(|e_inner| NuitrackError::OperationFailed(format!("FFI wait_update_gesture_recognizer: {}", e_inner)))
-                                                            })
-                                                        }).await
-                                                    }
-                                                };
-                                                if let Err(e) = wait_result {
-                                                    error!(error = %e, "Error in module waitUpdate");
-                                                    if NuitrackSession::is_fatal_error(&e) { token.cancel(); break 'update_loop; }
-                                                }
+                                    step_result = run_blocking(move || driver_for_step.step()) => {
+                                        match step_result {
+                                            Ok(Some(device_index)) => {
+                                                health.record_update(&driver.devices()[device_index].descriptor().serial_number);
                                             }
-                                        } else if !token.is_cancelled() && active_devices_are_present { 
-                                            debug!("No specific modules to wait on; falling back to global Nuitrack::update().");
-                                            // Fallback to global update if no specific modules, but devices are active
-                                            if let Err(e) = trace_span!("ffi", function="Nuitrack::update").in_scope(|| {
-                                                run_blocking(|| {
-                                                    core_ffi::update()
-                                                        .map_err(|cxx_e| NuitrackError::OperationFailed(format!("FFI Nuitrack::update in internal loop: {}", cxx_e)))
-                                                })
-                                            }).await { // Assuming nuitrack_update takes no args
-                                                error!(error = %e, "Error in global Nuitrack::update");
-                                                if NuitrackSession::is_fatal_error(&e) { token.cancel(); break 'update_loop; }
+                                            Ok(None) => {
+                                                info!("Backend has no more data; stopping update loop.");
+                                                health.finish();
+                                                break;
+                                            }
+                                            Err(e) => {
+                                                error!(error = %e, "Error while stepping backend; stopping update loop.");
+                                                health.fail(&e);
+                                                break;
                                             }
-                                        } else if token.is_cancelled() { // Ensure break if cancelled after module loop
-                                            break 'update_loop;
                                         }
                                     }
                                 }
                             }
                             debug!("Task stopped.");
                         }
-                        .instrument(info_span!("nuitrack_internal_update_loop")),
+                        .instrument(info_span!("nuitrack_backend_update_loop")),
                     );
-                    let mut handle_guard = task_handle_mutex.lock().await;
-                    *handle_guard = Some(update_task);
-
+                    let mut handles = task_handle_mutex_arc.lock().await;
+                    handles.push(update_task);
+                    self.health.reset_clocks();
+                    handles.push(tokio::spawn(
+                        Self::watchdog_loop(
+                            Arc::clone(token_arc),
+                            Arc::clone(&self.health),
+                            None,
+                            token_arc.child_token(),
+                            Vec::new(),
+                        )
+                        .instrument(info_span!("nuitrack_health_watchdog")),
+                    ));
                 } else {
                     error!("Internal logic error: update loop components were missing when run_internal_update_loop was true.");
                 }
@@ -325,6 +646,7 @@ impl NuitrackSession {
         }
         #[cfg(not(feature = "tokio_runtime"))]
         {
+            let _ = driver;
             if self.run_internal_update_loop {
                 warn!("Internal update loop was requested, but 'tokio_runtime' feature is not enabled. Manual updates via drive_update_cycle() are required.");
             }
@@ -332,8 +654,78 @@ impl NuitrackSession {
         Ok(())
     }
 
+    /// Subscribes to device health events. Every call returns an independent stream.
+    ///
+    /// Stall detection needs the internal update loop, or regular
+    /// `drive_update_cycle` calls for sessions without it.
+    pub fn events_stream(&self) -> SessionEventStream {
+        self.health.subscribe()
+    }
+
+    pub fn device_health(&self, serial_number: &str) -> Option<DeviceHealth> {
+        self.health.health(serial_number)
+    }
+
+    /// The active device with the given serial number.
+    pub fn device(&self, serial_number: &str) -> Option<&ActiveDeviceContext> {
+        self.active_devices.iter().find(|d| d.info.serial_number == serial_number)
+    }
+
+    pub fn device_mut(&mut self, serial_number: &str) -> Option<&mut ActiveDeviceContext> {
+        self.active_devices.iter_mut().find(|d| d.info.serial_number == serial_number)
+    }
+
+    /// Position of the device with the given serial number in `active_devices`.
+    pub fn device_index(&self, serial_number: &str) -> Option<usize> {
+        self.active_devices.iter().position(|d| d.info.serial_number == serial_number)
+    }
+
+    /// Takes one stream from every device that provides it and merges them into a
+    /// single device-tagged stream. Devices for which `take` returns `None` are skipped.
+    ///
+    /// ```no_run
+    /// # fn example(session: &mut nuitrack_rs::nuitrack::async_api::session::NuitrackSession)
+    /// #     -> nuitrack_rs::nuitrack::shared_types::error::Result<()> {
+    /// let skeletons = session.merged_streams(|device| {
+    ///     device.skeleton_tracker.as_mut().map(|tracker| tracker.skeleton_frames_stream())
+    /// })?;
+    /// # Ok(()) }
+    /// ```
+    pub fn merged_streams<S, F>(&mut self, mut take: F) -> NuitrackResult<MergedDeviceStream<S>>
+    where
+        S: Stream,
+        F: FnMut(&mut ActiveDeviceContext) -> Option<NuitrackResult<S>>,
+    {
+        let mut streams = Vec::new();
+        for device in &mut self.active_devices {
+            if let Some(stream) = take(device) {
+                streams.push(device.tag(stream?));
+            }
+        }
+        if streams.is_empty() {
+            return Err(NuitrackError::OperationFailed("No active device provides the requested stream".into()));
+        }
+        Ok(MergedDeviceStream::new(streams))
+    }
+
     #[instrument(skip(self))]
     pub async fn drive_update_cycle(&self) -> NuitrackResult<()> {
+        if let Some(driver) = &self.backend {
+            let driver = Arc::clone(driver);
+            let stepped = Arc::clone(&driver);
+            match run_blocking(move || stepped.step()).await {
+                Ok(Some(device_index)) => self.health.record_update(&driver.devices()[device_index].descriptor().serial_number),
+                Ok(None) => {
+                    debug!("Backend has no more data.");
+                    self.health.finish();
+                }
+                Err(e) => {
+                    self.health.fail(&e);
+                    return Err(e);
+                }
+            }
+            return Ok(());
+        }
         if self.active_devices.is_empty() {
             // No active devices, perhaps a global update is sufficient or do nothing
             return trace_span!("ffi", function="Nuitrack::update").in_scope(|| {
@@ -345,59 +737,22 @@ impl NuitrackSession {
         }
 
         for device_ctx in &self.active_devices {
-            // Determine which module to wait on for this device
-            // This logic should align with how modules_for_internal_loop is populated
-            let mut waited = false;
             let device_span = info_span!("device_update", serial = %device_ctx.info.serial_number);
-            let _enter = device_span.enter();
-
-            if let Some(cs_wrapper) = &device_ctx.color_sensor { // Prioritize skeleton
-                let ptr_clone = cs_wrapper.get_ffi_ptr_clone();
-                trace_span!("ffi", function="wait_update_skeleton_tracker").in_scope(|| {
-                    run_blocking(move || core_ffi::wait_update_color_sensor(&ptr_clone)
-                    .map_err(|e| NuitrackError::OperationFailed(format!("FFI wait_update_color_sensor: {}", e))))
-                }).await?;
-                waited = true;
-            } else if let Some(st_wrapper) = &device_ctx.skeleton_tracker { // Prioritize skeleton
-                let ptr_clone = st_wrapper.get_ffi_ptr_clone();
-                trace_span!("ffi", function="wait_update_hand_tracker").in_scope(|| {
-                    run_blocking(move || core_ffi::wait_update_skeleton_tracker(&ptr_clone)
-                    .map_err(|e| NuitrackError::OperationFailed(format!("FFI wait_update_skeleton_tracker: {}", e))))
-                }).await?;
-                waited = true;
-            } else if let Some(ht_wrapper) = &device_ctx.hand_tracker {
-                let ptr_clone = ht_wrapper.get_ffi_ptr_clone();
-                trace_span!("ffi", function="wait_update_color_sensor").in_scope(|| {
-                    run_blocking(move || core_ffi::wait_update_hand_tracker(&ptr_clone)
-                    .map_err(|e| NuitrackError::OperationFailed(format!("FFI wait_update_hand_tracker: {}", e))))
-                }).await?;
-                waited = true;
-            } else if let Some(ds_wrapper) = &device_ctx.depth_sensor { // Add this block
-                let ptr_clone = ds_wrapper.get_ffi_ptr_clone();
-                trace_span!("ffi", function="wait_update_depth_sensor").in_scope(|| {
-                    run_blocking(move || core_ffi::wait_update_depth_sensor(&ptr_clone)
-                    .map_err(|e| NuitrackError::OperationFailed(format!("FFI wait_update_depth_sensor: {}", e))))
-                }).await?;
-                waited = true;
-            } else if let Some(ut_wrapper) = &device_ctx.user_tracker { // ADD THIS BLOCK
-                let ptr_clone = ut_wrapper.get_ffi_ptr_clone();
-                trace_span!("ffi", function="wait_update_user_tracker").in_scope(|| {
-                    run_blocking(move || core_ffi::wait_update_user_tracker(&ptr_clone)
-                    .map_err(|e| NuitrackError::OperationFailed(format!("FFI wait_update_user_tracker: {}", e))))
-                }).await?;
-                waited = true;
-            } else if let Some(gr_wrapper) = &device_ctx.gesture_recognizer {
-                let ptr_clone = gr_wrapper.get_ffi_ptr_clone();
-                trace_span!("ffi", function="wait_update_gesture_recognizer").in_scope(|| {
-                    run_blocking(move || core_ffi::wait_update_gesture_recognizer(&ptr_clone)
-                    .map_err(|e| NuitrackError::OperationFailed(format!("FFI wait_update_gesture_recognizer: {}", e))))
-                }).await?;
-                waited = true;
-            }
-            if !waited {
-                debug!("Device has no representative module for a specific waitUpdate call.");
+            match device_ctx.update_module()? {
+                Some(module) => {
+                    let serial = &device_ctx.info.serial_number;
+                    match module.wait_update().instrument(device_span).await {
+                        Ok(()) => self.health.record_update(serial),
+                        Err(e) => {
+                            self.health.record_error(serial, &e);
+                            return Err(e);
+                        }
+                    }
+                }
+                None => device_span.in_scope(|| debug!("Device has no representative module for a specific waitUpdate call.")),
             }
         }
+        self.health.check_stalls();
         Ok(())
     }
     
@@ -422,23 +777,28 @@ impl NuitrackSession {
                     debug!("Requesting cancellation of internal update loop.");
                     token.cancel();
                 }
-                if let Some(handle_arc) = &self.update_task_handle {
-                    let mut handle_guard = handle_arc.lock().await;
-                    if let Some(handle) = handle_guard.take() {
-                        info!("Awaiting internal update task termination...");
+                if let Some(handles_arc) = &self.update_task_handle {
+                    let handles = std::mem::take(&mut *handles_arc.lock().await);
+                    if !handles.is_empty() {
+                        info!(tasks = handles.len(), "Awaiting internal update task termination...");
+                    }
+                    for handle in handles {
                         if let Err(e) = handle.await {
                             error!(join_error = ?e, "Internal update task panicked or was cancelled.");
-                        } else {
-                            info!("Internal update task joined cleanly.");
                         }
                     }
+                    info!("Internal update tasks joined.");
                 }
             }
         }
         
-        self.guard.release_async().await?;
-        debug!("Explicitly forgetting NuitrackRuntimeGuard to prevent double-release in Drop.");
-        std::mem::forget(self.guard); 
+        self.health.finish();
+
+        if let Some(guard) = self.guard {
+            guard.release_async().await?;
+            debug!("Explicitly forgetting NuitrackRuntimeGuard to prevent double-release in Drop.");
+            std::mem::forget(guard);
+        }
         info!("Nuitrack session closed successfully.");
         Ok(())
     }
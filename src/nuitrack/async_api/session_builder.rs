@@ -1,21 +1,26 @@
 use tracing::{info, info_span, instrument, trace_span, warn};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use cxx::SharedPtr;
 
 use crate::nuitrack::async_api::color_sensor::AsyncColorSensor;
-use crate::nuitrack::async_api::depth_sensor::AsyncDepthSensor;
+use crate::nuitrack::async_api::depth_sensor::{AsyncDepthSensor, OutputMode};
 use crate::nuitrack::async_api::gesture_recognizer::AsyncGestureRecognizer;
 use crate::nuitrack::async_api::user_tracker::AsyncUserTracker;
+use crate::nuitrack::backend::FrameBackend;
+use crate::nuitrack::backend::replay::{ReplayBackend, ReplayTiming};
+use crate::nuitrack::backend::virtual_device::BackendDriver;
 use crate::nuitrack_bridge::device::ffi as device_ffi;
 use super::async_dispatch::run_blocking;
+use super::health::HealthConfig;
 use super::skeleton_tracker::AsyncSkeletonTracker;
 use crate::nuitrack::shared_types::error::{NuitrackError, Result as NuitrackResult};
 use crate::nuitrack::shared_types::session_config::{
-    DeviceConfig, DeviceSelector, DiscoveredDeviceInfo, ModuleType
+    ActivationStatus, DeviceConfig, DeviceSelector, DiscoveredDeviceInfo, ModuleType, VideoMode, VideoStream
 };
 use super::session::{
-    ActiveDeviceContext, NuitrackRuntimeGuard, NuitrackSession, WaitableModuleFFIVariant, NUITRACK_GLOBAL_API_LOCK // Made pub(crate) in session.rs
+    ActiveDeviceContext, DeviceUpdateModule, NuitrackRuntimeGuard, NuitrackSession, RuntimeConfig, NUITRACK_GLOBAL_API_LOCK // Made pub(crate) in session.rs
 };
 // Import your async module wrappers
 use super::hand_tracker::AsyncHandTracker;
@@ -24,6 +29,9 @@ use super::hand_tracker::AsyncHandTracker;
 // Helper FFI type alias
 type FFIDevice = crate::nuitrack_bridge::device::ffi::Device;
 
+/// Nuitrack config key toggling the SDK's depth-to-color registration.
+const DEPTH_TO_COLOR_REGISTRATION_KEY: &str = "DepthProvider.Depth2ColorRegistration";
+
 
 #[derive(Default)]
 pub struct NuitrackSessionBuilder {
@@ -31,6 +39,9 @@ pub struct NuitrackSessionBuilder {
     device_configurations: Vec<DeviceConfig>,
     run_internal_update_loop: bool,
     config_values: HashMap<String, String>,
+    backend: Option<Box<dyn FrameBackend>>,
+    recording: Option<(PathBuf, ReplayTiming)>, // Opened lazily so that builder methods stay infallible
+    health_config: HealthConfig,
     // Add policy flags here if desired
     // policy_strict_device_match: bool, // e.g., error if a configured device selector finds no match
 }
@@ -49,6 +60,7 @@ impl NuitrackSessionBuilder {
         let device_config = DeviceConfig {
             selector: DeviceSelector::ByIndex(0), 
             modules_to_create,
+            ..Default::default()
         };
         Ok(Self::new()
             // .global_config_path("path/to/your/nuitrack.config") // Optional
@@ -68,6 +80,13 @@ impl NuitrackSessionBuilder {
         self
     }
 
+    /// Enables the SDK's depth-to-color registration, which warps depth into the color
+    /// camera's view. Pair with a [`Registration`](crate::nuitrack::shared_types::rgbd_frame::Registration)
+    /// to resample it to the color resolution.
+    pub fn with_depth_to_color_registration(self, enabled: bool) -> Self {
+        self.with_config_value(DEPTH_TO_COLOR_REGISTRATION_KEY, if enabled { "true" } else { "false" })
+    }
+
     pub fn with_device_config(mut self, config: DeviceConfig) -> Self {
         self.device_configurations.push(config);
         self
@@ -78,13 +97,71 @@ impl NuitrackSessionBuilder {
         self
     }
 
+    /// Drives the session from `backend` instead of the Nuitrack SDK.
+    ///
+    /// The SDK runtime is never initialized; `global_config_path` and config values are ignored.
+    pub fn with_backend(mut self, backend: impl FrameBackend) -> Self {
+        self.backend = Some(Box::new(backend));
+        self.recording = None;
+        self
+    }
+
+    /// Replays the recording at `path` instead of using live devices.
+    ///
+    /// Shorthand for `with_backend(ReplayBackend::open(path)?.with_timing(timing))`,
+    /// except that opening errors surface from `init_session` / `discover_devices_first`.
+    pub fn with_recording(mut self, path: impl AsRef<Path>, timing: ReplayTiming) -> Self {
+        self.recording = Some((path.as_ref().to_path_buf(), timing));
+        self.backend = None;
+        self
+    }
+
+    /// Stall and disconnect detection thresholds, and what to do when a device is lost.
+    pub fn with_health_config(mut self, config: HealthConfig) -> Self {
+        self.health_config = config;
+        self
+    }
+
+    fn runtime_config(&self) -> RuntimeConfig {
+        RuntimeConfig {
+            config_path: self.global_config_path.clone().unwrap_or_default(),
+            config_values: self.config_values.clone(),
+        }
+    }
+
+    fn take_backend(&mut self) -> NuitrackResult<Option<Box<dyn FrameBackend>>> {
+        if let Some((path, timing)) = self.recording.take() {
+            let replay = ReplayBackend::open(&path)?.with_timing(timing);
+            return Ok(Some(Box::new(replay)));
+        }
+        Ok(self.backend.take())
+    }
+
     /// Initializes the Nuitrack session based on the builder's configuration.
     /// This path is used when the user provides all configurations upfront.
     #[instrument(skip(self), name = "init_session")]
-    pub async fn init_session(self) -> NuitrackResult<NuitrackSession> {
+    pub async fn init_session(mut self) -> NuitrackResult<NuitrackSession> {
+        if let Some(backend) = self.take_backend()? {
+            let driver = Arc::new(BackendDriver::new(backend));
+            let available_devices = Self::virtual_devices_info(&driver);
+            let effective_configs = Self::resolve_device_configs(self.device_configurations, &available_devices)?;
+            let active_device_contexts =
+                Self::configure_virtual_devices_and_modules(&driver, &available_devices, effective_configs)?;
+            return NuitrackSession::new(
+                None,
+                Some(driver),
+                active_device_contexts,
+                Vec::new(),
+                self.run_internal_update_loop,
+                self.health_config,
+                None,
+            );
+        }
+
+        let runtime_config = self.runtime_config();
         let guard = NuitrackRuntimeGuard::acquire(
-            &self.global_config_path.unwrap_or_default(),
-            &self.config_values,
+            &runtime_config.config_path,
+            &runtime_config.config_values,
         ).await?;
         
         let available_devices_cache = Self::fetch_available_devices_info_internal().await.map_err(|e| {
@@ -92,12 +169,37 @@ impl NuitrackSessionBuilder {
             e
         })?;
         
-        let effective_configs = if self.device_configurations.is_empty() {
+        let effective_configs = Self::resolve_device_configs(self.device_configurations, &available_devices_cache)?;
+
+        let (active_device_contexts, modules_for_update_loop) = 
+            Self::configure_devices_and_modules(
+                available_devices_cache,
+                effective_configs,
+            ).await?;
+
+        NuitrackSession::new(
+            Some(guard),
+            None,
+            active_device_contexts,
+            modules_for_update_loop,
+            self.run_internal_update_loop,
+            self.health_config,
+            Some(runtime_config),
+        )
+    }
+
+    /// Falls back to a sensible default when no device configurations were given.
+    fn resolve_device_configs(
+        device_configurations: Vec<DeviceConfig>,
+        available_devices_cache: &[DiscoveredDeviceInfo],
+    ) -> NuitrackResult<Vec<DeviceConfig>> {
+        Ok(if device_configurations.is_empty() {
             if available_devices_cache.len() == 1 {
                 info!("No device configurations provided; defaulting to the single available device.");
                 vec![DeviceConfig { // Default to the single available device
                     selector: DeviceSelector::ByIndex(0), 
-                    modules_to_create: vec![ModuleType::HandTracker, ModuleType::SkeletonTracker], // Sensible defaults
+                    modules_to_create: vec![ModuleType::HandTracker, ModuleType::SkeletonTracker], // Sensible defaults,
+                    ..Default::default()
                 }]
             } else if available_devices_cache.is_empty() {
                 warn!("No devices found and no configurations provided. Session will have no active devices.");
@@ -108,26 +210,24 @@ impl NuitrackSessionBuilder {
                 return Err(NuitrackError::DeviceError("Multiple devices found; specific configuration or DefaultSingle selector required.".into()));
             }
         } else {
-            self.device_configurations
-        };
-
-        let (active_device_contexts, modules_for_update_loop) = 
-            Self::configure_devices_and_modules(
-                available_devices_cache,
-                effective_configs,
-            ).await?;
-
-        NuitrackSession::new(
-            guard,
-            active_device_contexts,
-            modules_for_update_loop,
-            self.run_internal_update_loop,
-        )
+            device_configurations
+        })
     }
 
     /// Starts a phased initialization allowing device discovery first.
     #[instrument(skip(self))]
-    pub async fn discover_devices_first(self) -> NuitrackResult<DeviceDiscoveryState> {
+    pub async fn discover_devices_first(mut self) -> NuitrackResult<DeviceDiscoveryState> {
+        if let Some(backend) = self.take_backend()? {
+            let driver = Arc::new(BackendDriver::new(backend));
+            let available_devices = Self::virtual_devices_info(&driver);
+            info!(count = available_devices.len(), "Backend device discovery complete.");
+            return Ok(DeviceDiscoveryState {
+                guard: None,
+                backend_driver: Some(driver),
+                available_devices,
+                builder_settings: self,
+            });
+        }
         let config_path_for_acquire = self.global_config_path.as_deref().unwrap_or_default();
         info!("Acquiring runtime guard and discovering devices...");
         let guard = NuitrackRuntimeGuard::acquire(
@@ -142,6 +242,7 @@ impl NuitrackSessionBuilder {
 
         Ok(DeviceDiscoveryState {
             guard: Some(guard),
+            backend_driver: None,
             available_devices,
             builder_settings: self, // Store the original builder settings (config_path, run_internal_update_loop)
         })
@@ -162,11 +263,21 @@ impl NuitrackSessionBuilder {
                     let name = device_ffi::device_info(&device, device_ffi::DeviceInfoType::DEVICE_NAME).unwrap_or_else(|_| "N/A".to_string());
                     let serial = device_ffi::device_info(&device, device_ffi::DeviceInfoType::SERIAL_NUMBER).unwrap_or_else(|_| "N/A".to_string());
                     let provider = device_ffi::device_info(&device, device_ffi::DeviceInfoType::PROVIDER_NAME).unwrap_or_else(|_| "N/A".to_string());
+                    let activation = device_ffi::activation_status(&device).map(ActivationStatus::from).unwrap_or(ActivationStatus::Unknown);
+                    let [depth_modes, color_modes] = VideoStream::ALL.map(|stream| {
+                        device_ffi::available_video_modes(&device, stream.into()).unwrap_or_else(|e| {
+                            warn!(serial = %serial, ?stream, error = %e, "Could not list video modes.");
+                            Vec::new()
+                        })
+                    });
                     devices_info_vec.push(DiscoveredDeviceInfo { 
                         name, 
                         serial_number: serial, 
                         provider_name: provider, 
                         original_index: i, 
+                        activation,
+                        depth_modes,
+                        color_modes,
                         ffi_device_ptr: device // Essential for selection
                     });
                     
@@ -176,14 +287,83 @@ impl NuitrackSessionBuilder {
         }).await
     }
 
+    /// Describes a backend's virtual devices the same way SDK devices are described.
+    fn virtual_devices_info(driver: &BackendDriver) -> Vec<DiscoveredDeviceInfo> {
+        driver
+            .devices()
+            .iter()
+            .enumerate()
+            .map(|(i, device)| {
+                let descriptor = device.descriptor();
+                DiscoveredDeviceInfo {
+                    name: descriptor.name.clone(),
+                    serial_number: descriptor.serial_number.clone(),
+                    provider_name: descriptor.provider_name.clone(),
+                    original_index: i,
+                    activation: ActivationStatus::Unknown,
+                    depth_modes: descriptor.depth_mode.iter().map(video_mode_of).collect(),
+                    color_modes: descriptor.color_mode.iter().map(video_mode_of).collect(),
+                    ffi_device_ptr: SharedPtr::null(),
+                }
+            })
+            .collect()
+    }
+
+    /// Backend counterpart of `configure_devices_and_modules`: modules subscribe to
+    /// virtual devices, and there is nothing for an SDK update loop to wait on.
+    #[instrument(skip_all)]
+    fn configure_virtual_devices_and_modules(
+        driver: &BackendDriver,
+        available_devices: &[DiscoveredDeviceInfo],
+        user_device_configs: Vec<DeviceConfig>,
+    ) -> NuitrackResult<Vec<ActiveDeviceContext>> {
+        let mut active_devices_built = Vec::new();
+        for (i, dev_config) in user_device_configs.into_iter().enumerate() {
+            let config_span = info_span!("device_config", id = i, selector = ?dev_config.selector);
+            let _enter = config_span.enter();
+            let (selected_device_info_ref, _) =
+                Self::find_target_device_from_cache(available_devices, &dev_config.selector)?;
+            Self::ensure_not_configured(&active_devices_built, selected_device_info_ref)?;
+            let device = driver.devices()[selected_device_info_ref.original_index].clone();
+
+            info!(device_serial = %selected_device_info_ref.serial_number, "Configuring virtual device.");
+            // Virtual devices have a single fixed mode per stream, so this only validates the request.
+            let video_modes = Self::resolve_video_modes(selected_device_info_ref, &dev_config)?;
+
+            let mut ad_context = ActiveDeviceContext {
+                info: selected_device_info_ref.clone(),
+                color_sensor: None,
+                hand_tracker: None,
+                skeleton_tracker: None,
+                depth_sensor: None,
+                user_tracker: None,
+                gesture_recognizer: None,
+                device_index: active_devices_built.len(),
+                video_modes,
+            };
+            for module_type in dev_config.modules_to_create {
+                match module_type {
+                    ModuleType::ColorSensor => ad_context.color_sensor = Some(AsyncColorSensor::new_virtual(device.clone())),
+                    ModuleType::HandTracker => ad_context.hand_tracker = Some(AsyncHandTracker::new_virtual(device.clone())),
+                    ModuleType::SkeletonTracker => ad_context.skeleton_tracker = Some(AsyncSkeletonTracker::new_virtual(device.clone())),
+                    ModuleType::DepthSensor => ad_context.depth_sensor = Some(AsyncDepthSensor::new_virtual(device.clone())),
+                    ModuleType::UserTracker => ad_context.user_tracker = Some(AsyncUserTracker::new_virtual(device.clone())),
+                    ModuleType::GestureRecognizer => ad_context.gesture_recognizer = Some(AsyncGestureRecognizer::new_virtual(device.clone())),
+                }
+            }
+            active_devices_built.push(ad_context);
+        }
+        Ok(active_devices_built)
+    }
+
     /// Common logic: takes discovered devices and user configs, sets devices, creates modules.
     #[instrument(skip(available_devices_cache, user_device_configs))]
     async fn configure_devices_and_modules(
         available_devices_cache: Vec<DiscoveredDeviceInfo>,
         user_device_configs: Vec<DeviceConfig>,
-    ) -> NuitrackResult<(Vec<ActiveDeviceContext>, Vec<WaitableModuleFFIVariant>)> {
+    ) -> NuitrackResult<(Vec<ActiveDeviceContext>, Vec<DeviceUpdateModule>)> {
         let mut active_devices_built = Vec::new();
-        let mut modules_for_update_loop: Vec<WaitableModuleFFIVariant> = Vec::new();
+        let mut modules_for_update_loop: Vec<DeviceUpdateModule> = Vec::new();
 
         if user_device_configs.is_empty() && !available_devices_cache.is_empty() {
             warn!("No device configurations provided, but devices are available. No modules will be activated.");
@@ -195,6 +375,7 @@ impl NuitrackSessionBuilder {
             let _enter = config_span.enter();
             let (selected_device_info_ref, target_ffi_device_ptr_clone) = 
                 Self::find_target_device_from_cache(&available_devices_cache, &dev_config.selector)?;
+            Self::ensure_not_configured(&active_devices_built, selected_device_info_ref)?;
 
             info!(device_serial = %selected_device_info_ref.serial_number, "Configuring device.");    
 
@@ -212,6 +393,23 @@ impl NuitrackSessionBuilder {
                 }).await?;
             }
 
+            let video_modes = Self::resolve_video_modes(selected_device_info_ref, &dev_config)?;
+            for &(stream, mode) in &video_modes {
+                let ptr_for_mode = target_ffi_device_ptr_clone.clone();
+                let selected = trace_span!("ffi", function="NuitrackDevice::setVideoMode").in_scope(|| {
+                    run_blocking(move || {
+                        let _g_lock = NUITRACK_GLOBAL_API_LOCK.lock().map_err(|_| NuitrackError::OperationFailed("Global API lock for set_video_mode".into()))?;
+                        device_ffi::set_video_mode(&ptr_for_mode, stream.into(), mode)
+                            .map_err(|cxx_e| NuitrackError::DeviceError(format!("FFI setVideoMode ({:?}) failed: {}", stream, cxx_e)))?;
+                        device_ffi::selected_video_mode(&ptr_for_mode, stream.into())
+                            .map_err(|cxx_e| NuitrackError::DeviceError(format!("FFI getSelectedVideoMode ({:?}) failed: {}", stream, cxx_e)))
+                    })
+                }).await?;
+                if selected != mode {
+                    warn!(?stream, requested = ?mode, ?selected, "Device selected a different video mode than requested.");
+                }
+            }
+
             let mut ad_context = ActiveDeviceContext {
                 info: selected_device_info_ref.clone(),
                 color_sensor: None,
@@ -220,67 +418,85 @@ impl NuitrackSessionBuilder {
                 depth_sensor: None,
                 user_tracker: None,
                 gesture_recognizer: None,
+                device_index: active_devices_built.len(),
+                video_modes,
             };
             
-            let mut representative_module_for_device: Option<WaitableModuleFFIVariant> = None;
-
             for module_type in dev_config.modules_to_create {
                 match module_type {
                     ModuleType::ColorSensor => {
                         let cs = AsyncColorSensor::new_async().await?; // Assumes device is set
-                        if representative_module_for_device.is_none() { // Prefer ColorSensor if Skeleton not chosen
-                            representative_module_for_device = Some(WaitableModuleFFIVariant::ColorSensor(cs.get_ffi_ptr_clone()));
-                        }
                         ad_context.color_sensor = Some(cs);
                     }
                     ModuleType::HandTracker => {
                         let ht = AsyncHandTracker::new_async().await?; // Assumes device is set
-                        if representative_module_for_device.is_none() { // Prefer HandTracker if Skeleton not chosen
-                           representative_module_for_device = Some(WaitableModuleFFIVariant::Hand(ht.get_ffi_ptr_clone()));
-                        }
                         ad_context.hand_tracker = Some(ht);
                     }
                     ModuleType::SkeletonTracker => {
                         let st = AsyncSkeletonTracker::new_async().await?;
-                        if representative_module_for_device.is_none() {
-                            representative_module_for_device = Some(WaitableModuleFFIVariant::Skeleton(st.get_ffi_ptr_clone()));
-                        }
                         ad_context.skeleton_tracker = Some(st);
-                        
                     }
                     ModuleType::DepthSensor => {
                         let ds = AsyncDepthSensor::new_async().await?;
-                        if representative_module_for_device.is_none() {
-                            representative_module_for_device = Some(WaitableModuleFFIVariant::DepthSensor(ds.get_ffi_ptr_clone()));
-                        }
                         ad_context.depth_sensor = Some(ds);
                     }
-                    ModuleType::UserTracker => { // ADD THIS BLOCK
+                    ModuleType::UserTracker => {
                         let ut = AsyncUserTracker::new_async().await?;
-                        if representative_module_for_device.is_none() {
-                            representative_module_for_device = Some(WaitableModuleFFIVariant::UserTracker(ut.get_ffi_ptr_clone()));
-                        }
                         ad_context.user_tracker = Some(ut);
                     }
                     ModuleType::GestureRecognizer => {
                         let gr = AsyncGestureRecognizer::new_async().await?;
-                        if representative_module_for_device.is_none() {
-                            representative_module_for_device = Some(WaitableModuleFFIVariant::GestureRecognizer(gr.get_ffi_ptr_clone()));
-                        }
                         ad_context.gesture_recognizer = Some(gr);
                     }
-                    _ => {}
-                    // ... other module types like DepthSensor, ColorSensor, UserTracker ...
                 }
             }
-            if let Some(rep_module) = representative_module_for_device {
-                modules_for_update_loop.push(rep_module);
+            if let Some(rep_module) = ad_context.update_module()? {
+                modules_for_update_loop.push(DeviceUpdateModule {
+                    serial_number: ad_context.info.serial_number.clone(),
+                    module: rep_module,
+                });
             }
             active_devices_built.push(ad_context);
         }
         Ok((active_devices_built, modules_for_update_loop))
     }
 
+    /// Picks the mode for every stream the device config asks for.
+    fn resolve_video_modes(
+        device: &DiscoveredDeviceInfo,
+        config: &DeviceConfig,
+    ) -> NuitrackResult<Vec<(VideoStream, VideoMode)>> {
+        VideoStream::ALL
+            .into_iter()
+            .filter_map(|stream| config.video_mode_request(stream).map(|request| (stream, request)))
+            .map(|(stream, request)| {
+                let available = device.video_modes(stream);
+                let mode = request.resolve(available).ok_or_else(|| {
+                    NuitrackError::DeviceError(format!(
+                        "No {:?} video mode of device '{}' satisfies {:?}. Available: {:?}",
+                        stream, device.serial_number, request, available
+                    ))
+                })?;
+                info!(?stream, ?request, ?mode, "Video mode selected.");
+                Ok((stream, mode))
+            })
+            .collect()
+    }
+
+    /// Each physical device can only back one `ActiveDeviceContext`.
+    fn ensure_not_configured(
+        active_devices: &[ActiveDeviceContext],
+        device: &DiscoveredDeviceInfo,
+    ) -> NuitrackResult<()> {
+        if active_devices.iter().any(|d| d.info.serial_number == device.serial_number) {
+            return Err(NuitrackError::DeviceError(format!(
+                "Device with serial '{}' is selected by more than one device configuration.",
+                device.serial_number
+            )));
+        }
+        Ok(())
+    }
+
     /// Helper to find a device in the cached list based on selector.
     #[instrument(skip(available_devices))]
     fn find_target_device_from_cache<'a>(
@@ -311,9 +527,14 @@ impl NuitrackSessionBuilder {
     }
 }
 
+fn video_mode_of(mode: &OutputMode) -> VideoMode {
+    VideoMode { width: mode.xres, height: mode.yres, fps: mode.fps }
+}
+
 // --- Typestate for Device Discovery ---
 pub struct DeviceDiscoveryState {
     guard: Option<NuitrackRuntimeGuard>, // Option to allow taking it for finalization
+    backend_driver: Option<Arc<BackendDriver>>, // Set instead of `guard` for backend-driven discovery
     pub available_devices: Vec<DiscoveredDeviceInfo>, // User inspects this
     builder_settings: NuitrackSessionBuilder, // Carries over settings like config_path, run_internal_update_loop
 }
@@ -323,6 +544,60 @@ impl DeviceDiscoveryState {
         &self.available_devices
     }
 
+    /// Queries the device's current license state and stores it in `available_devices`.
+    #[instrument(skip(self))]
+    pub async fn refresh_activation(&mut self, serial_number: &str) -> NuitrackResult<ActivationStatus> {
+        let index = self.position_of(serial_number)?;
+        if self.backend_driver.is_some() {
+            return Ok(self.available_devices[index].activation);
+        }
+        let device = self.available_devices[index].ffi_device_ptr.clone();
+        let status = trace_span!("ffi", function = "NuitrackDevice::getActivationStatus").in_scope(|| {
+            run_blocking(move || {
+                let _g_lock = NUITRACK_GLOBAL_API_LOCK.lock().map_err(|_| NuitrackError::OperationFailed("Global API lock for getActivationStatus".into()))?;
+                device_ffi::activation_status(&device)
+                    .map(ActivationStatus::from)
+                    .map_err(|e| NuitrackError::DeviceError(format!("FFI getActivationStatus: {}", e)))
+            })
+        }).await?;
+        self.available_devices[index].activation = status;
+        Ok(status)
+    }
+
+    /// Activates the device with a Nuitrack license key, then returns its new license state.
+    ///
+    /// Fails with [`NuitrackError::ActivationFailed`] if the SDK rejects the key or the
+    /// device is still not activated afterwards.
+    #[instrument(skip(self, activation_key))]
+    pub async fn activate_device(&mut self, serial_number: &str, activation_key: impl Into<String>) -> NuitrackResult<ActivationStatus> {
+        let index = self.position_of(serial_number)?;
+        if self.backend_driver.is_some() {
+            return Err(NuitrackError::Backend(format!("Virtual device '{}' cannot be activated.", serial_number)));
+        }
+        let device = self.available_devices[index].ffi_device_ptr.clone();
+        let activation_key = activation_key.into();
+        trace_span!("ffi", function = "NuitrackDevice::activate").in_scope(|| {
+            run_blocking(move || {
+                let _g_lock = NUITRACK_GLOBAL_API_LOCK.lock().map_err(|_| NuitrackError::OperationFailed("Global API lock for activate".into()))?;
+                device_ffi::activate(&device, &activation_key)
+                    .map_err(|e| NuitrackError::ActivationFailed(e.what().to_string()))
+            })
+        }).await?;
+        let status = self.refresh_activation(serial_number).await?;
+        if status == ActivationStatus::None {
+            return Err(NuitrackError::ActivationFailed(format!("Device '{}' is still not activated.", serial_number)));
+        }
+        info!(serial = %serial_number, ?status, "Device activated.");
+        Ok(status)
+    }
+
+    fn position_of(&self, serial_number: &str) -> NuitrackResult<usize> {
+        self.available_devices
+            .iter()
+            .position(|d| d.serial_number == serial_number)
+            .ok_or_else(|| NuitrackError::DeviceError(format!("Device with serial '{}' not found.", serial_number)))
+    }
+
     /// User calls this after inspecting devices and deciding on configurations.
     #[instrument(skip(self, user_selected_device_configs))]
     pub async fn finalize_session(
@@ -330,6 +605,22 @@ impl DeviceDiscoveryState {
         user_selected_device_configs: Vec<DeviceConfig>,
     ) -> NuitrackResult<NuitrackSession> {
         info!("Finalizing session from discovered devices.");
+        if let Some(driver) = self.backend_driver.take() {
+            let active_device_contexts = NuitrackSessionBuilder::configure_virtual_devices_and_modules(
+                &driver,
+                &self.available_devices,
+                user_selected_device_configs,
+            )?;
+            return NuitrackSession::new(
+                None,
+                Some(driver),
+                active_device_contexts,
+                Vec::new(),
+                self.builder_settings.run_internal_update_loop,
+                self.builder_settings.health_config.clone(),
+                None,
+            );
+        }
         let guard = self.guard.take().ok_or_else(|| NuitrackError::OperationFailed("NuitrackRuntimeGuard already taken/missing in DeviceDiscoveryState".into()))?;
         
         // Use the common configuration logic, passing the already discovered devices
@@ -340,10 +631,13 @@ impl DeviceDiscoveryState {
             ).await?;
         
         NuitrackSession::new(
-            guard,
+            Some(guard),
+            None,
             active_device_contexts,
             modules_for_update_loop,
             self.builder_settings.run_internal_update_loop,
+            self.builder_settings.health_config.clone(),
+            Some(self.builder_settings.runtime_config()),
         )
     }
 }
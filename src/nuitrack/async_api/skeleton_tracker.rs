@@ -1,3 +1,4 @@
+use crate::nuitrack::backend::virtual_device::stream_keys;
 use tracing::warn;
 use crate::nuitrack_bridge::modules::skeleton_tracker::ffi as st_ffi;
 use crate::nuitrack::shared_types::skeleton_frame::SkeletonFrame;
@@ -40,7 +41,7 @@ impl AsyncSkeletonTracker {
     /// Tracking >2 users may impact performance.
     #[instrument(skip(self))]
     pub async fn set_num_active_users(&self, num_users: i32) -> NuitrackResult<()> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="st_ffi::set_num_active_users").in_scope(|| {
             run_blocking(move || {
                 st_ffi::set_num_active_users(&ptr, num_users)
@@ -52,7 +53,7 @@ impl AsyncSkeletonTracker {
     /// Checks if auto-tracking of skeletons is enabled.
     #[instrument(skip(self))]
     pub async fn is_auto_tracking(&self) -> NuitrackResult<bool> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="st_ffi::is_auto_tracking").in_scope(|| {
             run_blocking(move || {
                 st_ffi::is_auto_tracking(&ptr)
@@ -65,7 +66,7 @@ impl AsyncSkeletonTracker {
     /// If true, tracking starts when a user appears. Otherwise, manual start is needed.
     #[instrument(skip(self))]
     pub async fn set_auto_tracking(&self, tracking: bool) -> NuitrackResult<()> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="st_ffi::set_auto_tracking").in_scope(|| {
             run_blocking(move || {
                 st_ffi::set_auto_tracking(&ptr, tracking)
@@ -77,7 +78,7 @@ impl AsyncSkeletonTracker {
     /// Starts tracking the skeleton of a specific user.
     #[instrument(skip(self))]
     pub async fn start_tracking(&self, user_id: i32) -> NuitrackResult<()> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="st_ffi::start_tracking").in_scope(|| {
             run_blocking(move || {
                 st_ffi::start_tracking(&ptr, user_id)
@@ -90,7 +91,7 @@ impl AsyncSkeletonTracker {
     /// Stops tracking the skeleton of a specific user.
     #[instrument(skip(self))]
     pub async fn stop_tracking(&self, user_id: i32) -> NuitrackResult<()> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="st_ffi::stop_tracking").in_scope(|| {
             run_blocking(move || {
                 st_ffi::stop_tracking(&ptr, user_id)
@@ -102,7 +103,7 @@ impl AsyncSkeletonTracker {
     /// Checks if a specific user's skeleton is currently being tracked.
     #[instrument(skip(self))]
     pub async fn is_tracking(&self, user_id: i32) -> NuitrackResult<bool> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="st_ffi::is_tracking").in_scope(|| {
             run_blocking(move || {
                 st_ffi::is_tracking(&ptr, user_id)
@@ -114,7 +115,12 @@ impl AsyncSkeletonTracker {
     /// Gets the last available skeleton data synchronously from the tracker.
     #[instrument(skip(self))] 
     pub async fn latest_skeletons_frame_sync(&self) -> NuitrackResult<SkeletonFrame> {
-        let ptr = self.get_ffi_ptr_clone();
+        if let Some(device) = self.virtual_device() {
+            return device.latest(stream_keys::SKELETON_FRAMES).ok_or_else(|| {
+                NuitrackError::OperationFailed("Backend has not produced a skeleton frame yet".to_string())
+            });
+        }
+        let ptr = self.get_ffi_ptr_clone()?;
         let ffi_skeleton_data_ptr = trace_span!("ffi", function="st_ffi::skeletons").in_scope(|| {
             run_blocking(move || {
                 // This calls the getSkeletons method on the SkeletonTracker FFI,
@@ -134,7 +140,7 @@ impl AsyncSkeletonTracker {
     /// Gets the last skeleton data processing time in milliseconds.
     #[instrument(skip(self))]
     pub async fn processing_time(&self) -> NuitrackResult<f32> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="st_ffi::processing_time").in_scope(|| {
             run_blocking(move || {
                 st_ffi::processing_time(&ptr)
@@ -147,7 +153,10 @@ impl AsyncSkeletonTracker {
     /// Gets the timestamp of the last processed data by the tracker in microseconds.
     #[instrument(skip(self))]
     pub async fn tracker_timestamp(&self) -> NuitrackResult<u64> {
-        let ptr = self.get_ffi_ptr_clone();
+        if let Some(device) = self.virtual_device() {
+            return Ok(device.last_timestamp());
+        }
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="st_ffi::tracker_timestamp").in_scope(|| {
             run_blocking(move || {
                 st_ffi::tracker_timestamp(&ptr)
@@ -159,7 +168,7 @@ impl AsyncSkeletonTracker {
     /// Checks if the Nuitrack module can update.
     #[instrument(skip(self))]
     pub async fn can_update(&self) -> NuitrackResult<bool> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function="st_ffi::can_update").in_scope(|| {
             run_blocking(move || {
                 st_ffi::can_update(&ptr)
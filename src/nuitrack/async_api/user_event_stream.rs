@@ -0,0 +1,169 @@
+//! One stream of user lifecycle events per device.
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use tracing::{debug, instrument};
+
+use crate::nuitrack::backend::BackendFrame;
+use crate::nuitrack::shared_types::{
+    error::{NuitrackError, Result as NuitrackResult},
+    gesture_frame::UserStateFrame,
+    skeleton_frame::SkeletonFrame,
+    user_frame::UserFrame,
+    user_lifecycle::{UserEvent, UserLifecycle},
+};
+
+use super::frame_bundle::{member_stream, MemberStream};
+use super::session::ActiveDeviceContext;
+
+struct Source {
+    name: &'static str,
+    stream: MemberStream,
+    ended: bool,
+}
+
+/// Merges the streams that report users into a [`UserLifecycle`] and yields its
+/// [`UserEvent`]s.
+///
+/// Sources are polled in the order they were attached, so attach new user events
+/// first and lost user events last to keep the events of one update in order, as
+/// [`for_device`](Self::for_device) does. With skeletons as the only source, users
+/// leave when their skeleton is lost (see [`UserLifecycle::set_skeletons_only`]). The
+/// stream ends once every source has ended; errors from sources are passed through.
+///
+/// ```no_run
+/// # fn example(session: &mut nuitrack_rs::nuitrack::async_api::session::NuitrackSession)
+/// #     -> nuitrack_rs::nuitrack::shared_types::error::Result<()> {
+/// use nuitrack_rs::nuitrack::async_api::user_event_stream::UserEventStream;
+///
+/// let events = UserEventStream::for_device(&mut session.active_devices[0])?;
+/// # Ok(()) }
+/// ```
+pub struct UserEventStream {
+    sources: Vec<Source>,
+    lifecycle: UserLifecycle,
+    pending: VecDeque<UserEvent>,
+}
+
+impl UserEventStream {
+    /// A stream without sources; attach some with the builder methods.
+    pub fn new(lifecycle: UserLifecycle) -> Self {
+        Self { sources: Vec::new(), lifecycle, pending: VecDeque::new() }
+    }
+
+    /// A stream over every user source of the device's modules.
+    ///
+    /// This takes each module's stream, just like calling its `*_stream()` method.
+    #[instrument(skip(device), fields(serial = %device.info.serial_number))]
+    pub fn for_device(device: &mut ActiveDeviceContext) -> NuitrackResult<Self> {
+        let mut stream = Self::new(UserLifecycle::new());
+        if let Some(tracker) = device.user_tracker.as_mut() {
+            stream = stream.new_users(tracker.new_user_events_stream()?);
+        }
+        if let Some(recognizer) = device.gesture_recognizer.as_mut() {
+            stream = stream.user_states(recognizer.user_state_changes_stream()?);
+        }
+        if let Some(tracker) = device.user_tracker.as_mut() {
+            stream = stream.user_frames(tracker.user_frames_stream()?);
+        }
+        if let Some(tracker) = device.skeleton_tracker.as_mut() {
+            stream = stream.skeletons(tracker.skeleton_frames_stream()?);
+        }
+        if let Some(tracker) = device.user_tracker.as_mut() {
+            stream = stream.lost_users(tracker.lost_user_events_stream()?);
+        }
+        if stream.sources.is_empty() {
+            return Err(NuitrackError::OperationFailed(
+                "UserEventStream needs a user tracker, gesture recognizer or skeleton tracker".to_string(),
+            ));
+        }
+        debug!(sources = ?stream.sources.iter().map(|s| s.name).collect::<Vec<_>>(), "Built user event stream.");
+        Ok(stream)
+    }
+
+    fn attach<S, T>(mut self, name: &'static str, stream: S, wrap: fn(T) -> BackendFrame) -> Self
+    where
+        S: Stream<Item = NuitrackResult<T>> + Send + 'static,
+        T: 'static,
+    {
+        self.sources.retain(|source| source.name != name);
+        self.sources.push(Source { name, stream: member_stream(stream, wrap), ended: false });
+        self.lifecycle.set_skeletons_only(self.sources.iter().all(|source| source.name == "skeletons"));
+        self
+    }
+
+    pub fn new_users(self, stream: impl Stream<Item = NuitrackResult<i32>> + Send + 'static) -> Self {
+        self.attach("new_users", stream, BackendFrame::NewUser)
+    }
+
+    pub fn lost_users(self, stream: impl Stream<Item = NuitrackResult<i32>> + Send + 'static) -> Self {
+        self.attach("lost_users", stream, BackendFrame::LostUser)
+    }
+
+    pub fn user_frames(self, stream: impl Stream<Item = NuitrackResult<UserFrame>> + Send + 'static) -> Self {
+        self.attach("user_frames", stream, BackendFrame::User)
+    }
+
+    pub fn user_states(self, stream: impl Stream<Item = NuitrackResult<UserStateFrame>> + Send + 'static) -> Self {
+        self.attach("user_states", stream, BackendFrame::UserStateChange)
+    }
+
+    pub fn skeletons(self, stream: impl Stream<Item = NuitrackResult<SkeletonFrame>> + Send + 'static) -> Self {
+        self.attach("skeletons", stream, BackendFrame::Skeleton)
+    }
+
+    /// The users currently in the scene.
+    pub fn lifecycle(&self) -> &UserLifecycle {
+        &self.lifecycle
+    }
+
+    fn process(&mut self, frame: BackendFrame) -> NuitrackResult<Vec<UserEvent>> {
+        match frame {
+            BackendFrame::NewUser(user_id) => Ok(self.lifecycle.user_appeared(user_id)),
+            BackendFrame::LostUser(user_id) => Ok(self.lifecycle.user_lost(user_id)),
+            BackendFrame::User(frame) => self.lifecycle.process_user_frame(&frame),
+            BackendFrame::UserStateChange(frame) => self.lifecycle.process_user_state_frame(&frame),
+            BackendFrame::Skeleton(frame) => self.lifecycle.process_skeleton_frame(&frame),
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
+impl Stream for UserEventStream {
+    type Item = NuitrackResult<UserEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        'next: loop {
+            if let Some(event) = this.pending.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            // Restart from the first source after every item, so earlier sources win.
+            for index in 0..this.sources.len() {
+                let source = &mut this.sources[index];
+                if source.ended {
+                    continue;
+                }
+                match source.stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(Ok(frame))) => {
+                        match this.process(frame) {
+                            Ok(events) => this.pending.extend(events),
+                            Err(e) => return Poll::Ready(Some(Err(e))),
+                        }
+                        continue 'next;
+                    }
+                    Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                    Poll::Ready(None) => {
+                        debug!(source = source.name, "User source ended.");
+                        source.ended = true;
+                    }
+                    Poll::Pending => {}
+                }
+            }
+            return if this.sources.iter().all(|s| s.ended) { Poll::Ready(None) } else { Poll::Pending };
+        }
+    }
+}
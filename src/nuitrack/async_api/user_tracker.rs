@@ -1,3 +1,4 @@
+use crate::nuitrack::backend::virtual_device::stream_keys;
 use tracing::{warn};
 use crate::nuitrack_bridge::modules::user_tracker::ffi as ut_ffi;
 use crate::nuitrack::shared_types::user_frame::UserFrame;
@@ -46,7 +47,12 @@ impl AsyncUserTracker {
     /// until the data is retrieved from the Nuitrack SDK.
     #[instrument(skip(self))]
     pub async fn latest_user_frame_sync(&self) -> NuitrackResult<UserFrame> {
-        let ptr = self.get_ffi_ptr_clone();
+        if let Some(device) = self.virtual_device() {
+            return device.latest(stream_keys::USER_FRAMES).ok_or_else(|| {
+                NuitrackError::OperationFailed("Backend has not produced a user frame yet".to_string())
+            });
+        }
+        let ptr = self.get_ffi_ptr_clone()?;
         let ffi_user_frame_ptr = trace_span!("ffi", function = "ut_ffi::user_frame").in_scope(
             || {
                 run_blocking(move || {
@@ -68,7 +74,7 @@ impl AsyncUserTracker {
     /// Gets the last frame processing time in milliseconds.
     #[instrument(skip(self))]
     pub async fn processing_time(&self) -> NuitrackResult<f32> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function = "ut_ffi::processing_time").in_scope(|| {
             run_blocking(move || {
                 ut_ffi::processing_time(&ptr)
@@ -81,7 +87,10 @@ impl AsyncUserTracker {
     /// Gets the timestamp of the last processed data by the tracker in microseconds.
     #[instrument(skip(self))]
     pub async fn tracker_timestamp(&self) -> NuitrackResult<u64> {
-        let ptr = self.get_ffi_ptr_clone();
+        if let Some(device) = self.virtual_device() {
+            return Ok(device.last_timestamp());
+        }
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function = "ut_ffi::tracker_timestamp").in_scope(|| {
             run_blocking(move || {
                 ut_ffi::tracker_timestamp(&ptr)
@@ -94,7 +103,7 @@ impl AsyncUserTracker {
     /// Checks if the Nuitrack module can update.
     #[instrument(skip(self))]
     pub async fn can_update(&self) -> NuitrackResult<bool> {
-        let ptr = self.get_ffi_ptr_clone();
+        let ptr = self.get_ffi_ptr_clone()?;
         trace_span!("ffi", function = "ut_ffi::can_update").in_scope(|| {
             run_blocking(move || {
                 ut_ffi::can_update(&ptr)
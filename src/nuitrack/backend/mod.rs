@@ -0,0 +1,99 @@
+//! Frame sources other than the Nuitrack SDK.
+//!
+//! A [`FrameBackend`] produces frames entirely in Rust. Handing one to
+//! [`NuitrackSessionBuilder::with_backend`](crate::nuitrack::async_api::session_builder::NuitrackSessionBuilder::with_backend)
+//! yields a session whose trackers and streams behave like their hardware
+//! counterparts, without the SDK runtime ever being initialized.
+
+pub mod replay;
+pub mod simulated;
+pub(crate) mod virtual_device;
+
+use crate::nuitrack::shared_types::{
+    depth_frame::DepthFrame,
+    error::Result as NuitrackResult,
+    gesture_frame::{GestureFrame, UserGesturesFrame, UserStateFrame},
+    hand_frame::HandFrame,
+    rgb_frame::RGBFrame,
+    skeleton_frame::SkeletonFrame,
+    user_frame::UserFrame,
+};
+use crate::nuitrack_bridge::types::output_mode::ffi::OutputMode;
+
+/// Static description of a device exposed by a backend.
+#[derive(Debug, Clone)]
+pub struct VirtualDeviceDescriptor {
+    pub name: String,
+    pub serial_number: String,
+    pub provider_name: String,
+    /// `None` if the device has no depth stream.
+    pub depth_mode: Option<OutputMode>,
+    /// `None` if the device has no color stream.
+    pub color_mode: Option<OutputMode>,
+}
+
+/// A single item routed to the stream of the matching tracker.
+#[derive(Clone)]
+pub enum BackendFrame {
+    Depth(DepthFrame),
+    Color(RGBFrame),
+    User(UserFrame),
+    Skeleton(SkeletonFrame),
+    Hand(HandFrame),
+    CompletedGestures(GestureFrame),
+    UserStateChange(UserStateFrame),
+    GestureUpdate(UserGesturesFrame),
+    /// Delivered to both the user tracker's and skeleton tracker's new-user streams.
+    NewUser(i32),
+    /// Delivered to both the user tracker's and skeleton tracker's lost-user streams.
+    LostUser(i32),
+}
+
+impl BackendFrame {
+    /// The frame's own timestamp, or `None` for user events, which carry no timestamp.
+    pub fn timestamp(&self) -> NuitrackResult<Option<u64>> {
+        Ok(Some(match self {
+            BackendFrame::Depth(f) => f.timestamp()?,
+            BackendFrame::Color(f) => f.timestamp()?,
+            BackendFrame::User(f) => f.timestamp()?,
+            BackendFrame::Skeleton(f) => f.timestamp()?,
+            BackendFrame::Hand(f) => f.timestamp()?,
+            BackendFrame::CompletedGestures(f) => f.timestamp()?,
+            BackendFrame::UserStateChange(f) => f.timestamp()?,
+            BackendFrame::GestureUpdate(f) => f.timestamp()?,
+            BackendFrame::NewUser(_) | BackendFrame::LostUser(_) => return Ok(None),
+        }))
+    }
+}
+
+/// Everything one device produced during one update cycle.
+#[derive(Clone)]
+pub struct FrameBatch {
+    /// Index into [`FrameBackend::devices`].
+    pub device_index: usize,
+    /// Timestamp of the cycle in microseconds.
+    pub timestamp: u64,
+    pub frames: Vec<BackendFrame>,
+}
+
+/// A pluggable source of frames.
+///
+/// `next_batch` plays the role of the SDK's `waitUpdate`: it may block until the
+/// next cycle is due, and is always called from a blocking-friendly context.
+pub trait FrameBackend: Send + 'static {
+    /// Human-readable backend name, used in logs and errors.
+    fn name(&self) -> &str;
+
+    /// The devices this backend exposes. Called once, when the session is built.
+    fn devices(&self) -> Vec<VirtualDeviceDescriptor>;
+
+    /// Produces the next batch, or `Ok(None)` once the backend has no more data.
+    /// Streams of a session end once its backend is exhausted or returns an error.
+    fn next_batch(&mut self) -> NuitrackResult<Option<FrameBatch>>;
+
+    /// When `true`, the session never steps the backend on its own; each call to
+    /// `NuitrackSession::drive_update_cycle` delivers exactly one batch.
+    fn manual_stepping(&self) -> bool {
+        false
+    }
+}
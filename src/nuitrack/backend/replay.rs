@@ -0,0 +1,164 @@
+//! Plays back a recording made with
+//! [`SessionRecorder`](crate::nuitrack::recording::recorder::SessionRecorder).
+//!
+//! Frames are delivered exactly as they were recorded: same payloads, same
+//! timestamps, same per-device grouping. Only the pacing is configurable.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use tracing::{debug, info, instrument, trace};
+
+use crate::nuitrack::recording::format::RecordingReader;
+use crate::nuitrack::shared_types::error::Result as NuitrackResult;
+
+use super::{BackendFrame, FrameBackend, FrameBatch, VirtualDeviceDescriptor};
+
+/// How a [`ReplayBackend`] paces the recorded frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplayTiming {
+    /// Frames are released with the same spacing as their original timestamps.
+    #[default]
+    Original,
+    /// Frames are released as fast as the session consumes them.
+    AsFastAsPossible,
+    /// One batch per `NuitrackSession::drive_update_cycle` call; the internal
+    /// update loop is never started.
+    Stepped,
+}
+
+/// Replays a recorded capture through the regular async API.
+///
+/// ```no_run
+/// # async fn example() -> nuitrack_rs::nuitrack::shared_types::error::Result<()> {
+/// use nuitrack_rs::nuitrack::async_api::session_builder::NuitrackSessionBuilder;
+/// use nuitrack_rs::nuitrack::backend::replay::ReplayTiming;
+///
+/// let session = NuitrackSessionBuilder::new()
+///     .with_recording("capture.ntrk", ReplayTiming::AsFastAsPossible)
+///     .init_session()
+///     .await?;
+/// # Ok(()) }
+/// ```
+pub struct ReplayBackend {
+    reader: RecordingReader<BufReader<File>>,
+    devices: Vec<VirtualDeviceDescriptor>,
+    timing: ReplayTiming,
+    /// Frames read ahead of the next batch, grouped by `(timestamp, device_index)`.
+    pending: BTreeMap<(u64, usize), Vec<BackendFrame>>,
+    /// Latest timestamp read from the file so far.
+    newest_read: Option<u64>,
+    /// Wall-clock start and the recording timestamp it corresponds to.
+    clock: Option<(Instant, u64)>,
+}
+
+impl ReplayBackend {
+    /// Opens the recording at `path` with [`ReplayTiming::Original`] pacing.
+    #[instrument(skip(path), fields(path = %path.as_ref().display()))]
+    pub fn open(path: impl AsRef<Path>) -> NuitrackResult<Self> {
+        let file = File::open(path.as_ref())?;
+        Self::from_reader(RecordingReader::new(BufReader::new(file))?)
+    }
+
+    pub fn from_reader(reader: RecordingReader<BufReader<File>>) -> NuitrackResult<Self> {
+        let devices = reader
+            .header()
+            .devices
+            .iter()
+            .map(|d| VirtualDeviceDescriptor {
+                name: d.name.clone(),
+                serial_number: d.serial_number.clone(),
+                provider_name: d.provider_name.clone(),
+                depth_mode: d.depth_mode,
+                color_mode: d.color_mode,
+            })
+            .collect::<Vec<_>>();
+        info!(devices = devices.len(), chunks = reader.chunks().len(), "Opened recording for replay.");
+        Ok(Self { reader, devices, timing: ReplayTiming::default(), pending: BTreeMap::new(), newest_read: None, clock: None })
+    }
+
+    pub fn with_timing(mut self, timing: ReplayTiming) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    pub fn timing(&self) -> ReplayTiming {
+        self.timing
+    }
+
+    /// Timestamp range covered by the recording, if it contains any records.
+    pub fn time_range(&self) -> Option<(u64, u64)> {
+        self.reader.time_range()
+    }
+
+    /// Skips ahead so that the next batch is the first one at or after `timestamp`.
+    pub fn seek_to_timestamp(&mut self, timestamp: u64) -> NuitrackResult<()> {
+        self.pending.clear();
+        self.newest_read = None;
+        self.clock = None;
+        self.reader.seek_to_timestamp(timestamp)
+    }
+
+    /// Restarts playback from the beginning of the recording.
+    pub fn rewind(&mut self) {
+        self.pending.clear();
+        self.newest_read = None;
+        self.clock = None;
+        self.reader.rewind();
+    }
+
+    fn wait_until_due(&mut self, timestamp: u64) {
+        let (started_at, first_timestamp) = *self.clock.get_or_insert((Instant::now(), timestamp));
+        let due = started_at + Duration::from_micros(timestamp.saturating_sub(first_timestamp));
+        let now = Instant::now();
+        if due > now {
+            std::thread::sleep(due - now);
+        }
+    }
+}
+
+impl FrameBackend for ReplayBackend {
+    fn name(&self) -> &str {
+        "replay"
+    }
+
+    fn devices(&self) -> Vec<VirtualDeviceDescriptor> {
+        self.devices.clone()
+    }
+
+    fn next_batch(&mut self) -> NuitrackResult<Option<FrameBatch>> {
+        // Records of one update cycle share a timestamp but need not be adjacent in the
+        // file, so read ahead until a later timestamp shows the earliest cycle is complete.
+        loop {
+            let head_complete = match (self.pending.keys().next(), self.newest_read) {
+                (Some(&(head, _)), Some(newest)) => newest > head,
+                _ => false,
+            };
+            if head_complete {
+                break;
+            }
+            let Some(record) = self.reader.next_record()? else {
+                break;
+            };
+            self.newest_read = self.newest_read.max(Some(record.timestamp));
+            self.pending.entry((record.timestamp, record.device_index)).or_default().push(record.frame);
+        }
+        let Some(((timestamp, device_index), frames)) = self.pending.pop_first() else {
+            debug!("Reached end of recording.");
+            return Ok(None);
+        };
+
+        if self.timing == ReplayTiming::Original {
+            self.wait_until_due(timestamp);
+        }
+        trace!(device_index, timestamp, frames = frames.len(), "Replaying batch.");
+        Ok(Some(FrameBatch { device_index, timestamp, frames }))
+    }
+
+    fn manual_stepping(&self) -> bool {
+        self.timing == ReplayTiming::Stepped
+    }
+}
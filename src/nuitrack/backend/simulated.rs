@@ -0,0 +1,456 @@
+//! A self-contained backend that synthesizes a plausible scene.
+//!
+//! Every device sees the same configurable number of users swaying in front of
+//! a flat wall. Each user periodically waves with the right hand, which drives
+//! gesture progress, completed `Waving` gestures and a raised right hand in the
+//! skeleton and hand streams. Output is fully deterministic for a given
+//! configuration; only the pacing depends on the wall clock.
+
+use std::f32::consts::PI;
+use std::time::{Duration, Instant};
+
+use tracing::{instrument, trace};
+
+use crate::nuitrack::shared_types::{
+    depth_frame::DepthFrame,
+    error::{NuitrackError, Result as NuitrackResult},
+    gesture::{Gesture, GestureState, GestureType, UserGestures, UserState, UserStateType},
+    gesture_frame::{GestureFrame, UserGesturesFrame, UserStateFrame},
+    hand::{Hand, UserHands},
+    hand_frame::HandFrame,
+    rgb_frame::{Color3, RGBFrame},
+    skeleton::{Joint, JointType, Skeleton},
+    skeleton_frame::SkeletonFrame,
+    user::User,
+    user_frame::UserFrame,
+};
+use crate::nuitrack_bridge::types::bounding_box::ffi::BoundingBox;
+use crate::nuitrack_bridge::types::output_mode::ffi::{Intrinsics, OutputMode};
+use crate::nuitrack_bridge::types::skeleton::ffi::Orientation;
+use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+
+use super::{BackendFrame, FrameBackend, FrameBatch, VirtualDeviceDescriptor};
+
+/// Depth of the background wall in millimeters.
+const WALL_DEPTH_MM: u16 = 3500;
+/// Height of the floor below the sensor in millimeters.
+const FLOOR_HEIGHT_MM: f32 = 1150.0;
+/// A wave starts every `WAVE_PERIOD_S` seconds and lasts `WAVE_DURATION_S`.
+const WAVE_PERIOD_S: f32 = 8.0;
+const WAVE_DURATION_S: f32 = 2.0;
+
+/// Most users a scene can hold, as many as Nuitrack tracks at once.
+pub const MAX_USERS: usize = 6;
+
+const IDENTITY_ORIENTATION: Orientation = Orientation {
+    matrix: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+};
+
+/// Synthesizes depth, color, user, skeleton, hand and gesture data without a sensor.
+///
+/// ```no_run
+/// # async fn example() -> nuitrack_rs::nuitrack::shared_types::error::Result<()> {
+/// use nuitrack_rs::nuitrack::async_api::session_builder::NuitrackSessionBuilder;
+/// use nuitrack_rs::nuitrack::backend::simulated::SimulatedBackend;
+///
+/// let session = NuitrackSessionBuilder::new()
+///     .with_backend(SimulatedBackend::new().with_users(2)?)
+///     .init_session()
+///     .await?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SimulatedBackend {
+    device_count: usize,
+    user_count: usize,
+    xres: i32,
+    yres: i32,
+    fps: i32,
+    hfov: f32,
+    realtime: bool,
+    tick: u64,
+    next_device: usize,
+    started_at: Option<Instant>,
+    /// `(device_index, from_us, to_us)`: the device produces nothing in that timestamp range.
+    dropouts: Vec<(usize, u64, u64)>,
+}
+
+impl Default for SimulatedBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatedBackend {
+    /// One 640x480 device at 30 FPS with a single user, paced in real time.
+    pub fn new() -> Self {
+        Self {
+            device_count: 1,
+            user_count: 1,
+            xres: 640,
+            yres: 480,
+            fps: 30,
+            hfov: 60f32.to_radians(),
+            realtime: true,
+            tick: 0,
+            next_device: 0,
+            started_at: None,
+            dropouts: Vec::new(),
+        }
+    }
+
+    /// Number of virtual devices. Each one sees the same scene.
+    pub fn with_devices(mut self, count: usize) -> Self {
+        self.device_count = count;
+        self
+    }
+
+    /// Number of users in the scene. User IDs start at 1. Fails for more than
+    /// [`MAX_USERS`] users.
+    pub fn with_users(mut self, count: usize) -> NuitrackResult<Self> {
+        if count > MAX_USERS {
+            return Err(NuitrackError::OperationFailed(format!(
+                "Simulated scene supports at most {} users, got {}",
+                MAX_USERS, count
+            )));
+        }
+        self.user_count = count;
+        Ok(self)
+    }
+
+    /// Resolution shared by the depth and color streams.
+    pub fn with_resolution(mut self, xres: i32, yres: i32) -> Self {
+        self.xres = xres;
+        self.yres = yres;
+        self
+    }
+
+    pub fn with_fps(mut self, fps: i32) -> Self {
+        self.fps = fps.max(1);
+        self
+    }
+
+    /// Horizontal field of view in radians.
+    pub fn with_hfov(mut self, hfov: f32) -> Self {
+        self.hfov = hfov;
+        self
+    }
+
+    /// When disabled, frames are produced as fast as they are consumed.
+    pub fn realtime(mut self, enabled: bool) -> Self {
+        self.realtime = enabled;
+        self
+    }
+
+    /// Simulates a disconnect: `device_index` delivers no frames between the given
+    /// offsets from the start of the simulation, then resumes.
+    pub fn with_dropout(mut self, device_index: usize, from: Duration, to: Duration) -> Self {
+        self.dropouts.push((device_index, from.as_micros() as u64, to.as_micros() as u64));
+        self
+    }
+
+    fn is_dropped_out(&self, device_index: usize, timestamp: u64) -> bool {
+        self.dropouts
+            .iter()
+            .any(|&(index, from, to)| index == device_index && (from..to).contains(&timestamp))
+    }
+
+    fn frame_interval_us(&self) -> u64 {
+        1_000_000 / self.fps as u64
+    }
+
+    fn output_mode(&self) -> OutputMode {
+        let fx = self.xres as f32 / (2.0 * (self.hfov / 2.0).tan());
+        OutputMode {
+            fps: self.fps,
+            xres: self.xres,
+            yres: self.yres,
+            hfov: self.hfov,
+            intrinsics: Intrinsics {
+                fx,
+                fy: fx,
+                cx: self.xres as f32 / 2.0,
+                cy: self.yres as f32 / 2.0,
+            },
+        }
+    }
+
+    /// Projects a real-world point (mm, Y up) to normalized image coordinates, keeping depth in `z`.
+    fn project(&self, mode: &OutputMode, real: Vector3) -> Vector3 {
+        let z = real.z.max(1.0);
+        Vector3 {
+            x: (mode.intrinsics.cx + mode.intrinsics.fx * real.x / z) / mode.xres as f32,
+            y: (mode.intrinsics.cy - mode.intrinsics.fy * real.y / z) / mode.yres as f32,
+            z: real.z,
+        }
+    }
+
+    /// Wave progress in `0..=100` for a user at time `t`, or `None` outside a wave.
+    fn wave_progress(user_index: usize, t: f32) -> Option<i32> {
+        let phase = (t + user_index as f32 * 3.0) % WAVE_PERIOD_S;
+        (phase < WAVE_DURATION_S).then(|| (phase / WAVE_DURATION_S * 100.0) as i32)
+    }
+
+    fn skeleton(&self, mode: &OutputMode, user_index: usize, t: f32) -> Skeleton {
+        let phase = user_index as f32 * 1.7;
+        let spread = (user_index as f32 - (self.user_count as f32 - 1.0) / 2.0) * 800.0;
+        let torso = Vector3 {
+            x: spread + 300.0 * (0.5 * t + phase).sin(),
+            y: 0.0,
+            z: 2200.0 + 300.0 * (0.3 * t + phase).cos(),
+        };
+
+        let waving = Self::wave_progress(user_index, t).is_some();
+        let wave_offset = 80.0 * (6.0 * PI * t).sin();
+        let right_arm: [(f32, f32); 3] = if waving {
+            [(300.0, 300.0), (320.0 + wave_offset, 550.0), (330.0 + wave_offset, 620.0)]
+        } else {
+            [(200.0, 0.0), (210.0, -220.0), (215.0, -280.0)]
+        };
+
+        // Offsets from the torso in millimeters; `None` marks joints the SDK does not track.
+        let offsets: [(JointType, Option<(f32, f32)>); 24] = [
+            (JointType::Head, Some((0.0, 450.0))),
+            (JointType::Neck, Some((0.0, 300.0))),
+            (JointType::Torso, Some((0.0, 0.0))),
+            (JointType::Waist, Some((0.0, -200.0))),
+            (JointType::LeftCollar, Some((0.0, 250.0))),
+            (JointType::LeftShoulder, Some((-180.0, 250.0))),
+            (JointType::LeftElbow, Some((-200.0, 0.0))),
+            (JointType::LeftWrist, Some((-210.0, -220.0))),
+            (JointType::LeftHand, Some((-215.0, -280.0))),
+            (JointType::LeftFingertip, None),
+            (JointType::RightCollar, None),
+            (JointType::RightShoulder, Some((180.0, 250.0))),
+            (JointType::RightElbow, Some(right_arm[0])),
+            (JointType::RightWrist, Some(right_arm[1])),
+            (JointType::RightHand, Some(right_arm[2])),
+            (JointType::RightFingertip, None),
+            (JointType::LeftHip, Some((-100.0, -250.0))),
+            (JointType::LeftKnee, Some((-110.0, -700.0))),
+            (JointType::LeftAnkle, Some((-110.0, -1100.0))),
+            (JointType::LeftFoot, None),
+            (JointType::RightHip, Some((100.0, -250.0))),
+            (JointType::RightKnee, Some((110.0, -700.0))),
+            (JointType::RightAnkle, Some((110.0, -1100.0))),
+            (JointType::RightFoot, None),
+        ];
+
+        let joints = offsets
+            .iter()
+            .map(|&(joint_type, offset)| match offset {
+                Some((dx, dy)) => {
+                    let real = Vector3 { x: torso.x + dx, y: torso.y + dy, z: torso.z };
+                    Joint {
+                        joint_type,
+                        confidence: 0.75,
+                        real,
+                        proj: self.project(mode, real),
+                        orient: IDENTITY_ORIENTATION,
+                    }
+                }
+                None => Joint {
+                    joint_type,
+                    confidence: 0.0,
+                    real: Vector3::default(),
+                    proj: Vector3::default(),
+                    orient: IDENTITY_ORIENTATION,
+                },
+            })
+            .collect();
+
+        Skeleton { user_id: user_index as i32 + 1, joints }
+    }
+
+    fn hand_from_joint(joint: &Joint) -> Hand {
+        Hand {
+            x: joint.proj.x.clamp(0.0, 1.0),
+            y: joint.proj.y.clamp(0.0, 1.0),
+            click: false,
+            pressure: 0,
+            x_real: joint.real.x,
+            y_real: joint.real.y,
+            z_real: joint.real.z,
+        }
+    }
+
+    fn joint(skeleton: &Skeleton, joint_type: JointType) -> &Joint {
+        // `skeleton()` always emits every joint except `JointType::None`, in enum order.
+        &skeleton.joints[joint_type.repr as usize - 1]
+    }
+
+    /// Builds every frame for one device at one tick.
+    #[instrument(level = "trace", skip(self))]
+    fn synthesize(&self, tick: u64, timestamp: u64) -> Vec<BackendFrame> {
+        let mode = self.output_mode();
+        let t = timestamp as f32 / 1_000_000.0;
+        let (cols, rows) = (self.xres.max(0) as usize, self.yres.max(0) as usize);
+
+        let skeletons: Vec<Skeleton> = (0..self.user_count).map(|i| self.skeleton(&mode, i, t)).collect();
+
+        let mut depth = vec![WALL_DEPTH_MM; cols * rows];
+        let mut labels = vec![0u16; cols * rows];
+        let mut users = Vec::with_capacity(skeletons.len());
+
+        for skeleton in &skeletons {
+            let tracked: Vec<&Joint> = skeleton.joints.iter().filter(|j| j.confidence > 0.0).collect();
+            let pad = 0.02;
+            let bbox = BoundingBox {
+                left: tracked.iter().map(|j| j.proj.x).fold(f32::MAX, f32::min) - pad,
+                right: tracked.iter().map(|j| j.proj.x).fold(f32::MIN, f32::max) + pad,
+                top: tracked.iter().map(|j| j.proj.y).fold(f32::MAX, f32::min) - pad,
+                bottom: tracked.iter().map(|j| j.proj.y).fold(f32::MIN, f32::max) + pad,
+            };
+            let torso = *Self::joint(skeleton, JointType::Torso);
+
+            // Fill an ellipse inscribed in the bounding box; closer users overwrite farther ones.
+            let (cx, cy) = ((bbox.left + bbox.right) / 2.0, (bbox.top + bbox.bottom) / 2.0);
+            let (rx, ry) = ((bbox.right - bbox.left) / 2.0, (bbox.bottom - bbox.top) / 2.0);
+            let user_depth = torso.real.z.clamp(1.0, WALL_DEPTH_MM as f32) as u16;
+            let row_range = ((bbox.top.max(0.0) * rows as f32) as usize)..((bbox.bottom.min(1.0) * rows as f32) as usize);
+            let col_range = ((bbox.left.max(0.0) * cols as f32) as usize)..((bbox.right.min(1.0) * cols as f32) as usize);
+            for r in row_range {
+                let ny = ((r as f32 + 0.5) / rows as f32 - cy) / ry;
+                for c in col_range.clone() {
+                    let nx = ((c as f32 + 0.5) / cols as f32 - cx) / rx;
+                    let idx = r * cols + c;
+                    if nx * nx + ny * ny <= 1.0 && user_depth < depth[idx] {
+                        depth[idx] = user_depth;
+                        labels[idx] = skeleton.user_id as u16;
+                    }
+                }
+            }
+
+            users.push(User {
+                id: skeleton.user_id,
+                proj: torso.proj,
+                real: torso.real,
+                r#box: bbox,
+                occlusion: 0.0,
+            });
+        }
+
+        let color: Vec<Color3> = labels
+            .iter()
+            .enumerate()
+            .map(|(idx, &label)| {
+                let (r, c) = (idx / cols.max(1), idx % cols.max(1));
+                if label == 0 {
+                    Color3 {
+                        blue: 160,
+                        green: (r * 255 / rows.max(1)) as u8,
+                        red: (c * 255 / cols.max(1)) as u8,
+                    }
+                } else {
+                    Color3 { blue: 40, green: 40 * label as u8, red: 200 }
+                }
+            })
+            .collect();
+
+        let users_hands = skeletons
+            .iter()
+            .map(|s| UserHands {
+                user_id: s.user_id,
+                left_hand: Some(Self::hand_from_joint(Self::joint(s, JointType::LeftHand))),
+                right_hand: Some(Self::hand_from_joint(Self::joint(s, JointType::RightHand))),
+            })
+            .collect();
+
+        let previous_t = t - self.frame_interval_us() as f32 / 1_000_000.0;
+        let mut completed = Vec::new();
+        let mut user_gestures = Vec::with_capacity(self.user_count);
+        for i in 0..self.user_count {
+            let user_id = i as i32 + 1;
+            let progress = Self::wave_progress(i, t);
+            if tick > 0 && progress.is_none() && Self::wave_progress(i, previous_t).is_some() {
+                completed.push(Gesture { user_id, gesture_type: GestureType::Waving });
+            }
+            user_gestures.push(UserGestures {
+                user_id,
+                user_state: UserStateType::Active,
+                gestures: vec![GestureState { gesture_type: GestureType::Waving, progress: progress.unwrap_or(0) }],
+            });
+        }
+
+        let mut frames = Vec::new();
+        if tick == 0 {
+            frames.extend((1..=self.user_count as i32).map(BackendFrame::NewUser));
+            let states = (1..=self.user_count as i32)
+                .map(|user_id| UserState { user_id, state: UserStateType::Active })
+                .collect();
+            frames.push(BackendFrame::UserStateChange(UserStateFrame::synthetic(timestamp, states)));
+        }
+        let frame_id = tick + 1;
+        frames.push(BackendFrame::Depth(DepthFrame::synthetic(self.yres, self.xres, frame_id, timestamp, depth)));
+        frames.push(BackendFrame::Color(RGBFrame::synthetic(self.yres, self.xres, frame_id, timestamp, color)));
+        frames.push(BackendFrame::User(UserFrame::synthetic(
+            self.yres,
+            self.xres,
+            timestamp,
+            labels,
+            users,
+            Vector3 { x: 0.0, y: -FLOOR_HEIGHT_MM, z: 2500.0 },
+            Vector3 { x: 0.0, y: 1.0, z: 0.0 },
+        )));
+        frames.push(BackendFrame::Skeleton(SkeletonFrame::synthetic(timestamp, skeletons)));
+        frames.push(BackendFrame::Hand(HandFrame::synthetic(timestamp, users_hands)));
+        frames.push(BackendFrame::GestureUpdate(UserGesturesFrame::synthetic(timestamp, user_gestures)));
+        if !completed.is_empty() {
+            frames.push(BackendFrame::CompletedGestures(GestureFrame::synthetic(timestamp, completed)));
+        }
+        frames
+    }
+}
+
+impl FrameBackend for SimulatedBackend {
+    fn name(&self) -> &str {
+        "simulated"
+    }
+
+    fn devices(&self) -> Vec<VirtualDeviceDescriptor> {
+        let mode = self.output_mode();
+        (0..self.device_count)
+            .map(|i| VirtualDeviceDescriptor {
+                name: "Simulated Sensor".to_string(),
+                serial_number: format!("SIM-{:04}", i),
+                provider_name: "nuitrack-rs".to_string(),
+                depth_mode: Some(mode),
+                color_mode: Some(mode),
+            })
+            .collect()
+    }
+
+    fn next_batch(&mut self) -> NuitrackResult<Option<FrameBatch>> {
+        if self.device_count == 0 {
+            return Ok(None);
+        }
+        loop {
+            let timestamp = self.tick * self.frame_interval_us();
+
+            if self.realtime {
+                let started_at = *self.started_at.get_or_insert_with(Instant::now);
+                let due = started_at + Duration::from_micros(timestamp);
+                let now = Instant::now();
+                if due > now {
+                    std::thread::sleep(due - now);
+                }
+            }
+
+            let (tick, device_index) = (self.tick, self.next_device);
+            self.next_device += 1;
+            if self.next_device == self.device_count {
+                self.next_device = 0;
+                self.tick += 1;
+            }
+            // Time keeps advancing while a device is silent, even if it is the only one.
+            if self.is_dropped_out(device_index, timestamp) {
+                trace!(timestamp, device_index, "Device dropped out; skipping batch.");
+                continue;
+            }
+
+            let frames = self.synthesize(tick, timestamp);
+            trace!(tick, device_index, frames = frames.len(), "Synthesized batch.");
+            return Ok(Some(FrameBatch { device_index, timestamp, frames }));
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use futures_channel::mpsc::UnboundedSender;
+use tracing::{debug, instrument, trace, warn};
+
+use crate::nuitrack::shared_types::error::{NuitrackError, Result as NuitrackResult};
+
+use super::{BackendFrame, FrameBackend, VirtualDeviceDescriptor};
+
+/// Stream keys shared between the tracker macro and the backend driver.
+///
+/// Each key is `"<module>::<stream method>"`, exactly as `generate_async_tracker!`
+/// builds it from its own identifiers.
+pub(crate) mod stream_keys {
+    pub(crate) const DEPTH_FRAMES: &str = "depth_sensor::depth_frames_stream";
+    pub(crate) const RGB_FRAMES: &str = "color_sensor::rgb_frames_stream";
+    pub(crate) const USER_FRAMES: &str = "user_tracker::user_frames_stream";
+    pub(crate) const USER_TRACKER_NEW_USERS: &str = "user_tracker::new_user_events_stream";
+    pub(crate) const USER_TRACKER_LOST_USERS: &str = "user_tracker::lost_user_events_stream";
+    pub(crate) const SKELETON_FRAMES: &str = "skeleton_tracker::skeleton_frames_stream";
+    pub(crate) const SKELETON_TRACKER_NEW_USERS: &str = "skeleton_tracker::new_user_events_stream";
+    pub(crate) const SKELETON_TRACKER_LOST_USERS: &str = "skeleton_tracker::lost_user_events_stream";
+    pub(crate) const HAND_FRAMES: &str = "hand_tracker::hand_frames_stream";
+    pub(crate) const COMPLETED_GESTURES: &str = "gesture_recognizer::completed_gestures_frames_stream";
+    pub(crate) const USER_STATE_CHANGES: &str = "gesture_recognizer::user_state_changes_stream";
+    pub(crate) const GESTURE_UPDATES: &str = "gesture_recognizer::updates_stream";
+}
+
+/// In-process stand-in for a sensor: trackers created on a virtual device
+/// subscribe here instead of registering SDK callbacks.
+pub(crate) struct VirtualDevice {
+    descriptor: VirtualDeviceDescriptor,
+    subscribers: Mutex<HashMap<&'static str, Vec<(u64, Box<dyn Any + Send>)>>>,
+    latest: Mutex<HashMap<&'static str, Box<dyn Any + Send>>>,
+    last_timestamp: AtomicU64,
+    next_subscription_id: AtomicU64,
+}
+
+impl VirtualDevice {
+    pub(crate) fn new(descriptor: VirtualDeviceDescriptor) -> Self {
+        Self {
+            descriptor,
+            subscribers: Mutex::new(HashMap::new()),
+            latest: Mutex::new(HashMap::new()),
+            last_timestamp: AtomicU64::new(0),
+            next_subscription_id: AtomicU64::new(1),
+        }
+    }
+
+    pub(crate) fn descriptor(&self) -> &VirtualDeviceDescriptor {
+        &self.descriptor
+    }
+
+    /// Timestamp of the most recent batch published to this device.
+    pub(crate) fn last_timestamp(&self) -> u64 {
+        self.last_timestamp.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn subscribe<T: Send + 'static>(
+        &self,
+        key: &'static str,
+        sender: UnboundedSender<NuitrackResult<T>>,
+    ) -> u64 {
+        let id = self.next_subscription_id.fetch_add(1, Ordering::SeqCst);
+        trace!(key, id, "Subscribing to virtual device stream.");
+        self.subscribers.lock().unwrap().entry(key).or_default().push((id, Box::new(sender)));
+        id
+    }
+
+    pub(crate) fn unsubscribe(&self, key: &'static str, id: u64) {
+        trace!(key, id, "Unsubscribing from virtual device stream.");
+        if let Some(list) = self.subscribers.lock().unwrap().get_mut(key) {
+            list.retain(|(sub_id, _)| *sub_id != id);
+        }
+    }
+
+    /// Returns a clone of the last item published on `key`, if any.
+    pub(crate) fn latest<T: Clone + 'static>(&self, key: &'static str) -> Option<T> {
+        self.latest.lock().unwrap().get(key).and_then(|item| item.downcast_ref::<T>()).cloned()
+    }
+
+    fn publish<T: Clone + Send + 'static>(&self, key: &'static str, item: T) {
+        if let Some(list) = self.subscribers.lock().unwrap().get(key) {
+            for (id, sender) in list {
+                match sender.downcast_ref::<UnboundedSender<NuitrackResult<T>>>() {
+                    Some(tx) => {
+                        if tx.unbounded_send(Ok(item.clone())).is_err() {
+                            debug!(key, id, "Virtual stream receiver dropped.");
+                        }
+                    }
+                    None => warn!(key, id, "Subscriber registered with a mismatched item type."),
+                }
+            }
+        }
+        self.latest.lock().unwrap().insert(key, Box::new(item));
+    }
+
+    /// Drops every subscriber's sender so that all open streams terminate.
+    fn end_streams(&self) {
+        self.subscribers.lock().unwrap().clear();
+    }
+
+    fn dispatch(&self, timestamp: u64, frame: BackendFrame) {
+        use stream_keys::*;
+        self.last_timestamp.store(timestamp, Ordering::SeqCst);
+        match frame {
+            BackendFrame::Depth(f) => self.publish(DEPTH_FRAMES, f),
+            BackendFrame::Color(f) => self.publish(RGB_FRAMES, f),
+            BackendFrame::User(f) => self.publish(USER_FRAMES, f),
+            BackendFrame::Skeleton(f) => self.publish(SKELETON_FRAMES, f),
+            BackendFrame::Hand(f) => self.publish(HAND_FRAMES, f),
+            BackendFrame::CompletedGestures(f) => self.publish(COMPLETED_GESTURES, f),
+            BackendFrame::UserStateChange(f) => self.publish(USER_STATE_CHANGES, f),
+            BackendFrame::GestureUpdate(f) => self.publish(GESTURE_UPDATES, f),
+            BackendFrame::NewUser(id) => {
+                self.publish(USER_TRACKER_NEW_USERS, id);
+                self.publish(SKELETON_TRACKER_NEW_USERS, id);
+            }
+            BackendFrame::LostUser(id) => {
+                self.publish(USER_TRACKER_LOST_USERS, id);
+                self.publish(SKELETON_TRACKER_LOST_USERS, id);
+            }
+        }
+    }
+}
+
+/// Owns the backend and its virtual devices, and moves batches from one to the other.
+pub(crate) struct BackendDriver {
+    backend: Mutex<Box<dyn FrameBackend>>,
+    devices: Vec<Arc<VirtualDevice>>,
+}
+
+impl BackendDriver {
+    pub(crate) fn new(backend: Box<dyn FrameBackend>) -> Self {
+        let devices = backend
+            .devices()
+            .into_iter()
+            .map(|descriptor| Arc::new(VirtualDevice::new(descriptor)))
+            .collect();
+        Self { backend: Mutex::new(backend), devices }
+    }
+
+    pub(crate) fn devices(&self) -> &[Arc<VirtualDevice>] {
+        &self.devices
+    }
+
+    pub(crate) fn manual_stepping(&self) -> bool {
+        self.backend.lock().map(|backend| backend.manual_stepping()).unwrap_or(false)
+    }
+
+    /// Pulls one batch from the backend and publishes it. Blocks for as long as
+    /// the backend does. Returns the index of the updated device, or `Ok(None)`
+    /// once the backend is exhausted. Streams end once the backend is exhausted
+    /// or fails.
+    #[instrument(skip(self))]
+    pub(crate) fn step(&self) -> NuitrackResult<Option<usize>> {
+        let result = self.publish_next_batch();
+        if let Err(e) = &result {
+            debug!(error = %e, "Backend failed; ending streams.");
+            self.devices.iter().for_each(|device| device.end_streams());
+        }
+        result
+    }
+
+    fn publish_next_batch(&self) -> NuitrackResult<Option<usize>> {
+        let batch = {
+            let mut backend = self.backend.lock().map_err(|_| {
+                NuitrackError::Backend("Backend lock poisoned".into())
+            })?;
+            backend.next_batch()?
+        };
+        let Some(batch) = batch else {
+            debug!("Backend exhausted; ending streams.");
+            self.devices.iter().for_each(|device| device.end_streams());
+            return Ok(None);
+        };
+        let device = self.devices.get(batch.device_index).ok_or_else(|| {
+            NuitrackError::Backend(format!("Batch targets unknown device index {}", batch.device_index))
+        })?;
+        trace!(device_index = batch.device_index, timestamp = batch.timestamp, frames = batch.frames.len(), "Publishing batch.");
+        for frame in batch.frames {
+            device.dispatch(batch.timestamp, frame);
+        }
+        Ok(Some(batch.device_index))
+    }
+}
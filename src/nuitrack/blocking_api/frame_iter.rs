@@ -0,0 +1,89 @@
+//! Iterator adapter over the async tracker streams.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures_core::Stream;
+
+use crate::nuitrack::async_api::async_dispatch::{block_on, block_on_until};
+
+/// Blocking view of a tracker stream.
+///
+/// [`Iterator::next`] parks the calling thread until the next item arrives and
+/// returns `None` once the stream has ended, e.g. when the session is closed or a
+/// backend runs out of data.
+///
+/// Without the internal update loop, new items only arrive while
+/// [`BlockingSession::update`](super::session::BlockingSession::update) runs. In that
+/// case, call `update` and then drain the ready items with [`FrameIter::try_next`].
+pub struct FrameIter<S> {
+    stream: Pin<Box<S>>,
+    terminated: bool,
+}
+
+impl<S: Stream> FrameIter<S> {
+    pub fn new(stream: S) -> Self {
+        Self { stream: Box::pin(stream), terminated: false }
+    }
+
+    /// Returns an item that is already available, without blocking.
+    pub fn try_next(&mut self) -> Option<S::Item> {
+        if self.terminated {
+            return None;
+        }
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+        match self.stream.as_mut().poll_next(&mut cx) {
+            Poll::Ready(item) => self.observe(item),
+            Poll::Pending => None,
+        }
+    }
+
+    /// Waits at most `timeout` for the next item.
+    pub fn next_timeout(&mut self, timeout: Duration) -> Option<S::Item> {
+        if self.terminated {
+            return None;
+        }
+        let item = block_on_until(Next { stream: self.stream.as_mut() }, Some(Instant::now() + timeout))?;
+        self.observe(item)
+    }
+
+    /// Whether the underlying stream has ended.
+    pub fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+
+    pub fn into_inner(self) -> Pin<Box<S>> {
+        self.stream
+    }
+
+    fn observe(&mut self, item: Option<S::Item>) -> Option<S::Item> {
+        self.terminated = item.is_none();
+        item
+    }
+}
+
+impl<S: Stream> Iterator for FrameIter<S> {
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.terminated {
+            return None;
+        }
+        let item = block_on(Next { stream: self.stream.as_mut() });
+        self.observe(item)
+    }
+}
+
+struct Next<'a, S> {
+    stream: Pin<&'a mut S>,
+}
+
+impl<S: Stream> Future for Next<'_, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
@@ -1 +1,29 @@
-pub mod hand_tracker;
\ No newline at end of file
+//! Synchronous API for applications without an async runtime.
+//!
+//! Everything here wraps [`async_api`](crate::nuitrack::async_api) and hands out the
+//! same [`shared_types`](crate::nuitrack::shared_types) frames. The calling thread
+//! blocks until each operation completes, and frames are delivered either through
+//! [`FrameIter`](frame_iter::FrameIter) iterators or through callbacks run by
+//! [`BlockingSession::update`](session::BlockingSession::update).
+//!
+//! ```no_run
+//! use nuitrack_rs::nuitrack::blocking_api::session::BlockingSessionBuilder;
+//! use nuitrack_rs::nuitrack::shared_types::session_config::ModuleType;
+//!
+//! # fn main() -> nuitrack_rs::nuitrack::shared_types::error::Result<()> {
+//! let mut session = BlockingSessionBuilder::create_session_from_single_default_device(vec![ModuleType::SkeletonTracker])?;
+//! let mut skeletons = session.devices().next().unwrap().skeleton_tracker().unwrap().skeleton_frames()?;
+//! session.start_processing()?;
+//! for _ in 0..100 {
+//!     session.update()?;
+//!     while let Some(frame) = skeletons.try_next() {
+//!         println!("skeleton frame at {}", frame?.timestamp()?);
+//!     }
+//! }
+//! session.close()
+//! # }
+//! ```
+
+pub mod frame_iter;
+pub mod session;
+pub mod trackers;
@@ -0,0 +1,265 @@
+//! Blocking session builder and session.
+//!
+//! [`BlockingSessionBuilder`] mirrors [`NuitrackSessionBuilder`] but never starts the
+//! internal update loop: the application advances the session itself by calling
+//! [`BlockingSession::update`], which waits for the devices, then runs every callback
+//! registered with [`BlockingSession::on_frames`] on the calling thread.
+
+use std::path::Path;
+
+use futures_core::Stream;
+use tracing::{debug, instrument};
+
+use crate::nuitrack::async_api::async_dispatch::block_on;
+use crate::nuitrack::async_api::health::{DeviceHealth, HealthConfig, SessionEventStream};
+use crate::nuitrack::async_api::session::{ActiveDeviceContext, NuitrackSession};
+use crate::nuitrack::async_api::session_builder::{DeviceDiscoveryState, NuitrackSessionBuilder};
+use crate::nuitrack::backend::FrameBackend;
+use crate::nuitrack::backend::replay::ReplayTiming;
+use crate::nuitrack::shared_types::error::Result as NuitrackResult;
+use crate::nuitrack::shared_types::rgbd_frame::Registration;
+use crate::nuitrack::shared_types::session_config::{ActivationStatus, DeviceConfig, DeviceSelector, DiscoveredDeviceInfo, ModuleType};
+
+use super::frame_iter::FrameIter;
+use super::trackers::{
+    BlockingColorSensor, BlockingDepthSensor, BlockingGestureRecognizer, BlockingHandTracker,
+    BlockingSkeletonTracker, BlockingUserTracker,
+};
+
+/// Builds a [`BlockingSession`]. See [`NuitrackSessionBuilder`] for the options.
+pub struct BlockingSessionBuilder {
+    inner: NuitrackSessionBuilder,
+}
+
+impl Default for BlockingSessionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockingSessionBuilder {
+    pub fn new() -> Self {
+        Self { inner: NuitrackSessionBuilder::new().manage_update_loop(false) }
+    }
+
+    /// Session for the first device with the given modules.
+    pub fn create_session_from_single_default_device(modules_to_create: Vec<ModuleType>) -> NuitrackResult<BlockingSession> {
+        Self::new()
+            .with_device_config(DeviceConfig::new(DeviceSelector::ByIndex(0), modules_to_create))
+            .init_session()
+    }
+
+    pub fn global_config_path(self, path: impl AsRef<Path>) -> Self {
+        Self { inner: self.inner.global_config_path(path) }
+    }
+
+    pub fn with_config_value(self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self { inner: self.inner.with_config_value(key, value) }
+    }
+
+    pub fn with_depth_to_color_registration(self, enabled: bool) -> Self {
+        Self { inner: self.inner.with_depth_to_color_registration(enabled) }
+    }
+
+    pub fn with_device_config(self, config: DeviceConfig) -> Self {
+        Self { inner: self.inner.with_device_config(config) }
+    }
+
+    pub fn with_backend(self, backend: impl FrameBackend) -> Self {
+        Self { inner: self.inner.with_backend(backend) }
+    }
+
+    pub fn with_recording(self, path: impl AsRef<Path>, timing: ReplayTiming) -> Self {
+        Self { inner: self.inner.with_recording(path, timing) }
+    }
+
+    /// Stall and disconnect thresholds. Stalls are detected during [`BlockingSession::update`];
+    /// runtime recovery needs the async internal update loop and does not apply here.
+    pub fn with_health_config(self, config: HealthConfig) -> Self {
+        Self { inner: self.inner.with_health_config(config) }
+    }
+
+    #[instrument(skip(self), name = "blocking_init_session")]
+    pub fn init_session(self) -> NuitrackResult<BlockingSession> {
+        block_on(self.inner.init_session()).map(BlockingSession::new)
+    }
+
+    /// Initializes the runtime and lists the connected devices, so they can be
+    /// inspected before choosing a [`DeviceConfig`] for each.
+    #[instrument(skip(self), name = "blocking_discover_devices_first")]
+    pub fn discover_devices_first(self) -> NuitrackResult<BlockingDeviceDiscovery> {
+        block_on(self.inner.discover_devices_first()).map(|inner| BlockingDeviceDiscovery { inner })
+    }
+}
+
+/// Devices found by [`BlockingSessionBuilder::discover_devices_first`].
+pub struct BlockingDeviceDiscovery {
+    inner: DeviceDiscoveryState,
+}
+
+impl BlockingDeviceDiscovery {
+    pub fn list_devices(&self) -> &[DiscoveredDeviceInfo] {
+        self.inner.list_devices()
+    }
+
+    pub fn refresh_activation(&mut self, serial_number: &str) -> NuitrackResult<ActivationStatus> {
+        block_on(self.inner.refresh_activation(serial_number))
+    }
+
+    pub fn activate_device(&mut self, serial_number: &str, activation_key: impl Into<String>) -> NuitrackResult<ActivationStatus> {
+        block_on(self.inner.activate_device(serial_number, activation_key))
+    }
+
+    pub fn finalize_session(self, device_configs: Vec<DeviceConfig>) -> NuitrackResult<BlockingSession> {
+        block_on(self.inner.finalize_session(device_configs)).map(BlockingSession::new)
+    }
+}
+
+/// Blocking access to one device's trackers.
+pub struct BlockingDevice<'a> {
+    inner: &'a mut ActiveDeviceContext,
+}
+
+impl<'a> BlockingDevice<'a> {
+    pub fn info(&self) -> &DiscoveredDeviceInfo {
+        &self.inner.info
+    }
+
+    pub fn serial_number(&self) -> &str {
+        self.inner.serial_number()
+    }
+
+    /// See [`ActiveDeviceContext::registration`].
+    pub fn registration(&self) -> NuitrackResult<Option<Registration>> {
+        block_on(self.inner.registration())
+    }
+
+    pub fn depth_sensor(&mut self) -> Option<BlockingDepthSensor<'_>> {
+        self.inner.depth_sensor.as_mut().map(BlockingDepthSensor::new)
+    }
+
+    pub fn color_sensor(&mut self) -> Option<BlockingColorSensor<'_>> {
+        self.inner.color_sensor.as_mut().map(BlockingColorSensor::new)
+    }
+
+    pub fn user_tracker(&mut self) -> Option<BlockingUserTracker<'_>> {
+        self.inner.user_tracker.as_mut().map(BlockingUserTracker::new)
+    }
+
+    pub fn skeleton_tracker(&mut self) -> Option<BlockingSkeletonTracker<'_>> {
+        self.inner.skeleton_tracker.as_mut().map(BlockingSkeletonTracker::new)
+    }
+
+    pub fn hand_tracker(&mut self) -> Option<BlockingHandTracker<'_>> {
+        self.inner.hand_tracker.as_mut().map(BlockingHandTracker::new)
+    }
+
+    pub fn gesture_recognizer(&mut self) -> Option<BlockingGestureRecognizer<'_>> {
+        self.inner.gesture_recognizer.as_mut().map(BlockingGestureRecognizer::new)
+    }
+
+    /// The underlying async device context.
+    pub fn as_async(&mut self) -> &mut ActiveDeviceContext {
+        self.inner
+    }
+}
+
+/// Drains one registered stream into its callback. Returns `false` once the stream has ended.
+type FrameDispatcher = Box<dyn FnMut() -> bool + Send>;
+
+/// A Nuitrack session driven from the calling thread.
+pub struct BlockingSession {
+    inner: NuitrackSession,
+    dispatchers: Vec<FrameDispatcher>,
+}
+
+impl BlockingSession {
+    fn new(inner: NuitrackSession) -> Self {
+        Self { inner, dispatchers: Vec::new() }
+    }
+
+    /// Starts the SDK's processing (or the backend).
+    #[instrument(skip(self), name = "blocking_start_processing")]
+    pub fn start_processing(&self) -> NuitrackResult<()> {
+        block_on(self.inner.start_processing())
+    }
+
+    /// Waits for every device to update, then invokes the callbacks of all frames
+    /// that arrived. Call this in the application's main loop.
+    pub fn update(&mut self) -> NuitrackResult<()> {
+        block_on(self.inner.drive_update_cycle())?;
+        self.dispatch();
+        Ok(())
+    }
+
+    /// Invokes the callbacks for items that are already available, without updating.
+    pub fn dispatch(&mut self) {
+        let before = self.dispatchers.len();
+        self.dispatchers.retain_mut(|dispatch| dispatch());
+        if self.dispatchers.len() != before {
+            debug!(ended = before - self.dispatchers.len(), "Removed callbacks of ended streams.");
+        }
+    }
+
+    /// Calls `callback` with every item of `frames` during [`update`](Self::update) or
+    /// [`dispatch`](Self::dispatch), on the thread that calls them. The callback is
+    /// dropped once the stream ends.
+    ///
+    /// ```no_run
+    /// # use nuitrack_rs::nuitrack::blocking_api::session::BlockingSession;
+    /// # fn example(mut session: BlockingSession) -> nuitrack_rs::nuitrack::shared_types::error::Result<()> {
+    /// let skeletons = session.devices().next().unwrap().skeleton_tracker().unwrap().skeleton_frames()?;
+    /// session.on_frames(skeletons, |frame| match frame {
+    ///     Ok(frame) => println!("{} skeletons", frame.skeletons().map(|s| s.len()).unwrap_or(0)),
+    ///     Err(e) => eprintln!("{e}"),
+    /// });
+    /// loop {
+    ///     session.update()?;
+    /// }
+    /// # }
+    /// ```
+    pub fn on_frames<S, F>(&mut self, mut frames: FrameIter<S>, mut callback: F)
+    where
+        S: Stream + Send + 'static,
+        F: FnMut(S::Item) + Send + 'static,
+    {
+        self.dispatchers.push(Box::new(move || {
+            while let Some(item) = frames.try_next() {
+                callback(item);
+            }
+            !frames.is_terminated()
+        }));
+    }
+
+    /// Every active device, in session order.
+    pub fn devices(&mut self) -> impl Iterator<Item = BlockingDevice<'_>> {
+        self.inner.active_devices.iter_mut().map(|inner| BlockingDevice { inner })
+    }
+
+    /// The active device with the given serial number.
+    pub fn device(&mut self, serial_number: &str) -> Option<BlockingDevice<'_>> {
+        self.inner.device_mut(serial_number).map(|inner| BlockingDevice { inner })
+    }
+
+    pub fn device_health(&self, serial_number: &str) -> Option<DeviceHealth> {
+        self.inner.device_health(serial_number)
+    }
+
+    /// Health events as a blocking iterator. Use [`FrameIter::try_next`] to poll it
+    /// between updates.
+    pub fn events(&self) -> FrameIter<SessionEventStream> {
+        FrameIter::new(self.inner.events_stream())
+    }
+
+    /// The underlying async session.
+    pub fn as_async(&mut self) -> &mut NuitrackSession {
+        &mut self.inner
+    }
+
+    #[instrument(skip(self), name = "blocking_close")]
+    pub fn close(mut self) -> NuitrackResult<()> {
+        debug!(callbacks = self.dispatchers.len(), "Dropping frame callbacks.");
+        self.dispatchers.clear();
+        block_on(self.inner.close())
+    }
+}
@@ -0,0 +1,185 @@
+//! Blocking views of the async trackers.
+//!
+//! Each view borrows a tracker from its [`BlockingDevice`](super::session::BlockingDevice)
+//! and runs the tracker's async methods to completion on the calling thread. Streams
+//! are returned as [`FrameIter`]s and can also be handed to
+//! [`BlockingSession::on_frames`](super::session::BlockingSession::on_frames).
+
+use crate::nuitrack::async_api::async_dispatch::block_on;
+use crate::nuitrack::async_api::color_sensor::{AsyncColorSensor, RGBFrameStream};
+use crate::nuitrack::async_api::depth_sensor::{AsyncDepthSensor, DepthFrameStream, OutputMode, Vector3};
+use crate::nuitrack::async_api::gesture_recognizer::{
+    AsyncGestureRecognizer, CompletedGesturesFrameStream, UpdateStream, UserStateChangeStream,
+};
+use crate::nuitrack::async_api::hand_tracker::{AsyncHandTracker, HandFrameStream};
+use crate::nuitrack::async_api::skeleton_tracker::{
+    AsyncSkeletonTracker, LostUserEventStream as SkeletonLostUserEventStream,
+    NewUserEventStream as SkeletonNewUserEventStream, SkeletonFrameStream,
+};
+use crate::nuitrack::async_api::user_tracker::{
+    AsyncUserTracker, LostUserEventStream, NewUserEventStream, UserFrameStream,
+};
+use crate::nuitrack::shared_types::camera_model::CameraModel;
+use crate::nuitrack::shared_types::depth_frame::DepthFrame;
+use crate::nuitrack::shared_types::error::Result as NuitrackResult;
+use crate::nuitrack::shared_types::hand_frame::HandFrame;
+use crate::nuitrack::shared_types::rgb_frame::RGBFrame;
+use crate::nuitrack::shared_types::skeleton_frame::SkeletonFrame;
+use crate::nuitrack::shared_types::user_frame::UserFrame;
+
+use super::frame_iter::FrameIter;
+
+macro_rules! blocking_tracker {
+    (
+        $(#[$meta:meta])*
+        $name:ident => $async_ty:ty {
+            streams: { $( $(#[$stream_meta:meta])* $iter_method:ident => $stream_method:ident : $stream_ty:ty ),* $(,)? }
+            methods: { $( $(#[$method_meta:meta])* fn $method:ident ( $($arg:ident : $arg_ty:ty),* ) -> $ret:ty; )* }
+        }
+    ) => {
+        $(#[$meta])*
+        pub struct $name<'a> {
+            inner: &'a mut $async_ty,
+        }
+
+        impl<'a> $name<'a> {
+            pub(crate) fn new(inner: &'a mut $async_ty) -> Self {
+                Self { inner }
+            }
+
+            /// The wrapped async tracker.
+            pub fn as_async(&mut self) -> &mut $async_ty {
+                self.inner
+            }
+
+            $(
+                $(#[$stream_meta])*
+                pub fn $iter_method(&mut self) -> NuitrackResult<FrameIter<$stream_ty>> {
+                    self.inner.$stream_method().map(FrameIter::new)
+                }
+            )*
+
+            $(
+                $(#[$method_meta])*
+                pub fn $method(&self, $($arg: $arg_ty),*) -> NuitrackResult<$ret> {
+                    block_on(self.inner.$method($($arg),*))
+                }
+            )*
+        }
+    };
+}
+
+blocking_tracker! {
+    /// Blocking view of an [`AsyncDepthSensor`].
+    BlockingDepthSensor => AsyncDepthSensor {
+        streams: {
+            depth_frames => depth_frames_stream: DepthFrameStream,
+        }
+        methods: {
+            fn output_mode() -> OutputMode;
+            fn camera_model() -> CameraModel;
+            /// The most recent frame, without waiting for a new one.
+            fn latest_depth_frame_sync() -> DepthFrame;
+            fn is_mirror() -> bool;
+            fn set_mirror(mirror: bool) -> ();
+            fn convert_proj_to_real(point: Vector3) -> Vector3;
+            fn convert_real_to_proj(point: Vector3) -> Vector3;
+            fn sensor_timestamp() -> u64;
+            fn can_update() -> bool;
+        }
+    }
+}
+
+blocking_tracker! {
+    /// Blocking view of an [`AsyncColorSensor`].
+    BlockingColorSensor => AsyncColorSensor {
+        streams: {
+            rgb_frames => rgb_frames_stream: RGBFrameStream,
+        }
+        methods: {
+            fn output_mode() -> OutputMode;
+            fn camera_model() -> CameraModel;
+            /// The most recent frame, without waiting for a new one.
+            fn latest_color_frame_sync() -> RGBFrame;
+            fn sensor_timestamp() -> u64;
+            fn can_update() -> bool;
+        }
+    }
+}
+
+blocking_tracker! {
+    /// Blocking view of an [`AsyncUserTracker`].
+    BlockingUserTracker => AsyncUserTracker {
+        streams: {
+            user_frames => user_frames_stream: UserFrameStream,
+            new_user_events => new_user_events_stream: NewUserEventStream,
+            lost_user_events => lost_user_events_stream: LostUserEventStream,
+        }
+        methods: {
+            /// The most recent frame, without waiting for a new one.
+            fn latest_user_frame_sync() -> UserFrame;
+            fn processing_time() -> f32;
+            fn tracker_timestamp() -> u64;
+            fn can_update() -> bool;
+        }
+    }
+}
+
+blocking_tracker! {
+    /// Blocking view of an [`AsyncSkeletonTracker`].
+    BlockingSkeletonTracker => AsyncSkeletonTracker {
+        streams: {
+            skeleton_frames => skeleton_frames_stream: SkeletonFrameStream,
+            new_user_events => new_user_events_stream: SkeletonNewUserEventStream,
+            lost_user_events => lost_user_events_stream: SkeletonLostUserEventStream,
+        }
+        methods: {
+            /// Sets the maximum number of tracked users (0 to 6, 2 by default).
+            fn set_num_active_users(num_users: i32) -> ();
+            fn is_auto_tracking() -> bool;
+            fn set_auto_tracking(tracking: bool) -> ();
+            fn start_tracking(user_id: i32) -> ();
+            fn stop_tracking(user_id: i32) -> ();
+            fn is_tracking(user_id: i32) -> bool;
+            /// The most recent frame, without waiting for a new one.
+            fn latest_skeletons_frame_sync() -> SkeletonFrame;
+            fn processing_time() -> f32;
+            fn tracker_timestamp() -> u64;
+            fn can_update() -> bool;
+        }
+    }
+}
+
+blocking_tracker! {
+    /// Blocking view of an [`AsyncHandTracker`].
+    BlockingHandTracker => AsyncHandTracker {
+        streams: {
+            hand_frames => hand_frames_stream: HandFrameStream,
+        }
+        methods: {
+            /// The most recent frame, without waiting for a new one.
+            fn latest_hand_frame_sync() -> HandFrame;
+            fn processing_time() -> f32;
+            fn tracker_timestamp() -> u64;
+            fn can_update() -> bool;
+        }
+    }
+}
+
+blocking_tracker! {
+    /// Blocking view of an [`AsyncGestureRecognizer`].
+    BlockingGestureRecognizer => AsyncGestureRecognizer {
+        streams: {
+            completed_gestures_frames => completed_gestures_frames_stream: CompletedGesturesFrameStream,
+            user_state_changes => user_state_changes_stream: UserStateChangeStream,
+            updates => updates_stream: UpdateStream,
+        }
+        methods: {
+            /// Enables or disables recognition of control gestures such as push and swipe.
+            fn set_control_gestures_status(status: bool) -> ();
+            fn processing_time() -> f32;
+            fn recognizer_timestamp() -> u64;
+            fn can_update() -> bool;
+        }
+    }
+}
@@ -1,3 +1,5 @@
 pub mod async_api;
+pub mod backend;
 pub mod blocking_api;
+pub mod recording;
 pub mod shared_types;
\ No newline at end of file
@@ -0,0 +1,840 @@
+//! On-disk layout of a session recording.
+//!
+//! All integers are little-endian. A file is laid out as:
+//!
+//! ```text
+//! FileHeader   magic "NTRKREC\0", u16 version, u32 header length, header payload
+//! Chunk*       magic "CHNK", u32 payload length, u32 record count,
+//!              u64 first timestamp, u64 last timestamp, payload
+//! Index        magic "NIDX", u32 entry count, entries
+//!              (u64 offset, u64 first timestamp, u64 last timestamp, u32 record count)
+//! Footer       u64 index offset, magic "NTRKEND\0"
+//! ```
+//!
+//! A chunk payload is a sequence of records: u8 kind, u16 device index,
+//! u64 timestamp, u32 payload length, payload. The index and footer are only
+//! written when a recording is finished cleanly; readers fall back to scanning
+//! chunks when they are missing.
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use tracing::{debug, instrument, warn};
+
+use crate::nuitrack::backend::BackendFrame;
+use crate::nuitrack::shared_types::{
+    depth_frame::DepthFrame,
+    error::{NuitrackError, Result as NuitrackResult},
+    gesture::{Gesture, GestureState, GestureType, UserGestures, UserState, UserStateType},
+    gesture_frame::{GestureFrame, UserGesturesFrame, UserStateFrame},
+    hand::{Hand, UserHands},
+    hand_frame::HandFrame,
+    rgb_frame::{Color3, RGBFrame},
+    skeleton::{Joint, JointType, Skeleton},
+    skeleton_frame::SkeletonFrame,
+    user::User,
+    user_frame::UserFrame,
+};
+use crate::nuitrack_bridge::types::bounding_box::ffi::BoundingBox;
+use crate::nuitrack_bridge::types::output_mode::ffi::{Intrinsics, OutputMode};
+use crate::nuitrack_bridge::types::skeleton::ffi::Orientation;
+use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+
+pub const FILE_MAGIC: &[u8; 8] = b"NTRKREC\0";
+pub const FORMAT_VERSION: u16 = 1;
+const CHUNK_MAGIC: &[u8; 4] = b"CHNK";
+const INDEX_MAGIC: &[u8; 4] = b"NIDX";
+const FOOTER_MAGIC: &[u8; 8] = b"NTRKEND\0";
+const FOOTER_LEN: i64 = 16;
+/// Bytes of a chunk ahead of its payload: magic, length, record count and time span.
+const CHUNK_HEADER_LEN: u64 = 28;
+/// Bytes of one index entry.
+const INDEX_ENTRY_LEN: u64 = 28;
+
+// Smallest encoded size of each repeated element, used to reject counts that
+// cannot fit in the data that follows them.
+const MIN_DEVICE_SIZE: usize = 3 * 4 + 4 + 2;
+const MIN_USER_SIZE: usize = 4 + 2 * 12 + 4 * 4 + 4;
+const MIN_SKELETON_SIZE: usize = 4 + 4;
+const JOINT_SIZE: usize = 4 + 4 + 2 * 12 + 9 * 4;
+const MIN_USER_HANDS_SIZE: usize = 4 + 2;
+const MIN_USER_GESTURES_SIZE: usize = 4 + 4 + 4;
+
+/// Per-device metadata stored in the file header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedDevice {
+    pub name: String,
+    pub serial_number: String,
+    pub provider_name: String,
+    pub original_index: usize,
+    /// Depth sensor output mode (including intrinsics), if a depth sensor was active.
+    pub depth_mode: Option<OutputMode>,
+    /// Color sensor output mode (including intrinsics), if a color sensor was active.
+    pub color_mode: Option<OutputMode>,
+}
+
+/// Everything stored ahead of the first chunk.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RecordingHeader {
+    pub devices: Vec<RecordedDevice>,
+}
+
+/// Location and time span of one chunk, as stored in the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkInfo {
+    pub offset: u64,
+    pub first_timestamp: u64,
+    pub last_timestamp: u64,
+    pub record_count: u32,
+}
+
+/// A single recorded item.
+#[derive(Clone)]
+pub struct Record {
+    /// Index into [`RecordingHeader::devices`].
+    pub device_index: usize,
+    pub timestamp: u64,
+    pub frame: BackendFrame,
+}
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RecordKind {
+    Depth = 1,
+    Color = 2,
+    User = 3,
+    Skeleton = 4,
+    Hand = 5,
+    CompletedGestures = 6,
+    UserStateChange = 7,
+    GestureUpdate = 8,
+    NewUser = 9,
+    LostUser = 10,
+}
+
+impl RecordKind {
+    fn from_u8(value: u8) -> NuitrackResult<Self> {
+        Ok(match value {
+            1 => Self::Depth,
+            2 => Self::Color,
+            3 => Self::User,
+            4 => Self::Skeleton,
+            5 => Self::Hand,
+            6 => Self::CompletedGestures,
+            7 => Self::UserStateChange,
+            8 => Self::GestureUpdate,
+            9 => Self::NewUser,
+            10 => Self::LostUser,
+            other => return Err(NuitrackError::InvalidRecording(format!("Unknown record kind {}", other))),
+        })
+    }
+}
+
+// --- Primitive encoding ---
+
+/// Converts a length or index to the narrower integer the format stores it as.
+fn narrow<T: TryFrom<usize>>(value: usize, what: &str) -> NuitrackResult<T> {
+    T::try_from(value).map_err(|_| {
+        NuitrackError::InvalidRecording(format!("{} {} does not fit the recording format", what, value))
+    })
+}
+
+struct Encoder(Vec<u8>);
+
+impl Encoder {
+    fn u8(&mut self, v: u8) { self.0.push(v); }
+    fn u16(&mut self, v: u16) { self.0.extend_from_slice(&v.to_le_bytes()); }
+    fn u32(&mut self, v: u32) { self.0.extend_from_slice(&v.to_le_bytes()); }
+    fn u64(&mut self, v: u64) { self.0.extend_from_slice(&v.to_le_bytes()); }
+    fn i32(&mut self, v: i32) { self.0.extend_from_slice(&v.to_le_bytes()); }
+    fn f32(&mut self, v: f32) { self.0.extend_from_slice(&v.to_le_bytes()); }
+    fn len(&mut self, v: usize) -> NuitrackResult<()> {
+        self.u32(narrow(v, "Length")?);
+        Ok(())
+    }
+    fn str(&mut self, v: &str) -> NuitrackResult<()> {
+        self.len(v.len())?;
+        self.0.extend_from_slice(v.as_bytes());
+        Ok(())
+    }
+    fn vector3(&mut self, v: &Vector3) {
+        self.f32(v.x);
+        self.f32(v.y);
+        self.f32(v.z);
+    }
+    fn output_mode(&mut self, mode: &Option<OutputMode>) {
+        match mode {
+            None => self.u8(0),
+            Some(m) => {
+                self.u8(1);
+                self.i32(m.fps);
+                self.i32(m.xres);
+                self.i32(m.yres);
+                self.f32(m.hfov);
+                self.f32(m.intrinsics.fx);
+                self.f32(m.intrinsics.fy);
+                self.f32(m.intrinsics.cx);
+                self.f32(m.intrinsics.cy);
+            }
+        }
+    }
+    fn hand(&mut self, hand: &Option<Hand>) {
+        match hand {
+            None => self.u8(0),
+            Some(h) => {
+                self.u8(1);
+                self.f32(h.x);
+                self.f32(h.y);
+                self.u8(h.click as u8);
+                self.i32(h.pressure);
+                self.f32(h.x_real);
+                self.f32(h.y_real);
+                self.f32(h.z_real);
+            }
+        }
+    }
+}
+
+struct Decoder<'a>(&'a [u8]);
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, n: usize) -> NuitrackResult<&'a [u8]> {
+        if self.0.len() < n {
+            return Err(NuitrackError::InvalidRecording("Unexpected end of record data".into()));
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+    fn array<const N: usize>(&mut self) -> NuitrackResult<[u8; N]> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+    fn u8(&mut self) -> NuitrackResult<u8> { Ok(self.take(1)?[0]) }
+    fn u16(&mut self) -> NuitrackResult<u16> { Ok(u16::from_le_bytes(self.array()?)) }
+    fn u32(&mut self) -> NuitrackResult<u32> { Ok(u32::from_le_bytes(self.array()?)) }
+    fn u64(&mut self) -> NuitrackResult<u64> { Ok(u64::from_le_bytes(self.array()?)) }
+    fn i32(&mut self) -> NuitrackResult<i32> { Ok(i32::from_le_bytes(self.array()?)) }
+    fn f32(&mut self) -> NuitrackResult<f32> { Ok(f32::from_le_bytes(self.array()?)) }
+    fn len(&mut self) -> NuitrackResult<usize> { Ok(self.u32()? as usize) }
+    /// Reads an element count and checks that the remaining data can hold that many
+    /// elements of at least `min_size` bytes each.
+    fn count(&mut self, min_size: usize) -> NuitrackResult<usize> {
+        let count = self.len()?;
+        if count.saturating_mul(min_size) > self.0.len() {
+            return Err(NuitrackError::InvalidRecording(format!(
+                "Element count {} exceeds the remaining {} bytes", count, self.0.len()
+            )));
+        }
+        Ok(count)
+    }
+    fn str(&mut self) -> NuitrackResult<String> {
+        let len = self.len()?;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|e| NuitrackError::InvalidRecording(format!("Invalid UTF-8 string: {}", e)))
+    }
+    fn vector3(&mut self) -> NuitrackResult<Vector3> {
+        Ok(Vector3 { x: self.f32()?, y: self.f32()?, z: self.f32()? })
+    }
+    fn output_mode(&mut self) -> NuitrackResult<Option<OutputMode>> {
+        if self.u8()? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(OutputMode {
+            fps: self.i32()?,
+            xres: self.i32()?,
+            yres: self.i32()?,
+            hfov: self.f32()?,
+            intrinsics: Intrinsics { fx: self.f32()?, fy: self.f32()?, cx: self.f32()?, cy: self.f32()? },
+        }))
+    }
+    fn hand(&mut self) -> NuitrackResult<Option<Hand>> {
+        if self.u8()? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(Hand {
+            x: self.f32()?,
+            y: self.f32()?,
+            click: self.u8()? != 0,
+            pressure: self.i32()?,
+            x_real: self.f32()?,
+            y_real: self.f32()?,
+            z_real: self.f32()?,
+        }))
+    }
+}
+
+// --- Header ---
+
+pub(crate) fn encode_header(header: &RecordingHeader) -> NuitrackResult<Vec<u8>> {
+    let mut e = Encoder(Vec::new());
+    e.len(header.devices.len())?;
+    for device in &header.devices {
+        e.str(&device.name)?;
+        e.str(&device.serial_number)?;
+        e.str(&device.provider_name)?;
+        e.u32(narrow(device.original_index, "Device index")?);
+        e.output_mode(&device.depth_mode);
+        e.output_mode(&device.color_mode);
+    }
+    Ok(e.0)
+}
+
+fn decode_header(bytes: &[u8]) -> NuitrackResult<RecordingHeader> {
+    let mut d = Decoder(bytes);
+    let count = d.count(MIN_DEVICE_SIZE)?;
+    let mut devices = Vec::with_capacity(count);
+    for _ in 0..count {
+        devices.push(RecordedDevice {
+            name: d.str()?,
+            serial_number: d.str()?,
+            provider_name: d.str()?,
+            original_index: d.u32()? as usize,
+            depth_mode: d.output_mode()?,
+            color_mode: d.output_mode()?,
+        });
+    }
+    Ok(RecordingHeader { devices })
+}
+
+// --- Frames ---
+
+/// Serializes a frame's contents. Fails only if the frame's own accessors fail.
+pub(crate) fn encode_frame(frame: &BackendFrame) -> NuitrackResult<(u8, Vec<u8>)> {
+    let mut e = Encoder(Vec::new());
+    let kind = match frame {
+        BackendFrame::Depth(f) => {
+            e.i32(f.rows()?);
+            e.i32(f.cols()?);
+            e.u64(f.frame_id()?);
+            e.u64(f.timestamp()?);
+            let data = f.data()?;
+            e.len(data.len())?;
+            data.iter().for_each(|v| e.u16(*v));
+            RecordKind::Depth
+        }
+        BackendFrame::Color(f) => {
+            e.i32(f.rows()?);
+            e.i32(f.cols()?);
+            e.u64(f.frame_id()?);
+            e.u64(f.timestamp()?);
+            let data = f.data()?;
+            e.len(data.len())?;
+            for px in data {
+                e.u8(px.blue);
+                e.u8(px.green);
+                e.u8(px.red);
+            }
+            RecordKind::Color
+        }
+        BackendFrame::User(f) => {
+            e.i32(f.rows()?);
+            e.i32(f.cols()?);
+            e.u64(f.timestamp()?);
+            e.vector3(&f.floor()?);
+            e.vector3(&f.floor_normal()?);
+            let users = f.users()?;
+            e.len(users.len())?;
+            for u in users {
+                e.i32(u.id);
+                e.vector3(&u.proj);
+                e.vector3(&u.real);
+                e.f32(u.r#box.top);
+                e.f32(u.r#box.bottom);
+                e.f32(u.r#box.left);
+                e.f32(u.r#box.right);
+                e.f32(u.occlusion);
+            }
+            let data = f.data()?;
+            e.len(data.len())?;
+            data.iter().for_each(|v| e.u16(*v));
+            RecordKind::User
+        }
+        BackendFrame::Skeleton(f) => {
+            e.u64(f.timestamp()?);
+            let skeletons = f.skeletons()?;
+            e.len(skeletons.len())?;
+            for s in skeletons {
+                e.i32(s.user_id);
+                e.len(s.joints.len())?;
+                for j in &s.joints {
+                    e.i32(j.joint_type.repr);
+                    e.f32(j.confidence);
+                    e.vector3(&j.real);
+                    e.vector3(&j.proj);
+                    j.orient.matrix.iter().for_each(|v| e.f32(*v));
+                }
+            }
+            RecordKind::Skeleton
+        }
+        BackendFrame::Hand(f) => {
+            e.u64(f.timestamp()?);
+            let users_hands = f.users_hands()?;
+            e.len(users_hands.len())?;
+            for uh in users_hands {
+                e.i32(uh.user_id);
+                e.hand(&uh.left_hand);
+                e.hand(&uh.right_hand);
+            }
+            RecordKind::Hand
+        }
+        BackendFrame::CompletedGestures(f) => {
+            e.u64(f.timestamp()?);
+            let gestures = f.gestures()?;
+            e.len(gestures.len())?;
+            for g in gestures {
+                e.i32(g.user_id);
+                e.i32(g.gesture_type.repr);
+            }
+            RecordKind::CompletedGestures
+        }
+        BackendFrame::UserStateChange(f) => {
+            e.u64(f.timestamp()?);
+            let states = f.user_states()?;
+            e.len(states.len())?;
+            for s in states {
+                e.i32(s.user_id);
+                e.i32(s.state.repr);
+            }
+            RecordKind::UserStateChange
+        }
+        BackendFrame::GestureUpdate(f) => {
+            e.u64(f.timestamp()?);
+            let users = f.users()?;
+            e.len(users.len())?;
+            for u in users {
+                e.i32(u.user_id);
+                e.i32(u.user_state.repr);
+                e.len(u.gestures.len())?;
+                for g in &u.gestures {
+                    e.i32(g.gesture_type.repr);
+                    e.i32(g.progress);
+                }
+            }
+            RecordKind::GestureUpdate
+        }
+        BackendFrame::NewUser(id) => {
+            e.i32(*id);
+            RecordKind::NewUser
+        }
+        BackendFrame::LostUser(id) => {
+            e.i32(*id);
+            RecordKind::LostUser
+        }
+    };
+    Ok((kind as u8, e.0))
+}
+
+/// Rebuilds a frame from a record payload. Decoded frames are backed by Rust memory.
+pub(crate) fn decode_frame(kind: u8, payload: &[u8]) -> NuitrackResult<BackendFrame> {
+    let mut d = Decoder(payload);
+    let frame = match RecordKind::from_u8(kind)? {
+        RecordKind::Depth => {
+            let (rows, cols, id, timestamp) = (d.i32()?, d.i32()?, d.u64()?, d.u64()?);
+            let len = d.count(2)?;
+            let data = (0..len).map(|_| d.u16()).collect::<NuitrackResult<_>>()?;
+            BackendFrame::Depth(DepthFrame::synthetic(rows, cols, id, timestamp, data))
+        }
+        RecordKind::Color => {
+            let (rows, cols, id, timestamp) = (d.i32()?, d.i32()?, d.u64()?, d.u64()?);
+            let len = d.count(3)?;
+            let data = (0..len)
+                .map(|_| Ok(Color3 { blue: d.u8()?, green: d.u8()?, red: d.u8()? }))
+                .collect::<NuitrackResult<_>>()?;
+            BackendFrame::Color(RGBFrame::synthetic(rows, cols, id, timestamp, data))
+        }
+        RecordKind::User => {
+            let (rows, cols, timestamp) = (d.i32()?, d.i32()?, d.u64()?);
+            let (floor, floor_normal) = (d.vector3()?, d.vector3()?);
+            let count = d.count(MIN_USER_SIZE)?;
+            let users = (0..count)
+                .map(|_| {
+                    Ok(User {
+                        id: d.i32()?,
+                        proj: d.vector3()?,
+                        real: d.vector3()?,
+                        r#box: BoundingBox { top: d.f32()?, bottom: d.f32()?, left: d.f32()?, right: d.f32()? },
+                        occlusion: d.f32()?,
+                    })
+                })
+                .collect::<NuitrackResult<_>>()?;
+            let len = d.count(2)?;
+            let data = (0..len).map(|_| d.u16()).collect::<NuitrackResult<_>>()?;
+            BackendFrame::User(UserFrame::synthetic(rows, cols, timestamp, data, users, floor, floor_normal))
+        }
+        RecordKind::Skeleton => {
+            let timestamp = d.u64()?;
+            let count = d.count(MIN_SKELETON_SIZE)?;
+            let mut skeletons = Vec::with_capacity(count);
+            for _ in 0..count {
+                let user_id = d.i32()?;
+                let joint_count = d.count(JOINT_SIZE)?;
+                let mut joints = Vec::with_capacity(joint_count);
+                for _ in 0..joint_count {
+                    let joint_type = JointType { repr: d.i32()? };
+                    let confidence = d.f32()?;
+                    let (real, proj) = (d.vector3()?, d.vector3()?);
+                    let mut matrix = [0.0; 9];
+                    for v in matrix.iter_mut() {
+                        *v = d.f32()?;
+                    }
+                    joints.push(Joint { joint_type, confidence, real, proj, orient: Orientation { matrix } });
+                }
+                skeletons.push(Skeleton { user_id, joints });
+            }
+            BackendFrame::Skeleton(SkeletonFrame::synthetic(timestamp, skeletons))
+        }
+        RecordKind::Hand => {
+            let timestamp = d.u64()?;
+            let count = d.count(MIN_USER_HANDS_SIZE)?;
+            let users_hands = (0..count)
+                .map(|_| Ok(UserHands { user_id: d.i32()?, left_hand: d.hand()?, right_hand: d.hand()? }))
+                .collect::<NuitrackResult<_>>()?;
+            BackendFrame::Hand(HandFrame::synthetic(timestamp, users_hands))
+        }
+        RecordKind::CompletedGestures => {
+            let timestamp = d.u64()?;
+            let count = d.count(8)?;
+            let gestures = (0..count)
+                .map(|_| Ok(Gesture { user_id: d.i32()?, gesture_type: GestureType { repr: d.i32()? } }))
+                .collect::<NuitrackResult<_>>()?;
+            BackendFrame::CompletedGestures(GestureFrame::synthetic(timestamp, gestures))
+        }
+        RecordKind::UserStateChange => {
+            let timestamp = d.u64()?;
+            let count = d.count(8)?;
+            let states = (0..count)
+                .map(|_| Ok(UserState { user_id: d.i32()?, state: UserStateType { repr: d.i32()? } }))
+                .collect::<NuitrackResult<_>>()?;
+            BackendFrame::UserStateChange(UserStateFrame::synthetic(timestamp, states))
+        }
+        RecordKind::GestureUpdate => {
+            let timestamp = d.u64()?;
+            let count = d.count(MIN_USER_GESTURES_SIZE)?;
+            let mut users = Vec::with_capacity(count);
+            for _ in 0..count {
+                let user_id = d.i32()?;
+                let user_state = UserStateType { repr: d.i32()? };
+                let gesture_count = d.count(8)?;
+                let gestures = (0..gesture_count)
+                    .map(|_| Ok(GestureState { gesture_type: GestureType { repr: d.i32()? }, progress: d.i32()? }))
+                    .collect::<NuitrackResult<_>>()?;
+                users.push(UserGestures { user_id, user_state, gestures });
+            }
+            BackendFrame::GestureUpdate(UserGesturesFrame::synthetic(timestamp, users))
+        }
+        RecordKind::NewUser => BackendFrame::NewUser(d.i32()?),
+        RecordKind::LostUser => BackendFrame::LostUser(d.i32()?),
+    };
+    if !d.0.is_empty() {
+        warn!(kind, trailing = d.0.len(), "Record payload has trailing bytes.");
+    }
+    Ok(frame)
+}
+
+// --- Writer ---
+
+/// Writes a recording incrementally, one chunk at a time.
+pub struct RecordingWriter<W: Write + Seek> {
+    out: W,
+    chunk: Vec<u8>,
+    chunk_records: u32,
+    chunk_first_timestamp: u64,
+    chunk_last_timestamp: u64,
+    chunk_size_limit: usize,
+    index: Vec<ChunkInfo>,
+    records_written: u64,
+}
+
+impl<W: Write + Seek> RecordingWriter<W> {
+    /// Default size at which a chunk is flushed to the output.
+    pub const DEFAULT_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+    /// Writes the file header and prepares for records.
+    pub fn new(mut out: W, header: &RecordingHeader) -> NuitrackResult<Self> {
+        let header_bytes = encode_header(header)?;
+        out.write_all(FILE_MAGIC)?;
+        out.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        out.write_all(&narrow::<u32>(header_bytes.len(), "Header length")?.to_le_bytes())?;
+        out.write_all(&header_bytes)?;
+        Ok(Self {
+            out,
+            chunk: Vec::new(),
+            chunk_records: 0,
+            chunk_first_timestamp: 0,
+            chunk_last_timestamp: 0,
+            chunk_size_limit: Self::DEFAULT_CHUNK_SIZE,
+            index: Vec::new(),
+            records_written: 0,
+        })
+    }
+
+    /// Sets the approximate chunk size in bytes. Smaller chunks make seeking finer-grained.
+    pub fn with_chunk_size(mut self, bytes: usize) -> Self {
+        self.chunk_size_limit = bytes.max(1);
+        self
+    }
+
+    pub fn write_record(&mut self, record: &Record) -> NuitrackResult<()> {
+        let (kind, payload) = encode_frame(&record.frame)?;
+        let device_index: u16 = narrow(record.device_index, "Device index")?;
+        let payload_len: u32 = narrow(payload.len(), "Record length")?;
+        if self.chunk_records == 0 {
+            self.chunk_first_timestamp = record.timestamp;
+        }
+        self.chunk_first_timestamp = self.chunk_first_timestamp.min(record.timestamp);
+        self.chunk_last_timestamp = self.chunk_last_timestamp.max(record.timestamp);
+        self.chunk.push(kind);
+        self.chunk.extend_from_slice(&device_index.to_le_bytes());
+        self.chunk.extend_from_slice(&record.timestamp.to_le_bytes());
+        self.chunk.extend_from_slice(&payload_len.to_le_bytes());
+        self.chunk.extend_from_slice(&payload);
+        self.chunk_records += 1;
+        self.records_written += 1;
+        if self.chunk.len() >= self.chunk_size_limit {
+            self.flush_chunk()?;
+        }
+        Ok(())
+    }
+
+    /// Writes out the pending chunk, if any.
+    pub fn flush_chunk(&mut self) -> NuitrackResult<()> {
+        if self.chunk_records == 0 {
+            return Ok(());
+        }
+        let chunk_len: u32 = narrow(self.chunk.len(), "Chunk length")?;
+        let offset = self.out.stream_position()?;
+        self.out.write_all(CHUNK_MAGIC)?;
+        self.out.write_all(&chunk_len.to_le_bytes())?;
+        self.out.write_all(&self.chunk_records.to_le_bytes())?;
+        self.out.write_all(&self.chunk_first_timestamp.to_le_bytes())?;
+        self.out.write_all(&self.chunk_last_timestamp.to_le_bytes())?;
+        self.out.write_all(&self.chunk)?;
+        self.out.flush()?;
+        self.index.push(ChunkInfo {
+            offset,
+            first_timestamp: self.chunk_first_timestamp,
+            last_timestamp: self.chunk_last_timestamp,
+            record_count: self.chunk_records,
+        });
+        self.chunk.clear();
+        self.chunk_records = 0;
+        self.chunk_last_timestamp = 0;
+        Ok(())
+    }
+
+    pub fn records_written(&self) -> u64 {
+        self.records_written
+    }
+
+    /// Flushes the last chunk and writes the index and footer.
+    #[instrument(skip(self))]
+    pub fn finish(mut self) -> NuitrackResult<W> {
+        self.flush_chunk()?;
+        let index_offset = self.out.stream_position()?;
+        self.out.write_all(INDEX_MAGIC)?;
+        self.out.write_all(&narrow::<u32>(self.index.len(), "Chunk count")?.to_le_bytes())?;
+        for info in &self.index {
+            self.out.write_all(&info.offset.to_le_bytes())?;
+            self.out.write_all(&info.first_timestamp.to_le_bytes())?;
+            self.out.write_all(&info.last_timestamp.to_le_bytes())?;
+            self.out.write_all(&info.record_count.to_le_bytes())?;
+        }
+        self.out.write_all(&index_offset.to_le_bytes())?;
+        self.out.write_all(FOOTER_MAGIC)?;
+        self.out.flush()?;
+        debug!(chunks = self.index.len(), records = self.records_written, "Recording finished.");
+        Ok(self.out)
+    }
+}
+
+// --- Reader ---
+
+/// Random-access reader over a recording.
+pub struct RecordingReader<R: Read + Seek> {
+    input: R,
+    header: RecordingHeader,
+    index: Vec<ChunkInfo>,
+    file_len: u64,
+    /// Records of the chunk currently being read, in file order.
+    pending: std::collections::VecDeque<Record>,
+    next_chunk: usize,
+}
+
+impl<R: Read + Seek> RecordingReader<R> {
+    /// Reads the header and the chunk index, rebuilding the index by scanning if
+    /// the recording was not finished cleanly.
+    #[instrument(skip(input))]
+    pub fn new(mut input: R) -> NuitrackResult<Self> {
+        let file_len = input.seek(SeekFrom::End(0))?;
+        input.seek(SeekFrom::Start(0))?;
+        let mut magic = [0u8; 8];
+        input.read_exact(&mut magic)?;
+        if &magic != FILE_MAGIC {
+            return Err(NuitrackError::InvalidRecording("Not a nuitrack-rs recording".into()));
+        }
+        let version = u16::from_le_bytes(read_array(&mut input)?);
+        if version != FORMAT_VERSION {
+            return Err(NuitrackError::InvalidRecording(format!(
+                "Unsupported format version {} (expected {})", version, FORMAT_VERSION
+            )));
+        }
+        let header_len = u32::from_le_bytes(read_array(&mut input)?);
+        check_within(input.stream_position()?, header_len.into(), file_len, "Header")?;
+        let mut header_bytes = vec![0u8; header_len as usize];
+        input.read_exact(&mut header_bytes)?;
+        let header = decode_header(&header_bytes)?;
+        let data_start = input.stream_position()?;
+
+        let index = match Self::read_index(&mut input, file_len)? {
+            Some(index) => index,
+            None => {
+                warn!("Recording has no index; scanning chunks.");
+                Self::scan_chunks(&mut input, data_start)?
+            }
+        };
+        Ok(Self { input, header, index, file_len, pending: Default::default(), next_chunk: 0 })
+    }
+
+    fn read_index(input: &mut R, file_len: u64) -> NuitrackResult<Option<Vec<ChunkInfo>>> {
+        if input.seek(SeekFrom::End(-FOOTER_LEN)).is_err() {
+            return Ok(None);
+        }
+        let index_offset = u64::from_le_bytes(read_array(input)?);
+        let magic: [u8; 8] = read_array(input)?;
+        if &magic != FOOTER_MAGIC {
+            return Ok(None);
+        }
+        input.seek(SeekFrom::Start(index_offset))?;
+        let magic: [u8; 4] = read_array(input)?;
+        if &magic != INDEX_MAGIC {
+            return Err(NuitrackError::InvalidRecording("Footer points to an invalid index".into()));
+        }
+        let count = u32::from_le_bytes(read_array(input)?);
+        check_within(input.stream_position()?, u64::from(count) * INDEX_ENTRY_LEN, file_len, "Index")?;
+        let mut index = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            index.push(ChunkInfo {
+                offset: u64::from_le_bytes(read_array(input)?),
+                first_timestamp: u64::from_le_bytes(read_array(input)?),
+                last_timestamp: u64::from_le_bytes(read_array(input)?),
+                record_count: u32::from_le_bytes(read_array(input)?),
+            });
+        }
+        Ok(Some(index))
+    }
+
+    fn scan_chunks(input: &mut R, mut offset: u64) -> NuitrackResult<Vec<ChunkInfo>> {
+        let mut index = Vec::new();
+        loop {
+            input.seek(SeekFrom::Start(offset))?;
+            let magic: [u8; 4] = match read_array(input) {
+                Ok(m) => m,
+                Err(_) => break,
+            };
+            if &magic != CHUNK_MAGIC {
+                break;
+            }
+            let (len, record_count) = match (read_array(input), read_array(input)) {
+                (Ok(len), Ok(count)) => (u32::from_le_bytes(len) as u64, u32::from_le_bytes(count)),
+                _ => break,
+            };
+            let (first, last) = match (read_array(input), read_array(input)) {
+                (Ok(first), Ok(last)) => (u64::from_le_bytes(first), u64::from_le_bytes(last)),
+                _ => break,
+            };
+            let end = offset + CHUNK_HEADER_LEN + len;
+            if input.seek(SeekFrom::End(0))? < end {
+                warn!(offset, "Truncated chunk at end of recording ignored.");
+                break;
+            }
+            index.push(ChunkInfo { offset, first_timestamp: first, last_timestamp: last, record_count });
+            offset = end;
+        }
+        Ok(index)
+    }
+
+    pub fn header(&self) -> &RecordingHeader {
+        &self.header
+    }
+
+    pub fn chunks(&self) -> &[ChunkInfo] {
+        &self.index
+    }
+
+    /// Timestamp range covered by the recording, if it contains any records.
+    pub fn time_range(&self) -> Option<(u64, u64)> {
+        let first = self.index.iter().map(|c| c.first_timestamp).min()?;
+        let last = self.index.iter().map(|c| c.last_timestamp).max()?;
+        Some((first, last))
+    }
+
+    /// Restarts reading from the first record.
+    pub fn rewind(&mut self) {
+        self.pending.clear();
+        self.next_chunk = 0;
+    }
+
+    /// Positions the reader so that the next record is the first one with
+    /// a timestamp at or after `timestamp`.
+    pub fn seek_to_timestamp(&mut self, timestamp: u64) -> NuitrackResult<()> {
+        self.pending.clear();
+        self.next_chunk = self
+            .index
+            .iter()
+            .position(|c| c.last_timestamp >= timestamp)
+            .unwrap_or(self.index.len());
+        while let Some(record) = self.next_record()? {
+            if record.timestamp >= timestamp {
+                self.pending.push_front(record);
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn load_chunk(&mut self, info: ChunkInfo) -> NuitrackResult<()> {
+        self.input.seek(SeekFrom::Start(info.offset + 4))?;
+        let len = u32::from_le_bytes(read_array(&mut self.input)?);
+        check_within(info.offset.saturating_add(CHUNK_HEADER_LEN), len.into(), self.file_len, "Chunk")?;
+        self.input.seek(SeekFrom::Current(4 + 8 + 8))?;
+        let mut payload = vec![0u8; len as usize];
+        self.input.read_exact(&mut payload)?;
+        let mut d = Decoder(&payload);
+        while !d.0.is_empty() {
+            let kind = d.u8()?;
+            let device_index = d.u16()? as usize;
+            let timestamp = d.u64()?;
+            let len = d.len()?;
+            let frame = decode_frame(kind, d.take(len)?)?;
+            self.pending.push_back(Record { device_index, timestamp, frame });
+        }
+        Ok(())
+    }
+
+    /// Returns the next record in file order, or `None` at the end of the recording.
+    pub fn next_record(&mut self) -> NuitrackResult<Option<Record>> {
+        while self.pending.is_empty() {
+            let Some(info) = self.index.get(self.next_chunk).copied() else {
+                return Ok(None);
+            };
+            self.next_chunk += 1;
+            self.load_chunk(info)?;
+        }
+        Ok(self.pending.pop_front())
+    }
+}
+
+/// Fails unless `len` bytes starting at `offset` lie within a file of `file_len` bytes.
+fn check_within(offset: u64, len: u64, file_len: u64, what: &str) -> NuitrackResult<()> {
+    if offset.saturating_add(len) > file_len {
+        return Err(NuitrackError::InvalidRecording(format!(
+            "{} of {} bytes at offset {} extends past the end of the file", what, len, offset
+        )));
+    }
+    Ok(())
+}
+
+fn read_array<const N: usize, R: Read>(input: &mut R) -> io::Result<[u8; N]> {
+    let mut buf = [0u8; N];
+    input.read_exact(&mut buf)?;
+    Ok(buf)
+}
@@ -0,0 +1,8 @@
+//! Capturing sessions to disk.
+//!
+//! [`recorder::SessionRecorder`] taps every active stream of a running
+//! [`NuitrackSession`](crate::nuitrack::async_api::session::NuitrackSession) and
+//! writes the frames into the container described in [`format`].
+
+pub mod format;
+pub mod recorder;
@@ -0,0 +1,334 @@
+use std::fs::File;
+use std::future::{poll_fn, Future};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::thread::JoinHandle;
+
+use futures_channel::oneshot;
+use futures_core::Stream;
+use pin_project::pin_project;
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::nuitrack::async_api::async_dispatch::{block_on, run_blocking};
+use crate::nuitrack::async_api::generate_tracker::ClosableStream;
+use crate::nuitrack::async_api::session::{ActiveDeviceContext, NuitrackSession};
+use crate::nuitrack::backend::BackendFrame;
+use crate::nuitrack::shared_types::error::{NuitrackError, Result as NuitrackResult};
+
+use super::format::{RecordedDevice, Record, RecordingHeader, RecordingWriter};
+
+/// Records taken from a single stream before the worker re-checks for a stop request.
+const MAX_RECORDS_PER_POLL: usize = 16;
+
+type TapItem = (usize, NuitrackResult<BackendFrame>);
+type BoxedTap = Pin<Box<dyn ClosableStream<Item = TapItem> + Send>>;
+
+/// Adapts one tracker stream into device-tagged [`BackendFrame`]s.
+#[pin_project]
+struct Tap<S, T> {
+    #[pin]
+    stream: S,
+    device_index: usize,
+    wrap: fn(T) -> BackendFrame,
+}
+
+impl<S, T> Stream for Tap<S, T>
+where
+    S: Stream<Item = NuitrackResult<T>>,
+{
+    type Item = TapItem;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let device_index = *this.device_index;
+        let wrap = *this.wrap;
+        this.stream
+            .poll_next(cx)
+            .map(|item| item.map(|result| (device_index, result.map(wrap))))
+    }
+}
+
+impl<S, T> ClosableStream for Tap<S, T>
+where
+    S: ClosableStream<Item = NuitrackResult<T>>,
+{
+    fn close(self: Pin<&mut Self>) {
+        self.project().stream.close();
+    }
+}
+
+fn tap<S, T>(stream: S, device_index: usize, wrap: fn(T) -> BackendFrame) -> BoxedTap
+where
+    S: ClosableStream<Item = NuitrackResult<T>> + Send + 'static,
+    T: 'static,
+{
+    Box::pin(Tap { stream, device_index, wrap })
+}
+
+/// Holds records back until their update cycle is complete, so the file is written
+/// in cycle order even though every stream is drained separately.
+///
+/// A cycle is complete once a later frame from the same device had been read before
+/// the current pass started: that frame was produced after the whole cycle, so a pass
+/// that empties every tap has read the rest of it too. Complete cycles are released
+/// ordered by timestamp.
+#[derive(Default)]
+struct CycleBuffer {
+    pending: Vec<Record>,
+    /// Newest frame timestamp read per device.
+    newest: Vec<Option<u64>>,
+    /// `newest` as it was when the current pass started.
+    newest_at_pass_start: Vec<Option<u64>>,
+}
+
+impl CycleBuffer {
+    fn start_pass(&mut self) {
+        self.newest_at_pass_start.clone_from(&self.newest);
+    }
+
+    fn push(&mut self, device_index: usize, frame: BackendFrame) -> NuitrackResult<()> {
+        if self.newest.len() <= device_index {
+            self.newest.resize(device_index + 1, None);
+        }
+        let newest = &mut self.newest[device_index];
+        let timestamp = match frame.timestamp()? {
+            Some(ts) => {
+                *newest = Some(newest.map_or(ts, |n| n.max(ts)));
+                ts
+            }
+            // User events carry no timestamp of their own. They join the device's
+            // newest cycle, which is still pending and therefore not yet written.
+            None => newest.unwrap_or(0),
+        };
+        self.pending.push(Record { device_index, timestamp, frame });
+        Ok(())
+    }
+
+    /// Removes every record whose cycle is complete, in write order. Only valid after
+    /// a pass that emptied every tap.
+    fn take_complete(&mut self) -> Vec<Record> {
+        let complete_before = &self.newest_at_pass_start;
+        let (ready, pending) = std::mem::take(&mut self.pending).into_iter().partition(|r: &Record| {
+            complete_before.get(r.device_index).copied().flatten().is_some_and(|n| r.timestamp < n)
+        });
+        self.pending = pending;
+        Self::sorted(ready)
+    }
+
+    /// Removes every record, including the still-open cycles, in write order.
+    fn take_all(&mut self) -> Vec<Record> {
+        Self::sorted(std::mem::take(&mut self.pending))
+    }
+
+    fn sorted(mut records: Vec<Record>) -> Vec<Record> {
+        // Stable, so records of one cycle keep the order they were read in.
+        records.sort_by_key(|r| (r.timestamp, r.device_index));
+        records
+    }
+}
+
+/// Summary returned once a recording has been finalized.
+#[derive(Debug, Clone)]
+pub struct RecordingSummary {
+    pub path: PathBuf,
+    pub records: u64,
+}
+
+/// Records every active stream of a session into a single file.
+///
+/// The recorder subscribes alongside the application's own streams, so it can be
+/// started and stopped at any time without disturbing them. Frames are written
+/// on a dedicated thread.
+pub struct SessionRecorder {
+    path: PathBuf,
+    stop_tx: Option<oneshot::Sender<()>>,
+    worker: Option<JoinHandle<NuitrackResult<u64>>>,
+}
+
+impl SessionRecorder {
+    /// Starts recording all streams of all active devices of `session` to `path`.
+    #[instrument(skip(session, path))]
+    pub async fn start(session: &NuitrackSession, path: impl AsRef<Path>) -> NuitrackResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut header = RecordingHeader::default();
+        let (mut taps, mut frame_taps) = (Vec::new(), Vec::new());
+
+        for (device_index, device) in session.active_devices.iter().enumerate() {
+            header.devices.push(Self::describe_device(device).await);
+            Self::tap_device(device, device_index, &mut taps, &mut frame_taps)?;
+        }
+        taps.append(&mut frame_taps);
+        info!(path = %path.display(), devices = header.devices.len(), streams = taps.len(), "Starting session recording.");
+
+        let file = File::create(&path)?;
+        let writer = RecordingWriter::new(BufWriter::new(file), &header)?;
+        let (stop_tx, stop_rx) = oneshot::channel();
+        let worker = std::thread::Builder::new()
+            .name("nuitrack-recorder".into())
+            .spawn(move || Self::run_worker(writer, taps, stop_rx))?;
+
+        Ok(Self { path, stop_tx: Some(stop_tx), worker: Some(worker) })
+    }
+
+    async fn describe_device(device: &ActiveDeviceContext) -> RecordedDevice {
+        let depth_mode = match &device.depth_sensor {
+            Some(sensor) => sensor.output_mode().await
+                .inspect_err(|e| warn!(error = %e, "Could not read depth output mode for recording header."))
+                .ok(),
+            None => None,
+        };
+        let color_mode = match &device.color_sensor {
+            Some(sensor) => sensor.output_mode().await
+                .inspect_err(|e| warn!(error = %e, "Could not read color output mode for recording header."))
+                .ok(),
+            None => None,
+        };
+        RecordedDevice {
+            name: device.info.name.clone(),
+            serial_number: device.info.serial_number.clone(),
+            provider_name: device.info.provider_name.clone(),
+            original_index: device.info.original_index,
+            depth_mode,
+            color_mode,
+        }
+    }
+
+    /// Opens the taps of one device. User event taps go to `events`, everything else to `frames`.
+    fn tap_device(
+        device: &ActiveDeviceContext,
+        i: usize,
+        events: &mut Vec<BoxedTap>,
+        frames: &mut Vec<BoxedTap>,
+    ) -> NuitrackResult<()> {
+        if let Some(ds) = &device.depth_sensor {
+            frames.push(tap(ds.tap_depth_frames()?, i, BackendFrame::Depth));
+        }
+        if let Some(cs) = &device.color_sensor {
+            frames.push(tap(cs.tap_rgb_frames()?, i, BackendFrame::Color));
+        }
+        if let Some(ut) = &device.user_tracker {
+            frames.push(tap(ut.tap_user_frames()?, i, BackendFrame::User));
+            events.push(tap(ut.tap_new_user_events()?, i, BackendFrame::NewUser));
+            events.push(tap(ut.tap_lost_user_events()?, i, BackendFrame::LostUser));
+        }
+        if let Some(st) = &device.skeleton_tracker {
+            frames.push(tap(st.tap_skeleton_frames()?, i, BackendFrame::Skeleton));
+            // User events are identical on both trackers; record them only once.
+            if device.user_tracker.is_none() {
+                events.push(tap(st.tap_new_user_events()?, i, BackendFrame::NewUser));
+                events.push(tap(st.tap_lost_user_events()?, i, BackendFrame::LostUser));
+            }
+        }
+        if let Some(ht) = &device.hand_tracker {
+            frames.push(tap(ht.tap_hand_frames()?, i, BackendFrame::Hand));
+        }
+        if let Some(gr) = &device.gesture_recognizer {
+            frames.push(tap(gr.tap_completed_gestures_frames()?, i, BackendFrame::CompletedGestures));
+            frames.push(tap(gr.tap_user_state_changes()?, i, BackendFrame::UserStateChange));
+            frames.push(tap(gr.tap_updates()?, i, BackendFrame::GestureUpdate));
+        }
+        Ok(())
+    }
+
+    /// Drains the taps in order and writes complete update cycles as they become available.
+    ///
+    /// `taps` must list the user event taps first: a pass only moves on to a tap once
+    /// every tap before it is empty, so an event is always read before the frames of
+    /// any cycle that started after it was raised.
+    fn run_worker(
+        mut writer: RecordingWriter<BufWriter<File>>,
+        mut taps: Vec<BoxedTap>,
+        mut stop_rx: oneshot::Receiver<()>,
+    ) -> NuitrackResult<u64> {
+        let mut cycles = CycleBuffer::default();
+        let mut write_error = None;
+        let mut stopping = false;
+
+        block_on(poll_fn(|cx| {
+            // A dropped sender counts as a stop request too. Closing the taps keeps new
+            // frames out while everything already queued is still written, so the drain
+            // ends even if the writer lags behind the streams.
+            if !stopping && Pin::new(&mut stop_rx).poll(cx).is_ready() {
+                debug!("Stop requested; draining queued frames.");
+                stopping = true;
+                taps.iter_mut().for_each(|tap| tap.as_mut().close());
+            }
+            cycles.start_pass();
+            let mut budget_exhausted = false;
+            taps.retain_mut(|tap| {
+                if budget_exhausted {
+                    return true;
+                }
+                for _ in 0..MAX_RECORDS_PER_POLL {
+                    match tap.as_mut().poll_next(cx) {
+                        Poll::Pending => return true,
+                        Poll::Ready(None) => return false,
+                        Poll::Ready(Some((_, Err(e)))) => warn!(error = %e, "Recorded stream yielded an error."),
+                        Poll::Ready(Some((device_index, Ok(frame)))) => {
+                            if let Err(e) = cycles.push(device_index, frame) {
+                                warn!(error = %e, "Could not read the timestamp of a recorded frame; skipping it.");
+                            }
+                        }
+                    }
+                }
+                budget_exhausted = true;
+                true
+            });
+            let finished = taps.is_empty();
+            let ready = match (finished, budget_exhausted) {
+                (true, _) => cycles.take_all(),
+                (false, false) => cycles.take_complete(),
+                (false, true) => Vec::new(),
+            };
+            if let Err(e) = ready.iter().try_for_each(|record| writer.write_record(record)) {
+                error!(error = %e, "Failed to write record; stopping recording.");
+                write_error = Some(e);
+                return Poll::Ready(());
+            }
+            if finished {
+                return Poll::Ready(());
+            }
+            if budget_exhausted {
+                // More records are queued; come back after re-checking the stop signal.
+                cx.waker().wake_by_ref();
+            }
+            Poll::Pending
+        }));
+
+        drop(taps);
+        if let Some(e) = write_error {
+            return Err(e);
+        }
+        let records = writer.records_written();
+        writer.finish()?;
+        debug!(records, "Recorder worker finished.");
+        Ok(records)
+    }
+
+    /// Stops recording and finalizes the file (flushing the last chunk and writing the index).
+    #[instrument(skip(self))]
+    pub async fn finish(mut self) -> NuitrackResult<RecordingSummary> {
+        if let Some(stop_tx) = self.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+        let worker = self.worker.take().ok_or_else(|| {
+            NuitrackError::OperationFailed("Recorder worker already joined".into())
+        })?;
+        let records = run_blocking(move || {
+            worker.join().map_err(|_| NuitrackError::OperationFailed("Recorder worker panicked".into()))?
+        }).await?;
+        info!(path = %self.path.display(), records, "Session recording finalized.");
+        Ok(RecordingSummary { path: self.path.clone(), records })
+    }
+}
+
+impl Drop for SessionRecorder {
+    fn drop(&mut self) {
+        if let Some(stop_tx) = self.stop_tx.take() {
+            debug!("SessionRecorder dropped without finish(); finalizing in the background.");
+            let _ = stop_tx.send(());
+        }
+    }
+}
@@ -0,0 +1,429 @@
+//! Per-user body models: calibration, bone length constraints and retargeting.
+//!
+//! The SDK estimates every joint independently, so a user's bone lengths vary from
+//! frame to frame. [`BodyCalibrator`] collects a user's first frames into a
+//! [`BodyModel`] of median bone lengths and from then on rebuilds each skeleton with
+//! those lengths, keeping the measured bone directions. A [`Rig`] does the same with a
+//! fixed set of lengths and rest directions, and reports per-bone rotations for
+//! driving an avatar or exporting animation.
+
+use std::collections::HashMap;
+
+use tracing::{debug, trace};
+
+use crate::nuitrack_bridge::types::skeleton::ffi::{Joint, JointType, Orientation};
+use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+
+use super::camera_model::CameraModel;
+use super::error::Result as NuitrackResult;
+use super::frame::SkeletonFrameData;
+use super::kinematics::{Bone, Quaternion, BONES};
+use super::skeleton::Skeleton;
+use super::skeleton_frame::SkeletonFrame;
+
+/// Distance from the head joint to the top of the head plus the ankle joint to the
+/// sole, added to the joint chain when estimating height.
+const CROWN_AND_SOLE: f32 = 150.0;
+
+fn add(a: Vector3, b: Vector3) -> Vector3 {
+    Vector3 { x: a.x + b.x, y: a.y + b.y, z: a.z + b.z }
+}
+
+fn scale(v: Vector3, s: f32) -> Vector3 {
+    Vector3 { x: v.x * s, y: v.y * s, z: v.z * s }
+}
+
+fn unit(v: Vector3) -> Option<Vector3> {
+    let length = (v.x * v.x + v.y * v.y + v.z * v.z).sqrt();
+    (length > f32::EPSILON).then(|| scale(v, 1.0 / length))
+}
+
+/// Left joints and their right counterparts.
+const MIRRORED: [(JointType, JointType); 9] = [
+    (JointType::LeftShoulder, JointType::RightShoulder),
+    (JointType::LeftElbow, JointType::RightElbow),
+    (JointType::LeftWrist, JointType::RightWrist),
+    (JointType::LeftHand, JointType::RightHand),
+    (JointType::LeftFingertip, JointType::RightFingertip),
+    (JointType::LeftHip, JointType::RightHip),
+    (JointType::LeftKnee, JointType::RightKnee),
+    (JointType::LeftAnkle, JointType::RightAnkle),
+    (JointType::LeftFoot, JointType::RightFoot),
+];
+
+fn mirror(joint: JointType) -> JointType {
+    MIRRORED
+        .iter()
+        .find_map(|&(left, right)| match joint {
+            j if j == left => Some(right),
+            j if j == right => Some(left),
+            _ => None,
+        })
+        .unwrap_or(joint)
+}
+
+/// Rebuilds `skeleton` root first, placing each tracked child at `length(bone)` from
+/// its (already rebuilt) parent along the measured bone direction. Bones without a
+/// length, or with an untracked end, keep the measured offset.
+fn rebuild(skeleton: &Skeleton, length: impl Fn(Bone) -> Option<f32>, camera: Option<&CameraModel>) -> Skeleton {
+    let mut rebuilt: HashMap<JointType, Vector3> = HashMap::new();
+    for bone in BONES {
+        let (Some(parent), Some(child)) = (skeleton.position(bone.parent), skeleton.position(bone.child)) else {
+            continue;
+        };
+        let start = *rebuilt.entry(bone.parent).or_insert(parent);
+        let offset = Vector3 { x: child.x - parent.x, y: child.y - parent.y, z: child.z - parent.z };
+        let offset = match (length(bone), unit(offset)) {
+            (Some(length), Some(direction)) => scale(direction, length),
+            _ => offset,
+        };
+        rebuilt.insert(bone.child, add(start, offset));
+    }
+    let joints = skeleton
+        .joints
+        .iter()
+        .map(|joint| match rebuilt.get(&joint.joint_type) {
+            Some(&real) if joint.confidence > 0.0 => Joint {
+                real,
+                proj: camera.map_or(joint.proj, |c| c.proj_to_normalized(c.real_to_proj(real))),
+                ..*joint
+            },
+            _ => *joint,
+        })
+        .collect();
+    Skeleton { user_id: skeleton.user_id, joints }
+}
+
+fn median(values: &mut [f32]) -> f32 {
+    values.sort_by(f32::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) { (values[mid - 1] + values[mid]) / 2.0 } else { values[mid] }
+}
+
+/// Stable segment lengths of one person, in millimeters.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyModel {
+    /// Length per bone, in [`BONES`] order. Bones that were never measured are absent.
+    pub bone_lengths: Vec<(Bone, f32)>,
+    /// Number of skeletons the model was estimated from.
+    pub samples: usize,
+}
+
+impl BodyModel {
+    /// The median length of each bone over `skeletons`, counting only bones measured in
+    /// at least half of them.
+    pub fn from_skeletons<'a>(skeletons: impl IntoIterator<Item = &'a Skeleton>) -> Self {
+        let mut lengths: Vec<Vec<f32>> = vec![Vec::new(); BONES.len()];
+        let mut samples = 0;
+        for skeleton in skeletons {
+            samples += 1;
+            for (i, &bone) in BONES.iter().enumerate() {
+                lengths[i].extend(skeleton.bone_length(bone));
+            }
+        }
+        let bone_lengths = BONES
+            .iter()
+            .zip(lengths.iter_mut())
+            .filter(|(_, values)| !values.is_empty() && values.len() * 2 >= samples)
+            .map(|(&bone, values)| (bone, median(values)))
+            .collect();
+        Self { bone_lengths, samples }
+    }
+
+    pub fn bone_length(&self, bone: Bone) -> Option<f32> {
+        self.bone_lengths.iter().find(|(b, _)| *b == bone).map(|&(_, length)| length)
+    }
+
+    /// Approximate standing height: the leg (mean of both sides if measured), spine,
+    /// neck and head segments, plus an allowance for the crown and sole. `None` unless
+    /// the spine, head and at least one leg are measured.
+    pub fn height(&self) -> Option<f32> {
+        let chain = |joints: &[JointType]| -> Option<f32> {
+            joints.windows(2).map(|w| self.bone_length(Bone { parent: w[0], child: w[1] })).sum()
+        };
+        let legs: Vec<f32> = [
+            [JointType::LeftHip, JointType::LeftKnee, JointType::LeftAnkle],
+            [JointType::RightHip, JointType::RightKnee, JointType::RightAnkle],
+        ]
+        .iter()
+        .filter_map(|leg| chain(leg))
+        .collect();
+        if legs.is_empty() {
+            return None;
+        }
+        let leg = legs.iter().sum::<f32>() / legs.len() as f32;
+        let upper = chain(&[JointType::Waist, JointType::Torso, JointType::Neck, JointType::Head])?;
+        Some(leg + upper + CROWN_AND_SOLE)
+    }
+
+    /// The model with each left/right pair of bones set to their mean, or to the one
+    /// measured side.
+    pub fn symmetrized(&self) -> Self {
+        let bone_lengths = BONES
+            .iter()
+            .filter_map(|&bone| {
+                let mirrored = Bone { parent: mirror(bone.parent), child: mirror(bone.child) };
+                match (self.bone_length(bone), self.bone_length(mirrored)) {
+                    (Some(a), Some(b)) => Some((bone, (a + b) / 2.0)),
+                    (a, b) => a.or(b).map(|length| (bone, length)),
+                }
+            })
+            .collect();
+        Self { bone_lengths, samples: self.samples }
+    }
+
+    /// `skeleton` with every measured bone set to the model's length. Joints keep their
+    /// confidence and orientation; `proj` is unchanged.
+    pub fn constrain(&self, skeleton: &Skeleton) -> Skeleton {
+        rebuild(skeleton, |bone| self.bone_length(bone), None)
+    }
+}
+
+/// Collects each user's first skeletons into a [`BodyModel`] and constrains the
+/// skeletons that follow.
+#[derive(Debug, Clone)]
+pub struct BodyCalibrator {
+    frames: usize,
+    symmetric: bool,
+    camera: Option<CameraModel>,
+    pending: HashMap<i32, Vec<Skeleton>>,
+    models: HashMap<i32, BodyModel>,
+}
+
+impl Default for BodyCalibrator {
+    fn default() -> Self {
+        Self::new(30)
+    }
+}
+
+impl BodyCalibrator {
+    /// Calibrates each user from their first `frames` skeletons.
+    pub fn new(frames: usize) -> Self {
+        Self { frames: frames.max(1), symmetric: false, camera: None, pending: HashMap::new(), models: HashMap::new() }
+    }
+
+    /// Averages left and right bones in the calibrated models.
+    pub fn symmetric(mut self, symmetric: bool) -> Self {
+        self.symmetric = symmetric;
+        self
+    }
+
+    /// Recomputes `Joint::proj` of constrained skeletons with the depth sensor's camera
+    /// model. Without one, `proj` keeps the SDK's value.
+    pub fn with_camera_model(mut self, camera: CameraModel) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    /// Fraction of the calibration frames collected for `user_id`, 1 once calibrated.
+    pub fn progress(&self, user_id: i32) -> f32 {
+        if self.models.contains_key(&user_id) {
+            return 1.0;
+        }
+        self.pending.get(&user_id).map_or(0.0, |s| s.len() as f32 / self.frames as f32)
+    }
+
+    pub fn model(&self, user_id: i32) -> Option<&BodyModel> {
+        self.models.get(&user_id)
+    }
+
+    pub fn models(&self) -> impl Iterator<Item = (i32, &BodyModel)> {
+        self.models.iter().map(|(&user_id, model)| (user_id, model))
+    }
+
+    /// Uses `model` for `user_id` instead of calibrating, e.g. one stored from an
+    /// earlier session. Like calibrated models, it is discarded once a frame passed to
+    /// [`apply`](Self::apply) lacks the user.
+    pub fn set_model(&mut self, user_id: i32, model: BodyModel) {
+        self.pending.remove(&user_id);
+        self.models.insert(user_id, model);
+    }
+
+    /// Discards the calibration of `user_id`, which recalibrates from its next frames.
+    pub fn reset_user(&mut self, user_id: i32) {
+        self.pending.remove(&user_id);
+        self.models.remove(&user_id);
+    }
+
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.models.clear();
+    }
+
+    /// Records skeletons of users still calibrating, and returns every skeleton
+    /// constrained to its user's model. Skeletons of users not yet calibrated are
+    /// returned unchanged; the frame completing a calibration is already constrained.
+    /// Users missing from `skeletons` are forgotten and recalibrate if they return.
+    pub fn apply(&mut self, skeletons: &[Skeleton]) -> Vec<Skeleton> {
+        let present = |user_id: &i32| skeletons.iter().any(|s| s.user_id == *user_id);
+        self.pending.retain(|user_id, _| present(user_id));
+        self.models.retain(|user_id, _| present(user_id));
+        skeletons
+            .iter()
+            .map(|skeleton| {
+                let user_id = skeleton.user_id;
+                if !self.models.contains_key(&user_id) {
+                    let pending = self.pending.entry(user_id).or_default();
+                    pending.push(skeleton.clone());
+                    if pending.len() < self.frames {
+                        return skeleton.clone();
+                    }
+                    let mut model = BodyModel::from_skeletons(pending.iter());
+                    if self.symmetric {
+                        model = model.symmetrized();
+                    }
+                    debug!(user_id, bones = model.bone_lengths.len(), height = ?model.height(), "User calibrated.");
+                    self.pending.remove(&user_id);
+                    self.models.insert(user_id, model);
+                }
+                let model = &self.models[&user_id];
+                trace!(user_id, "Skeleton constrained.");
+                rebuild(skeleton, |bone| model.bone_length(bone), self.camera.as_ref())
+            })
+            .collect()
+    }
+
+    /// [`apply`](Self::apply) to every skeleton of `frame`, as a new frame with the
+    /// same timestamp.
+    pub fn apply_frame(&mut self, frame: &impl SkeletonFrameData) -> NuitrackResult<SkeletonFrame> {
+        Ok(SkeletonFrame::synthetic(frame.timestamp()?, self.apply(frame.skeletons()?)))
+    }
+}
+
+/// One bone of a [`Rig`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RigBone {
+    pub bone: Bone,
+    pub length: f32,
+    /// Unit direction from parent to child in the rest pose.
+    pub rest_direction: Vector3,
+}
+
+/// A complete skeleton with fixed bone lengths and a rest pose, to retarget users onto.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rig {
+    /// One entry per bone, in [`BONES`] order.
+    pub bones: Vec<RigBone>,
+}
+
+/// Lengths and rest directions of [`Rig::standard`], in [`BONES`] order. The rest
+/// pose is a T-pose facing the sensor, with the user's left at `-x`.
+const STANDARD_RIG: [(f32, [f32; 3]); 22] = [
+    (200.0, [0.0, 1.0, 0.0]),
+    (300.0, [0.0, 1.0, 0.0]),
+    (150.0, [0.0, 1.0, 0.0]),
+    (250.0, [0.0, 1.0, 0.0]),
+    (180.0, [-1.0, 0.0, 0.0]),
+    (290.0, [-1.0, 0.0, 0.0]),
+    (250.0, [-1.0, 0.0, 0.0]),
+    (60.0, [-1.0, 0.0, 0.0]),
+    (90.0, [-1.0, 0.0, 0.0]),
+    (180.0, [1.0, 0.0, 0.0]),
+    (290.0, [1.0, 0.0, 0.0]),
+    (250.0, [1.0, 0.0, 0.0]),
+    (60.0, [1.0, 0.0, 0.0]),
+    (90.0, [1.0, 0.0, 0.0]),
+    (110.0, [-0.894, -0.447, 0.0]),
+    (450.0, [0.0, -1.0, 0.0]),
+    (420.0, [0.0, -1.0, 0.0]),
+    (150.0, [0.0, 0.0, -1.0]),
+    (110.0, [0.894, -0.447, 0.0]),
+    (450.0, [0.0, -1.0, 0.0]),
+    (420.0, [0.0, -1.0, 0.0]),
+    (150.0, [0.0, 0.0, -1.0]),
+];
+
+impl Rig {
+    /// An adult rig about 1.72 m tall in a T-pose.
+    pub fn standard() -> Self {
+        let bones = BONES
+            .iter()
+            .zip(STANDARD_RIG)
+            .map(|(&bone, (length, [x, y, z]))| RigBone { bone, length, rest_direction: Vector3 { x, y, z } })
+            .collect();
+        Self { bones }
+    }
+
+    /// The standard rig with the lengths of `model` where it has them.
+    pub fn from_model(model: &BodyModel) -> Self {
+        let mut rig = Self::standard();
+        for rig_bone in &mut rig.bones {
+            if let Some(length) = model.bone_length(rig_bone.bone) {
+                rig_bone.length = length;
+            }
+        }
+        rig
+    }
+
+    pub fn bone(&self, bone: Bone) -> Option<&RigBone> {
+        self.bones.iter().find(|b| b.bone == bone)
+    }
+
+    /// Poses the rig like `skeleton`, rooted at its waist. Bones with an untracked end
+    /// take their rest direction; bones whose parent joint the rig does not reach
+    /// from the waist, e.g. listed before their parent's bone, are skipped. Returns
+    /// `None` if the waist is not tracked.
+    pub fn retarget(&self, skeleton: &Skeleton) -> Option<RetargetedPose> {
+        let root = skeleton.position(JointType::Waist)?;
+        let mut positions: HashMap<JointType, Vector3> = HashMap::from([(JointType::Waist, root)]);
+        let mut rotations = Vec::with_capacity(self.bones.len());
+        for rig_bone in &self.bones {
+            let Some(&start) = positions.get(&rig_bone.bone.parent) else {
+                continue;
+            };
+            let direction = skeleton.bone_vector(rig_bone.bone).and_then(unit).unwrap_or(rig_bone.rest_direction);
+            positions.insert(rig_bone.bone.child, add(start, scale(direction, rig_bone.length)));
+            rotations.push((rig_bone.bone, Quaternion::between(rig_bone.rest_direction, direction)));
+        }
+        let joints = JointType::ALL[1..]
+            .iter()
+            .filter_map(|&joint_type| {
+                let real = *positions.get(&joint_type)?;
+                let source = skeleton.joint(joint_type);
+                Some(Joint {
+                    joint_type,
+                    confidence: source.map_or(0.0, |j| j.confidence),
+                    real,
+                    proj: source.map_or(Vector3::default(), |j| j.proj),
+                    orient: Orientation::from_quaternion(&Quaternion::IDENTITY),
+                })
+            })
+            .collect();
+        Some(RetargetedPose { skeleton: Skeleton { user_id: skeleton.user_id, joints }, rotations })
+    }
+}
+
+/// A [`Rig`] posed after a user.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone)]
+pub struct RetargetedPose {
+    /// Every joint the rig reaches from the waist, at the rig's bone lengths.
+    /// Confidences are the source's, and orientations are left as identity; use
+    /// `rotations` instead.
+    pub skeleton: Skeleton,
+    /// Per bone, the rotation taking its rest direction to its posed direction, in
+    /// [`BONES`] order.
+    pub rotations: Vec<(Bone, Quaternion)>,
+}
+
+impl RetargetedPose {
+    /// The rotation of `bone` from its rest direction, in sensor coordinates.
+    pub fn rotation(&self, bone: Bone) -> Option<Quaternion> {
+        self.rotations.iter().find(|(b, _)| *b == bone).map(|&(_, q)| q)
+    }
+
+    /// The rotation of `bone` relative to its parent bone's rotation, as animation
+    /// formats expect. Bones attached to the root are relative to the sensor.
+    pub fn local_rotation(&self, bone: Bone) -> Option<Quaternion> {
+        let global = self.rotation(bone)?;
+        let parent = BONES.iter().find(|b| b.child == bone.parent).and_then(|&b| self.rotation(b));
+        Some(parent.map_or(global, |p| p.conjugate() * global))
+    }
+}
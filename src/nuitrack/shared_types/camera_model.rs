@@ -0,0 +1,153 @@
+//! Pure-Rust conversion between projective and real-world coordinates.
+//!
+//! [`CameraModel`] mirrors `DepthSensor::convertProjToReal` / `convertRealToProj` with a
+//! pinhole model, so points can be converted in hot loops without an FFI call each.
+//!
+//! Projective coordinates are pixels of the sensor image in `x`/`y` with depth in
+//! millimeters in `z`. Real-world coordinates are millimeters, `x` to the right, `y` up
+//! and `z` away from the sensor. Note that `Joint::proj` and hand positions are
+//! normalized to `0..1`; use [`CameraModel::normalized_to_proj`] to scale them.
+
+use crate::nuitrack_bridge::types::output_mode::ffi::{Intrinsics, OutputMode};
+use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+
+use super::error::{NuitrackError, Result as NuitrackResult};
+use super::frame::DepthFrameData;
+
+/// Pinhole model of one sensor at one resolution.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraModel {
+    pub width: i32,
+    pub height: i32,
+    pub intrinsics: Intrinsics,
+    /// Whether the image is flipped horizontally, as with `DepthSensor::setMirror(true)`.
+    pub mirror: bool,
+}
+
+impl CameraModel {
+    /// Builds the model from an output mode's intrinsics, falling back to its horizontal
+    /// field of view when the SDK reports none.
+    pub fn new(mode: &OutputMode) -> NuitrackResult<Self> {
+        let Intrinsics { fx, fy, .. } = mode.intrinsics;
+        if mode.xres <= 0 || mode.yres <= 0 {
+            return Err(NuitrackError::OperationFailed(format!("Invalid output mode {}x{}", mode.xres, mode.yres)));
+        }
+        let intrinsics = if fx > 0.0 && fy > 0.0 {
+            mode.intrinsics
+        } else if mode.hfov > 0.0 {
+            let f = mode.xres as f32 / (2.0 * (mode.hfov / 2.0).tan());
+            Intrinsics { fx: f, fy: f, cx: mode.xres as f32 / 2.0, cy: mode.yres as f32 / 2.0 }
+        } else {
+            return Err(NuitrackError::OperationFailed(format!(
+                "Output mode {}x{} has neither intrinsics nor a field of view",
+                mode.xres, mode.yres
+            )));
+        };
+        Ok(Self { width: mode.xres, height: mode.yres, intrinsics, mirror: false })
+    }
+
+    pub fn with_mirror(mut self, mirror: bool) -> Self {
+        self.mirror = mirror;
+        self
+    }
+
+    /// The same camera at another resolution, e.g. for frames smaller than the mode.
+    pub fn scaled_to(&self, width: i32, height: i32) -> Self {
+        let sx = width as f32 / self.width as f32;
+        let sy = height as f32 / self.height as f32;
+        let Intrinsics { fx, fy, cx, cy } = self.intrinsics;
+        Self {
+            width,
+            height,
+            intrinsics: Intrinsics { fx: fx * sx, fy: fy * sy, cx: cx * sx, cy: cy * sy },
+            mirror: self.mirror,
+        }
+    }
+
+    /// Pixel coordinates and depth to a real-world point.
+    #[inline]
+    pub fn proj_to_real(&self, proj: Vector3) -> Vector3 {
+        let Intrinsics { fx, fy, cx, cy } = self.intrinsics;
+        let u = if self.mirror { (self.width - 1) as f32 - proj.x } else { proj.x };
+        Vector3 { x: (u - cx) * proj.z / fx, y: (cy - proj.y) * proj.z / fy, z: proj.z }
+    }
+
+    /// A real-world point to pixel coordinates, keeping depth in `z`. Points at or
+    /// behind the sensor plane map to the principal point.
+    #[inline]
+    pub fn real_to_proj(&self, real: Vector3) -> Vector3 {
+        let Intrinsics { fx, fy, cx, cy } = self.intrinsics;
+        if real.z <= 0.0 {
+            return Vector3 { x: cx, y: cy, z: real.z };
+        }
+        let u = cx + fx * real.x / real.z;
+        Vector3 {
+            x: if self.mirror { (self.width - 1) as f32 - u } else { u },
+            y: cy - fy * real.y / real.z,
+            z: real.z,
+        }
+    }
+
+    /// Normalized (`0..1`) image coordinates, as in `Joint::proj`, to pixels.
+    #[inline]
+    pub fn normalized_to_proj(&self, normalized: Vector3) -> Vector3 {
+        Vector3 { x: normalized.x * self.width as f32, y: normalized.y * self.height as f32, z: normalized.z }
+    }
+
+    /// Pixels to normalized (`0..1`) image coordinates.
+    #[inline]
+    pub fn proj_to_normalized(&self, proj: Vector3) -> Vector3 {
+        Vector3 { x: proj.x / self.width as f32, y: proj.y / self.height as f32, z: proj.z }
+    }
+
+    pub fn proj_to_real_in_place(&self, points: &mut [Vector3]) {
+        for p in points {
+            *p = self.proj_to_real(*p);
+        }
+    }
+
+    pub fn real_to_proj_in_place(&self, points: &mut [Vector3]) {
+        for p in points {
+            *p = self.real_to_proj(*p);
+        }
+    }
+
+    /// Whether a projective point lies inside the image.
+    pub fn contains(&self, proj: Vector3) -> bool {
+        proj.z > 0.0 && proj.x >= 0.0 && proj.y >= 0.0 && proj.x < self.width as f32 && proj.y < self.height as f32
+    }
+
+    /// Real-world points for every pixel of `frame`, row-major. Pixels without depth
+    /// yield the origin. The model is scaled if the frame's size differs from it.
+    pub fn frame_to_real(&self, frame: &impl DepthFrameData) -> NuitrackResult<Vec<Vector3>> {
+        let (rows, cols) = (frame.rows()?, frame.cols()?);
+        let data = frame.data()?;
+        if data.len() != (rows * cols) as usize {
+            return Err(NuitrackError::OperationFailed("Depth data does not match its dimensions".into()));
+        }
+        let model = if (cols, rows) == (self.width, self.height) { *self } else { self.scaled_to(cols, rows) };
+        let Intrinsics { fx, fy, cx, cy } = model.intrinsics;
+        let kx: Vec<f32> = (0..cols)
+            .map(|u| {
+                let u = if model.mirror { (cols - 1 - u) as f32 } else { u as f32 };
+                (u - cx) / fx
+            })
+            .collect();
+        let ky: Vec<f32> = (0..rows).map(|v| (cy - v as f32) / fy).collect();
+        let origin = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
+        Ok(data
+            .iter()
+            .enumerate()
+            .map(|(i, &z)| {
+                if z == 0 {
+                    return origin;
+                }
+                let z = f32::from(z);
+                let (v, u) = (i / cols as usize, i % cols as usize);
+                Vector3 { x: kx[u] * z, y: ky[v] * z, z }
+            })
+            .collect())
+    }
+}
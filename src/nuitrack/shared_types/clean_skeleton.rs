@@ -0,0 +1,289 @@
+//! Confidence gating and gap filling for skeleton joints.
+//!
+//! The SDK reports every joint in every skeleton, including joints it is unsure of and
+//! joints it never tracks. [`JointGate`] turns each [`Skeleton`] into a
+//! [`CleanSkeleton`] whose joints state explicitly whether they can be used, and
+//! bridges short dropouts by holding or extrapolating the last good position.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tracing::trace;
+
+use crate::nuitrack_bridge::types::skeleton::ffi::{Joint, JointType, Orientation};
+use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+
+use super::error::Result as NuitrackResult;
+use super::frame::SkeletonFrameData;
+use super::skeleton::Skeleton;
+
+/// Where a [`CleanJoint`]'s position comes from.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JointValidity {
+    /// Measured in this frame with sufficient confidence.
+    Tracked,
+    /// The last tracked position, held during a short gap.
+    Held,
+    /// Extrapolated from the last tracked position and velocity during a short gap.
+    Extrapolated,
+    /// Measured with a confidence below the threshold, and no recent tracked position.
+    LowConfidence,
+    /// Not reported by the SDK in this frame.
+    Missing,
+    /// Documented as not tracked by the SDK (see [`JointType::is_unused`]).
+    Unused,
+}
+
+impl JointValidity {
+    /// Whether the position is trustworthy: tracked, or filled from a recent tracked position.
+    pub fn is_usable(self) -> bool {
+        matches!(self, JointValidity::Tracked | JointValidity::Held | JointValidity::Extrapolated)
+    }
+}
+
+/// How [`JointGate`] fills short gaps in a joint's tracking.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GapFill {
+    /// Report the gap as is.
+    None,
+    /// Repeat the last tracked position.
+    #[default]
+    Hold,
+    /// Continue the last tracked position at the last tracked velocity.
+    Extrapolate,
+}
+
+/// A joint with explicit validity.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy)]
+pub struct CleanJoint {
+    pub joint_type: JointType,
+    pub validity: JointValidity,
+    /// The SDK's confidence for this frame, 0 if the joint is missing.
+    pub confidence: f32,
+    /// Position in millimeters. Unspecified unless the joint is usable.
+    pub real: Vector3,
+    /// Normalized projective position. Unspecified unless the joint is usable.
+    pub proj: Vector3,
+    pub orient: Orientation,
+}
+
+impl CleanJoint {
+    pub fn is_usable(&self) -> bool {
+        self.validity.is_usable()
+    }
+}
+
+/// A skeleton with one [`CleanJoint`] per [`JointType`], in [`JointType::ALL`] order
+/// without `None`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone)]
+pub struct CleanSkeleton {
+    pub user_id: i32,
+    /// Timestamp of the source frame, typically in microseconds.
+    pub timestamp: u64,
+    pub joints: Vec<CleanJoint>,
+}
+
+impl CleanSkeleton {
+    pub fn joint(&self, joint_type: JointType) -> Option<&CleanJoint> {
+        self.joints.iter().find(|j| j.joint_type == joint_type)
+    }
+
+    /// The joint if it is usable.
+    pub fn usable_joint(&self, joint_type: JointType) -> Option<&CleanJoint> {
+        self.joint(joint_type).filter(|j| j.is_usable())
+    }
+
+    pub fn usable_joints(&self) -> impl Iterator<Item = &CleanJoint> {
+        self.joints.iter().filter(|j| j.is_usable())
+    }
+
+    /// Converts back to a [`Skeleton`]. Unusable joints get a confidence of 0, so
+    /// consumers that skip untracked joints (such as `SkeletonFilter`) ignore them.
+    pub fn to_skeleton(&self) -> Skeleton {
+        let joints = self
+            .joints
+            .iter()
+            .map(|j| Joint {
+                joint_type: j.joint_type,
+                confidence: if j.is_usable() { j.confidence.max(f32::MIN_POSITIVE) } else { 0.0 },
+                real: j.real,
+                proj: j.proj,
+                orient: j.orient,
+            })
+            .collect();
+        Skeleton { user_id: self.user_id, joints }
+    }
+}
+
+/// Thresholds and gap handling for [`JointGate`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct JointGateConfig {
+    /// Joints with a lower confidence are not tracked.
+    pub min_confidence: f32,
+    /// Per-joint overrides of `min_confidence`.
+    pub joint_thresholds: HashMap<JointType, f32>,
+    pub gap_fill: GapFill,
+    /// Gaps longer than this are not filled.
+    pub max_gap: Duration,
+}
+
+impl Default for JointGateConfig {
+    fn default() -> Self {
+        Self {
+            min_confidence: 0.5,
+            joint_thresholds: HashMap::new(),
+            gap_fill: GapFill::default(),
+            max_gap: Duration::from_millis(250),
+        }
+    }
+}
+
+impl JointGateConfig {
+    pub fn with_min_confidence(mut self, min_confidence: f32) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    pub fn with_joint_threshold(mut self, joint: JointType, min_confidence: f32) -> Self {
+        self.joint_thresholds.insert(joint, min_confidence);
+        self
+    }
+
+    pub fn with_gap_fill(mut self, gap_fill: GapFill, max_gap: Duration) -> Self {
+        self.gap_fill = gap_fill;
+        self.max_gap = max_gap;
+        self
+    }
+
+    pub fn threshold_for(&self, joint: JointType) -> f32 {
+        self.joint_thresholds.get(&joint).copied().unwrap_or(self.min_confidence)
+    }
+}
+
+/// The last tracked sample of a joint.
+#[derive(Debug, Clone, Copy)]
+struct LastGood {
+    timestamp: u64,
+    real: Vector3,
+    proj: Vector3,
+    /// Per-second velocities of `real` and `proj`.
+    real_velocity: Vector3,
+    proj_velocity: Vector3,
+}
+
+fn lerp(p: Vector3, velocity: Vector3, seconds: f32) -> Vector3 {
+    Vector3 { x: p.x + velocity.x * seconds, y: p.y + velocity.y * seconds, z: p.z + velocity.z * seconds }
+}
+
+fn velocity(from: Vector3, to: Vector3, seconds: f32) -> Vector3 {
+    Vector3 { x: (to.x - from.x) / seconds, y: (to.y - from.y) / seconds, z: (to.z - from.z) / seconds }
+}
+
+const ZERO: Vector3 = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
+const IDENTITY: Orientation = Orientation { matrix: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0] };
+
+/// Produces [`CleanSkeleton`]s, remembering each user's last tracked joints.
+#[derive(Debug, Clone, Default)]
+pub struct JointGate {
+    config: JointGateConfig,
+    users: HashMap<i32, HashMap<JointType, LastGood>>,
+}
+
+impl JointGate {
+    pub fn new(config: JointGateConfig) -> Self {
+        Self { config, users: HashMap::new() }
+    }
+
+    pub fn config(&self) -> &JointGateConfig {
+        &self.config
+    }
+
+    /// Forgets every user's last tracked joints.
+    pub fn reset(&mut self) {
+        self.users.clear();
+    }
+
+    /// Gates `skeletons` observed at `timestamp` (in microseconds). History of users
+    /// missing from `skeletons` is dropped.
+    pub fn apply(&mut self, timestamp: u64, skeletons: &[Skeleton]) -> Vec<CleanSkeleton> {
+        self.users.retain(|user_id, _| skeletons.iter().any(|s| s.user_id == *user_id));
+        skeletons.iter().map(|skeleton| self.apply_one(timestamp, skeleton)).collect()
+    }
+
+    /// Gates every skeleton of `frame`.
+    pub fn clean_frame(&mut self, frame: &impl SkeletonFrameData) -> NuitrackResult<Vec<CleanSkeleton>> {
+        Ok(self.apply(frame.timestamp()?, frame.skeletons()?))
+    }
+
+    fn apply_one(&mut self, timestamp: u64, skeleton: &Skeleton) -> CleanSkeleton {
+        let history = self.users.entry(skeleton.user_id).or_default();
+        let max_gap = self.config.max_gap.as_micros() as u64;
+        let joints = JointType::ALL[1..]
+            .iter()
+            .map(|&joint_type| {
+                let measured = skeleton.joint(joint_type);
+                let mut clean = CleanJoint {
+                    joint_type,
+                    validity: JointValidity::Missing,
+                    confidence: measured.map_or(0.0, |j| j.confidence),
+                    real: measured.map_or(ZERO, |j| j.real),
+                    proj: measured.map_or(ZERO, |j| j.proj),
+                    orient: measured.map_or(IDENTITY, |j| j.orient),
+                };
+                if joint_type.is_unused() {
+                    clean.validity = JointValidity::Unused;
+                    return clean;
+                }
+                if let Some(joint) = measured.filter(|j| j.confidence >= self.config.threshold_for(joint_type)) {
+                    let (real_velocity, proj_velocity) = match history.get(&joint_type) {
+                        Some(last) if timestamp > last.timestamp && timestamp - last.timestamp <= max_gap => {
+                            let seconds = (timestamp - last.timestamp) as f32 / 1_000_000.0;
+                            (velocity(last.real, joint.real, seconds), velocity(last.proj, joint.proj, seconds))
+                        }
+                        _ => (ZERO, ZERO),
+                    };
+                    history.insert(joint_type, LastGood { timestamp, real: joint.real, proj: joint.proj, real_velocity, proj_velocity });
+                    clean.validity = JointValidity::Tracked;
+                    return clean;
+                }
+
+                clean.validity = if measured.is_some() { JointValidity::LowConfidence } else { JointValidity::Missing };
+                let Some(last) = history.get(&joint_type) else {
+                    return clean;
+                };
+                let gap = timestamp.saturating_sub(last.timestamp);
+                if gap > max_gap {
+                    history.remove(&joint_type);
+                    return clean;
+                }
+                let seconds = gap as f32 / 1_000_000.0;
+                match self.config.gap_fill {
+                    GapFill::None => {}
+                    GapFill::Hold => {
+                        clean.validity = JointValidity::Held;
+                        clean.real = last.real;
+                        clean.proj = last.proj;
+                    }
+                    GapFill::Extrapolate => {
+                        clean.validity = JointValidity::Extrapolated;
+                        clean.real = lerp(last.real, last.real_velocity, seconds);
+                        clean.proj = lerp(last.proj, last.proj_velocity, seconds);
+                    }
+                }
+                clean
+            })
+            .collect();
+        trace!(user_id = skeleton.user_id, "Skeleton gated.");
+        CleanSkeleton { user_id: skeleton.user_id, timestamp, joints }
+    }
+}
@@ -0,0 +1,381 @@
+//! Recognition of user-defined gestures from skeleton motion.
+//!
+//! A [`GestureTemplate`] is an example trajectory of a few joints, recorded with
+//! [`GestureRecorder`]. [`CustomGestureRecognizer`] keeps a running subsequence
+//! dynamic time warping (DTW) alignment of each user's recent motion against every
+//! template, so gestures are found wherever they start and at any speed within a
+//! factor of two of the example.
+//!
+//! Joint positions are taken relative to the torso and divided by the torso-to-neck
+//! length, so templates transfer between users of different size and position.
+//! Distances are in those units: 0.1 is roughly 3 cm for an adult.
+//!
+//! For streams, wrap a skeleton stream and the recognizer in a
+//! [`ProcessedStream`](crate::nuitrack::async_api::processed_stream::ProcessedStream).
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tracing::{debug, trace};
+
+use crate::nuitrack_bridge::types::skeleton::ffi::JointType;
+
+use super::error::{NuitrackError, Result as NuitrackResult};
+use super::frame::SkeletonFrameData;
+use super::skeleton::Skeleton;
+
+/// The positions of a template's joints in one frame, normalized to the body.
+type Pose = Vec<[f32; 3]>;
+
+/// `skeleton`'s `joints` relative to the torso, in torso-to-neck lengths. `None` if any
+/// of them, the torso or the neck is untracked.
+fn normalized_pose(skeleton: &Skeleton, joints: &[JointType]) -> Option<Pose> {
+    let torso = skeleton.position(JointType::Torso)?;
+    let neck = skeleton.position(JointType::Neck)?;
+    let scale = ((neck.x - torso.x).powi(2) + (neck.y - torso.y).powi(2) + (neck.z - torso.z).powi(2)).sqrt();
+    if scale <= f32::EPSILON {
+        return None;
+    }
+    joints
+        .iter()
+        .map(|&joint| {
+            let p = skeleton.position(joint)?;
+            Some([(p.x - torso.x) / scale, (p.y - torso.y) / scale, (p.z - torso.z) / scale])
+        })
+        .collect()
+}
+
+/// Mean distance between corresponding joints. Both poses must have the same joints,
+/// which [`GestureTemplate::validate`] ensures for templates.
+fn pose_distance(a: &Pose, b: &Pose) -> f32 {
+    debug_assert_eq!(a.len(), b.len(), "poses of different joints");
+    let total: f32 = a
+        .iter()
+        .zip(b)
+        .map(|(p, q)| ((p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2) + (p[2] - q[2]).powi(2)).sqrt())
+        .sum();
+    total / a.len().max(1) as f32
+}
+
+/// An example of a gesture: a trajectory of normalized joint positions.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureTemplate {
+    /// The gesture this is an example of. Several templates may share a name.
+    pub name: String,
+    /// The joints the gesture is defined by, e.g. the right wrist and elbow.
+    pub joints: Vec<JointType>,
+    /// One pose per recorded frame, each with a position per joint.
+    pub poses: Vec<Vec<[f32; 3]>>,
+    /// Time from the first to the last recorded frame.
+    pub duration: Duration,
+}
+
+impl GestureTemplate {
+    /// Builds a template from timestamped skeletons (timestamps in microseconds).
+    /// Skeletons with any of `joints` untracked are skipped.
+    pub fn from_skeletons<'a>(
+        name: impl Into<String>,
+        joints: Vec<JointType>,
+        skeletons: impl IntoIterator<Item = (u64, &'a Skeleton)>,
+    ) -> NuitrackResult<Self> {
+        let mut recorder = GestureRecorder::new(joints);
+        for (timestamp, skeleton) in skeletons {
+            recorder.push(timestamp, skeleton);
+        }
+        recorder.finish(name)
+    }
+
+    /// Checks that the template can be matched: at least one joint, at least two
+    /// poses, and a position for every joint in each pose.
+    pub fn validate(&self) -> NuitrackResult<()> {
+        let name = &self.name;
+        if self.joints.is_empty() {
+            return Err(NuitrackError::OperationFailed(format!("Gesture '{name}' has no joints")));
+        }
+        if self.poses.len() < 2 {
+            return Err(NuitrackError::OperationFailed(format!(
+                "Gesture '{name}' needs at least 2 poses, got {}",
+                self.poses.len()
+            )));
+        }
+        if let Some(i) = self.poses.iter().position(|pose| pose.len() != self.joints.len()) {
+            return Err(NuitrackError::OperationFailed(format!(
+                "Gesture '{name}' pose {i} has {} positions for {} joints",
+                self.poses[i].len(),
+                self.joints.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Records one user's motion into a [`GestureTemplate`].
+#[derive(Debug, Clone)]
+pub struct GestureRecorder {
+    joints: Vec<JointType>,
+    poses: Vec<Pose>,
+    first: Option<u64>,
+    last: u64,
+}
+
+impl GestureRecorder {
+    pub fn new(joints: Vec<JointType>) -> Self {
+        Self { joints, poses: Vec::new(), first: None, last: 0 }
+    }
+
+    /// Adds `skeleton` observed at `timestamp` (in microseconds). Returns `false` if it
+    /// was skipped because a required joint is untracked.
+    pub fn push(&mut self, timestamp: u64, skeleton: &Skeleton) -> bool {
+        let Some(pose) = normalized_pose(skeleton, &self.joints) else {
+            return false;
+        };
+        self.first.get_or_insert(timestamp);
+        self.last = timestamp;
+        self.poses.push(pose);
+        true
+    }
+
+    /// Adds the skeleton of `user_id` from `frame`, if present.
+    pub fn push_frame(&mut self, frame: &impl SkeletonFrameData, user_id: i32) -> NuitrackResult<bool> {
+        let timestamp = frame.timestamp()?;
+        Ok(frame.skeletons()?.iter().find(|s| s.user_id == user_id).is_some_and(|s| self.push(timestamp, s)))
+    }
+
+    /// Number of frames recorded so far.
+    pub fn len(&self) -> usize {
+        self.poses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.poses.is_empty()
+    }
+
+    /// Discards the recording to start over.
+    pub fn clear(&mut self) {
+        self.poses.clear();
+        self.first = None;
+    }
+
+    /// The recorded motion as a template. Fails with fewer than two frames.
+    pub fn finish(self, name: impl Into<String>) -> NuitrackResult<GestureTemplate> {
+        let name = name.into();
+        if self.poses.len() < 2 {
+            return Err(NuitrackError::OperationFailed(format!(
+                "Gesture '{name}' needs at least 2 recorded frames, got {}",
+                self.poses.len()
+            )));
+        }
+        let duration = Duration::from_micros(self.last - self.first.unwrap_or(self.last));
+        Ok(GestureTemplate { name, joints: self.joints, poses: self.poses, duration })
+    }
+}
+
+/// A recognized custom gesture.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomGestureEvent {
+    pub user_id: i32,
+    /// Name of the matched template.
+    pub name: String,
+    /// 1 for a perfect match, falling to 0 at the recognizer's threshold.
+    pub confidence: f32,
+    /// Timestamp of the frame where the gesture started.
+    pub start_timestamp: u64,
+    /// Timestamp of the frame completing the gesture. The event itself is reported
+    /// with the following frame.
+    pub timestamp: u64,
+}
+
+/// Matching parameters of [`CustomGestureRecognizer`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CustomGestureConfig {
+    /// Largest alignment cost that still counts as a match: the summed pose distance
+    /// along the warping path, divided by the template's length.
+    pub threshold: f32,
+    /// Time after a match during which the same gesture is not reported again for
+    /// that user.
+    pub cooldown: Duration,
+}
+
+impl Default for CustomGestureConfig {
+    fn default() -> Self {
+        Self { threshold: 0.25, cooldown: Duration::from_millis(500) }
+    }
+}
+
+/// Running alignment of one user's motion against one template: the cost and start
+/// of the best warping path ending at each template frame.
+#[derive(Debug, Clone)]
+struct Alignment {
+    cost: Vec<f32>,
+    start: Vec<u64>,
+    /// The best match so far of a gesture that may still be improving.
+    candidate: Option<Candidate>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    cost: f32,
+    start: u64,
+    timestamp: u64,
+}
+
+impl Alignment {
+    fn new(template_len: usize) -> Self {
+        Self { cost: vec![f32::INFINITY; template_len], start: vec![0; template_len], candidate: None }
+    }
+
+    /// Advances by one observed pose. The path may start at any frame (subsequence
+    /// DTW), which is what lets gestures begin at any time.
+    fn step(&mut self, template: &[Pose], pose: &Pose, timestamp: u64) {
+        let mut diagonal = (0.0, timestamp);
+        let mut left = (f32::INFINITY, 0);
+        for (i, template_pose) in template.iter().enumerate() {
+            let up = (self.cost[i], self.start[i]);
+            let (cost, start) = [diagonal, up, left].into_iter().min_by(|a, b| a.0.total_cmp(&b.0)).expect("three candidates");
+            diagonal = up;
+            (self.cost[i], self.start[i]) = (cost + pose_distance(pose, template_pose), start);
+            left = (self.cost[i], self.start[i]);
+        }
+    }
+
+    /// Cost per template frame and start of the best path through the whole template.
+    fn end(&self) -> Option<(f32, u64)> {
+        let last = self.cost.len() - 1;
+        self.cost[last].is_finite().then(|| (self.cost[last] / self.cost.len() as f32, self.start[last]))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct UserState {
+    alignments: Vec<Alignment>,
+    /// Per gesture name, when it may be reported again.
+    cooldown_until: HashMap<String, u64>,
+}
+
+/// Matches users' motion against [`GestureTemplate`]s.
+#[derive(Debug, Clone, Default)]
+pub struct CustomGestureRecognizer {
+    config: CustomGestureConfig,
+    templates: Vec<GestureTemplate>,
+    users: HashMap<i32, UserState>,
+}
+
+impl CustomGestureRecognizer {
+    pub fn new(config: CustomGestureConfig) -> Self {
+        Self { config, templates: Vec::new(), users: HashMap::new() }
+    }
+
+    pub fn with_template(mut self, template: GestureTemplate) -> NuitrackResult<Self> {
+        self.add_template(template)?;
+        Ok(self)
+    }
+
+    pub fn config(&self) -> &CustomGestureConfig {
+        &self.config
+    }
+
+    pub fn templates(&self) -> &[GestureTemplate] {
+        &self.templates
+    }
+
+    /// Adds a template. Matching of every user restarts. Fails if the template does not
+    /// [`validate`](GestureTemplate::validate), e.g. one deserialized from a bad file.
+    pub fn add_template(&mut self, template: GestureTemplate) -> NuitrackResult<()> {
+        template.validate()?;
+        self.templates.push(template);
+        self.users.clear();
+        Ok(())
+    }
+
+    /// Removes every template named `name` and returns how many there were.
+    pub fn remove_templates(&mut self, name: &str) -> usize {
+        let before = self.templates.len();
+        self.templates.retain(|t| t.name != name);
+        self.users.clear();
+        before - self.templates.len()
+    }
+
+    /// Forgets every user's motion.
+    pub fn reset(&mut self) {
+        self.users.clear();
+    }
+
+    /// Feeds `skeletons` observed at `timestamp` (in microseconds) and returns the
+    /// completed gestures, at most one per name and user. A gesture is reported on the
+    /// first frame that does not improve its match, i.e. usually one frame after it
+    /// ends. Users missing from `skeletons` are forgotten.
+    pub fn apply(&mut self, timestamp: u64, skeletons: &[Skeleton]) -> Vec<CustomGestureEvent> {
+        self.users.retain(|user_id, _| skeletons.iter().any(|s| s.user_id == *user_id));
+        let mut events = Vec::new();
+        for skeleton in skeletons {
+            let state = self.users.entry(skeleton.user_id).or_insert_with(|| UserState {
+                alignments: self.templates.iter().map(|t| Alignment::new(t.poses.len())).collect(),
+                cooldown_until: HashMap::new(),
+            });
+            let mut best: HashMap<&str, CustomGestureEvent> = HashMap::new();
+            for (template, alignment) in self.templates.iter().zip(&mut state.alignments) {
+                let Some(pose) = normalized_pose(skeleton, &template.joints) else {
+                    continue;
+                };
+                alignment.step(&template.poses, &pose, timestamp);
+                let expected = template.duration.as_micros() as u64;
+                let current = alignment.end().filter(|&(cost, start)| {
+                    let elapsed = timestamp.saturating_sub(start);
+                    cost <= self.config.threshold && elapsed >= expected / 2 && elapsed <= expected * 2
+                });
+                // Hold a match while it keeps improving, and report it once it stops.
+                let candidate = match (current, alignment.candidate) {
+                    (Some((cost, start)), candidate) if candidate.is_none_or(|c| cost < c.cost) => {
+                        trace!(user_id = skeleton.user_id, name = template.name, cost, "Custom gesture candidate.");
+                        alignment.candidate = Some(Candidate { cost, start, timestamp });
+                        continue;
+                    }
+                    (_, Some(candidate)) => candidate,
+                    (_, None) => continue,
+                };
+                alignment.candidate = None;
+                let confidence = 1.0 - candidate.cost / self.config.threshold;
+                if best.get(template.name.as_str()).is_none_or(|e| confidence > e.confidence) {
+                    best.insert(
+                        &template.name,
+                        CustomGestureEvent {
+                            user_id: skeleton.user_id,
+                            name: template.name.clone(),
+                            confidence,
+                            start_timestamp: candidate.start,
+                            timestamp: candidate.timestamp,
+                        },
+                    );
+                }
+            }
+            let mut completed: Vec<_> = best
+                .into_values()
+                .filter(|event| state.cooldown_until.get(&event.name).is_none_or(|&until| timestamp >= until))
+                .collect();
+            completed.sort_by(|a, b| a.name.cmp(&b.name));
+            for event in &completed {
+                debug!(user_id = event.user_id, name = event.name, confidence = event.confidence, "Custom gesture recognized.");
+                state.cooldown_until.insert(event.name.clone(), timestamp + self.config.cooldown.as_micros() as u64);
+                // Restart the alignments of this gesture so the same motion is not matched twice.
+                for (template, alignment) in self.templates.iter().zip(&mut state.alignments) {
+                    if template.name == event.name {
+                        *alignment = Alignment::new(template.poses.len());
+                    }
+                }
+            }
+            events.extend(completed);
+        }
+        events
+    }
+
+    /// [`apply`](Self::apply) to every skeleton of `frame`.
+    pub fn process_frame(&mut self, frame: &impl SkeletonFrameData) -> NuitrackResult<Vec<CustomGestureEvent>> {
+        Ok(self.apply(frame.timestamp()?, frame.skeletons()?))
+    }
+}
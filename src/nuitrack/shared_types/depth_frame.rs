@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use cxx::SharedPtr;
 use crate::{
     nuitrack::shared_types::error::Result as NuitrackResult,
@@ -5,8 +6,38 @@ use crate::{
 };
 use tracing::{instrument, trace, trace_span};
 
+use super::frame::{DepthFrameData, Frame};
+
+/// A depth map produced by the depth sensor.
+///
+/// Cloning a `DepthFrame` is cheap: the underlying buffer is shared, not copied.
+#[derive(Clone)]
 pub struct DepthFrame {
-    internal_ptr: SharedPtr<depth_frame_ffi::DepthFrame>,
+    inner: DepthFrameInner,
+}
+
+/// Where the frame's data lives: in SDK memory or in a Rust-side buffer.
+#[derive(Clone)]
+enum DepthFrameInner {
+    Ffi(SharedPtr<depth_frame_ffi::DepthFrame>),
+    Owned(Arc<OwnedDepthFrame>),
+}
+
+/// A depth map held entirely in Rust memory.
+///
+/// Unlike [`DepthFrame`], it does not keep any SDK buffer alive, so it can be
+/// constructed freely, stored indefinitely and sent anywhere.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedDepthFrame {
+    pub rows: i32,
+    pub cols: i32,
+    pub id: u64,
+    /// Typically in microseconds.
+    pub timestamp: u64,
+    /// `rows * cols` depth values in millimeters, row-major.
+    pub data: Vec<u16>,
 }
 
 impl DepthFrame {
@@ -22,19 +53,45 @@ impl DepthFrame {
             None
         } else {
             Some(DepthFrame {
-                internal_ptr: ffi_ptr,
+                inner: DepthFrameInner::Ffi(ffi_ptr),
             })
         }
     }
 
+    /// Creates a `DepthFrame` backed by a Rust-side buffer instead of SDK memory.
+    ///
+    /// This is how pure-Rust backends (such as the simulated backend) produce frames.
+    /// `data` is expected to hold `rows * cols` depth values in row-major order.
+    pub fn synthetic(rows: i32, cols: i32, id: u64, timestamp: u64, data: Vec<u16>) -> Self {
+        OwnedDepthFrame { rows, cols, id, timestamp, data }.into()
+    }
+
+    /// Copies the frame into an [`OwnedDepthFrame`], releasing any tie to SDK memory.
+    #[instrument(skip(self))]
+    pub fn to_owned(&self) -> NuitrackResult<OwnedDepthFrame> {
+        match &self.inner {
+            DepthFrameInner::Ffi(_) => Ok(OwnedDepthFrame {
+                rows: self.rows()?,
+                cols: self.cols()?,
+                id: self.frame_id()?,
+                timestamp: self.timestamp()?,
+                data: self.data()?.to_vec(),
+            }),
+            DepthFrameInner::Owned(frame) => Ok(OwnedDepthFrame::clone(frame)),
+        }
+    }
+
     /// Gets the number of rows (height) of the depth frame.
     ///
     /// # Returns
     /// A `NuitrackResult` containing the number of rows as an `i32`.
     #[instrument(skip(self))]
     pub fn rows(&self) -> NuitrackResult<i32> {
-        trace_span!("ffi", function = "depth_frame_ffi::rows")
-            .in_scope(|| Ok(depth_frame_ffi::rows(&self.internal_ptr)?))
+        match &self.inner {
+            DepthFrameInner::Ffi(ptr) => trace_span!("ffi", function = "depth_frame_ffi::rows")
+                .in_scope(|| Ok(depth_frame_ffi::rows(ptr)?)),
+            DepthFrameInner::Owned(frame) => Ok(frame.rows),
+        }
     }
 
     /// Gets the number of columns (width) of the depth frame.
@@ -43,8 +100,11 @@ impl DepthFrame {
     /// A `NuitrackResult` containing the number of columns as an `i32`.
     #[instrument(skip(self))]
     pub fn cols(&self) -> NuitrackResult<i32> {
-        trace_span!("ffi", function = "depth_frame_ffi::cols")
-            .in_scope(|| Ok(depth_frame_ffi::cols(&self.internal_ptr)?))
+        match &self.inner {
+            DepthFrameInner::Ffi(ptr) => trace_span!("ffi", function = "depth_frame_ffi::cols")
+                .in_scope(|| Ok(depth_frame_ffi::cols(ptr)?)),
+            DepthFrameInner::Owned(frame) => Ok(frame.cols),
+        }
     }
 
     /// Gets the unique ID of the depth frame.
@@ -53,8 +113,11 @@ impl DepthFrame {
     /// A `NuitrackResult` containing the frame ID as a `u64`.
     #[instrument(skip(self))]
     pub fn frame_id(&self) -> NuitrackResult<u64> {
-        trace_span!("ffi", function = "depth_frame_ffi::id")
-            .in_scope(|| Ok(depth_frame_ffi::id(&self.internal_ptr)?))
+        match &self.inner {
+            DepthFrameInner::Ffi(ptr) => trace_span!("ffi", function = "depth_frame_ffi::id")
+                .in_scope(|| Ok(depth_frame_ffi::id(ptr)?)),
+            DepthFrameInner::Owned(frame) => Ok(frame.id),
+        }
     }
 
     /// Gets the timestamp of the depth frame, typically in microseconds.
@@ -64,8 +127,11 @@ impl DepthFrame {
     /// A `NuitrackResult` containing the timestamp as a `u64`.
     #[instrument(skip(self))]
     pub fn timestamp(&self) -> NuitrackResult<u64> {
-        trace_span!("ffi", function = "depth_frame_ffi::timestamp")
-            .in_scope(|| Ok(depth_frame_ffi::timestamp(&self.internal_ptr)?))
+        match &self.inner {
+            DepthFrameInner::Ffi(ptr) => trace_span!("ffi", function = "depth_frame_ffi::timestamp")
+                .in_scope(|| Ok(depth_frame_ffi::timestamp(ptr)?)),
+            DepthFrameInner::Owned(frame) => Ok(frame.timestamp),
+        }
     }
 
     /// Gets a slice representing the pixel data of the depth frame.
@@ -78,16 +144,89 @@ impl DepthFrame {
     /// A `NuitrackResult` containing a slice of `u16` depth values.
     #[instrument(skip(self))]
     pub fn data(&self) -> NuitrackResult<&[u16]> {
-        trace_span!("ffi", function = "depth_frame_ffi::data")
-            .in_scope(|| Ok(depth_frame_ffi::data(&self.internal_ptr)?))
+        match &self.inner {
+            DepthFrameInner::Ffi(ptr) => trace_span!("ffi", function = "depth_frame_ffi::data")
+                .in_scope(|| Ok(depth_frame_ffi::data(ptr)?)),
+            DepthFrameInner::Owned(frame) => Ok(&frame.data),
+        }
     }
 
     /// Provides access to the internal `SharedPtr` to the FFI `DepthFrame` type.
     ///
+    /// Returns `None` for frames that were not produced by the Nuitrack SDK.
     /// This is `pub(crate)` and primarily intended for internal use or advanced scenarios
     /// where direct access to the FFI pointer is necessary.
     #[allow(dead_code)]
-    pub(crate) fn ffi_ptr(&self) -> &SharedPtr<depth_frame_ffi::DepthFrame> {
-        &self.internal_ptr
+    pub(crate) fn ffi_ptr(&self) -> Option<&SharedPtr<depth_frame_ffi::DepthFrame>> {
+        match &self.inner {
+            DepthFrameInner::Ffi(ptr) => Some(ptr),
+            DepthFrameInner::Owned(_) => None,
+        }
+    }
+}
+
+impl From<OwnedDepthFrame> for DepthFrame {
+    fn from(frame: OwnedDepthFrame) -> Self {
+        DepthFrame { inner: DepthFrameInner::Owned(Arc::new(frame)) }
+    }
+}
+
+impl Frame for DepthFrame {
+    type Owned = OwnedDepthFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        DepthFrame::timestamp(self)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedDepthFrame> {
+        DepthFrame::to_owned(self)
+    }
+}
+
+impl DepthFrameData for DepthFrame {
+    fn rows(&self) -> NuitrackResult<i32> {
+        DepthFrame::rows(self)
+    }
+
+    fn cols(&self) -> NuitrackResult<i32> {
+        DepthFrame::cols(self)
+    }
+
+    fn frame_id(&self) -> NuitrackResult<u64> {
+        DepthFrame::frame_id(self)
+    }
+
+    fn data(&self) -> NuitrackResult<&[u16]> {
+        DepthFrame::data(self)
+    }
+}
+
+impl Frame for OwnedDepthFrame {
+    type Owned = OwnedDepthFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        Ok(self.timestamp)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedDepthFrame> {
+        Ok(self.clone())
     }
-}
\ No newline at end of file
+}
+
+impl DepthFrameData for OwnedDepthFrame {
+    fn rows(&self) -> NuitrackResult<i32> {
+        Ok(self.rows)
+    }
+
+    fn cols(&self) -> NuitrackResult<i32> {
+        Ok(self.cols)
+    }
+
+    fn frame_id(&self) -> NuitrackResult<u64> {
+        Ok(self.id)
+    }
+
+    fn data(&self) -> NuitrackResult<&[u16]> {
+        Ok(&self.data)
+    }
+}
@@ -17,6 +17,9 @@ pub enum NuitrackError {
     #[error("Nuitrack device error: {0}")]
     DeviceError(String),
 
+    #[error("Nuitrack device activation failed: {0}")]
+    ActivationFailed(String),
+
     #[error("Nuitrack module creation failed: {0}")] // Specifically for creating modules like HandTracker
     ModuleCreationFailed(String),
 
@@ -25,6 +28,15 @@ pub enum NuitrackError {
 
     #[error("Nuitrack operation failed: {0}")]
     OperationFailed(String),
+
+    #[error("Nuitrack backend error: {0}")]
+    Backend(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid recording: {0}")]
+    InvalidRecording(String),
     // Add more specific Nuitrack errors as you identify them
 }
 
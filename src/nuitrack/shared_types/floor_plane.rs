@@ -0,0 +1,187 @@
+//! The floor plane detected by the user tracker.
+//!
+//! Nuitrack reports the floor as a point and a normal in each user frame. [`FloorPlane`]
+//! turns them into a plane with distance and projection queries, and
+//! [`FloorPlaneSmoother`] filters the per-frame estimates, which jitter noticeably.
+//!
+//! Distances are in millimeters and positive above the floor.
+
+use crate::nuitrack_bridge::types::skeleton::ffi::{Joint, JointType};
+use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+
+use super::error::Result as NuitrackResult;
+use super::frame::UserFrameData;
+use super::skeleton::Skeleton;
+use super::user_frame::UserFrame;
+
+fn dot(a: Vector3, b: Vector3) -> f32 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+/// A plane in real-world coordinates with a unit normal pointing up, away from the floor.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloorPlane {
+    pub normal: Vector3,
+    /// Signed distance from the plane to the sensor, i.e. `-dot(normal, point)` for any
+    /// point on the plane.
+    pub offset: f32,
+}
+
+impl FloorPlane {
+    /// The plane through `point` with the given normal, flipped to point up if it points
+    /// down. Returns `None` if `normal` is zero, which is how the SDK reports that no
+    /// floor was detected.
+    pub fn new(point: Vector3, normal: Vector3) -> Option<Self> {
+        let length = dot(normal, normal).sqrt();
+        if !length.is_finite() || length < 1e-6 {
+            return None;
+        }
+        let length = if normal.y < 0.0 { -length } else { length };
+        let normal = Vector3 { x: normal.x / length, y: normal.y / length, z: normal.z / length };
+        Some(Self { normal, offset: -dot(normal, point) })
+    }
+
+    /// The floor of `frame`, or `None` if none was detected.
+    pub fn from_frame(frame: &impl UserFrameData) -> NuitrackResult<Option<Self>> {
+        Ok(Self::new(frame.floor()?, frame.floor_normal()?))
+    }
+
+    /// Height of `point` above the floor; negative below it.
+    pub fn signed_distance(&self, point: Vector3) -> f32 {
+        dot(self.normal, point) + self.offset
+    }
+
+    /// The point on the floor directly below (or above) `point`.
+    pub fn project(&self, point: Vector3) -> Vector3 {
+        let d = self.signed_distance(point);
+        Vector3 { x: point.x - d * self.normal.x, y: point.y - d * self.normal.y, z: point.z - d * self.normal.z }
+    }
+
+    /// A point on the plane: the projection of the sensor's position.
+    pub fn origin(&self) -> Vector3 {
+        self.project(Vector3 { x: 0.0, y: 0.0, z: 0.0 })
+    }
+
+    /// Height of the sensor above the floor.
+    pub fn sensor_height(&self) -> f32 {
+        self.offset
+    }
+
+    /// Angle in radians between the floor normal and the sensor's `y` axis.
+    pub fn tilt(&self) -> f32 {
+        self.normal.y.clamp(-1.0, 1.0).acos()
+    }
+
+    /// Rotation of the sensor about its `x` axis in radians; positive when it looks down.
+    pub fn pitch(&self) -> f32 {
+        (-self.normal.z).atan2(self.normal.y)
+    }
+
+    /// Rotation of the sensor about its `z` axis in radians; positive when the floor
+    /// normal leans towards the sensor's `+x`.
+    pub fn roll(&self) -> f32 {
+        self.normal.x.atan2(self.normal.y)
+    }
+
+    /// Height of `joint` above the floor.
+    pub fn joint_height(&self, joint: &Joint) -> f32 {
+        self.signed_distance(joint.real)
+    }
+
+    /// Height of the lower ankle above the floor, or `None` if neither ankle is tracked.
+    ///
+    /// Nuitrack does not track the feet, so the ankles are the lowest reliable joints;
+    /// expect a standing user to report roughly the ankle height (50 to 100 mm).
+    pub fn feet_height(&self, skeleton: &Skeleton) -> Option<f32> {
+        skeleton
+            .joints
+            .iter()
+            .filter(|j| matches!(j.joint_type, JointType::LeftAnkle | JointType::RightAnkle) && j.confidence > 0.0)
+            .map(|j| self.joint_height(j))
+            .min_by(f32::total_cmp)
+    }
+
+    /// Whether the lower ankle is within `tolerance` millimeters of the floor.
+    pub fn has_foot_contact(&self, skeleton: &Skeleton, tolerance: f32) -> bool {
+        self.feet_height(skeleton).is_some_and(|h| h <= tolerance)
+    }
+}
+
+/// Exponential smoothing of per-frame floor estimates.
+///
+/// Frames without a floor keep the current estimate. Estimates whose normal differs
+/// by more than `max_jump` from the current one are treated as a new floor (e.g. after
+/// the sensor was moved) and replace the estimate outright.
+#[derive(Debug, Clone)]
+pub struct FloorPlaneSmoother {
+    alpha: f32,
+    max_jump: f32,
+    current: Option<FloorPlane>,
+}
+
+impl Default for FloorPlaneSmoother {
+    fn default() -> Self {
+        Self::new(0.1)
+    }
+}
+
+impl FloorPlaneSmoother {
+    /// `alpha` is the weight of each new estimate, in `0..=1`.
+    pub fn new(alpha: f32) -> Self {
+        Self { alpha: alpha.clamp(0.0, 1.0), max_jump: 15f32.to_radians(), current: None }
+    }
+
+    /// Angle in radians above which an estimate resets the filter.
+    pub fn with_max_jump(mut self, max_jump: f32) -> Self {
+        self.max_jump = max_jump;
+        self
+    }
+
+    /// Feeds one estimate and returns the smoothed floor.
+    pub fn update(&mut self, estimate: Option<FloorPlane>) -> Option<FloorPlane> {
+        let Some(estimate) = estimate else {
+            return self.current;
+        };
+        let smoothed = match self.current {
+            Some(current) if dot(current.normal, estimate.normal).clamp(-1.0, 1.0).acos() <= self.max_jump => {
+                let a = self.alpha;
+                let blend = |c: f32, e: f32| c + a * (e - c);
+                let normal = Vector3 {
+                    x: blend(current.normal.x, estimate.normal.x),
+                    y: blend(current.normal.y, estimate.normal.y),
+                    z: blend(current.normal.z, estimate.normal.z),
+                };
+                let length = dot(normal, normal).sqrt();
+                FloorPlane {
+                    normal: Vector3 { x: normal.x / length, y: normal.y / length, z: normal.z / length },
+                    offset: blend(current.offset, estimate.offset),
+                }
+            }
+            _ => estimate,
+        };
+        self.current = Some(smoothed);
+        self.current
+    }
+
+    /// Convenience for [`update`](Self::update) with a user frame's floor.
+    pub fn update_from_frame(&mut self, frame: &impl UserFrameData) -> NuitrackResult<Option<FloorPlane>> {
+        Ok(self.update(FloorPlane::from_frame(frame)?))
+    }
+
+    pub fn current(&self) -> Option<FloorPlane> {
+        self.current
+    }
+
+    pub fn reset(&mut self) {
+        self.current = None;
+    }
+}
+
+impl UserFrame {
+    /// The detected floor, or `None` if the SDK has not found one.
+    pub fn floor_plane(&self) -> NuitrackResult<Option<FloorPlane>> {
+        FloorPlane::from_frame(self)
+    }
+}
@@ -0,0 +1,60 @@
+//! Traits shared by SDK-backed frames and their owned counterparts.
+//!
+//! Every frame type (e.g. [`DepthFrame`](super::depth_frame::DepthFrame)) has an
+//! owned twin (e.g. [`OwnedDepthFrame`](super::depth_frame::OwnedDepthFrame)) that
+//! holds plain Rust data and no reference into SDK memory. Code that only reads
+//! frames can accept either by taking `impl DepthFrameData` instead of a concrete type.
+
+use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+
+use super::error::Result as NuitrackResult;
+use super::hand::UserHands;
+use super::rgb_frame::Color3;
+use super::skeleton::Skeleton;
+use super::user::User;
+
+/// Functionality common to every frame type.
+pub trait Frame {
+    /// The owned, SDK-independent representation of this frame.
+    type Owned: Frame<Owned = Self::Owned> + Clone + Send + Sync + 'static;
+
+    /// Timestamp of the frame, typically in microseconds.
+    fn timestamp(&self) -> NuitrackResult<u64>;
+
+    /// Deep-copies the frame into its owned representation.
+    fn to_owned_frame(&self) -> NuitrackResult<Self::Owned>;
+}
+
+pub trait DepthFrameData: Frame {
+    fn rows(&self) -> NuitrackResult<i32>;
+    fn cols(&self) -> NuitrackResult<i32>;
+    fn frame_id(&self) -> NuitrackResult<u64>;
+    /// `rows * cols` depth values in millimeters, row-major.
+    fn data(&self) -> NuitrackResult<&[u16]>;
+}
+
+pub trait ColorFrameData: Frame {
+    fn rows(&self) -> NuitrackResult<i32>;
+    fn cols(&self) -> NuitrackResult<i32>;
+    fn frame_id(&self) -> NuitrackResult<u64>;
+    /// `rows * cols` pixels, row-major.
+    fn data(&self) -> NuitrackResult<&[Color3]>;
+}
+
+pub trait UserFrameData: Frame {
+    fn rows(&self) -> NuitrackResult<i32>;
+    fn cols(&self) -> NuitrackResult<i32>;
+    /// `rows * cols` user IDs, row-major; 0 is the background.
+    fn data(&self) -> NuitrackResult<&[u16]>;
+    fn users(&self) -> NuitrackResult<&[User]>;
+    fn floor(&self) -> NuitrackResult<Vector3>;
+    fn floor_normal(&self) -> NuitrackResult<Vector3>;
+}
+
+pub trait SkeletonFrameData: Frame {
+    fn skeletons(&self) -> NuitrackResult<&[Skeleton]>;
+}
+
+pub trait HandFrameData: Frame {
+    fn users_hands(&self) -> NuitrackResult<&[UserHands]>;
+}
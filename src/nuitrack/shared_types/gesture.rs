@@ -5,7 +5,9 @@ pub use crate::nuitrack_bridge::types::gesture::ffi::{
     Gesture, GestureState, GestureType, UserState, UserStateType,
 };
 
-#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
 pub struct UserGestures {
     pub user_id: i32,
     pub user_state: UserStateType,
@@ -1,4 +1,4 @@
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 use tracing::{debug, instrument, trace, trace_span, warn};
 use cxx::SharedPtr;
 
@@ -6,16 +6,33 @@ use crate::nuitrack::shared_types::error::NuitrackError;
 use crate::nuitrack::shared_types::gesture::{UserGestures, UserState};
 use crate::nuitrack_bridge::types::gesture_data::ffi::{self as gesture_data_ffi, GestureData as FFIGestureData, UserStateData as FFIUserStateData, UserGesturesStateData as FFIUserGesturesStateData};
 use super::error::Result as NuitrackResult;
+use super::frame::Frame;
 use super::gesture::Gesture;
 
 /// A high-level wrapper for a Nuitrack gesture frame.
 ///
 /// This frame contains a list of gestures that have been completed at a specific moment.
 pub struct GestureFrame {
-    internal_ptr: SharedPtr<FFIGestureData>,
+    inner: GestureFrameInner,
     gestures_cache: OnceLock<NuitrackResult<Vec<Gesture>>>,
 }
 
+#[derive(Clone)]
+enum GestureFrameInner {
+    Ffi(SharedPtr<FFIGestureData>),
+    Owned(Arc<OwnedGestureFrame>),
+}
+
+/// Completed gestures of one frame held entirely in Rust memory.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedGestureFrame {
+    /// Typically in microseconds.
+    pub timestamp: u64,
+    pub gestures: Vec<Gesture>,
+}
+
 impl GestureFrame {
     /// Creates a new `GestureFrame` from a CXX shared pointer. Returns `None` if the pointer is null.
     pub(crate) fn new(ffi_ptr: SharedPtr<FFIGestureData>) -> Option<Self> {
@@ -24,22 +41,43 @@ impl GestureFrame {
             None
         } else {
             Some(GestureFrame {
-                internal_ptr: ffi_ptr,
+                inner: GestureFrameInner::Ffi(ffi_ptr),
                 gestures_cache: OnceLock::new(),
             })
         }
     }
 
+    /// Creates a `GestureFrame` from gestures held in Rust memory instead of SDK memory.
+    pub fn synthetic(timestamp: u64, gestures: Vec<Gesture>) -> Self {
+        OwnedGestureFrame { timestamp, gestures }.into()
+    }
+
+    /// Copies the frame into an [`OwnedGestureFrame`], releasing any tie to SDK memory.
+    #[instrument(skip(self))]
+    pub fn to_owned(&self) -> NuitrackResult<OwnedGestureFrame> {
+        match &self.inner {
+            GestureFrameInner::Ffi(_) => Ok(OwnedGestureFrame {
+                timestamp: self.timestamp()?,
+                gestures: self.gestures()?.to_vec(),
+            }),
+            GestureFrameInner::Owned(frame) => Ok(OwnedGestureFrame::clone(frame)),
+        }
+    }
+
     /// Gets the list of completed gestures in this frame.
     ///
     /// This operation is cached after the first call.
     #[instrument(skip(self))]
     pub fn gestures(&self) -> NuitrackResult<&[Gesture]> {
+        let ffi_ptr = match &self.inner {
+            GestureFrameInner::Ffi(ptr) => ptr,
+            GestureFrameInner::Owned(frame) => return Ok(frame.gestures.as_slice()),
+        };
         let cached_result = self.gestures_cache.get_or_init(|| {
             debug!("Populating GestureFrame cache: converting FFI Gestures to Rust.");
             
             let owned_vec = trace_span!("ffi", function = "gesture_data_ffi::gesture_data_gestures")
-                .in_scope(|| gesture_data_ffi::gesture_data_gestures(&self.internal_ptr))?;
+                .in_scope(|| gesture_data_ffi::gesture_data_gestures(ffi_ptr))?;
 
             Ok(owned_vec)
         });
@@ -59,16 +97,58 @@ impl GestureFrame {
     /// Gets the number of completed gestures in this frame.
     #[instrument(skip(self))]
     pub fn num_gestures(&self) -> NuitrackResult<i32> {
-        trace_span!("ffi", function = "gesture_data_ffi::gesture_data_num_gestures").in_scope(|| 
-            Ok(gesture_data_ffi::gesture_data_num_gestures(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            GestureFrameInner::Ffi(ptr) => trace_span!("ffi", function = "gesture_data_ffi::gesture_data_num_gestures").in_scope(|| 
+                Ok(gesture_data_ffi::gesture_data_num_gestures(ptr)?)
+            ),
+            GestureFrameInner::Owned(frame) => Ok(frame.gestures.len() as i32),
+        }
     }
     
     /// Gets the timestamp of the frame in microseconds.
     #[instrument(skip(self))]
     pub fn timestamp(&self) -> NuitrackResult<u64> {
-        trace_span!("ffi", function = "gesture_data_ffi::gesture_data_timestamp")
-            .in_scope(|| Ok(gesture_data_ffi::gesture_data_timestamp(&self.internal_ptr)?))
+        match &self.inner {
+            GestureFrameInner::Ffi(ptr) => trace_span!("ffi", function = "gesture_data_ffi::gesture_data_timestamp")
+                .in_scope(|| Ok(gesture_data_ffi::gesture_data_timestamp(ptr)?)),
+            GestureFrameInner::Owned(frame) => Ok(frame.timestamp),
+        }
+    }
+}
+
+impl Clone for GestureFrame {
+    fn clone(&self) -> Self {
+        GestureFrame { inner: self.inner.clone(), gestures_cache: OnceLock::new() }
+    }
+}
+
+impl From<OwnedGestureFrame> for GestureFrame {
+    fn from(frame: OwnedGestureFrame) -> Self {
+        GestureFrame { inner: GestureFrameInner::Owned(Arc::new(frame)), gestures_cache: OnceLock::new() }
+    }
+}
+
+impl Frame for GestureFrame {
+    type Owned = OwnedGestureFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        GestureFrame::timestamp(self)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedGestureFrame> {
+        GestureFrame::to_owned(self)
+    }
+}
+
+impl Frame for OwnedGestureFrame {
+    type Owned = OwnedGestureFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        Ok(self.timestamp)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedGestureFrame> {
+        Ok(self.clone())
     }
 }
 
@@ -77,10 +157,26 @@ impl GestureFrame {
 ///
 /// This frame contains updates about users changing their state (e.g., appearing, becoming active).
 pub struct UserStateFrame {
-    internal_ptr: SharedPtr<FFIUserStateData>,
+    inner: UserStateFrameInner,
     user_states_cache: OnceLock<NuitrackResult<Vec<UserState>>>,
 }
 
+#[derive(Clone)]
+enum UserStateFrameInner {
+    Ffi(SharedPtr<FFIUserStateData>),
+    Owned(Arc<OwnedUserStateFrame>),
+}
+
+/// User state changes of one frame held entirely in Rust memory.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedUserStateFrame {
+    /// Typically in microseconds.
+    pub timestamp: u64,
+    pub user_states: Vec<UserState>,
+}
+
 impl UserStateFrame {
     pub(crate) fn new(ffi_ptr: SharedPtr<FFIUserStateData>) -> Option<Self> {
         if ffi_ptr.is_null() {
@@ -88,20 +184,41 @@ impl UserStateFrame {
             None
         } else {
             Some(UserStateFrame {
-                internal_ptr: ffi_ptr,
+                inner: UserStateFrameInner::Ffi(ffi_ptr),
                 user_states_cache: OnceLock::new(),
             })
         }
     }
 
+    /// Creates a `UserStateFrame` from state changes held in Rust memory instead of SDK memory.
+    pub fn synthetic(timestamp: u64, user_states: Vec<UserState>) -> Self {
+        OwnedUserStateFrame { timestamp, user_states }.into()
+    }
+
+    /// Copies the frame into an [`OwnedUserStateFrame`], releasing any tie to SDK memory.
+    #[instrument(skip(self))]
+    pub fn to_owned(&self) -> NuitrackResult<OwnedUserStateFrame> {
+        match &self.inner {
+            UserStateFrameInner::Ffi(_) => Ok(OwnedUserStateFrame {
+                timestamp: self.timestamp()?,
+                user_states: self.user_states()?.to_vec(),
+            }),
+            UserStateFrameInner::Owned(frame) => Ok(OwnedUserStateFrame::clone(frame)),
+        }
+    }
+
     /// Gets the list of user state changes in this frame.
     #[instrument(skip(self))]
     pub fn user_states(&self) -> NuitrackResult<&[UserState]> {
+        let ffi_ptr = match &self.inner {
+            UserStateFrameInner::Ffi(ptr) => ptr,
+            UserStateFrameInner::Owned(frame) => return Ok(frame.user_states.as_slice()),
+        };
         let cached_result = self.user_states_cache.get_or_init(|| {
             debug!("Populating UserStateFrame cache: converting FFI UserStates to Rust.");
             
             let owned_vec = trace_span!("ffi", function = "gesture_data_ffi::user_state_data_user_states")
-                .in_scope(|| gesture_data_ffi::user_state_data_user_states(&self.internal_ptr))?;
+                .in_scope(|| gesture_data_ffi::user_state_data_user_states(ffi_ptr))?;
 
             Ok(owned_vec)
         });
@@ -120,18 +237,73 @@ impl UserStateFrame {
 
     #[instrument(skip(self))]
     pub fn timestamp(&self) -> NuitrackResult<u64> {
-        trace_span!("ffi", function = "gesture_data_ffi::user_state_data_timestamp")
-            .in_scope(|| Ok(gesture_data_ffi::user_state_data_timestamp(&self.internal_ptr)?))
+        match &self.inner {
+            UserStateFrameInner::Ffi(ptr) => trace_span!("ffi", function = "gesture_data_ffi::user_state_data_timestamp")
+                .in_scope(|| Ok(gesture_data_ffi::user_state_data_timestamp(ptr)?)),
+            UserStateFrameInner::Owned(frame) => Ok(frame.timestamp),
+        }
+    }
+}
+
+impl Clone for UserStateFrame {
+    fn clone(&self) -> Self {
+        UserStateFrame { inner: self.inner.clone(), user_states_cache: OnceLock::new() }
+    }
+}
+
+impl From<OwnedUserStateFrame> for UserStateFrame {
+    fn from(frame: OwnedUserStateFrame) -> Self {
+        UserStateFrame { inner: UserStateFrameInner::Owned(Arc::new(frame)), user_states_cache: OnceLock::new() }
+    }
+}
+
+impl Frame for UserStateFrame {
+    type Owned = OwnedUserStateFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        UserStateFrame::timestamp(self)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedUserStateFrame> {
+        UserStateFrame::to_owned(self)
+    }
+}
+
+impl Frame for OwnedUserStateFrame {
+    type Owned = OwnedUserStateFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        Ok(self.timestamp)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedUserStateFrame> {
+        Ok(self.clone())
     }
 }
 
 
 /// A frame containing the gesture progress state for all tracked users.
 pub struct UserGesturesFrame {
-    internal_ptr: SharedPtr<FFIUserGesturesStateData>,
+    inner: UserGesturesFrameInner,
     users_cache: OnceLock<NuitrackResult<Vec<UserGestures>>>,
 }
 
+#[derive(Clone)]
+enum UserGesturesFrameInner {
+    Ffi(SharedPtr<FFIUserGesturesStateData>),
+    Owned(Arc<OwnedUserGesturesFrame>),
+}
+
+/// Gesture progress of every user of one frame held entirely in Rust memory.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedUserGesturesFrame {
+    /// Typically in microseconds.
+    pub timestamp: u64,
+    pub users: Vec<UserGestures>,
+}
+
 impl UserGesturesFrame {
     pub(crate) fn new(ffi_ptr: SharedPtr<FFIUserGesturesStateData>) -> Option<Self> {
         if ffi_ptr.is_null() {
@@ -139,20 +311,41 @@ impl UserGesturesFrame {
             None
         } else {
             Some(UserGesturesFrame {
-                internal_ptr: ffi_ptr,
+                inner: UserGesturesFrameInner::Ffi(ffi_ptr),
                 users_cache: OnceLock::new(),
             })
         }
     }
 
+    /// Creates a `UserGesturesFrame` from gesture states held in Rust memory instead of SDK memory.
+    pub fn synthetic(timestamp: u64, users: Vec<UserGestures>) -> Self {
+        OwnedUserGesturesFrame { timestamp, users }.into()
+    }
+
+    /// Copies the frame into an [`OwnedUserGesturesFrame`], releasing any tie to SDK memory.
+    #[instrument(skip(self))]
+    pub fn to_owned(&self) -> NuitrackResult<OwnedUserGesturesFrame> {
+        match &self.inner {
+            UserGesturesFrameInner::Ffi(_) => Ok(OwnedUserGesturesFrame {
+                timestamp: self.timestamp()?,
+                users: self.users()?.to_vec(),
+            }),
+            UserGesturesFrameInner::Owned(frame) => Ok(OwnedUserGesturesFrame::clone(frame)),
+        }
+    }
+
     /// Gets the list of user gesture states in this frame.
     #[instrument(skip(self))]
     pub fn users(&self) -> NuitrackResult<&[UserGestures]> {
+        let ffi_ptr = match &self.inner {
+            UserGesturesFrameInner::Ffi(ptr) => ptr,
+            UserGesturesFrameInner::Owned(frame) => return Ok(frame.users.as_slice()),
+        };
         let cached_result = self.users_cache.get_or_init(|| {
             debug!("Populating UserGesturesFrame cache: fetching user gestures from FFI.");
             let conversion = || -> NuitrackResult<Vec<UserGestures>> {
                 let ffi_users_ptr = trace_span!("ffi", function = "gesture_data_ffi::user_gestures_state_data_user_gestures_states")
-                    .in_scope(|| gesture_data_ffi::user_gestures_state_data_user_gestures_states(&self.internal_ptr))?;
+                    .in_scope(|| gesture_data_ffi::user_gestures_state_data_user_gestures_states(ffi_ptr))?;
                 
                 let ffi_vec = ffi_users_ptr.as_ref().ok_or_else(|| {
                     warn!("FFI returned null user gestures vector pointer.");
@@ -183,7 +376,46 @@ impl UserGesturesFrame {
 
     #[instrument(skip(self))]
     pub fn timestamp(&self) -> NuitrackResult<u64> {
-        trace_span!("ffi", function = "gesture_data_ffi::user_gestures_state_data_timestamp")
-            .in_scope(|| Ok(gesture_data_ffi::user_gestures_state_data_timestamp(&self.internal_ptr)?))
+        match &self.inner {
+            UserGesturesFrameInner::Ffi(ptr) => trace_span!("ffi", function = "gesture_data_ffi::user_gestures_state_data_timestamp")
+                .in_scope(|| Ok(gesture_data_ffi::user_gestures_state_data_timestamp(ptr)?)),
+            UserGesturesFrameInner::Owned(frame) => Ok(frame.timestamp),
+        }
+    }
+}
+
+impl Clone for UserGesturesFrame {
+    fn clone(&self) -> Self {
+        UserGesturesFrame { inner: self.inner.clone(), users_cache: OnceLock::new() }
+    }
+}
+
+impl From<OwnedUserGesturesFrame> for UserGesturesFrame {
+    fn from(frame: OwnedUserGesturesFrame) -> Self {
+        UserGesturesFrame { inner: UserGesturesFrameInner::Owned(Arc::new(frame)), users_cache: OnceLock::new() }
+    }
+}
+
+impl Frame for UserGesturesFrame {
+    type Owned = OwnedUserGesturesFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        UserGesturesFrame::timestamp(self)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedUserGesturesFrame> {
+        UserGesturesFrame::to_owned(self)
     }
-}
\ No newline at end of file
+}
+
+impl Frame for OwnedUserGesturesFrame {
+    type Owned = OwnedUserGesturesFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        Ok(self.timestamp)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedUserGesturesFrame> {
+        Ok(self.clone())
+    }
+}
@@ -0,0 +1,277 @@
+//! Typed gesture lifecycle events from the gesture recognizer's progress updates.
+//!
+//! The recognizer's `update` stream reports the progress (0 to 100) of every gesture
+//! of every user, and its `completed_gestures_frame` stream reports completions.
+//! [`GestureProgressTracker`] combines both into [`GestureEvent`]s: a gesture starts
+//! once its progress stays above a threshold for the debounce time, reports progress
+//! while it advances, and ends either completed or, if its progress falls back without
+//! a completion, cancelled. After a completion the same gesture of the same user is
+//! ignored for its cooldown.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tracing::debug;
+
+use super::error::Result as NuitrackResult;
+use super::gesture::{Gesture, GestureType, UserGestures, UserStateType};
+use super::gesture_frame::{GestureFrame, UserGesturesFrame};
+
+/// Settings for [`GestureProgressTracker`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureProgressConfig {
+    /// Progress at which a gesture counts as under way.
+    pub start_threshold: i32,
+    /// How long progress must stay at or above `start_threshold` before the gesture
+    /// starts.
+    pub debounce: Duration,
+    /// Smallest progress change reported as [`GestureEvent::GestureProgress`].
+    pub progress_step: i32,
+    /// How long a gesture whose progress fell below `start_threshold` waits for its
+    /// completion before it is cancelled.
+    pub completion_grace: Duration,
+    /// Time after a completion during which the same gesture of the same user is
+    /// ignored, unless overridden in `cooldowns`.
+    pub cooldown: Duration,
+    pub cooldowns: HashMap<GestureType, Duration>,
+}
+
+impl Default for GestureProgressConfig {
+    fn default() -> Self {
+        Self {
+            start_threshold: 10,
+            debounce: Duration::from_millis(100),
+            progress_step: 5,
+            completion_grace: Duration::from_millis(200),
+            cooldown: Duration::from_millis(500),
+            cooldowns: HashMap::new(),
+        }
+    }
+}
+
+impl GestureProgressConfig {
+    pub fn with_start_threshold(mut self, threshold: i32) -> Self {
+        self.start_threshold = threshold;
+        self
+    }
+
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    pub fn with_progress_step(mut self, step: i32) -> Self {
+        self.progress_step = step.max(1);
+        self
+    }
+
+    pub fn with_completion_grace(mut self, grace: Duration) -> Self {
+        self.completion_grace = grace;
+        self
+    }
+
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Overrides the cooldown of one gesture type.
+    pub fn with_gesture_cooldown(mut self, gesture_type: GestureType, cooldown: Duration) -> Self {
+        self.cooldowns.insert(gesture_type, cooldown);
+        self
+    }
+
+    pub fn cooldown_for(&self, gesture_type: GestureType) -> Duration {
+        self.cooldowns.get(&gesture_type).copied().unwrap_or(self.cooldown)
+    }
+}
+
+/// A step in the lifecycle of a user's gesture.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", tag = "kind"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GestureEvent {
+    GestureStarted { user_id: i32, gesture_type: GestureType, progress: i32, timestamp: u64 },
+    GestureProgress { user_id: i32, gesture_type: GestureType, progress: i32, timestamp: u64 },
+    /// Reported by the recognizer, whether or not the gesture was seen starting.
+    GestureCompleted { user_id: i32, gesture_type: GestureType, timestamp: u64 },
+    /// The gesture's progress fell back without a completion, or the user left.
+    GestureCancelled { user_id: i32, gesture_type: GestureType, progress: i32, timestamp: u64 },
+}
+
+impl GestureEvent {
+    pub fn user_id(&self) -> i32 {
+        match self {
+            GestureEvent::GestureStarted { user_id, .. }
+            | GestureEvent::GestureProgress { user_id, .. }
+            | GestureEvent::GestureCompleted { user_id, .. }
+            | GestureEvent::GestureCancelled { user_id, .. } => *user_id,
+        }
+    }
+
+    pub fn gesture_type(&self) -> GestureType {
+        match self {
+            GestureEvent::GestureStarted { gesture_type, .. }
+            | GestureEvent::GestureProgress { gesture_type, .. }
+            | GestureEvent::GestureCompleted { gesture_type, .. }
+            | GestureEvent::GestureCancelled { gesture_type, .. } => *gesture_type,
+        }
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            GestureEvent::GestureStarted { timestamp, .. }
+            | GestureEvent::GestureProgress { timestamp, .. }
+            | GestureEvent::GestureCompleted { timestamp, .. }
+            | GestureEvent::GestureCancelled { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+enum Phase {
+    #[default]
+    Idle,
+    /// Progress reached the start threshold at the given time.
+    Pending(u64),
+    Active {
+        /// Last reported progress.
+        reported: i32,
+        /// When progress fell below the start threshold.
+        fell_at: Option<u64>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct GestureTrack {
+    phase: Phase,
+    progress: i32,
+    /// Completions before this time are ignored.
+    cooldown_until: u64,
+}
+
+/// Turns gesture progress updates and completions into [`GestureEvent`]s.
+#[derive(Debug, Clone, Default)]
+pub struct GestureProgressTracker {
+    config: GestureProgressConfig,
+    tracks: HashMap<(i32, GestureType), GestureTrack>,
+}
+
+impl GestureProgressTracker {
+    pub fn new(config: GestureProgressConfig) -> Self {
+        Self { config, tracks: HashMap::new() }
+    }
+
+    pub fn config(&self) -> &GestureProgressConfig {
+        &self.config
+    }
+
+    /// Whether `user_id`'s gesture has started and not yet ended.
+    pub fn is_active(&self, user_id: i32, gesture_type: GestureType) -> bool {
+        self.tracks.get(&(user_id, gesture_type)).is_some_and(|t| matches!(t.phase, Phase::Active { .. }))
+    }
+
+    /// Forgets all gestures and cooldowns without reporting them.
+    pub fn reset(&mut self) {
+        self.tracks.clear();
+    }
+
+    /// Applies the progress of every user at `timestamp` (in microseconds). Gestures of
+    /// users missing from `users` or absent are cancelled.
+    pub fn apply_update(&mut self, timestamp: u64, users: &[UserGestures]) -> Vec<GestureEvent> {
+        let config = &self.config;
+        let mut events = Vec::new();
+        let present = |user_id: i32| users.iter().any(|u| u.user_id == user_id && u.user_state != UserStateType::IsAbsent);
+        let mut lost: Vec<(i32, GestureType)> = self.tracks.keys().filter(|(user_id, _)| !present(*user_id)).copied().collect();
+        lost.sort_by_key(|(user_id, gesture_type)| (*user_id, gesture_type.repr));
+        for (user_id, gesture_type) in lost {
+            let track = self.tracks.remove(&(user_id, gesture_type)).expect("lost gestures are tracked");
+            if let Phase::Active { .. } = track.phase {
+                events.push(GestureEvent::GestureCancelled { user_id, gesture_type, progress: track.progress, timestamp });
+            }
+        }
+
+        for user in users.iter().filter(|u| u.user_state != UserStateType::IsAbsent) {
+            let user_id = user.user_id;
+            for state in &user.gestures {
+                let gesture_type = state.gesture_type;
+                let track = self.tracks.entry((user_id, gesture_type)).or_default();
+                let progress = state.progress;
+                track.progress = progress;
+                let above = progress >= config.start_threshold;
+                match track.phase {
+                    Phase::Idle | Phase::Pending(_) if !above || timestamp < track.cooldown_until => {
+                        track.phase = Phase::Idle;
+                    }
+                    Phase::Idle => {
+                        track.phase = Phase::Pending(timestamp);
+                        if config.debounce.is_zero() {
+                            Self::start(track, user_id, gesture_type, timestamp, &mut events);
+                        }
+                    }
+                    Phase::Pending(since) => {
+                        if timestamp.saturating_sub(since) >= config.debounce.as_micros() as u64 {
+                            Self::start(track, user_id, gesture_type, timestamp, &mut events);
+                        }
+                    }
+                    Phase::Active { reported, fell_at } => {
+                        let fell_at = if above { None } else { Some(fell_at.unwrap_or(timestamp)) };
+                        if fell_at.is_some_and(|t| timestamp.saturating_sub(t) >= config.completion_grace.as_micros() as u64) {
+                            debug!(user_id, ?gesture_type, "Gesture cancelled.");
+                            track.phase = Phase::Idle;
+                            events.push(GestureEvent::GestureCancelled { user_id, gesture_type, progress: reported, timestamp });
+                            continue;
+                        }
+                        let reported = if above && (progress - reported).abs() >= config.progress_step {
+                            events.push(GestureEvent::GestureProgress { user_id, gesture_type, progress, timestamp });
+                            progress
+                        } else {
+                            reported
+                        };
+                        track.phase = Phase::Active { reported, fell_at };
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    /// Applies gestures the recognizer completed at `timestamp`. Completions during
+    /// their cooldown are dropped.
+    pub fn apply_completed(&mut self, timestamp: u64, gestures: &[Gesture]) -> Vec<GestureEvent> {
+        let mut events = Vec::new();
+        for gesture in gestures {
+            let (user_id, gesture_type) = (gesture.user_id, gesture.gesture_type);
+            let track = self.tracks.entry((user_id, gesture_type)).or_default();
+            if timestamp < track.cooldown_until {
+                debug!(user_id, ?gesture_type, "Completed gesture within its cooldown ignored.");
+                continue;
+            }
+            debug!(user_id, ?gesture_type, "Gesture completed.");
+            track.phase = Phase::Idle;
+            track.cooldown_until = timestamp + self.config.cooldown_for(gesture_type).as_micros() as u64;
+            events.push(GestureEvent::GestureCompleted { user_id, gesture_type, timestamp });
+        }
+        events
+    }
+
+    /// [`apply_update`](Self::apply_update) for a frame of the recognizer's `update`
+    /// stream.
+    pub fn process_update(&mut self, frame: &UserGesturesFrame) -> NuitrackResult<Vec<GestureEvent>> {
+        Ok(self.apply_update(frame.timestamp()?, frame.users()?))
+    }
+
+    /// [`apply_completed`](Self::apply_completed) for a frame of the recognizer's
+    /// `completed_gestures_frame` stream.
+    pub fn process_completed(&mut self, frame: &GestureFrame) -> NuitrackResult<Vec<GestureEvent>> {
+        Ok(self.apply_completed(frame.timestamp()?, frame.gestures()?))
+    }
+
+    fn start(track: &mut GestureTrack, user_id: i32, gesture_type: GestureType, timestamp: u64, events: &mut Vec<GestureEvent>) {
+        debug!(user_id, ?gesture_type, "Gesture started.");
+        track.phase = Phase::Active { reported: track.progress, fell_at: None };
+        events.push(GestureEvent::GestureStarted { user_id, gesture_type, progress: track.progress, timestamp });
+    }
+}
@@ -5,6 +5,8 @@ use crate::nuitrack_bridge::types::hand::ffi::{self as hand_ffi}; // Corrected p
 pub use crate::nuitrack_bridge::types::hand::ffi::Hand;
 
 /// Represents the hands (left and/or right) detected for a single user.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone, PartialEq)]
 pub struct UserHands {
     /// The ID of the user to whom this hand information applies.
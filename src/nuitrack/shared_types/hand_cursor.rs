@@ -0,0 +1,425 @@
+//! Screen cursors driven by users' hands.
+//!
+//! [`HandCursorTracker`] turns the normalized hand positions of hand frames into one
+//! smoothed cursor per user and hand, mapped through an [`ActiveRegion`] to screen
+//! coordinates, and reports [`CursorEvent`]s: hovering, press and release on edges of
+//! [`Hand::click`], dwell clicks for hands held still, and drags.
+//!
+//! Distances such as [`HandCursorConfig::dwell_radius`] are fractions of the screen
+//! size, so they do not depend on [`HandCursorConfig::screen`].
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use tracing::debug;
+
+use super::error::Result as NuitrackResult;
+use super::frame::HandFrameData;
+use super::hand::{Hand, UserHands};
+use super::kinematics::Side;
+
+/// The part of the hand tracker's normalized `0..1` range that maps onto the whole
+/// screen. Users rarely reach the edges of the full range comfortably.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActiveRegion {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl Default for ActiveRegion {
+    /// The full tracking range.
+    fn default() -> Self {
+        Self { left: 0.0, top: 0.0, right: 1.0, bottom: 1.0 }
+    }
+}
+
+impl ActiveRegion {
+    /// The bounding box of `points` grown by `margin` on every side, or `None` if it
+    /// would be empty.
+    pub fn fit(points: impl IntoIterator<Item = (f32, f32)>, margin: f32) -> Option<Self> {
+        let mut region = Self { left: f32::INFINITY, top: f32::INFINITY, right: f32::NEG_INFINITY, bottom: f32::NEG_INFINITY };
+        for (x, y) in points {
+            region.left = region.left.min(x);
+            region.top = region.top.min(y);
+            region.right = region.right.max(x);
+            region.bottom = region.bottom.max(y);
+        }
+        let region = Self {
+            left: region.left - margin,
+            top: region.top - margin,
+            right: region.right + margin,
+            bottom: region.bottom + margin,
+        };
+        (region.right > region.left && region.bottom > region.top).then_some(region)
+    }
+
+    /// Maps a hand position to `0..=1` across the region, clamping positions outside it.
+    pub fn map(&self, x: f32, y: f32) -> (f32, f32) {
+        let along = |v: f32, lo: f32, hi: f32| ((v - lo) / (hi - lo)).clamp(0.0, 1.0);
+        (along(x, self.left, self.right), along(y, self.top, self.bottom))
+    }
+}
+
+/// Settings for [`HandCursorTracker`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandCursorConfig {
+    /// Screen width and height that cursor coordinates are scaled to.
+    pub screen: (f32, f32),
+    /// The hands that drive cursors.
+    pub hands: Vec<Side>,
+    /// Region used for cursors without a calibrated one.
+    pub region: ActiveRegion,
+    /// Exponential smoothing factor per frame, from 0 (frozen) to 1 (raw positions).
+    pub smoothing: f32,
+    /// How long a hovering cursor must stay within `dwell_radius` to click, or `None`
+    /// to disable dwell clicks.
+    pub dwell: Option<Duration>,
+    pub dwell_radius: f32,
+    /// How far a pressed cursor must move before a drag starts.
+    pub drag_threshold: f32,
+}
+
+impl Default for HandCursorConfig {
+    fn default() -> Self {
+        Self {
+            screen: (1.0, 1.0),
+            hands: vec![Side::Left, Side::Right],
+            region: ActiveRegion::default(),
+            smoothing: 0.5,
+            dwell: Some(Duration::from_secs(1)),
+            dwell_radius: 0.03,
+            drag_threshold: 0.04,
+        }
+    }
+}
+
+impl HandCursorConfig {
+    pub fn with_screen(mut self, width: f32, height: f32) -> Self {
+        self.screen = (width, height);
+        self
+    }
+
+    pub fn with_hands(mut self, hands: impl IntoIterator<Item = Side>) -> Self {
+        self.hands = hands.into_iter().collect();
+        self
+    }
+
+    pub fn with_region(mut self, region: ActiveRegion) -> Self {
+        self.region = region;
+        self
+    }
+
+    pub fn with_smoothing(mut self, smoothing: f32) -> Self {
+        self.smoothing = smoothing.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_dwell(mut self, dwell: Option<Duration>, radius: f32) -> Self {
+        self.dwell = dwell;
+        self.dwell_radius = radius;
+        self
+    }
+
+    pub fn with_drag_threshold(mut self, threshold: f32) -> Self {
+        self.drag_threshold = threshold;
+        self
+    }
+}
+
+/// Identifies the cursor of one hand of one user.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CursorId {
+    pub user_id: i32,
+    pub side: Side,
+}
+
+/// The current state of a cursor.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HandCursor {
+    pub id: CursorId,
+    /// Position in screen coordinates.
+    pub x: f32,
+    pub y: f32,
+    pub pressed: bool,
+    pub pressure: i32,
+    pub dragging: bool,
+    /// Progress of the pending dwell click from 0 to 1, e.g. to draw a ring.
+    pub dwell_progress: f32,
+}
+
+/// What happened to a cursor.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorEventKind {
+    /// The hand appeared.
+    Entered,
+    /// The cursor moved while not pressed.
+    Hover,
+    /// The hand closed.
+    Pressed,
+    /// The hand opened.
+    Released,
+    /// The cursor hovered in place for the dwell duration.
+    DwellClick,
+    /// The pressed cursor moved past the drag threshold.
+    DragStarted,
+    /// The cursor moved while dragging.
+    Dragged,
+    /// The drag ended with a release or the hand being lost.
+    DragEnded,
+    /// The hand was lost.
+    Left,
+}
+
+/// A cursor event, at the cursor's position after the frame.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CursorEvent {
+    pub id: CursorId,
+    pub kind: CursorEventKind,
+    /// Position in screen coordinates.
+    pub x: f32,
+    pub y: f32,
+    /// Typically in microseconds.
+    pub timestamp: u64,
+}
+
+/// Per-cursor state, with positions normalized to the screen.
+#[derive(Debug, Clone, Copy)]
+struct CursorState {
+    position: (f32, f32),
+    pressed: bool,
+    pressure: i32,
+    /// Where the cursor was pressed, until a drag starts.
+    press_origin: Option<(f32, f32)>,
+    dragging: bool,
+    /// Where and when the cursor came to rest.
+    dwell_anchor: (f32, f32),
+    dwell_since: u64,
+    /// Whether the cursor already clicked at `dwell_anchor`.
+    dwell_done: bool,
+    dwell_progress: f32,
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+/// Tracks a cursor for each configured hand of each user.
+#[derive(Debug, Clone, Default)]
+pub struct HandCursorTracker {
+    config: HandCursorConfig,
+    regions: HashMap<CursorId, ActiveRegion>,
+    calibrations: HashMap<CursorId, Vec<(f32, f32)>>,
+    cursors: HashMap<CursorId, CursorState>,
+}
+
+impl HandCursorTracker {
+    pub fn new(config: HandCursorConfig) -> Self {
+        Self { config, ..Default::default() }
+    }
+
+    pub fn config(&self) -> &HandCursorConfig {
+        &self.config
+    }
+
+    /// The region mapping `id`'s hand to the screen.
+    pub fn region(&self, id: CursorId) -> ActiveRegion {
+        self.regions.get(&id).copied().unwrap_or(self.config.region)
+    }
+
+    pub fn set_region(&mut self, id: CursorId, region: ActiveRegion) {
+        self.regions.insert(id, region);
+    }
+
+    /// Starts recording the raw positions of `id`'s hand, e.g. while the user is asked
+    /// to trace the edges of a comfortable area.
+    pub fn start_calibration(&mut self, id: CursorId) {
+        self.calibrations.insert(id, Vec::new());
+    }
+
+    /// Stops recording and, if the hand covered an area, makes the area grown by
+    /// `margin` the hand's region and returns it.
+    pub fn finish_calibration(&mut self, id: CursorId, margin: f32) -> Option<ActiveRegion> {
+        let region = ActiveRegion::fit(self.calibrations.remove(&id)?, margin)?;
+        debug!(?id, ?region, "Cursor region calibrated.");
+        self.regions.insert(id, region);
+        Some(region)
+    }
+
+    /// Cursors of hands currently tracked.
+    pub fn cursors(&self) -> impl Iterator<Item = HandCursor> + '_ {
+        self.cursors.iter().map(|(id, state)| self.cursor(*id, state))
+    }
+
+    pub fn cursor_of(&self, id: CursorId) -> Option<HandCursor> {
+        self.cursors.get(&id).map(|state| self.cursor(id, state))
+    }
+
+    /// Forgets every cursor without reporting it, keeping calibrated regions.
+    pub fn reset(&mut self) {
+        self.cursors.clear();
+        self.calibrations.clear();
+    }
+
+    /// Updates the cursors with the hands of `users_hands` observed at `timestamp`
+    /// (in microseconds) and returns the resulting events. Hands missing from
+    /// `users_hands` are lost.
+    pub fn apply(&mut self, timestamp: u64, users_hands: &[UserHands]) -> Vec<CursorEvent> {
+        let mut events = Vec::new();
+        let mut seen = HashSet::new();
+        for user in users_hands {
+            for side in self.config.hands.clone() {
+                let hand = match side {
+                    Side::Left => user.left_hand,
+                    Side::Right => user.right_hand,
+                };
+                if let Some(hand) = hand {
+                    let id = CursorId { user_id: user.user_id, side };
+                    seen.insert(id);
+                    self.update(id, &hand, timestamp, &mut events);
+                }
+            }
+        }
+        let lost: Vec<CursorId> = self.cursors.keys().filter(|id| !seen.contains(id)).copied().collect();
+        for id in lost {
+            let state = self.cursors.remove(&id).expect("lost cursors are tracked");
+            let mut emit = |kind| events.push(self.event(id, kind, state.position, timestamp));
+            if state.dragging {
+                emit(CursorEventKind::DragEnded);
+            }
+            if state.pressed {
+                emit(CursorEventKind::Released);
+            }
+            emit(CursorEventKind::Left);
+        }
+        events
+    }
+
+    /// [`apply`](Self::apply) to every user of `frame`.
+    pub fn process_frame(&mut self, frame: &impl HandFrameData) -> NuitrackResult<Vec<CursorEvent>> {
+        Ok(self.apply(frame.timestamp()?, frame.users_hands()?))
+    }
+
+    fn update(&mut self, id: CursorId, hand: &Hand, timestamp: u64, events: &mut Vec<CursorEvent>) {
+        if let Some(samples) = self.calibrations.get_mut(&id) {
+            samples.push((hand.x, hand.y));
+        }
+        let target = self.region(id).map(hand.x, hand.y);
+        let config = &self.config;
+        let mut kinds = Vec::new();
+        let mut state = match self.cursors.get(&id) {
+            Some(previous) => {
+                let a = config.smoothing;
+                let position = (
+                    previous.position.0 + a * (target.0 - previous.position.0),
+                    previous.position.1 + a * (target.1 - previous.position.1),
+                );
+                if position != previous.position {
+                    // Movement of a pressed cursor within the drag threshold is not reported.
+                    if previous.dragging {
+                        kinds.push(CursorEventKind::Dragged);
+                    } else if !previous.pressed {
+                        kinds.push(CursorEventKind::Hover);
+                    }
+                }
+                CursorState { position, pressure: hand.pressure, ..*previous }
+            }
+            None => {
+                kinds.push(CursorEventKind::Entered);
+                CursorState {
+                    position: target,
+                    pressed: false,
+                    pressure: hand.pressure,
+                    press_origin: None,
+                    dragging: false,
+                    dwell_anchor: target,
+                    dwell_since: timestamp,
+                    dwell_done: false,
+                    dwell_progress: 0.0,
+                }
+            }
+        };
+
+        // Press and release on edges of `click`.
+        if hand.click && !state.pressed {
+            state.pressed = true;
+            state.press_origin = Some(state.position);
+            state.dwell_progress = 0.0;
+            kinds.push(CursorEventKind::Pressed);
+        } else if !hand.click && state.pressed {
+            if state.dragging {
+                kinds.push(CursorEventKind::DragEnded);
+            }
+            state.pressed = false;
+            state.dragging = false;
+            state.press_origin = None;
+            // The release was the click; dwelling re-arms once the cursor moves away.
+            state.dwell_anchor = state.position;
+            state.dwell_done = true;
+            kinds.push(CursorEventKind::Released);
+        }
+
+        if state.press_origin.is_some_and(|origin| distance(origin, state.position) > config.drag_threshold) {
+            state.press_origin = None;
+            state.dragging = true;
+            kinds.push(CursorEventKind::DragStarted);
+        }
+
+        if let Some(dwell) = config.dwell.filter(|_| !state.pressed) {
+            if distance(state.dwell_anchor, state.position) > config.dwell_radius {
+                state.dwell_anchor = state.position;
+                state.dwell_since = timestamp;
+                state.dwell_done = false;
+                state.dwell_progress = 0.0;
+            } else if !state.dwell_done {
+                let held = timestamp.saturating_sub(state.dwell_since) as f32 / dwell.as_micros().max(1) as f32;
+                state.dwell_progress = held.min(1.0);
+                if held >= 1.0 {
+                    state.dwell_done = true;
+                    state.dwell_progress = 0.0;
+                    kinds.push(CursorEventKind::DwellClick);
+                }
+            }
+        }
+
+        for kind in kinds {
+            events.push(self.event(id, kind, state.position, timestamp));
+        }
+        self.cursors.insert(id, state);
+    }
+
+    fn event(&self, id: CursorId, kind: CursorEventKind, position: (f32, f32), timestamp: u64) -> CursorEvent {
+        let (x, y) = self.to_screen(position);
+        CursorEvent { id, kind, x, y, timestamp }
+    }
+
+    fn cursor(&self, id: CursorId, state: &CursorState) -> HandCursor {
+        let (x, y) = self.to_screen(state.position);
+        HandCursor {
+            id,
+            x,
+            y,
+            pressed: state.pressed,
+            pressure: state.pressure,
+            dragging: state.dragging,
+            dwell_progress: state.dwell_progress,
+        }
+    }
+
+    fn to_screen(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        (x * self.config.screen.0, y * self.config.screen.1)
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 use tracing::{debug, instrument, trace, trace_span, warn};
 use cxx::SharedPtr;
 use crate::nuitrack_bridge::types::hand_data::ffi as hand_data_ffi;
@@ -6,13 +6,31 @@ use crate::nuitrack_bridge::types::hand_data::ffi as hand_data_ffi;
 // Import the public Rust UserHands type we defined earlier
 use super::hand::UserHands; 
 use super::error::{NuitrackError, Result as NuitrackResult};
+use super::frame::{Frame, HandFrameData};
 
 pub struct HandFrame {
-    /// Internal pointer to the FFI HandData object.
-    internal_ptr: SharedPtr<hand_data_ffi::HandData>,
+    /// Where the frame's data lives: in SDK memory or in a Rust-side buffer.
+    inner: HandFrameInner,
     users_hands_cache: OnceLock<NuitrackResult<Vec<UserHands>>>,
 }
 
+#[derive(Clone)]
+enum HandFrameInner {
+    /// Internal pointer to the FFI HandData object.
+    Ffi(SharedPtr<hand_data_ffi::HandData>),
+    Owned(Arc<OwnedHandFrame>),
+}
+
+/// Hand data of one frame held entirely in Rust memory.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedHandFrame {
+    /// Typically in microseconds.
+    pub timestamp: u64,
+    pub users_hands: Vec<UserHands>,
+}
+
 impl HandFrame {
     /// Creates a new `HandFrame` from a shared pointer to the FFI `HandData` object.
     ///
@@ -23,7 +41,26 @@ impl HandFrame {
         if is_null {
             None
         } else {
-            Some(HandFrame { internal_ptr: ffi_ptr , users_hands_cache: OnceLock::new(),})
+            Some(HandFrame { inner: HandFrameInner::Ffi(ffi_ptr), users_hands_cache: OnceLock::new(),})
+        }
+    }
+
+    /// Creates a `HandFrame` from hand data held in Rust memory instead of SDK memory.
+    ///
+    /// This is how pure-Rust backends (such as the simulated backend) produce frames.
+    pub fn synthetic(timestamp: u64, users_hands: Vec<UserHands>) -> Self {
+        OwnedHandFrame { timestamp, users_hands }.into()
+    }
+
+    /// Copies the frame into an [`OwnedHandFrame`], releasing any tie to SDK memory.
+    #[instrument(skip(self))]
+    pub fn to_owned(&self) -> NuitrackResult<OwnedHandFrame> {
+        match &self.inner {
+            HandFrameInner::Ffi(_) => Ok(OwnedHandFrame {
+                timestamp: self.timestamp()?,
+                users_hands: self.users_hands()?.to_vec(),
+            }),
+            HandFrameInner::Owned(frame) => Ok(OwnedHandFrame::clone(frame)),
         }
     }
 
@@ -34,11 +71,14 @@ impl HandFrame {
     /// FFI call fails.
     #[instrument(skip(self))]
     pub fn num_users(&self) -> NuitrackResult<i32> {
-        // Calls the FFI function on the internal HandData pointer.
-        // The `?` operator propagates any error from the FFI call.
-        trace_span!("ffi", function = "hand_data_ffi::num_users").in_scope( ||
-            Ok(hand_data_ffi::num_users(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            // Calls the FFI function on the internal HandData pointer.
+            // The `?` operator propagates any error from the FFI call.
+            HandFrameInner::Ffi(ptr) => trace_span!("ffi", function = "hand_data_ffi::num_users").in_scope( ||
+                Ok(hand_data_ffi::num_users(ptr)?)
+            ),
+            HandFrameInner::Owned(frame) => Ok(frame.users_hands.len() as i32),
+        }
     }
 
     /// Gets a list of `UserHands` objects, each representing the detected hands
@@ -49,6 +89,10 @@ impl HandFrame {
     /// the FFI call or data conversion fails.
     #[instrument(skip(self))]
     pub fn users_hands(&self) -> NuitrackResult<&[UserHands]> {
+        let ffi_ptr = match &self.inner {
+            HandFrameInner::Ffi(ptr) => ptr,
+            HandFrameInner::Owned(frame) => return Ok(frame.users_hands.as_slice()),
+        };
         // Use get_or_init to lazily populate our cache.
         // The closure will only ever be executed ONCE for this HandFrame instance.
         let cached_result = self.users_hands_cache.get_or_init(|| {
@@ -58,7 +102,7 @@ impl HandFrame {
             let conversion = || -> NuitrackResult<Vec<UserHands>> {
                 // 1. Call FFI to get the owned vector of opaque UserHands objects
                 let ffi_users_hands_ptr = trace_span!("ffi", function = "hand_data_ffi::users_hands").in_scope( ||
-                    hand_data_ffi::users_hands(&self.internal_ptr)
+                    hand_data_ffi::users_hands(ffi_ptr)
                 )?;
                 let ffi_users_hands_vec_ref = ffi_users_hands_ptr
                     .as_ref()
@@ -103,9 +147,61 @@ impl HandFrame {
     /// FFI call fails.
     #[instrument(skip(self))]
     pub fn timestamp(&self) -> NuitrackResult<u64> {
-        // Calls the FFI function on the internal HandData pointer.
-        trace_span!("ffi", function = "hand_data_ffi::timestamp").in_scope( ||
-            Ok(hand_data_ffi::timestamp(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            // Calls the FFI function on the internal HandData pointer.
+            HandFrameInner::Ffi(ptr) => trace_span!("ffi", function = "hand_data_ffi::timestamp").in_scope( ||
+                Ok(hand_data_ffi::timestamp(ptr)?)
+            ),
+            HandFrameInner::Owned(frame) => Ok(frame.timestamp),
+        }
+    }
+}
+
+/// Cloning shares the underlying frame; the Rust-side hand cache is rebuilt lazily.
+impl Clone for HandFrame {
+    fn clone(&self) -> Self {
+        HandFrame { inner: self.inner.clone(), users_hands_cache: OnceLock::new() }
+    }
+}
+
+impl From<OwnedHandFrame> for HandFrame {
+    fn from(frame: OwnedHandFrame) -> Self {
+        HandFrame { inner: HandFrameInner::Owned(Arc::new(frame)), users_hands_cache: OnceLock::new() }
+    }
+}
+
+impl Frame for HandFrame {
+    type Owned = OwnedHandFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        HandFrame::timestamp(self)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedHandFrame> {
+        HandFrame::to_owned(self)
+    }
+}
+
+impl HandFrameData for HandFrame {
+    fn users_hands(&self) -> NuitrackResult<&[UserHands]> {
+        HandFrame::users_hands(self)
+    }
+}
+
+impl Frame for OwnedHandFrame {
+    type Owned = OwnedHandFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        Ok(self.timestamp)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedHandFrame> {
+        Ok(self.clone())
+    }
+}
+
+impl HandFrameData for OwnedHandFrame {
+    fn users_hands(&self) -> NuitrackResult<&[UserHands]> {
+        Ok(&self.users_hands)
     }
 }
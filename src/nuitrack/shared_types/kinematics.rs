@@ -0,0 +1,416 @@
+//! Bones, joint angles and orientation conversions for skeletons.
+//!
+//! Nuitrack reports joints as independent positions with a rotation matrix each.
+//! This module adds the bone hierarchy connecting them and the measurements built on
+//! it: bone vectors and lengths, flexion angles of the limbs, trunk lean, and
+//! [`Quaternion`] and [`EulerAngles`] forms of [`Orientation`].
+//!
+//! Positions are in millimeters and angles in radians. Joints with a confidence of 0
+//! are untracked, and every measurement involving one returns `None`.
+
+use std::ops::Mul;
+
+use crate::nuitrack_bridge::types::skeleton::ffi::{JointType, Orientation};
+use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+
+use super::skeleton::Skeleton;
+
+fn sub(a: Vector3, b: Vector3) -> Vector3 {
+    Vector3 { x: a.x - b.x, y: a.y - b.y, z: a.z - b.z }
+}
+
+fn dot(a: Vector3, b: Vector3) -> f32 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+fn length(v: Vector3) -> f32 {
+    dot(v, v).sqrt()
+}
+
+/// Angle between two vectors, or `None` if either is zero.
+fn angle_between(a: Vector3, b: Vector3) -> Option<f32> {
+    let lengths = length(a) * length(b);
+    (lengths > f32::EPSILON).then(|| (dot(a, b) / lengths).clamp(-1.0, 1.0).acos())
+}
+
+/// A body side.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    fn pick(self, left: JointType, right: JointType) -> JointType {
+        match self {
+            Side::Left => left,
+            Side::Right => right,
+        }
+    }
+}
+
+/// A bone, connecting a joint to its parent in the hierarchy.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bone {
+    pub parent: JointType,
+    pub child: JointType,
+}
+
+/// The skeleton hierarchy as used by Nuitrack's avatars, rooted at `Waist`. Parents
+/// precede their children, so the list can be walked to propagate transforms.
+///
+/// The SDK reports the collar on `LeftCollar` only and leaves `RightCollar` unused
+/// (see [`JointType::is_unused`]), so both shoulders hang from `LeftCollar`.
+pub const BONES: [Bone; 22] = [
+    Bone { parent: JointType::Waist, child: JointType::Torso },
+    Bone { parent: JointType::Torso, child: JointType::Neck },
+    Bone { parent: JointType::Neck, child: JointType::Head },
+    Bone { parent: JointType::Torso, child: JointType::LeftCollar },
+    Bone { parent: JointType::LeftCollar, child: JointType::LeftShoulder },
+    Bone { parent: JointType::LeftShoulder, child: JointType::LeftElbow },
+    Bone { parent: JointType::LeftElbow, child: JointType::LeftWrist },
+    Bone { parent: JointType::LeftWrist, child: JointType::LeftHand },
+    Bone { parent: JointType::LeftHand, child: JointType::LeftFingertip },
+    Bone { parent: JointType::LeftCollar, child: JointType::RightShoulder },
+    Bone { parent: JointType::RightShoulder, child: JointType::RightElbow },
+    Bone { parent: JointType::RightElbow, child: JointType::RightWrist },
+    Bone { parent: JointType::RightWrist, child: JointType::RightHand },
+    Bone { parent: JointType::RightHand, child: JointType::RightFingertip },
+    Bone { parent: JointType::Waist, child: JointType::LeftHip },
+    Bone { parent: JointType::LeftHip, child: JointType::LeftKnee },
+    Bone { parent: JointType::LeftKnee, child: JointType::LeftAnkle },
+    Bone { parent: JointType::LeftAnkle, child: JointType::LeftFoot },
+    Bone { parent: JointType::Waist, child: JointType::RightHip },
+    Bone { parent: JointType::RightHip, child: JointType::RightKnee },
+    Bone { parent: JointType::RightKnee, child: JointType::RightAnkle },
+    Bone { parent: JointType::RightAnkle, child: JointType::RightFoot },
+];
+
+impl JointType {
+    /// The joint's parent in [`BONES`], or `None` for the root (`Waist`), `None` and
+    /// the unused `RightCollar`.
+    pub fn parent(self) -> Option<JointType> {
+        BONES.iter().find(|bone| bone.child == self).map(|bone| bone.parent)
+    }
+
+    /// The joints whose parent is this one.
+    pub fn children(self) -> impl Iterator<Item = JointType> {
+        BONES.iter().filter(move |bone| bone.parent == self).map(|bone| bone.child)
+    }
+}
+
+impl Skeleton {
+    /// Position of a tracked joint.
+    pub fn position(&self, joint_type: JointType) -> Option<Vector3> {
+        self.joint(joint_type).filter(|j| j.confidence > 0.0).map(|j| j.real)
+    }
+
+    /// Vector from the bone's parent joint to its child joint.
+    pub fn bone_vector(&self, bone: Bone) -> Option<Vector3> {
+        Some(sub(self.position(bone.child)?, self.position(bone.parent)?))
+    }
+
+    pub fn bone_length(&self, bone: Bone) -> Option<f32> {
+        self.bone_vector(bone).map(length)
+    }
+
+    /// Length of every bone in [`BONES`] whose joints are both tracked.
+    pub fn bone_lengths(&self) -> impl Iterator<Item = (Bone, f32)> + '_ {
+        BONES.iter().filter_map(|&bone| Some((bone, self.bone_length(bone)?)))
+    }
+
+    /// Angle at `vertex` between the segments to `a` and `b`: π when the three joints
+    /// are in line, smaller as the segments fold together.
+    pub fn angle_at(&self, a: JointType, vertex: JointType, b: JointType) -> Option<f32> {
+        let v = self.position(vertex)?;
+        angle_between(sub(self.position(a)?, v), sub(self.position(b)?, v))
+    }
+
+    /// Elbow flexion: 0 with the arm straight, π/2 with the forearm at a right angle.
+    pub fn elbow_flexion(&self, side: Side) -> Option<f32> {
+        self.flexion(
+            side.pick(JointType::LeftShoulder, JointType::RightShoulder),
+            side.pick(JointType::LeftElbow, JointType::RightElbow),
+            side.pick(JointType::LeftWrist, JointType::RightWrist),
+        )
+    }
+
+    /// Knee flexion: 0 with the leg straight.
+    pub fn knee_flexion(&self, side: Side) -> Option<f32> {
+        self.flexion(
+            side.pick(JointType::LeftHip, JointType::RightHip),
+            side.pick(JointType::LeftKnee, JointType::RightKnee),
+            side.pick(JointType::LeftAnkle, JointType::RightAnkle),
+        )
+    }
+
+    /// Hip flexion: angle between the thigh and the downward trunk direction (neck to
+    /// waist), 0 when standing and π/2 with the thigh raised perpendicular to the trunk.
+    pub fn hip_flexion(&self, side: Side) -> Option<f32> {
+        let down = sub(self.position(JointType::Waist)?, self.position(JointType::Neck)?);
+        let thigh = self.bone_vector(Bone {
+            parent: side.pick(JointType::LeftHip, JointType::RightHip),
+            child: side.pick(JointType::LeftKnee, JointType::RightKnee),
+        })?;
+        angle_between(down, thigh)
+    }
+
+    /// Shoulder elevation: angle between the upper arm and the downward trunk direction,
+    /// 0 with the arm hanging and π with it raised overhead.
+    pub fn shoulder_elevation(&self, side: Side) -> Option<f32> {
+        let down = sub(self.position(JointType::Waist)?, self.position(JointType::Neck)?);
+        let upper_arm = self.bone_vector(Bone {
+            parent: side.pick(JointType::LeftShoulder, JointType::RightShoulder),
+            child: side.pick(JointType::LeftElbow, JointType::RightElbow),
+        })?;
+        angle_between(down, upper_arm)
+    }
+
+    /// Angle between the trunk (waist to neck) and `up`, 0 when upright. Pass
+    /// `FloorPlane::normal` for a lean relative to the floor rather than the sensor.
+    pub fn trunk_lean(&self, up: Vector3) -> Option<f32> {
+        angle_between(sub(self.position(JointType::Neck)?, self.position(JointType::Waist)?), up)
+    }
+
+    /// Rotation of `joint_type` relative to its parent, i.e. `parent⁻¹ · joint`.
+    pub fn relative_rotation(&self, joint_type: JointType) -> Option<Quaternion> {
+        let joint = self.joint(joint_type)?;
+        let parent = self.joint(joint_type.parent()?)?;
+        Some(parent.orient.to_quaternion().conjugate() * joint.orient.to_quaternion())
+    }
+
+    fn flexion(&self, a: JointType, vertex: JointType, b: JointType) -> Option<f32> {
+        self.angle_at(a, vertex, b).map(|angle| std::f32::consts::PI - angle)
+    }
+}
+
+/// A unit quaternion representing a rotation.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    pub w: f32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Quaternion {
+    pub const IDENTITY: Quaternion = Quaternion { w: 1.0, x: 0.0, y: 0.0, z: 0.0 };
+
+    /// Rotation by `angle` about `axis`, which need not be normalized.
+    pub fn from_axis_angle(axis: Vector3, angle: f32) -> Self {
+        let n = length(axis);
+        if n <= f32::EPSILON {
+            return Self::IDENTITY;
+        }
+        let (s, c) = (angle / 2.0).sin_cos();
+        Self { w: c, x: axis.x / n * s, y: axis.y / n * s, z: axis.z / n * s }
+    }
+
+    /// The shortest rotation taking direction `from` to direction `to`.
+    pub fn between(from: Vector3, to: Vector3) -> Self {
+        let Some(angle) = angle_between(from, to) else {
+            return Self::IDENTITY;
+        };
+        let axis = Vector3 {
+            x: from.y * to.z - from.z * to.y,
+            y: from.z * to.x - from.x * to.z,
+            z: from.x * to.y - from.y * to.x,
+        };
+        if length(axis) > 1e-6 * length(from) * length(to) {
+            return Self::from_axis_angle(axis, angle);
+        }
+        if angle < 1.0 {
+            return Self::IDENTITY;
+        }
+        // Opposite directions: rotate by π about any perpendicular axis.
+        let helper = if from.x.abs() < 0.9 * length(from) {
+            Vector3 { x: 1.0, y: 0.0, z: 0.0 }
+        } else {
+            Vector3 { x: 0.0, y: 1.0, z: 0.0 }
+        };
+        let axis = Vector3 {
+            x: from.y * helper.z - from.z * helper.y,
+            y: from.z * helper.x - from.x * helper.z,
+            z: from.x * helper.y - from.y * helper.x,
+        };
+        Self::from_axis_angle(axis, std::f32::consts::PI)
+    }
+
+    /// The rotation of a row-major rotation matrix.
+    pub fn from_matrix(m: &[f32; 9]) -> Self {
+        let trace = m[0] + m[4] + m[8];
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self { w: 0.25 * s, x: (m[7] - m[5]) / s, y: (m[2] - m[6]) / s, z: (m[3] - m[1]) / s }
+        } else if m[0] > m[4] && m[0] > m[8] {
+            let s = (1.0 + m[0] - m[4] - m[8]).sqrt() * 2.0;
+            Self { w: (m[7] - m[5]) / s, x: 0.25 * s, y: (m[1] + m[3]) / s, z: (m[2] + m[6]) / s }
+        } else if m[4] > m[8] {
+            let s = (1.0 + m[4] - m[0] - m[8]).sqrt() * 2.0;
+            Self { w: (m[2] - m[6]) / s, x: (m[1] + m[3]) / s, y: 0.25 * s, z: (m[5] + m[7]) / s }
+        } else {
+            let s = (1.0 + m[8] - m[0] - m[4]).sqrt() * 2.0;
+            Self { w: (m[3] - m[1]) / s, x: (m[2] + m[6]) / s, y: (m[5] + m[7]) / s, z: 0.25 * s }
+        };
+        q.normalized()
+    }
+
+    /// The row-major rotation matrix of this rotation.
+    pub fn to_matrix(&self) -> [f32; 9] {
+        let Self { w, x, y, z } = *self;
+        [
+            1.0 - 2.0 * (y * y + z * z),
+            2.0 * (x * y - w * z),
+            2.0 * (x * z + w * y),
+            2.0 * (x * y + w * z),
+            1.0 - 2.0 * (x * x + z * z),
+            2.0 * (y * z - w * x),
+            2.0 * (x * z - w * y),
+            2.0 * (y * z + w * x),
+            1.0 - 2.0 * (x * x + y * y),
+        ]
+    }
+
+    pub fn normalized(&self) -> Self {
+        let n = (self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+        if n <= f32::EPSILON {
+            return Self::IDENTITY;
+        }
+        Self { w: self.w / n, x: self.x / n, y: self.y / n, z: self.z / n }
+    }
+
+    /// The inverse rotation.
+    pub fn conjugate(&self) -> Self {
+        Self { w: self.w, x: -self.x, y: -self.y, z: -self.z }
+    }
+
+    pub fn dot(&self, other: &Self) -> f32 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Angle of the rotation taking `self` to `other`, in `0..=π`.
+    pub fn angle_to(&self, other: &Self) -> f32 {
+        2.0 * self.dot(other).abs().clamp(0.0, 1.0).acos()
+    }
+
+    /// Rotates `v`.
+    pub fn rotate(&self, v: Vector3) -> Vector3 {
+        let p = *self * Quaternion { w: 0.0, x: v.x, y: v.y, z: v.z } * self.conjugate();
+        Vector3 { x: p.x, y: p.y, z: p.z }
+    }
+
+    /// Spherical interpolation from `self` (`t = 0`) to `other` (`t = 1`) along the
+    /// shorter arc.
+    pub fn slerp(&self, other: &Self, t: f32) -> Self {
+        let mut cos = self.dot(other);
+        let other = if cos < 0.0 {
+            cos = -cos;
+            Quaternion { w: -other.w, x: -other.x, y: -other.y, z: -other.z }
+        } else {
+            *other
+        };
+        let (a, b) = if cos > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let theta = cos.acos();
+            let sin = theta.sin();
+            (((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+        };
+        Quaternion {
+            w: a * self.w + b * other.w,
+            x: a * self.x + b * other.x,
+            y: a * self.y + b * other.y,
+            z: a * self.z + b * other.z,
+        }
+        .normalized()
+    }
+
+    pub fn to_euler(&self) -> EulerAngles {
+        EulerAngles::from_matrix(&self.to_matrix())
+    }
+}
+
+impl Mul for Quaternion {
+    type Output = Quaternion;
+
+    /// The rotation applying `rhs` first, then `self`.
+    fn mul(self, rhs: Quaternion) -> Quaternion {
+        Quaternion {
+            w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            x: self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            y: self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            z: self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        }
+    }
+}
+
+/// A rotation as yaw about `y`, then pitch about the rotated `x`, then roll about the
+/// rotated `z` (intrinsic Y-X-Z), i.e. the matrix `Ry(yaw) · Rx(pitch) · Rz(roll)`.
+///
+/// Yaw and roll are in `-π..=π` and pitch in `-π/2..=π/2`. At a pitch of ±π/2 the
+/// decomposition is ambiguous and roll is reported as 0.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EulerAngles {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub roll: f32,
+}
+
+impl EulerAngles {
+    pub fn from_matrix(m: &[f32; 9]) -> Self {
+        let pitch = (-m[5]).clamp(-1.0, 1.0).asin();
+        if m[5].abs() < 0.99999 {
+            Self { yaw: m[2].atan2(m[8]), pitch, roll: m[3].atan2(m[4]) }
+        } else {
+            Self { yaw: (-m[6]).atan2(m[0]), pitch, roll: 0.0 }
+        }
+    }
+
+    /// The row-major rotation matrix.
+    pub fn to_matrix(&self) -> [f32; 9] {
+        let (sy, cy) = self.yaw.sin_cos();
+        let (sx, cx) = self.pitch.sin_cos();
+        let (sz, cz) = self.roll.sin_cos();
+        [
+            cy * cz + sy * sx * sz,
+            -cy * sz + sy * sx * cz,
+            sy * cx,
+            cx * sz,
+            cx * cz,
+            -sx,
+            -sy * cz + cy * sx * sz,
+            sy * sz + cy * sx * cz,
+            cy * cx,
+        ]
+    }
+
+    pub fn to_quaternion(&self) -> Quaternion {
+        Quaternion::from_matrix(&self.to_matrix())
+    }
+}
+
+impl Orientation {
+    pub fn to_quaternion(&self) -> Quaternion {
+        Quaternion::from_matrix(&self.matrix)
+    }
+
+    pub fn to_euler(&self) -> EulerAngles {
+        EulerAngles::from_matrix(&self.matrix)
+    }
+
+    pub fn from_quaternion(q: &Quaternion) -> Self {
+        Self { matrix: q.to_matrix() }
+    }
+}
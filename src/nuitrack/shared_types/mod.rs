@@ -1,13 +1,28 @@
+pub mod body_model;
+pub mod camera_model;
+pub mod clean_skeleton;
+pub mod custom_gesture;
 pub mod depth_frame;
 pub mod error;
+pub mod floor_plane;
+pub mod frame;
 pub mod gesture_frame;
+pub mod gesture_progress;
 pub mod gesture;
+pub mod hand_cursor;
 pub mod hand_frame;
 pub mod hand;
+pub mod kinematics;
+pub mod point_cloud;
+pub mod pose;
 pub mod rgb_frame;
+pub mod rgbd_frame;
 pub mod session_config;
+pub mod skeleton_filter;
 pub mod skeleton_frame;
 pub mod skeleton;
 pub mod user_frame;
+pub mod user_lifecycle;
+pub mod user_mask;
 pub mod user;
 
@@ -0,0 +1,285 @@
+//! Point clouds built from depth frames, with PLY and PCD export.
+//!
+//! Deprojection runs entirely in Rust through the depth sensor's [`CameraModel`], so
+//! mirroring and frames smaller than the output mode are handled the same way as in
+//! [`CameraModel::frame_to_real`], without an SDK round-trip through
+//! `AsyncDepthSensor::convert_proj_to_real`.
+//!
+//! Points use Nuitrack's real-world convention: millimeters, `x` to the right, `y` up
+//! and `z` away from the sensor.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use tracing::{debug, instrument};
+
+use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+
+use super::camera_model::CameraModel;
+use super::depth_frame::DepthFrame;
+use super::error::{NuitrackError, Result as NuitrackResult};
+use super::frame::{ColorFrameData, DepthFrameData, UserFrameData};
+use super::rgb_frame::{Color3, RGBFrame};
+use super::user_frame::UserFrame;
+
+/// An unorganized set of 3D points, optionally colored.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PointCloud {
+    /// Timestamp of the source depth frame, typically in microseconds.
+    pub timestamp: u64,
+    /// Points in millimeters.
+    pub points: Vec<Vector3>,
+    /// One color per point, if the cloud was built with a color frame.
+    pub colors: Option<Vec<Color3>>,
+}
+
+/// Data encoding for [`PointCloud::write_ply`] and [`PointCloud::write_pcd`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CloudEncoding {
+    Ascii,
+    /// Little-endian binary; smaller and faster to load.
+    #[default]
+    Binary,
+}
+
+impl PointCloud {
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Fails unless there is either no color or one color per point.
+    fn check_colors(&self) -> NuitrackResult<()> {
+        match &self.colors {
+            Some(colors) if colors.len() != self.points.len() => Err(NuitrackError::OperationFailed(format!(
+                "Point cloud has {} colors for {} points",
+                colors.len(),
+                self.points.len()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Writes the cloud as a PLY file with `x y z` float and, if present, `red green blue`
+    /// uchar vertex properties. Fails if `colors` does not hold one color per point.
+    pub fn write_ply(&self, mut writer: impl Write, encoding: CloudEncoding) -> NuitrackResult<()> {
+        self.check_colors()?;
+        let format = match encoding {
+            CloudEncoding::Ascii => "ascii",
+            CloudEncoding::Binary => "binary_little_endian",
+        };
+        writeln!(writer, "ply\nformat {format} 1.0\nelement vertex {}", self.len())?;
+        writeln!(writer, "property float x\nproperty float y\nproperty float z")?;
+        if self.colors.is_some() {
+            writeln!(writer, "property uchar red\nproperty uchar green\nproperty uchar blue")?;
+        }
+        writeln!(writer, "end_header")?;
+
+        for (i, p) in self.points.iter().enumerate() {
+            let color = self.colors.as_ref().map(|colors| colors[i]);
+            match encoding {
+                CloudEncoding::Ascii => {
+                    write!(writer, "{} {} {}", p.x, p.y, p.z)?;
+                    if let Some(c) = color {
+                        write!(writer, " {} {} {}", c.red, c.green, c.blue)?;
+                    }
+                    writeln!(writer)?;
+                }
+                CloudEncoding::Binary => {
+                    for v in [p.x, p.y, p.z] {
+                        writer.write_all(&v.to_le_bytes())?;
+                    }
+                    if let Some(c) = color {
+                        writer.write_all(&[c.red, c.green, c.blue])?;
+                    }
+                }
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the cloud as a PCD v0.7 file. Colors are stored in PCL's packed `rgb` field.
+    /// Fails if `colors` does not hold one color per point.
+    pub fn write_pcd(&self, mut writer: impl Write, encoding: CloudEncoding) -> NuitrackResult<()> {
+        self.check_colors()?;
+        let colored = self.colors.is_some();
+        let (fields, sizes, types, counts) = if colored {
+            ("x y z rgb", "4 4 4 4", "F F F U", "1 1 1 1")
+        } else {
+            ("x y z", "4 4 4", "F F F", "1 1 1")
+        };
+        let data = match encoding {
+            CloudEncoding::Ascii => "ascii",
+            CloudEncoding::Binary => "binary",
+        };
+        writeln!(writer, "# .PCD v0.7 - Point Cloud Data file format\nVERSION 0.7")?;
+        writeln!(writer, "FIELDS {fields}\nSIZE {sizes}\nTYPE {types}\nCOUNT {counts}")?;
+        writeln!(writer, "WIDTH {}\nHEIGHT 1\nVIEWPOINT 0 0 0 1 0 0 0\nPOINTS {}", self.len(), self.len())?;
+        writeln!(writer, "DATA {data}")?;
+
+        for (i, p) in self.points.iter().enumerate() {
+            let rgb = self
+                .colors
+                .as_ref()
+                .map(|colors| (u32::from(colors[i].red) << 16) | (u32::from(colors[i].green) << 8) | u32::from(colors[i].blue));
+            match encoding {
+                CloudEncoding::Ascii => {
+                    write!(writer, "{} {} {}", p.x, p.y, p.z)?;
+                    if let Some(rgb) = rgb {
+                        write!(writer, " {rgb}")?;
+                    }
+                    writeln!(writer)?;
+                }
+                CloudEncoding::Binary => {
+                    for v in [p.x, p.y, p.z] {
+                        writer.write_all(&v.to_le_bytes())?;
+                    }
+                    if let Some(rgb) = rgb {
+                        writer.write_all(&rgb.to_le_bytes())?;
+                    }
+                }
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(points = self.len()))]
+    pub fn save_ply(&self, path: impl AsRef<Path> + std::fmt::Debug, encoding: CloudEncoding) -> NuitrackResult<()> {
+        self.write_ply(BufWriter::new(File::create(path)?), encoding)
+    }
+
+    #[instrument(skip(self), fields(points = self.len()))]
+    pub fn save_pcd(&self, path: impl AsRef<Path> + std::fmt::Debug, encoding: CloudEncoding) -> NuitrackResult<()> {
+        self.write_pcd(BufWriter::new(File::create(path)?), encoding)
+    }
+}
+
+/// Configures how a depth frame is turned into a [`PointCloud`].
+///
+/// Color and user frames are sampled by scaling pixel coordinates, so they must cover
+/// the same view as the depth frame: enable the SDK's depth-to-color registration, or
+/// use a [`Registration`](super::rgbd_frame::Registration) for unregistered color.
+pub struct PointCloudBuilder<'a, D, C = RGBFrame, U = UserFrame> {
+    depth: &'a D,
+    camera: CameraModel,
+    color: Option<&'a C>,
+    mask: Option<(&'a U, Option<i32>)>,
+    step: usize,
+}
+
+impl<'a, D: DepthFrameData> PointCloudBuilder<'a, D> {
+    pub fn new(depth: &'a D, camera: CameraModel) -> Self {
+        Self { depth, camera, color: None, mask: None, step: 1 }
+    }
+}
+
+impl<'a, D: DepthFrameData, C: ColorFrameData, U: UserFrameData> PointCloudBuilder<'a, D, C, U> {
+    /// Colors each point from `color`.
+    pub fn with_color<C2: ColorFrameData>(self, color: &'a C2) -> PointCloudBuilder<'a, D, C2, U> {
+        PointCloudBuilder { depth: self.depth, camera: self.camera, color: Some(color), mask: self.mask, step: self.step }
+    }
+
+    /// Keeps only pixels that belong to a user in `users`: `user_id`, or any user if `None`.
+    pub fn with_user_mask<U2: UserFrameData>(self, users: &'a U2, user_id: Option<i32>) -> PointCloudBuilder<'a, D, C, U2> {
+        PointCloudBuilder { depth: self.depth, camera: self.camera, color: self.color, mask: Some((users, user_id)), step: self.step }
+    }
+
+    /// Uses every `step`-th pixel in both directions. Defaults to 1.
+    pub fn with_step(mut self, step: usize) -> Self {
+        self.step = step.max(1);
+        self
+    }
+
+    #[instrument(skip_all, fields(step = self.step))]
+    pub fn build(self) -> NuitrackResult<PointCloud> {
+        let (fx, fy) = (self.camera.intrinsics.fx, self.camera.intrinsics.fy);
+        if fx <= 0.0 || fy <= 0.0 {
+            return Err(NuitrackError::OperationFailed(format!("Invalid intrinsics: fx={fx}, fy={fy}")));
+        }
+        let (rows, cols) = (self.depth.rows()? as usize, self.depth.cols()? as usize);
+        let real = self.camera.frame_to_real(self.depth)?;
+        let color = match self.color {
+            Some(frame) => Some(Sampler::new(frame.rows()?, frame.cols()?, frame.data()?, rows, cols)?),
+            None => None,
+        };
+        let mask = match self.mask {
+            Some((frame, user_id)) => Some((Sampler::new(frame.rows()?, frame.cols()?, frame.data()?, rows, cols)?, user_id)),
+            None => None,
+        };
+
+        let mut points = Vec::with_capacity(real.len() / (self.step * self.step));
+        let mut colors = color.as_ref().map(|_| Vec::with_capacity(points.capacity()));
+        for v in (0..rows).step_by(self.step) {
+            let row = &real[v * cols..(v + 1) * cols];
+            for u in (0..cols).step_by(self.step) {
+                if row[u].z == 0.0 {
+                    continue;
+                }
+                if let Some((users, user_id)) = &mask {
+                    let id = *users.at(v, u);
+                    let keep = match user_id {
+                        Some(user_id) => i32::from(id) == *user_id,
+                        None => id != 0,
+                    };
+                    if !keep {
+                        continue;
+                    }
+                }
+                points.push(row[u]);
+                if let (Some(colors), Some(color)) = (colors.as_mut(), &color) {
+                    colors.push(*color.at(v, u));
+                }
+            }
+        }
+        debug!(points = points.len(), "Point cloud built.");
+        Ok(PointCloud { timestamp: self.depth.timestamp()?, points, colors })
+    }
+}
+
+/// Nearest-neighbor lookup of a `rows x cols` image in depth pixel coordinates.
+struct Sampler<'a, T> {
+    data: &'a [T],
+    cols: usize,
+    row_of: Vec<usize>,
+    col_of: Vec<usize>,
+}
+
+impl<'a, T> Sampler<'a, T> {
+    fn new(rows: i32, cols: i32, data: &'a [T], depth_rows: usize, depth_cols: usize) -> NuitrackResult<Self> {
+        let (rows, cols) = (rows as usize, cols as usize);
+        if rows == 0 || cols == 0 || data.len() != rows * cols {
+            return Err(NuitrackError::OperationFailed("Frame data does not match its dimensions".into()));
+        }
+        Ok(Self {
+            data,
+            cols,
+            row_of: (0..depth_rows).map(|v| v * rows / depth_rows).collect(),
+            col_of: (0..depth_cols).map(|u| u * cols / depth_cols).collect(),
+        })
+    }
+
+    fn at(&self, v: usize, u: usize) -> &T {
+        &self.data[self.row_of[v] * self.cols + self.col_of[u]]
+    }
+}
+
+impl DepthFrame {
+    /// Deprojects every pixel with a known depth. See [`DepthFrame::point_cloud`] to add
+    /// color or a user mask.
+    pub fn to_point_cloud(&self, camera: &CameraModel) -> NuitrackResult<PointCloud> {
+        self.point_cloud(camera).build()
+    }
+
+    /// Starts a [`PointCloudBuilder`] for this frame.
+    pub fn point_cloud(&self, camera: &CameraModel) -> PointCloudBuilder<'_, DepthFrame> {
+        PointCloudBuilder::new(self, *camera)
+    }
+}
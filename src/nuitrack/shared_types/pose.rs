@@ -0,0 +1,387 @@
+//! Recognition of held static poses, such as a T-pose or a squat.
+//!
+//! A [`PoseDefinition`] combines [`PoseCondition`]s on joint angles and relative joint
+//! positions, built from [`Measure`]s:
+//!
+//! ```
+//! # use nuitrack_rs::nuitrack::shared_types::pose::{Axis, Measure, PoseCondition, PoseDefinition};
+//! # use nuitrack_rs::nuitrack::shared_types::skeleton::JointType;
+//! # use std::time::Duration;
+//! let hand_up = PoseDefinition::new(
+//!     "right_hand_up",
+//!     PoseCondition::all([
+//!         Measure::offset(JointType::RightWrist, JointType::Head, Axis::Y).at_least(0.0),
+//!         Measure::angle(JointType::RightShoulder, JointType::RightElbow, JointType::RightWrist)
+//!             .at_least(150f32.to_radians()),
+//!     ]),
+//! )
+//! .with_hold(Duration::from_millis(500));
+//! ```
+//!
+//! [`PoseClassifier`] evaluates definitions against every skeleton and reports
+//! [`PoseEvent`]s when a user has held a pose for its hold duration and when they
+//! leave it. Angles are in radians and positions in millimeters, in sensor coordinates
+//! (`y` up).
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tracing::debug;
+
+use crate::nuitrack_bridge::types::skeleton::ffi::JointType;
+use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+
+use super::error::Result as NuitrackResult;
+use super::frame::SkeletonFrameData;
+use super::kinematics::Bone;
+use super::skeleton::Skeleton;
+
+/// A sensor axis.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+/// A quantity measured on a skeleton. Measures involving an untracked joint have no
+/// value, and conditions on them are not met.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", tag = "kind"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Measure {
+    /// Angle at `vertex` between the segments to `a` and `b`; π when straight.
+    Angle { a: JointType, vertex: JointType, b: JointType },
+    /// Angle between the bone from `from` to `to` and the `y` axis: 0 pointing up,
+    /// π/2 horizontal, π pointing down.
+    Elevation { from: JointType, to: JointType },
+    /// Coordinate of `joint` minus that of `reference` along `axis`.
+    Offset { joint: JointType, reference: JointType, axis: Axis },
+    /// Distance between two joints.
+    Distance { a: JointType, b: JointType },
+}
+
+impl Measure {
+    pub fn angle(a: JointType, vertex: JointType, b: JointType) -> Self {
+        Measure::Angle { a, vertex, b }
+    }
+
+    pub fn elevation(from: JointType, to: JointType) -> Self {
+        Measure::Elevation { from, to }
+    }
+
+    pub fn offset(joint: JointType, reference: JointType, axis: Axis) -> Self {
+        Measure::Offset { joint, reference, axis }
+    }
+
+    pub fn distance(a: JointType, b: JointType) -> Self {
+        Measure::Distance { a, b }
+    }
+
+    /// The measure's value for `skeleton`.
+    pub fn evaluate(&self, skeleton: &Skeleton) -> Option<f32> {
+        match *self {
+            Measure::Angle { a, vertex, b } => skeleton.angle_at(a, vertex, b),
+            Measure::Elevation { from, to } => {
+                let v = skeleton.bone_vector(Bone { parent: from, child: to })?;
+                let length = (v.x * v.x + v.y * v.y + v.z * v.z).sqrt();
+                (length > f32::EPSILON).then(|| (v.y / length).clamp(-1.0, 1.0).acos())
+            }
+            Measure::Offset { joint, reference, axis } => {
+                let (p, r) = (skeleton.position(joint)?, skeleton.position(reference)?);
+                let component = |v: Vector3| match axis {
+                    Axis::X => v.x,
+                    Axis::Y => v.y,
+                    Axis::Z => v.z,
+                };
+                Some(component(p) - component(r))
+            }
+            Measure::Distance { a, b } => {
+                let (p, q) = (skeleton.position(a)?, skeleton.position(b)?);
+                Some(((p.x - q.x).powi(2) + (p.y - q.y).powi(2) + (p.z - q.z).powi(2)).sqrt())
+            }
+        }
+    }
+
+    /// Met when the value is in `min..=max`.
+    pub fn between(self, min: f32, max: f32) -> PoseCondition {
+        PoseCondition::Range { measure: self, min, max }
+    }
+
+    pub fn at_least(self, min: f32) -> PoseCondition {
+        self.between(min, f32::INFINITY)
+    }
+
+    pub fn at_most(self, max: f32) -> PoseCondition {
+        self.between(f32::NEG_INFINITY, max)
+    }
+}
+
+/// A boolean condition on a skeleton.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", tag = "kind"))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum PoseCondition {
+    /// The measure has a value in `min..=max`.
+    Range { measure: Measure, min: f32, max: f32 },
+    /// Every condition is met.
+    All { conditions: Vec<PoseCondition> },
+    /// At least one condition is met.
+    Any { conditions: Vec<PoseCondition> },
+    /// The condition is not met.
+    Not { condition: Box<PoseCondition> },
+}
+
+impl PoseCondition {
+    pub fn all(conditions: impl IntoIterator<Item = PoseCondition>) -> Self {
+        PoseCondition::All { conditions: conditions.into_iter().collect() }
+    }
+
+    pub fn any(conditions: impl IntoIterator<Item = PoseCondition>) -> Self {
+        PoseCondition::Any { conditions: conditions.into_iter().collect() }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(condition: PoseCondition) -> Self {
+        PoseCondition::Not { condition: Box::new(condition) }
+    }
+
+    pub fn is_met(&self, skeleton: &Skeleton) -> bool {
+        match self {
+            PoseCondition::Range { measure, min, max } => {
+                measure.evaluate(skeleton).is_some_and(|value| (*min..=*max).contains(&value))
+            }
+            PoseCondition::All { conditions } => conditions.iter().all(|c| c.is_met(skeleton)),
+            PoseCondition::Any { conditions } => conditions.iter().any(|c| c.is_met(skeleton)),
+            PoseCondition::Not { condition } => !condition.is_met(skeleton),
+        }
+    }
+}
+
+/// A named pose with timing requirements.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoseDefinition {
+    pub name: String,
+    pub condition: PoseCondition,
+    /// How long the condition must hold before the pose is entered.
+    pub hold: Duration,
+    /// How long the condition may fail before the pose is exited, to ride out
+    /// tracking noise.
+    pub release: Duration,
+}
+
+impl PoseDefinition {
+    /// A pose entered after 300 ms and exited after 100 ms.
+    pub fn new(name: impl Into<String>, condition: PoseCondition) -> Self {
+        Self { name: name.into(), condition, hold: Duration::from_millis(300), release: Duration::from_millis(100) }
+    }
+
+    pub fn with_hold(mut self, hold: Duration) -> Self {
+        self.hold = hold;
+        self
+    }
+
+    pub fn with_release(mut self, release: Duration) -> Self {
+        self.release = release;
+        self
+    }
+
+    /// Both arms stretched out sideways at shoulder height.
+    pub fn t_pose() -> Self {
+        let arm = |shoulder, elbow, wrist| {
+            [
+                Measure::angle(shoulder, elbow, wrist).at_least(150f32.to_radians()),
+                Measure::elevation(shoulder, elbow).between(65f32.to_radians(), 115f32.to_radians()),
+                Measure::offset(wrist, shoulder, Axis::Y).between(-150.0, 150.0),
+            ]
+        };
+        let left = arm(JointType::LeftShoulder, JointType::LeftElbow, JointType::LeftWrist);
+        let right = arm(JointType::RightShoulder, JointType::RightElbow, JointType::RightWrist);
+        Self::new("t_pose", PoseCondition::all(left.into_iter().chain(right)))
+    }
+
+    /// Both wrists above the head.
+    pub fn arms_raised() -> Self {
+        Self::new(
+            "arms_raised",
+            PoseCondition::all([
+                Measure::offset(JointType::LeftWrist, JointType::Head, Axis::Y).at_least(0.0),
+                Measure::offset(JointType::RightWrist, JointType::Head, Axis::Y).at_least(0.0),
+            ]),
+        )
+    }
+
+    /// Both wrists close to the hips with the elbows bent out.
+    pub fn hands_on_hips() -> Self {
+        let side = |shoulder, elbow, wrist, hip| {
+            [
+                Measure::distance(wrist, hip).at_most(150.0),
+                Measure::angle(shoulder, elbow, wrist).between(50f32.to_radians(), 140f32.to_radians()),
+            ]
+        };
+        let left = side(JointType::LeftShoulder, JointType::LeftElbow, JointType::LeftWrist, JointType::LeftHip);
+        let right = side(JointType::RightShoulder, JointType::RightElbow, JointType::RightWrist, JointType::RightHip);
+        Self::new("hands_on_hips", PoseCondition::all(left.into_iter().chain(right)))
+    }
+
+    /// The bottom of a squat: both knees bent to at most 110° with the hips lowered
+    /// to within 150 mm of knee height.
+    pub fn squat() -> Self {
+        let leg = |hip, knee, ankle| {
+            [
+                Measure::angle(hip, knee, ankle).at_most(110f32.to_radians()),
+                Measure::offset(hip, knee, Axis::Y).at_most(150.0),
+            ]
+        };
+        let left = leg(JointType::LeftHip, JointType::LeftKnee, JointType::LeftAnkle);
+        let right = leg(JointType::RightHip, JointType::RightKnee, JointType::RightAnkle);
+        Self::new("squat", PoseCondition::all(left.into_iter().chain(right)))
+    }
+}
+
+/// A change in the poses a user holds.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", tag = "kind"))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum PoseEvent {
+    /// The user has held the pose for its hold duration.
+    PoseEntered {
+        user_id: i32,
+        name: String,
+        /// When the user started holding the pose, typically in microseconds.
+        since: u64,
+        timestamp: u64,
+    },
+    /// The user left the pose, or was lost while holding it.
+    PoseExited {
+        user_id: i32,
+        name: String,
+        /// Time from entering the pose until leaving it.
+        held: Duration,
+        timestamp: u64,
+    },
+}
+
+impl PoseEvent {
+    pub fn user_id(&self) -> i32 {
+        match self {
+            PoseEvent::PoseEntered { user_id, .. } | PoseEvent::PoseExited { user_id, .. } => *user_id,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            PoseEvent::PoseEntered { name, .. } | PoseEvent::PoseExited { name, .. } => name,
+        }
+    }
+}
+
+/// Progress of one user through one pose.
+#[derive(Debug, Clone, Copy, Default)]
+struct PoseState {
+    /// When the condition started being met, while not yet entered.
+    met_since: Option<u64>,
+    /// When the pose was entered.
+    entered: Option<u64>,
+    /// When the condition stopped being met, while entered.
+    failed_since: Option<u64>,
+}
+
+/// Evaluates [`PoseDefinition`]s against skeletons and tracks who holds which pose.
+#[derive(Debug, Clone, Default)]
+pub struct PoseClassifier {
+    definitions: Vec<PoseDefinition>,
+    /// Per user, one state per definition.
+    users: HashMap<i32, Vec<PoseState>>,
+}
+
+impl PoseClassifier {
+    pub fn new(definitions: impl IntoIterator<Item = PoseDefinition>) -> Self {
+        Self { definitions: definitions.into_iter().collect(), users: HashMap::new() }
+    }
+
+    pub fn with_definition(mut self, definition: PoseDefinition) -> Self {
+        self.definitions.push(definition);
+        self.users.clear();
+        self
+    }
+
+    pub fn definitions(&self) -> &[PoseDefinition] {
+        &self.definitions
+    }
+
+    /// Names of the poses `user_id` currently holds.
+    pub fn active_poses(&self, user_id: i32) -> impl Iterator<Item = &str> {
+        self.users
+            .get(&user_id)
+            .into_iter()
+            .flat_map(|states| states.iter().zip(&self.definitions))
+            .filter(|(state, _)| state.entered.is_some())
+            .map(|(_, definition)| definition.name.as_str())
+    }
+
+    /// Forgets every user without reporting exits.
+    pub fn reset(&mut self) {
+        self.users.clear();
+    }
+
+    /// Evaluates `skeletons` observed at `timestamp` (in microseconds) and returns the
+    /// resulting events. Users missing from `skeletons` exit all their poses.
+    pub fn apply(&mut self, timestamp: u64, skeletons: &[Skeleton]) -> Vec<PoseEvent> {
+        let mut events = Vec::new();
+        let lost: Vec<i32> = self.users.keys().filter(|id| !skeletons.iter().any(|s| s.user_id == **id)).copied().collect();
+        for user_id in lost {
+            for (state, definition) in self.users.remove(&user_id).unwrap_or_default().iter().zip(&self.definitions) {
+                if let Some(entered) = state.entered {
+                    events.push(Self::exited(user_id, definition, entered, timestamp));
+                }
+            }
+        }
+        for skeleton in skeletons {
+            let user_id = skeleton.user_id;
+            let states = self.users.entry(user_id).or_insert_with(|| vec![PoseState::default(); self.definitions.len()]);
+            for (state, definition) in states.iter_mut().zip(&self.definitions) {
+                let met = definition.condition.is_met(skeleton);
+                match state.entered {
+                    None if met => {
+                        let since = *state.met_since.get_or_insert(timestamp);
+                        if timestamp.saturating_sub(since) >= definition.hold.as_micros() as u64 {
+                            debug!(user_id, name = definition.name, "Pose entered.");
+                            *state = PoseState { entered: Some(timestamp), ..Default::default() };
+                            events.push(PoseEvent::PoseEntered { user_id, name: definition.name.clone(), since, timestamp });
+                        }
+                    }
+                    None => state.met_since = None,
+                    Some(_) if met => state.failed_since = None,
+                    Some(entered) => {
+                        let since = *state.failed_since.get_or_insert(timestamp);
+                        if timestamp.saturating_sub(since) >= definition.release.as_micros() as u64 {
+                            debug!(user_id, name = definition.name, "Pose exited.");
+                            *state = PoseState::default();
+                            events.push(Self::exited(user_id, definition, entered, timestamp));
+                        }
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    /// [`apply`](Self::apply) to every skeleton of `frame`.
+    pub fn process_frame(&mut self, frame: &impl SkeletonFrameData) -> NuitrackResult<Vec<PoseEvent>> {
+        Ok(self.apply(frame.timestamp()?, frame.skeletons()?))
+    }
+
+    fn exited(user_id: i32, definition: &PoseDefinition, entered: u64, timestamp: u64) -> PoseEvent {
+        PoseEvent::PoseExited {
+            user_id,
+            name: definition.name.clone(),
+            held: Duration::from_micros(timestamp.saturating_sub(entered)),
+            timestamp,
+        }
+    }
+}
@@ -1,11 +1,42 @@
+use std::sync::Arc;
 use cxx::SharedPtr;
 use crate::{nuitrack::shared_types::error::Result as NuitrackResult, nuitrack_bridge::types::rgb_frame::ffi::{self as rgb_frame_ffi}};
 use tracing::{instrument, trace, trace_span};
 
+use super::frame::{ColorFrameData, Frame};
+
 pub use crate::nuitrack_bridge::types::rgb_frame::ffi::Color3;
 
+/// A color image produced by the color sensor.
+///
+/// Cloning an `RGBFrame` is cheap: the underlying buffer is shared, not copied.
+#[derive(Clone)]
 pub struct RGBFrame {
-    internal_ptr: SharedPtr<rgb_frame_ffi::RGBFrame>,
+    inner: RGBFrameInner,
+}
+
+/// Where the frame's data lives: in SDK memory or in a Rust-side buffer.
+#[derive(Clone)]
+enum RGBFrameInner {
+    Ffi(SharedPtr<rgb_frame_ffi::RGBFrame>),
+    Owned(Arc<OwnedRGBFrame>),
+}
+
+/// A color image held entirely in Rust memory.
+///
+/// Unlike [`RGBFrame`], it does not keep any SDK buffer alive, so it can be
+/// constructed freely, stored indefinitely and sent anywhere.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedRGBFrame {
+    pub rows: i32,
+    pub cols: i32,
+    pub id: u64,
+    /// Typically in microseconds.
+    pub timestamp: u64,
+    /// `rows * cols` pixels, row-major.
+    pub data: Vec<Color3>,
 }
 
 impl RGBFrame {
@@ -21,20 +52,46 @@ impl RGBFrame {
             None
         } else {
             Some(RGBFrame {
-                internal_ptr: ffi_ptr,
+                inner: RGBFrameInner::Ffi(ffi_ptr),
             })
         }
     }
 
+    /// Creates an `RGBFrame` backed by a Rust-side buffer instead of SDK memory.
+    ///
+    /// This is how pure-Rust backends (such as the simulated backend) produce frames.
+    /// `data` is expected to hold `rows * cols` pixels in row-major order.
+    pub fn synthetic(rows: i32, cols: i32, id: u64, timestamp: u64, data: Vec<Color3>) -> Self {
+        OwnedRGBFrame { rows, cols, id, timestamp, data }.into()
+    }
+
+    /// Copies the frame into an [`OwnedRGBFrame`], releasing any tie to SDK memory.
+    #[instrument(skip(self))]
+    pub fn to_owned(&self) -> NuitrackResult<OwnedRGBFrame> {
+        match &self.inner {
+            RGBFrameInner::Ffi(_) => Ok(OwnedRGBFrame {
+                rows: self.rows()?,
+                cols: self.cols()?,
+                id: self.frame_id()?,
+                timestamp: self.timestamp()?,
+                data: self.data()?.to_vec(),
+            }),
+            RGBFrameInner::Owned(frame) => Ok(OwnedRGBFrame::clone(frame)),
+        }
+    }
+
     /// Gets the number of rows (height) of the color frame.
     ///
     /// # Returns
     /// A `NuitrackResult` containing the number of rows as an `i32`.
     #[instrument(skip(self))]
     pub fn rows(&self) -> NuitrackResult<i32> {
-        trace_span!("ffi", function = "rgb_frame_ffi::rows").in_scope(||
-            Ok(rgb_frame_ffi::rows(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            RGBFrameInner::Ffi(ptr) => trace_span!("ffi", function = "rgb_frame_ffi::rows").in_scope(||
+                Ok(rgb_frame_ffi::rows(ptr)?)
+            ),
+            RGBFrameInner::Owned(frame) => Ok(frame.rows),
+        }
     }
 
     /// Gets the number of columns (width) of the color frame.
@@ -43,9 +100,12 @@ impl RGBFrame {
     /// A `NuitrackResult` containing the number of columns as an `i32`.
     #[instrument(skip(self))]
     pub fn cols(&self) -> NuitrackResult<i32> {
-        trace_span!("ffi", function = "rgb_frame_ffi::cols").in_scope(||
-            Ok(rgb_frame_ffi::cols(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            RGBFrameInner::Ffi(ptr) => trace_span!("ffi", function = "rgb_frame_ffi::cols").in_scope(||
+                Ok(rgb_frame_ffi::cols(ptr)?)
+            ),
+            RGBFrameInner::Owned(frame) => Ok(frame.cols),
+        }
     }
 
     /// Gets the unique ID of the color frame.
@@ -54,9 +114,12 @@ impl RGBFrame {
     /// A `NuitrackResult` containing the frame ID as a `u64`.
     #[instrument(skip(self))]
     pub fn frame_id(&self) -> NuitrackResult<u64> {
-        trace_span!("ffi", function = "rgb_frame_ffi::id").in_scope(||
-            Ok(rgb_frame_ffi::id(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            RGBFrameInner::Ffi(ptr) => trace_span!("ffi", function = "rgb_frame_ffi::id").in_scope(||
+                Ok(rgb_frame_ffi::id(ptr)?)
+            ),
+            RGBFrameInner::Owned(frame) => Ok(frame.id),
+        }
     }
 
     /// Gets the timestamp of the color frame, typically in microseconds.
@@ -66,9 +129,12 @@ impl RGBFrame {
     /// A `NuitrackResult` containing the timestamp as a `u64`.
     #[instrument(skip(self))]
     pub fn timestamp(&self) -> NuitrackResult<u64> {
-        trace_span!("ffi", function = "rgb_frame_ffi::timestamp").in_scope(||
-            Ok(rgb_frame_ffi::timestamp(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            RGBFrameInner::Ffi(ptr) => trace_span!("ffi", function = "rgb_frame_ffi::timestamp").in_scope(||
+                Ok(rgb_frame_ffi::timestamp(ptr)?)
+            ),
+            RGBFrameInner::Owned(frame) => Ok(frame.timestamp),
+        }
     }
 
     /// Gets a slice representing the pixel data of the color frame.
@@ -81,18 +147,91 @@ impl RGBFrame {
     /// A `NuitrackResult` containing a slice of `Color3` pixels.
     #[instrument(skip(self))]
     pub fn data(&self) -> NuitrackResult<&[Color3]> {
-        trace_span!("ffi", function = "rgb_frame_ffi::data").in_scope(||
-            Ok(rgb_frame_ffi::data(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            RGBFrameInner::Ffi(ptr) => trace_span!("ffi", function = "rgb_frame_ffi::data").in_scope(||
+                Ok(rgb_frame_ffi::data(ptr)?)
+            ),
+            RGBFrameInner::Owned(frame) => Ok(&frame.data),
+        }
     }
 
     /// Provides access to the internal `SharedPtr` to the FFI `RGBFrame` type.
     ///
+    /// Returns `None` for frames that were not produced by the Nuitrack SDK.
     /// This is `pub(crate)` and primarily intended for internal use or advanced scenarios
     /// where direct access to the FFI pointer is necessary (e.g., passing it back to
     /// another C++ function).
     #[allow(dead_code)] // Allow dead code if not immediately used elsewhere in crate
-    pub(crate) fn ffi_ptr(&self) -> &SharedPtr<rgb_frame_ffi::RGBFrame> {
-        &self.internal_ptr
+    pub(crate) fn ffi_ptr(&self) -> Option<&SharedPtr<rgb_frame_ffi::RGBFrame>> {
+        match &self.inner {
+            RGBFrameInner::Ffi(ptr) => Some(ptr),
+            RGBFrameInner::Owned(_) => None,
+        }
+    }
+}
+
+impl From<OwnedRGBFrame> for RGBFrame {
+    fn from(frame: OwnedRGBFrame) -> Self {
+        RGBFrame { inner: RGBFrameInner::Owned(Arc::new(frame)) }
+    }
+}
+
+impl Frame for RGBFrame {
+    type Owned = OwnedRGBFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        RGBFrame::timestamp(self)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedRGBFrame> {
+        RGBFrame::to_owned(self)
+    }
+}
+
+impl ColorFrameData for RGBFrame {
+    fn rows(&self) -> NuitrackResult<i32> {
+        RGBFrame::rows(self)
+    }
+
+    fn cols(&self) -> NuitrackResult<i32> {
+        RGBFrame::cols(self)
+    }
+
+    fn frame_id(&self) -> NuitrackResult<u64> {
+        RGBFrame::frame_id(self)
+    }
+
+    fn data(&self) -> NuitrackResult<&[Color3]> {
+        RGBFrame::data(self)
+    }
+}
+
+impl Frame for OwnedRGBFrame {
+    type Owned = OwnedRGBFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        Ok(self.timestamp)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedRGBFrame> {
+        Ok(self.clone())
     }
-}
\ No newline at end of file
+}
+
+impl ColorFrameData for OwnedRGBFrame {
+    fn rows(&self) -> NuitrackResult<i32> {
+        Ok(self.rows)
+    }
+
+    fn cols(&self) -> NuitrackResult<i32> {
+        Ok(self.cols)
+    }
+
+    fn frame_id(&self) -> NuitrackResult<u64> {
+        Ok(self.id)
+    }
+
+    fn data(&self) -> NuitrackResult<&[Color3]> {
+        Ok(&self.data)
+    }
+}
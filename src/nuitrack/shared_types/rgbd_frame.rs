@@ -0,0 +1,175 @@
+//! Depth-to-color registration.
+//!
+//! The depth and color sensors have their own pixel grids and intrinsics, so a depth
+//! pixel and the color pixel at the same coordinates generally do not see the same
+//! point. [`Registration`] reprojects every depth pixel into the color image and
+//! produces an [`RgbdFrame`] in the depth grid, where each pixel carries both its
+//! depth and the color seen there.
+//!
+//! When the SDK's own registration is enabled
+//! (`NuitrackSessionBuilder::with_depth_to_color_registration`), depth already lies in
+//! the color camera's view; a [`Registration`] built from the sensors' output modes then
+//! reduces to resampling between the two resolutions.
+
+use tracing::{instrument, trace};
+
+use crate::nuitrack_bridge::types::output_mode::ffi::OutputMode;
+use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+
+use super::camera_model::CameraModel;
+use super::error::{NuitrackError, Result as NuitrackResult};
+use super::frame::{ColorFrameData, DepthFrameData, Frame};
+use super::rgb_frame::Color3;
+
+/// Rigid transform from the depth camera's real-world coordinate system to the color
+/// camera's, in Nuitrack's convention (millimeters, `y` up).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Extrinsics {
+    /// Row-major rotation matrix.
+    pub rotation: [[f32; 3]; 3],
+    /// Translation in millimeters.
+    pub translation: [f32; 3],
+}
+
+impl Default for Extrinsics {
+    /// The identity transform: both cameras share an optical center and orientation.
+    fn default() -> Self {
+        Self {
+            rotation: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            translation: [0.0; 3],
+        }
+    }
+}
+
+impl Extrinsics {
+    /// A pure translation, e.g. the baseline between the two lenses.
+    pub fn from_translation(x: f32, y: f32, z: f32) -> Self {
+        Self { translation: [x, y, z], ..Default::default() }
+    }
+
+    fn apply(&self, p: Vector3) -> Vector3 {
+        let r = &self.rotation;
+        let t = &self.translation;
+        Vector3 {
+            x: r[0][0] * p.x + r[0][1] * p.y + r[0][2] * p.z + t[0],
+            y: r[1][0] * p.x + r[1][1] * p.y + r[1][2] * p.z + t[1],
+            z: r[2][0] * p.x + r[2][1] * p.y + r[2][2] * p.z + t[2],
+        }
+    }
+}
+
+/// A depth map with a color sample per depth pixel.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RgbdFrame {
+    pub rows: i32,
+    pub cols: i32,
+    /// Timestamp of the depth frame, typically in microseconds.
+    pub timestamp: u64,
+    /// `rows * cols` depth values in millimeters, row-major.
+    pub depth: Vec<u16>,
+    /// `rows * cols` colors, row-major. `None` where the depth is unknown or the point
+    /// falls outside the color image.
+    pub color: Vec<Option<Color3>>,
+}
+
+impl RgbdFrame {
+    pub fn depth_at(&self, row: i32, col: i32) -> Option<u16> {
+        self.index(row, col).map(|i| self.depth[i])
+    }
+
+    pub fn color_at(&self, row: i32, col: i32) -> Option<Color3> {
+        self.index(row, col).and_then(|i| self.color[i])
+    }
+
+    /// Fraction of pixels that received a color sample.
+    pub fn coverage(&self) -> f32 {
+        if self.color.is_empty() {
+            return 0.0;
+        }
+        self.color.iter().filter(|c| c.is_some()).count() as f32 / self.color.len() as f32
+    }
+
+    fn index(&self, row: i32, col: i32) -> Option<usize> {
+        ((0..self.rows).contains(&row) && (0..self.cols).contains(&col)).then(|| (row * self.cols + col) as usize)
+    }
+}
+
+impl Frame for RgbdFrame {
+    type Owned = RgbdFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        Ok(self.timestamp)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<RgbdFrame> {
+        Ok(self.clone())
+    }
+}
+
+/// Maps depth pixels into the color image.
+///
+/// Build one per session from the sensors' output modes, e.g. with
+/// `ActiveDeviceContext::registration`, and reuse it for every frame pair.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Registration {
+    pub depth_mode: OutputMode,
+    pub color_mode: OutputMode,
+    pub extrinsics: Extrinsics,
+}
+
+impl Registration {
+    /// Registration with identity extrinsics, which is exact when the SDK registers
+    /// depth to color and a close approximation for sensors with a small baseline.
+    pub fn new(depth_mode: OutputMode, color_mode: OutputMode) -> Self {
+        Self { depth_mode, color_mode, extrinsics: Extrinsics::default() }
+    }
+
+    pub fn with_extrinsics(mut self, extrinsics: Extrinsics) -> Self {
+        self.extrinsics = extrinsics;
+        self
+    }
+
+    /// Produces an [`RgbdFrame`] in `depth`'s pixel grid, sampling `color` at the
+    /// nearest pixel each depth point projects to.
+    #[instrument(skip_all)]
+    pub fn align(&self, depth: &impl DepthFrameData, color: &impl ColorFrameData) -> NuitrackResult<RgbdFrame> {
+        let (rows, cols) = (depth.rows()?, depth.cols()?);
+        let (color_rows, color_cols) = (color.rows()?, color.cols()?);
+        let depth_data = depth.data()?;
+        let color_data = color.data()?;
+        if depth_data.len() != (rows * cols) as usize || color_data.len() != (color_rows * color_cols) as usize {
+            return Err(NuitrackError::OperationFailed("Frame data does not match its dimensions".into()));
+        }
+
+        let from = CameraModel::new(&self.depth_mode)?.scaled_to(cols, rows);
+        let to = CameraModel::new(&self.color_mode)?.scaled_to(color_cols, color_rows);
+        let mut aligned = Vec::with_capacity(depth_data.len());
+        for (i, &z) in depth_data.iter().enumerate() {
+            if z == 0 {
+                aligned.push(None);
+                continue;
+            }
+            let pixel = Vector3 { x: (i as i32 % cols) as f32, y: (i as i32 / cols) as f32, z: f32::from(z) };
+            let projected = to.real_to_proj(self.extrinsics.apply(from.proj_to_real(pixel)));
+            let (cu, cv) = (projected.x.round(), projected.y.round());
+            let inside = to.contains(Vector3 { x: cu, y: cv, z: projected.z });
+            aligned.push(inside.then(|| color_data[cv as usize * color_cols as usize + cu as usize]));
+        }
+
+        let frame = RgbdFrame {
+            rows,
+            cols,
+            timestamp: depth.timestamp()?,
+            depth: depth_data.to_vec(),
+            color: aligned,
+        };
+        trace!(coverage = frame.coverage(), "Depth frame registered to color.");
+        Ok(frame)
+    }
+}
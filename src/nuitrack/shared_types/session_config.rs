@@ -1,7 +1,13 @@
 // In src/nuitrack/mod.rs or a new session_config.rs
 
+use std::time::Duration;
+
 use cxx::SharedPtr;
-use crate::nuitrack_bridge::device::ffi::Device as FFIDevice;
+use crate::nuitrack_bridge::device::ffi::{
+    ActivationStatus as FFIActivationStatus, Device as FFIDevice, StreamType as FFIStreamType,
+};
+
+pub use crate::nuitrack_bridge::types::video_mode::ffi::VideoMode;
 
  // Assuming error module is at super::error
 
@@ -12,11 +18,71 @@ pub struct DiscoveredDeviceInfo {
     pub serial_number: String,
     pub provider_name: String, // Add if you can get it
     pub original_index: usize,
+    /// License state at discovery time. Virtual devices report [`ActivationStatus::Unknown`].
+    pub activation: ActivationStatus,
+    /// Modes the depth stream supports; empty if the device has none.
+    pub depth_modes: Vec<VideoMode>,
+    /// Modes the color stream supports; empty if the device has none.
+    pub color_modes: Vec<VideoMode>,
     // Keep the FFI Ptr to allow selection based on this discovered object later
     // This means FfiDevice needs to be Send+Sync for SharedPtr to be Send+Sync for Vec to be Send
     pub(crate) ffi_device_ptr: SharedPtr<FFIDevice>,
 }
 
+impl DiscoveredDeviceInfo {
+    pub fn video_modes(&self, stream: VideoStream) -> &[VideoMode] {
+        match stream {
+            VideoStream::Depth => &self.depth_modes,
+            VideoStream::Color => &self.color_modes,
+        }
+    }
+}
+
+/// License state of a sensor, as reported by `NuitrackDevice::getActivationStatus`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ActivationStatus {
+    /// Not activated; Nuitrack refuses to process frames.
+    None,
+    /// Trial license; sessions stop after a few minutes, see [`ActivationStatus::time_limit`].
+    Trial,
+    Pro,
+    Ai,
+    /// The SDK could not determine the status, or the device is virtual.
+    Unknown,
+}
+
+impl ActivationStatus {
+    /// Whether the device runs without the trial time limit.
+    pub fn is_licensed(&self) -> bool {
+        matches!(self, Self::Pro | Self::Ai)
+    }
+
+    /// Session time limit imposed by this license, if any.
+    ///
+    /// The SDK offers no way to query the limit, so for [`ActivationStatus::Trial`]
+    /// this is the 3 minutes Nuitrack documents for trial licenses. Treat it as an
+    /// estimate; a different SDK release may stop sooner or later.
+    pub fn time_limit(&self) -> Option<Duration> {
+        match self {
+            Self::Trial => Some(Duration::from_secs(3 * 60)),
+            _ => None,
+        }
+    }
+}
+
+impl From<FFIActivationStatus> for ActivationStatus {
+    fn from(status: FFIActivationStatus) -> Self {
+        match status {
+            FFIActivationStatus::NONE => Self::None,
+            FFIActivationStatus::TRIAL => Self::Trial,
+            FFIActivationStatus::PRO => Self::Pro,
+            FFIActivationStatus::AI => Self::Ai,
+            _ => Self::Unknown,
+        }
+    }
+}
+
 // --- For Builder Configuration ---
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ModuleType {
@@ -42,8 +108,120 @@ pub enum DeviceSelector {
     // AllAvailable,
 }
 
+/// Which device to use and what to create on it.
+///
+/// Build one with [`DeviceConfig::new`] and the `with_*` methods; fields may be
+/// added in future releases.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct DeviceConfig {
     pub selector: DeviceSelector,
     pub modules_to_create: Vec<ModuleType>,
+    /// Depth mode to select before the modules are created; `None` keeps the SDK default.
+    pub depth_mode: Option<VideoModeRequest>,
+    /// Color mode to select before the modules are created; `None` keeps the SDK default.
+    pub color_mode: Option<VideoModeRequest>,
+}
+
+impl Default for DeviceConfig {
+    fn default() -> Self {
+        Self {
+            selector: DeviceSelector::DefaultSingle,
+            modules_to_create: Vec::new(),
+            depth_mode: None,
+            color_mode: None,
+        }
+    }
+}
+
+impl DeviceConfig {
+    pub fn new(selector: DeviceSelector, modules_to_create: Vec<ModuleType>) -> Self {
+        Self { selector, modules_to_create, ..Default::default() }
+    }
+
+    pub fn with_depth_mode(mut self, request: VideoModeRequest) -> Self {
+        self.depth_mode = Some(request);
+        self
+    }
+
+    pub fn with_color_mode(mut self, request: VideoModeRequest) -> Self {
+        self.color_mode = Some(request);
+        self
+    }
+
+    pub(crate) fn video_mode_request(&self, stream: VideoStream) -> Option<&VideoModeRequest> {
+        match stream {
+            VideoStream::Depth => self.depth_mode.as_ref(),
+            VideoStream::Color => self.color_mode.as_ref(),
+        }
+    }
+}
+
+/// A sensor stream whose [`VideoMode`] can be chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VideoStream {
+    Depth,
+    Color,
+}
+
+impl VideoStream {
+    pub const ALL: [VideoStream; 2] = [VideoStream::Depth, VideoStream::Color];
+}
+
+impl From<VideoStream> for FFIStreamType {
+    fn from(stream: VideoStream) -> Self {
+        match stream {
+            VideoStream::Depth => FFIStreamType::DEPTH,
+            VideoStream::Color => FFIStreamType::COLOR,
+        }
+    }
+}
+
+/// How a [`DeviceConfig`] chooses among a stream's available modes.
+///
+/// Zero fields in the requested mode match any value, so
+/// `Exact(VideoMode { width: 0, height: 0, fps: 60 })` picks the largest 60 FPS mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VideoModeRequest {
+    /// Only a mode matching every non-zero field exactly; zero fields match any
+    /// value. Session creation fails if no mode matches.
+    Exact(VideoMode),
+    /// The available mode closest in resolution, then in frame rate.
+    BestMatch(VideoMode),
+}
+
+impl VideoModeRequest {
+    /// Picks a mode from `available`, or `None` if the request cannot be met.
+    /// Ties go to the higher frame rate, then the larger resolution.
+    pub fn resolve(&self, available: &[VideoMode]) -> Option<VideoMode> {
+        fn distance(requested: i32, actual: i32) -> i64 {
+            if requested == 0 { 0 } else { (i64::from(requested) - i64::from(actual)).abs() }
+        }
+        let preference = |mode: &&VideoMode| (mode.fps, i64::from(mode.width) * i64::from(mode.height));
+        match self {
+            Self::Exact(requested) => available
+                .iter()
+                .filter(|mode| {
+                    distance(requested.width, mode.width) == 0
+                        && distance(requested.height, mode.height) == 0
+                        && distance(requested.fps, mode.fps) == 0
+                })
+                .max_by_key(preference)
+                .copied(),
+            Self::BestMatch(requested) => available
+                .iter()
+                .min_by_key(|mode| {
+                    let (fps, area) = preference(mode);
+                    (
+                        distance(requested.width, mode.width) + distance(requested.height, mode.height),
+                        distance(requested.fps, mode.fps),
+                        std::cmp::Reverse(fps),
+                        std::cmp::Reverse(area),
+                    )
+                })
+                .copied(),
+        }
+    }
 }
@@ -3,6 +3,7 @@ use crate::nuitrack_bridge::types::skeleton::ffi::{self as skeleton_ffi};
 
 pub use crate::nuitrack_bridge::types::skeleton::ffi::JointType;
 pub use crate::nuitrack_bridge::types::skeleton::ffi::Joint;
+pub use crate::nuitrack_bridge::types::skeleton::ffi::Orientation;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
@@ -27,4 +28,56 @@ impl Skeleton {
             joints,
         }
     }
-}
\ No newline at end of file
+
+    /// The joint of the given type, if the skeleton has it.
+    pub fn joint(&self, joint_type: JointType) -> Option<&Joint> {
+        self.joints.iter().find(|j| j.joint_type == joint_type)
+    }
+}
+
+impl JointType {
+    /// Every joint type in SDK order, including the reserved `None`.
+    pub const ALL: [JointType; 25] = [
+        JointType::None,
+        JointType::Head,
+        JointType::Neck,
+        JointType::Torso,
+        JointType::Waist,
+        JointType::LeftCollar,
+        JointType::LeftShoulder,
+        JointType::LeftElbow,
+        JointType::LeftWrist,
+        JointType::LeftHand,
+        JointType::LeftFingertip,
+        JointType::RightCollar,
+        JointType::RightShoulder,
+        JointType::RightElbow,
+        JointType::RightWrist,
+        JointType::RightHand,
+        JointType::RightFingertip,
+        JointType::LeftHip,
+        JointType::LeftKnee,
+        JointType::LeftAnkle,
+        JointType::LeftFoot,
+        JointType::RightHip,
+        JointType::RightKnee,
+        JointType::RightAnkle,
+        JointType::RightFoot,
+    ];
+
+    /// Whether the SDK documents this joint as not used in the current version, or it
+    /// is the reserved `None`. Per `JointType` in `nuitrack/types/Skeleton.h` these are
+    /// the right collar (the collar is reported on `LeftCollar`), the fingertips and
+    /// the feet.
+    pub fn is_unused(self) -> bool {
+        matches!(
+            self,
+            JointType::None
+                | JointType::RightCollar
+                | JointType::LeftFingertip
+                | JointType::RightFingertip
+                | JointType::LeftFoot
+                | JointType::RightFoot
+        )
+    }
+}
@@ -0,0 +1,339 @@
+//! Temporal smoothing of skeleton joints.
+//!
+//! [`SkeletonFilter`] keeps one filter per user and per [`JointType`] and smooths
+//! `Joint::real` using the time between frame timestamps, so results do not depend on
+//! the frame rate. Each joint can use its own [`FilterConfig`], e.g. a responsive
+//! filter for the hands and a heavier one for the torso.
+//!
+//! For streams, wrap a skeleton stream in
+//! [`FilteredSkeletonStream`](crate::nuitrack::async_api::filtered_skeleton_stream::FilteredSkeletonStream).
+
+use std::collections::HashMap;
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use tracing::{debug, trace};
+
+use crate::nuitrack_bridge::types::skeleton::ffi::{Joint, JointType};
+use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+
+use super::camera_model::CameraModel;
+use super::error::Result as NuitrackResult;
+use super::frame::SkeletonFrameData;
+use super::skeleton::Skeleton;
+use super::skeleton_frame::SkeletonFrame;
+
+/// The filter applied to one joint. Parameters are in millimeters and seconds.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", tag = "kind"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterConfig {
+    /// Pass positions through unchanged.
+    None,
+    /// The 1€ filter (Casiez et al.): a low-pass filter whose cutoff rises with speed,
+    /// removing jitter at rest while keeping fast motion responsive.
+    OneEuro {
+        /// Cutoff frequency at rest, in Hz. Lower values remove more jitter.
+        min_cutoff: f32,
+        /// How much the cutoff rises per mm/s of speed. Higher values reduce lag.
+        beta: f32,
+        /// Cutoff frequency for the speed estimate, in Hz.
+        derivative_cutoff: f32,
+    },
+    /// Holt's double exponential smoothing, which tracks a level and a trend.
+    ///
+    /// The weights are given for frames [`REFERENCE_FRAME_INTERVAL`] apart and rescaled
+    /// to the actual time between frames.
+    DoubleExponential {
+        /// Weight of each new position, in `0..=1`.
+        alpha: f32,
+        /// Weight of each new trend estimate, in `0..=1`.
+        beta: f32,
+    },
+    /// A constant-velocity Kalman filter per axis.
+    Kalman {
+        /// Standard deviation of the unmodeled acceleration, in mm/s².
+        acceleration_std: f32,
+        /// Standard deviation of the measurement noise, in mm.
+        measurement_std: f32,
+    },
+}
+
+impl FilterConfig {
+    /// 1€ filter tuned for joint positions in millimeters.
+    pub fn one_euro() -> Self {
+        FilterConfig::OneEuro { min_cutoff: 1.0, beta: 0.005, derivative_cutoff: 1.0 }
+    }
+
+    pub fn double_exponential() -> Self {
+        FilterConfig::DoubleExponential { alpha: 0.5, beta: 0.3 }
+    }
+
+    pub fn kalman() -> Self {
+        FilterConfig::Kalman { acceleration_std: 3000.0, measurement_std: 15.0 }
+    }
+}
+
+impl Default for FilterConfig {
+    fn default() -> Self {
+        Self::one_euro()
+    }
+}
+
+/// Which filter each joint uses.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SkeletonFilterConfig {
+    /// Filter for joints without an entry in `joints`.
+    pub default: FilterConfig,
+    pub joints: HashMap<JointType, FilterConfig>,
+    /// A user's filters restart when their skeleton is missing for longer than this.
+    pub reset_after: Option<Duration>,
+}
+
+impl SkeletonFilterConfig {
+    pub fn new(default: FilterConfig) -> Self {
+        Self { default, joints: HashMap::new(), reset_after: Some(Duration::from_millis(500)) }
+    }
+
+    pub fn with_joint(mut self, joint: JointType, config: FilterConfig) -> Self {
+        self.joints.insert(joint, config);
+        self
+    }
+
+    pub fn with_joints(mut self, joints: impl IntoIterator<Item = JointType>, config: FilterConfig) -> Self {
+        self.joints.extend(joints.into_iter().map(|joint| (joint, config)));
+        self
+    }
+
+    pub fn with_reset_after(mut self, reset_after: Option<Duration>) -> Self {
+        self.reset_after = reset_after;
+        self
+    }
+
+    pub fn config_for(&self, joint: JointType) -> FilterConfig {
+        self.joints.get(&joint).copied().unwrap_or(self.default)
+    }
+}
+
+/// State of one scalar filter.
+#[derive(Debug, Clone, Copy)]
+enum AxisState {
+    OneEuro { value: f32, derivative: f32 },
+    DoubleExponential { level: f32, trend: f32 },
+    /// Position, velocity and the covariance `[p00, p01, p11]`.
+    Kalman { position: f32, velocity: f32, covariance: [f32; 3] },
+}
+
+/// Frame interval at which [`FilterConfig::DoubleExponential`] weights apply as given:
+/// Nuitrack's default 30 FPS.
+pub const REFERENCE_FRAME_INTERVAL: f32 = 1.0 / 30.0;
+
+/// A smoothing weight given per [`REFERENCE_FRAME_INTERVAL`], rescaled so that the
+/// old value decays by the same amount per second at any frame interval.
+fn rescaled_weight(weight: f32, dt: f32) -> f32 {
+    1.0 - (1.0 - weight.clamp(0.0, 1.0)).powf(dt / REFERENCE_FRAME_INTERVAL)
+}
+
+/// Smoothing factor of a first-order low-pass filter with the given cutoff.
+fn low_pass_alpha(cutoff: f32, dt: f32) -> f32 {
+    let tau = 1.0 / (2.0 * PI * cutoff.max(1e-3));
+    1.0 / (1.0 + tau / dt)
+}
+
+impl AxisState {
+    fn start(config: FilterConfig, x: f32, measurement_std: f32) -> Option<Self> {
+        match config {
+            FilterConfig::None => None,
+            FilterConfig::OneEuro { .. } => Some(AxisState::OneEuro { value: x, derivative: 0.0 }),
+            FilterConfig::DoubleExponential { .. } => Some(AxisState::DoubleExponential { level: x, trend: 0.0 }),
+            FilterConfig::Kalman { .. } => {
+                let r = measurement_std * measurement_std;
+                Some(AxisState::Kalman { position: x, velocity: 0.0, covariance: [r, 0.0, r * 100.0] })
+            }
+        }
+    }
+
+    fn update(&mut self, config: FilterConfig, x: f32, dt: f32) -> f32 {
+        match (self, config) {
+            (AxisState::OneEuro { value, derivative }, FilterConfig::OneEuro { min_cutoff, beta, derivative_cutoff }) => {
+                let raw_derivative = (x - *value) / dt;
+                *derivative += low_pass_alpha(derivative_cutoff, dt) * (raw_derivative - *derivative);
+                let cutoff = min_cutoff + beta * derivative.abs();
+                *value += low_pass_alpha(cutoff, dt) * (x - *value);
+                *value
+            }
+            (AxisState::DoubleExponential { level, trend }, FilterConfig::DoubleExponential { alpha, beta }) => {
+                let (alpha, beta) = (rescaled_weight(alpha, dt), rescaled_weight(beta, dt));
+                let previous = *level;
+                *level = alpha * x + (1.0 - alpha) * (*level + *trend * dt);
+                *trend = beta * (*level - previous) / dt + (1.0 - beta) * *trend;
+                *level
+            }
+            (
+                AxisState::Kalman { position, velocity, covariance },
+                FilterConfig::Kalman { acceleration_std, measurement_std },
+            ) => {
+                // Predict with a constant-velocity model and white-noise acceleration.
+                let q = acceleration_std * acceleration_std;
+                let [p00, p01, p11] = *covariance;
+                *position += *velocity * dt;
+                let p00 = p00 + dt * (2.0 * p01 + dt * p11) + q * dt.powi(4) / 4.0;
+                let p01 = p01 + dt * p11 + q * dt.powi(3) / 2.0;
+                let p11 = p11 + q * dt * dt;
+                // Correct with the measured position.
+                let s = p00 + measurement_std * measurement_std;
+                let (k0, k1) = (p00 / s, p01 / s);
+                let innovation = x - *position;
+                *position += k0 * innovation;
+                *velocity += k1 * innovation;
+                *covariance = [(1.0 - k0) * p00, (1.0 - k0) * p01, p11 - k1 * p01];
+                *position
+            }
+            (state, _) => {
+                // The configuration changed kind; restart from the measurement.
+                *state = AxisState::start(config, x, measurement_std_of(config)).unwrap_or(*state);
+                x
+            }
+        }
+    }
+}
+
+fn measurement_std_of(config: FilterConfig) -> f32 {
+    match config {
+        FilterConfig::Kalman { measurement_std, .. } => measurement_std,
+        _ => 0.0,
+    }
+}
+
+/// Filter state of one joint: one scalar filter per axis, and the last output.
+#[derive(Debug, Clone, Copy)]
+struct JointState {
+    axes: [AxisState; 3],
+    output: Vector3,
+}
+
+impl JointState {
+    fn start(config: FilterConfig, p: Vector3) -> Option<Self> {
+        let r = measurement_std_of(config);
+        Some(Self {
+            axes: [AxisState::start(config, p.x, r)?, AxisState::start(config, p.y, r)?, AxisState::start(config, p.z, r)?],
+            output: p,
+        })
+    }
+
+    fn update(&mut self, config: FilterConfig, p: Vector3, dt: f32) -> Vector3 {
+        let [x, y, z] = &mut self.axes;
+        self.output = Vector3 { x: x.update(config, p.x, dt), y: y.update(config, p.y, dt), z: z.update(config, p.z, dt) };
+        self.output
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct UserState {
+    last_timestamp: u64,
+    joints: HashMap<JointType, JointState>,
+}
+
+/// Smooths the joints of every tracked user across frames.
+#[derive(Debug, Clone)]
+pub struct SkeletonFilter {
+    config: SkeletonFilterConfig,
+    camera: Option<CameraModel>,
+    users: HashMap<i32, UserState>,
+}
+
+impl SkeletonFilter {
+    pub fn new(config: SkeletonFilterConfig) -> Self {
+        Self { config, camera: None, users: HashMap::new() }
+    }
+
+    /// Recomputes `Joint::proj` from the smoothed positions with the depth sensor's
+    /// camera model. Without one, `proj` keeps the SDK's unsmoothed value.
+    pub fn with_camera_model(mut self, camera: CameraModel) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    pub fn config(&self) -> &SkeletonFilterConfig {
+        &self.config
+    }
+
+    /// Replaces the configuration. Joints whose filter kind changed restart.
+    pub fn set_config(&mut self, config: SkeletonFilterConfig) {
+        self.config = config;
+    }
+
+    /// Forgets all filter state.
+    pub fn reset(&mut self) {
+        self.users.clear();
+    }
+
+    /// Forgets the filter state of one user.
+    pub fn reset_user(&mut self, user_id: i32) {
+        self.users.remove(&user_id);
+    }
+
+    /// Smooths `skeletons` observed at `timestamp` (in microseconds). Users missing from
+    /// `skeletons` keep their state until `reset_after` elapses.
+    pub fn apply(&mut self, timestamp: u64, skeletons: &[Skeleton]) -> Vec<Skeleton> {
+        if let Some(reset_after) = self.config.reset_after {
+            let limit = reset_after.as_micros() as u64;
+            self.users.retain(|user_id, state| {
+                let keep = timestamp.saturating_sub(state.last_timestamp) <= limit;
+                if !keep {
+                    debug!(user_id, "Skeleton filter state expired.");
+                }
+                keep
+            });
+        }
+        skeletons.iter().map(|skeleton| self.apply_one(timestamp, skeleton)).collect()
+    }
+
+    fn apply_one(&mut self, timestamp: u64, skeleton: &Skeleton) -> Skeleton {
+        let state = self.users.entry(skeleton.user_id).or_default();
+        // Frames with the same or an older timestamp carry no new information.
+        let dt = if state.joints.is_empty() || timestamp <= state.last_timestamp {
+            None
+        } else {
+            Some((timestamp - state.last_timestamp) as f32 / 1_000_000.0)
+        };
+        state.last_timestamp = state.last_timestamp.max(timestamp);
+
+        let joints = skeleton
+            .joints
+            .iter()
+            .map(|joint| {
+                let config = self.config.config_for(joint.joint_type);
+                if joint.confidence <= 0.0 || config == FilterConfig::None {
+                    state.joints.remove(&joint.joint_type);
+                    return *joint;
+                }
+                let real = match (state.joints.get_mut(&joint.joint_type), dt) {
+                    (Some(filter), Some(dt)) => filter.update(config, joint.real, dt),
+                    (Some(filter), None) => filter.output,
+                    (None, _) => {
+                        if let Some(filter) = JointState::start(config, joint.real) {
+                            state.joints.insert(joint.joint_type, filter);
+                        }
+                        joint.real
+                    }
+                };
+                let proj = match &self.camera {
+                    Some(camera) => camera.proj_to_normalized(camera.real_to_proj(real)),
+                    None => joint.proj,
+                };
+                Joint { real, proj, ..*joint }
+            })
+            .collect();
+        trace!(user_id = skeleton.user_id, "Skeleton filtered.");
+        Skeleton { user_id: skeleton.user_id, joints }
+    }
+
+    /// Smooths every skeleton of `frame` into a new frame with the same timestamp.
+    pub fn filter_frame(&mut self, frame: &impl SkeletonFrameData) -> NuitrackResult<SkeletonFrame> {
+        let timestamp = frame.timestamp()?;
+        Ok(SkeletonFrame::synthetic(timestamp, self.apply(timestamp, frame.skeletons()?)))
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 use tracing::{debug, instrument, trace, trace_span, warn};
 use cxx::SharedPtr;
 
@@ -6,14 +6,32 @@ use crate::nuitrack_bridge::modules::skeleton_tracker::ffi::SkeletonData as FFIS
 use crate::nuitrack_bridge::types::skeleton_data::ffi::{self as skeleton_data_ffi};
 
 use super::error::{NuitrackError, Result as NuitrackResult};
+use super::frame::{Frame, SkeletonFrameData};
 use super::skeleton::Skeleton;
 
 
 pub struct SkeletonFrame {
-    internal_ptr: SharedPtr<FFISkeletonData>,
+    inner: SkeletonFrameInner,
     skeletons_cache: OnceLock<NuitrackResult<Vec<Skeleton>>>,
 }
 
+/// Where the frame's data lives: in SDK memory or in a Rust-side buffer.
+#[derive(Clone)]
+enum SkeletonFrameInner {
+    Ffi(SharedPtr<FFISkeletonData>),
+    Owned(Arc<OwnedSkeletonFrame>),
+}
+
+/// Skeletons of one frame held entirely in Rust memory.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone)]
+pub struct OwnedSkeletonFrame {
+    /// Typically in microseconds.
+    pub timestamp: u64,
+    pub skeletons: Vec<Skeleton>,
+}
+
 impl SkeletonFrame {
     pub(crate) fn new(ffi_ptr: SharedPtr<FFISkeletonData>) -> Option<Self> {
         let is_null = ffi_ptr.is_null();
@@ -21,24 +39,50 @@ impl SkeletonFrame {
         if is_null {
             None
         } else {
-            Some(SkeletonFrame { internal_ptr: ffi_ptr, skeletons_cache: OnceLock::new() })
+            Some(SkeletonFrame { inner: SkeletonFrameInner::Ffi(ffi_ptr), skeletons_cache: OnceLock::new() })
+        }
+    }
+
+    /// Creates a `SkeletonFrame` from skeletons held in Rust memory instead of SDK memory.
+    ///
+    /// This is how pure-Rust backends (such as the simulated backend) produce frames.
+    pub fn synthetic(timestamp: u64, skeletons: Vec<Skeleton>) -> Self {
+        OwnedSkeletonFrame { timestamp, skeletons }.into()
+    }
+
+    /// Copies the frame into an [`OwnedSkeletonFrame`], releasing any tie to SDK memory.
+    #[instrument(skip(self))]
+    pub fn to_owned(&self) -> NuitrackResult<OwnedSkeletonFrame> {
+        match &self.inner {
+            SkeletonFrameInner::Ffi(_) => Ok(OwnedSkeletonFrame {
+                timestamp: self.timestamp()?,
+                skeletons: self.skeletons()?.to_vec(),
+            }),
+            SkeletonFrameInner::Owned(frame) => Ok(OwnedSkeletonFrame::clone(frame)),
         }
     }
 
     #[instrument(skip(self))]
     pub fn num_skeletons(&self) -> NuitrackResult<i32> {
-        trace_span!("ffi", function = "skeleton_data_ffi::num_skeletons").in_scope(||
-            Ok(skeleton_data_ffi::num_skeletons(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            SkeletonFrameInner::Ffi(ptr) => trace_span!("ffi", function = "skeleton_data_ffi::num_skeletons").in_scope(||
+                Ok(skeleton_data_ffi::num_skeletons(ptr)?)
+            ),
+            SkeletonFrameInner::Owned(frame) => Ok(frame.skeletons.len() as i32),
+        }
     }
 
     #[instrument(skip(self))]
     pub fn skeletons(&self) -> NuitrackResult<&[Skeleton]> {
+        let ffi_ptr = match &self.inner {
+            SkeletonFrameInner::Ffi(ptr) => ptr,
+            SkeletonFrameInner::Owned(frame) => return Ok(frame.skeletons.as_slice()),
+        };
         let cached_result = self.skeletons_cache.get_or_init(|| {
             debug!("Populating SkeletonFrame cache: converting FFI Skeletons to Rust.");
             let conversion = || -> NuitrackResult<Vec<Skeleton>> {
                 let ffi_skeletons = trace_span!("ffi", function = "skeleton_data_ffi::skeletons").in_scope(||
-                    skeleton_data_ffi::skeletons(&self.internal_ptr)
+                    skeleton_data_ffi::skeletons(ffi_ptr)
                 )?;
                 let ffi_vec = ffi_skeletons
                     .as_ref()
@@ -72,8 +116,60 @@ impl SkeletonFrame {
     
     #[instrument(skip(self))] 
     pub fn timestamp(&self) -> NuitrackResult<u64> {
-        trace_span!("ffi", function = "skeleton_data_ffi::timestamp").in_scope(||
-            Ok(skeleton_data_ffi::timestamp(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            SkeletonFrameInner::Ffi(ptr) => trace_span!("ffi", function = "skeleton_data_ffi::timestamp").in_scope(||
+                Ok(skeleton_data_ffi::timestamp(ptr)?)
+            ),
+            SkeletonFrameInner::Owned(frame) => Ok(frame.timestamp),
+        }
+    }
+}
+
+/// Cloning shares the underlying frame; the Rust-side skeleton cache is rebuilt lazily.
+impl Clone for SkeletonFrame {
+    fn clone(&self) -> Self {
+        SkeletonFrame { inner: self.inner.clone(), skeletons_cache: OnceLock::new() }
     }
-}
\ No newline at end of file
+}
+
+impl From<OwnedSkeletonFrame> for SkeletonFrame {
+    fn from(frame: OwnedSkeletonFrame) -> Self {
+        SkeletonFrame { inner: SkeletonFrameInner::Owned(Arc::new(frame)), skeletons_cache: OnceLock::new() }
+    }
+}
+
+impl Frame for SkeletonFrame {
+    type Owned = OwnedSkeletonFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        SkeletonFrame::timestamp(self)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedSkeletonFrame> {
+        SkeletonFrame::to_owned(self)
+    }
+}
+
+impl SkeletonFrameData for SkeletonFrame {
+    fn skeletons(&self) -> NuitrackResult<&[Skeleton]> {
+        SkeletonFrame::skeletons(self)
+    }
+}
+
+impl Frame for OwnedSkeletonFrame {
+    type Owned = OwnedSkeletonFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        Ok(self.timestamp)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedSkeletonFrame> {
+        Ok(self.clone())
+    }
+}
+
+impl SkeletonFrameData for OwnedSkeletonFrame {
+    fn skeletons(&self) -> NuitrackResult<&[Skeleton]> {
+        Ok(&self.skeletons)
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 use tracing::{debug, instrument, trace, trace_span, warn};
 use cxx::SharedPtr;
 
@@ -7,6 +7,7 @@ use crate::nuitrack_bridge::types::user_frame::ffi::{self as user_frame_ffi};
 use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
 
 use super::error::{NuitrackError, Result as NuitrackResult};
+use super::frame::{Frame, UserFrameData};
 use super::user::User;
 
 /// A high-level wrapper for a Nuitrack user frame.
@@ -15,10 +16,37 @@ use super::user::User;
 /// It contains a user segmentation map, a list of detailed user objects,
 /// and information about the detected floor plane.
 pub struct UserFrame {
-    internal_ptr: SharedPtr<FFIUserFrame>,
+    inner: UserFrameInner,
     users_cache: OnceLock<NuitrackResult<Vec<User>>>,
 }
 
+/// Where the frame's data lives: in SDK memory or in a Rust-side buffer.
+#[derive(Clone)]
+enum UserFrameInner {
+    Ffi(SharedPtr<FFIUserFrame>),
+    Owned(Arc<OwnedUserFrame>),
+}
+
+/// A user frame held entirely in Rust memory.
+///
+/// Unlike [`UserFrame`], it does not keep any SDK buffer alive, so it can be
+/// constructed freely, stored indefinitely and sent anywhere.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedUserFrame {
+    pub rows: i32,
+    pub cols: i32,
+    /// Typically in microseconds.
+    pub timestamp: u64,
+    /// `rows * cols` user IDs, row-major; 0 is the background.
+    pub data: Vec<u16>,
+    pub users: Vec<User>,
+    /// A point on the detected floor plane.
+    pub floor: Vector3,
+    pub floor_normal: Vector3,
+}
+
 impl UserFrame {
     /// Creates a new `UserFrame` from a CXX shared pointer. Returns `None` if the pointer is null.
     pub(crate) fn new(ffi_ptr: SharedPtr<FFIUserFrame>) -> Option<Self> {
@@ -27,23 +55,61 @@ impl UserFrame {
             None
         } else {
             Some(UserFrame {
-                internal_ptr: ffi_ptr,
+                inner: UserFrameInner::Ffi(ffi_ptr),
                 users_cache: OnceLock::new(),
             })
         }
     }
 
+    /// Creates a `UserFrame` backed by Rust-side data instead of SDK memory.
+    ///
+    /// This is how pure-Rust backends (such as the simulated backend) produce frames.
+    /// `data` is the `rows * cols` segmentation map, where 0 is the background.
+    #[allow(clippy::too_many_arguments)]
+    pub fn synthetic(
+        rows: i32,
+        cols: i32,
+        timestamp: u64,
+        data: Vec<u16>,
+        users: Vec<User>,
+        floor: Vector3,
+        floor_normal: Vector3,
+    ) -> Self {
+        OwnedUserFrame { rows, cols, timestamp, data, users, floor, floor_normal }.into()
+    }
+
+    /// Copies the frame into an [`OwnedUserFrame`], releasing any tie to SDK memory.
+    #[instrument(skip(self))]
+    pub fn to_owned(&self) -> NuitrackResult<OwnedUserFrame> {
+        match &self.inner {
+            UserFrameInner::Ffi(_) => Ok(OwnedUserFrame {
+                rows: self.rows()?,
+                cols: self.cols()?,
+                timestamp: self.timestamp()?,
+                data: self.data()?.to_vec(),
+                users: self.users()?.to_vec(),
+                floor: self.floor()?,
+                floor_normal: self.floor_normal()?,
+            }),
+            UserFrameInner::Owned(frame) => Ok(OwnedUserFrame::clone(frame)),
+        }
+    }
+
     /// Gets a list of all detected users in the frame.
     ///
     /// This operation is cached after the first call for this `UserFrame` instance
     /// to improve performance.
     #[instrument(skip(self))]
     pub fn users(&self) -> NuitrackResult<&[User]> {
+        let ffi_ptr = match &self.inner {
+            UserFrameInner::Ffi(ptr) => ptr,
+            UserFrameInner::Owned(frame) => return Ok(frame.users.as_slice()),
+        };
         let cached_result = self.users_cache.get_or_init(|| {
             debug!("Populating UserFrame cache: fetching users from FFI.");
             let conversion = || -> NuitrackResult<Vec<User>> {
                 let ffi_users_ptr = trace_span!("ffi", function = "user_frame_ffi::users")
-                    .in_scope(|| user_frame_ffi::users(&self.internal_ptr))?;
+                    .in_scope(|| user_frame_ffi::users(ffi_ptr))?;
                 
                 let ffi_vec = ffi_users_ptr.as_ref().ok_or_else(|| {
                     warn!("FFI returned null user vector pointer.");
@@ -77,48 +143,155 @@ impl UserFrame {
     /// The map's dimensions are `rows` x `cols`. A pixel value of 0 is the background.
     #[instrument(skip(self))]
     pub fn data(&self) -> NuitrackResult<&[u16]> {
-        trace_span!("ffi", function = "user_frame_ffi::data").in_scope(||
-            Ok(user_frame_ffi::data(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            UserFrameInner::Ffi(ptr) => trace_span!("ffi", function = "user_frame_ffi::data").in_scope(||
+                Ok(user_frame_ffi::data(ptr)?)
+            ),
+            UserFrameInner::Owned(frame) => Ok(&frame.data),
+        }
     }
 
     /// Gets the number of rows in the user map.
     #[instrument(skip(self))]
     pub fn rows(&self) -> NuitrackResult<i32> {
-        trace_span!("ffi", function = "user_frame_ffi::rows").in_scope(||
-            Ok(user_frame_ffi::rows(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            UserFrameInner::Ffi(ptr) => trace_span!("ffi", function = "user_frame_ffi::rows").in_scope(||
+                Ok(user_frame_ffi::rows(ptr)?)
+            ),
+            UserFrameInner::Owned(frame) => Ok(frame.rows),
+        }
     }
 
     /// Gets the number of columns in the user map.
     #[instrument(skip(self))]
     pub fn cols(&self) -> NuitrackResult<i32> {
-        trace_span!("ffi", function = "user_frame_ffi::cols").in_scope(||
-            Ok(user_frame_ffi::cols(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            UserFrameInner::Ffi(ptr) => trace_span!("ffi", function = "user_frame_ffi::cols").in_scope(||
+                Ok(user_frame_ffi::cols(ptr)?)
+            ),
+            UserFrameInner::Owned(frame) => Ok(frame.cols),
+        }
     }
 
     /// Gets the timestamp of the frame in microseconds.
     #[instrument(skip(self))]
     pub fn timestamp(&self) -> NuitrackResult<u64> {
-        trace_span!("ffi", function = "user_frame_ffi::timestamp").in_scope(||
-            Ok(user_frame_ffi::timestamp(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            UserFrameInner::Ffi(ptr) => trace_span!("ffi", function = "user_frame_ffi::timestamp").in_scope(||
+                Ok(user_frame_ffi::timestamp(ptr)?)
+            ),
+            UserFrameInner::Owned(frame) => Ok(frame.timestamp),
+        }
     }
 
     /// Gets a point on the detected floor plane.
     #[instrument(skip(self))]
     pub fn floor(&self) -> NuitrackResult<Vector3> {
-        trace_span!("ffi", function = "user_frame_ffi::floor").in_scope(||
-            Ok(user_frame_ffi::floor(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            UserFrameInner::Ffi(ptr) => trace_span!("ffi", function = "user_frame_ffi::floor").in_scope(||
+                Ok(user_frame_ffi::floor(ptr)?)
+            ),
+            UserFrameInner::Owned(frame) => Ok(frame.floor),
+        }
     }
 
     /// Gets the normal vector of the detected floor plane.
     #[instrument(skip(self))]
     pub fn floor_normal(&self) -> NuitrackResult<Vector3> {
-        trace_span!("ffi", function = "user_frame_ffi::floor_normal").in_scope(||
-            Ok(user_frame_ffi::floor_normal(&self.internal_ptr)?)
-        )
+        match &self.inner {
+            UserFrameInner::Ffi(ptr) => trace_span!("ffi", function = "user_frame_ffi::floor_normal").in_scope(||
+                Ok(user_frame_ffi::floor_normal(ptr)?)
+            ),
+            UserFrameInner::Owned(frame) => Ok(frame.floor_normal),
+        }
+    }
+}
+
+/// Cloning shares the underlying frame; the Rust-side user cache is rebuilt lazily.
+impl Clone for UserFrame {
+    fn clone(&self) -> Self {
+        UserFrame { inner: self.inner.clone(), users_cache: OnceLock::new() }
+    }
+}
+
+impl From<OwnedUserFrame> for UserFrame {
+    fn from(frame: OwnedUserFrame) -> Self {
+        UserFrame { inner: UserFrameInner::Owned(Arc::new(frame)), users_cache: OnceLock::new() }
     }
-}
\ No newline at end of file
+}
+
+impl Frame for UserFrame {
+    type Owned = OwnedUserFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        UserFrame::timestamp(self)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedUserFrame> {
+        UserFrame::to_owned(self)
+    }
+}
+
+impl UserFrameData for UserFrame {
+    fn rows(&self) -> NuitrackResult<i32> {
+        UserFrame::rows(self)
+    }
+
+    fn cols(&self) -> NuitrackResult<i32> {
+        UserFrame::cols(self)
+    }
+
+    fn data(&self) -> NuitrackResult<&[u16]> {
+        UserFrame::data(self)
+    }
+
+    fn users(&self) -> NuitrackResult<&[User]> {
+        UserFrame::users(self)
+    }
+
+    fn floor(&self) -> NuitrackResult<Vector3> {
+        UserFrame::floor(self)
+    }
+
+    fn floor_normal(&self) -> NuitrackResult<Vector3> {
+        UserFrame::floor_normal(self)
+    }
+}
+
+impl Frame for OwnedUserFrame {
+    type Owned = OwnedUserFrame;
+
+    fn timestamp(&self) -> NuitrackResult<u64> {
+        Ok(self.timestamp)
+    }
+
+    fn to_owned_frame(&self) -> NuitrackResult<OwnedUserFrame> {
+        Ok(self.clone())
+    }
+}
+
+impl UserFrameData for OwnedUserFrame {
+    fn rows(&self) -> NuitrackResult<i32> {
+        Ok(self.rows)
+    }
+
+    fn cols(&self) -> NuitrackResult<i32> {
+        Ok(self.cols)
+    }
+
+    fn data(&self) -> NuitrackResult<&[u16]> {
+        Ok(&self.data)
+    }
+
+    fn users(&self) -> NuitrackResult<&[User]> {
+        Ok(&self.users)
+    }
+
+    fn floor(&self) -> NuitrackResult<Vector3> {
+        Ok(self.floor)
+    }
+
+    fn floor_normal(&self) -> NuitrackResult<Vector3> {
+        Ok(self.floor_normal)
+    }
+}
@@ -0,0 +1,235 @@
+//! One lifecycle per user, combined from every source that reports users.
+//!
+//! The user tracker's new/lost user events and frames, the gesture recognizer's user
+//! state changes and skeleton frames each report part of a user's presence.
+//! [`UserLifecycle`] folds them into [`UserEvent`]s in a consistent order: a user
+//! appears before anything else happens to them, and on leaving first loses their
+//! skeleton and disengages.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use tracing::debug;
+
+use super::error::Result as NuitrackResult;
+use super::frame::SkeletonFrameData;
+use super::gesture::{UserState, UserStateType};
+use super::gesture_frame::UserStateFrame;
+use super::skeleton::Skeleton;
+use super::user::User;
+use super::user_frame::UserFrame;
+
+/// What happened to a user.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UserEventKind {
+    /// The user entered the scene.
+    Appeared,
+    /// The gesture recognizer considers the user active.
+    Engaged,
+    /// The user's skeleton is being tracked.
+    SkeletonAcquired,
+    /// The user's skeleton is no longer tracked.
+    SkeletonLost,
+    /// The user is no longer active.
+    Disengaged,
+    /// The user left the scene.
+    Left,
+}
+
+/// A change in a user's lifecycle.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserEvent {
+    pub user_id: i32,
+    pub kind: UserEventKind,
+    /// Timestamp of the frame that revealed the change, typically in microseconds.
+    /// New and lost user events carry no timestamp and use the latest one seen.
+    pub timestamp: u64,
+    /// The last user frame data of the user, if any user frame listed them.
+    pub user: Option<User>,
+}
+
+/// What is known about a user in the scene.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UserStatus {
+    pub user: Option<User>,
+    pub engaged: bool,
+    pub has_skeleton: bool,
+    /// When the user appeared.
+    pub since: u64,
+}
+
+/// Tracks every user's lifecycle across the sources that report users.
+#[derive(Debug, Clone, Default)]
+pub struct UserLifecycle {
+    users: HashMap<i32, UserStatus>,
+    /// Latest timestamp of any frame, for the untimestamped user events.
+    timestamp: u64,
+    /// Whether skeletons are the only source, so losing one means leaving.
+    skeletons_only: bool,
+}
+
+impl UserLifecycle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(&self, user_id: i32) -> Option<&UserStatus> {
+        self.users.get(&user_id)
+    }
+
+    /// Users in the scene, in no particular order.
+    pub fn users(&self) -> impl Iterator<Item = (i32, &UserStatus)> {
+        self.users.iter().map(|(id, status)| (*id, status))
+    }
+
+    /// Declares skeleton frames the only source of users. Nothing else would then
+    /// report users leaving, so a user whose skeleton is lost has [`Left`](UserEventKind::Left).
+    pub fn set_skeletons_only(&mut self, skeletons_only: bool) {
+        self.skeletons_only = skeletons_only;
+    }
+
+    /// Forgets every user without reporting them.
+    pub fn reset(&mut self) {
+        self.users.clear();
+    }
+
+    /// A user tracker new user event.
+    pub fn user_appeared(&mut self, user_id: i32) -> Vec<UserEvent> {
+        let mut events = Vec::new();
+        self.ensure(user_id, self.timestamp, &mut events);
+        events
+    }
+
+    /// A user tracker lost user event.
+    pub fn user_lost(&mut self, user_id: i32) -> Vec<UserEvent> {
+        let mut events = Vec::new();
+        self.leave(user_id, self.timestamp, &mut events);
+        events
+    }
+
+    /// Applies a user frame listing everyone in the scene at `timestamp`. Users missing
+    /// from `users` have left.
+    pub fn apply_users(&mut self, timestamp: u64, users: &[User]) -> Vec<UserEvent> {
+        self.advance(timestamp);
+        let mut events = Vec::new();
+        for user in users {
+            self.ensure(user.id, timestamp, &mut events);
+            if let Some(status) = self.users.get_mut(&user.id) {
+                status.user = Some(*user);
+            }
+        }
+        for user_id in self.missing(|id| users.iter().any(|u| u.id == id)) {
+            self.leave(user_id, timestamp, &mut events);
+        }
+        events
+    }
+
+    /// Applies gesture recognizer user state changes. Absent users have left.
+    pub fn apply_user_states(&mut self, timestamp: u64, states: &[UserState]) -> Vec<UserEvent> {
+        self.advance(timestamp);
+        let mut events = Vec::new();
+        for state in states {
+            let user_id = state.user_id;
+            if state.state == UserStateType::IsAbsent {
+                self.leave(user_id, timestamp, &mut events);
+                continue;
+            }
+            self.ensure(user_id, timestamp, &mut events);
+            let engaged = state.state == UserStateType::Active;
+            let Some(status) = self.users.get_mut(&user_id) else {
+                continue;
+            };
+            if status.engaged != engaged {
+                status.engaged = engaged;
+                let kind = if engaged { UserEventKind::Engaged } else { UserEventKind::Disengaged };
+                events.push(Self::event(user_id, kind, timestamp, status));
+            }
+        }
+        events
+    }
+
+    /// Applies the skeletons tracked at `timestamp`. Skeletons without any tracked joint
+    /// count as lost, and their users leave if skeletons are the only source (see
+    /// [`set_skeletons_only`](Self::set_skeletons_only)).
+    pub fn apply_skeletons(&mut self, timestamp: u64, skeletons: &[Skeleton]) -> Vec<UserEvent> {
+        self.advance(timestamp);
+        let mut events = Vec::new();
+        let tracked = |s: &Skeleton| s.joints.iter().any(|j| j.confidence > 0.0);
+        for skeleton in skeletons.iter().filter(|s| tracked(s)) {
+            let user_id = skeleton.user_id;
+            self.ensure(user_id, timestamp, &mut events);
+            if let Some(status) = self.users.get_mut(&user_id).filter(|s| !s.has_skeleton) {
+                status.has_skeleton = true;
+                events.push(Self::event(user_id, UserEventKind::SkeletonAcquired, timestamp, status));
+            }
+        }
+        let lost = self.missing(|id| skeletons.iter().any(|s| s.user_id == id && tracked(s)));
+        for user_id in lost {
+            if self.skeletons_only {
+                self.leave(user_id, timestamp, &mut events);
+            } else if let Some(status) = self.users.get_mut(&user_id).filter(|s| s.has_skeleton) {
+                status.has_skeleton = false;
+                events.push(Self::event(user_id, UserEventKind::SkeletonLost, timestamp, status));
+            }
+        }
+        events
+    }
+
+    /// [`apply_users`](Self::apply_users) for a user tracker frame.
+    pub fn process_user_frame(&mut self, frame: &UserFrame) -> NuitrackResult<Vec<UserEvent>> {
+        Ok(self.apply_users(frame.timestamp()?, frame.users()?))
+    }
+
+    /// [`apply_user_states`](Self::apply_user_states) for a gesture recognizer frame.
+    pub fn process_user_state_frame(&mut self, frame: &UserStateFrame) -> NuitrackResult<Vec<UserEvent>> {
+        Ok(self.apply_user_states(frame.timestamp()?, frame.user_states()?))
+    }
+
+    /// [`apply_skeletons`](Self::apply_skeletons) for a skeleton frame.
+    pub fn process_skeleton_frame(&mut self, frame: &impl SkeletonFrameData) -> NuitrackResult<Vec<UserEvent>> {
+        Ok(self.apply_skeletons(frame.timestamp()?, frame.skeletons()?))
+    }
+
+    fn advance(&mut self, timestamp: u64) {
+        self.timestamp = self.timestamp.max(timestamp);
+    }
+
+    /// Known users for which `present` is false, in ascending order.
+    fn missing(&self, present: impl Fn(i32) -> bool) -> Vec<i32> {
+        let mut missing: Vec<i32> = self.users.keys().copied().filter(|id| !present(*id)).collect();
+        missing.sort_unstable();
+        missing
+    }
+
+    fn ensure(&mut self, user_id: i32, timestamp: u64, events: &mut Vec<UserEvent>) {
+        if let Entry::Vacant(entry) = self.users.entry(user_id) {
+            debug!(user_id, "User appeared.");
+            let status = entry.insert(UserStatus { since: timestamp, ..Default::default() });
+            events.push(Self::event(user_id, UserEventKind::Appeared, timestamp, status));
+        }
+    }
+
+    fn leave(&mut self, user_id: i32, timestamp: u64, events: &mut Vec<UserEvent>) {
+        let Some(status) = self.users.remove(&user_id) else {
+            return;
+        };
+        debug!(user_id, "User left.");
+        if status.has_skeleton {
+            events.push(Self::event(user_id, UserEventKind::SkeletonLost, timestamp, &status));
+        }
+        if status.engaged {
+            events.push(Self::event(user_id, UserEventKind::Disengaged, timestamp, &status));
+        }
+        events.push(Self::event(user_id, UserEventKind::Left, timestamp, &status));
+    }
+
+    fn event(user_id: i32, kind: UserEventKind, timestamp: u64, status: &UserStatus) -> UserEvent {
+        UserEvent { user_id, kind, timestamp, user: status.user }
+    }
+}
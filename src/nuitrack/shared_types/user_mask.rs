@@ -0,0 +1,340 @@
+//! Binary masks derived from the user segmentation map.
+//!
+//! [`UserMask`] selects the pixels of one user (or of every user) from a user frame
+//! and provides the usual morphology and measurement helpers, plus compositing a
+//! user cut-out from a color frame for background removal.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use tracing::instrument;
+
+use crate::nuitrack_bridge::types::bounding_box::ffi::BoundingBox;
+
+use super::error::{NuitrackError, Result as NuitrackResult};
+use super::frame::{ColorFrameData, UserFrameData};
+use super::rgb_frame::{Color3, OwnedRGBFrame};
+use super::user_frame::UserFrame;
+
+/// A pixel position in an image.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pixel {
+    pub row: i32,
+    pub col: i32,
+}
+
+/// An axis-aligned rectangle in pixels, with inclusive bounds.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PixelRect {
+    pub top: i32,
+    pub bottom: i32,
+    pub left: i32,
+    pub right: i32,
+}
+
+impl PixelRect {
+    pub fn width(&self) -> i32 {
+        self.right - self.left + 1
+    }
+
+    pub fn height(&self) -> i32 {
+        self.bottom - self.top + 1
+    }
+}
+
+/// Offsets of the 8 neighbors as `(row, col)`, clockwise starting from west.
+const NEIGHBORS: [(i32, i32); 8] = [(0, -1), (-1, -1), (-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1)];
+
+/// A binary mask over a user frame's pixel grid.
+///
+/// The data always holds `rows * cols` flags; masks are only built through
+/// [`UserMask::new`], which checks this, including when deserialized.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawUserMask"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserMask {
+    rows: i32,
+    cols: i32,
+    /// `rows * cols` flags, row-major.
+    data: Vec<bool>,
+}
+
+/// Unchecked [`UserMask`] fields, validated by `UserMask::new` on deserialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawUserMask {
+    rows: i32,
+    cols: i32,
+    data: Vec<bool>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawUserMask> for UserMask {
+    type Error = NuitrackError;
+
+    fn try_from(raw: RawUserMask) -> NuitrackResult<Self> {
+        Self::new(raw.rows, raw.cols, raw.data)
+    }
+}
+
+impl UserMask {
+    /// Fails unless `data` holds exactly `rows * cols` flags.
+    pub fn new(rows: i32, cols: i32, data: Vec<bool>) -> NuitrackResult<Self> {
+        let len = usize::try_from(rows).ok().zip(usize::try_from(cols).ok()).and_then(|(r, c)| r.checked_mul(c));
+        if len != Some(data.len()) {
+            return Err(NuitrackError::OperationFailed(format!(
+                "Mask data of length {} does not match {rows}x{cols}",
+                data.len()
+            )));
+        }
+        Ok(Self { rows, cols, data })
+    }
+
+    pub fn rows(&self) -> i32 {
+        self.rows
+    }
+
+    pub fn cols(&self) -> i32 {
+        self.cols
+    }
+
+    /// `rows * cols` flags, row-major.
+    pub fn data(&self) -> &[bool] {
+        &self.data
+    }
+
+    /// The pixels of `user_id` in `frame`, or of every user if `None`.
+    pub fn from_frame(frame: &impl UserFrameData, user_id: Option<i32>) -> NuitrackResult<Self> {
+        let labels = frame.data()?;
+        let data = match user_id {
+            Some(id) => labels.iter().map(|&label| i32::from(label) == id).collect(),
+            None => labels.iter().map(|&label| label != 0).collect(),
+        };
+        Self::new(frame.rows()?, frame.cols()?, data)
+    }
+
+    pub fn get(&self, row: i32, col: i32) -> bool {
+        (0..self.rows).contains(&row) && (0..self.cols).contains(&col) && self.data[(row * self.cols + col) as usize]
+    }
+
+    /// Number of pixels in the mask.
+    pub fn count(&self) -> usize {
+        self.data.iter().filter(|&&set| set).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.data.contains(&true)
+    }
+
+    /// The mask with every pixel flipped, e.g. to select the background.
+    pub fn inverted(&self) -> Self {
+        Self { rows: self.rows, cols: self.cols, data: self.data.iter().map(|&set| !set).collect() }
+    }
+
+    /// The smallest rectangle containing the mask, or `None` if it is empty.
+    pub fn pixel_rect(&self) -> Option<PixelRect> {
+        let mut rect: Option<PixelRect> = None;
+        for (i, _) in self.data.iter().enumerate().filter(|(_, set)| **set) {
+            let (row, col) = (i as i32 / self.cols, i as i32 % self.cols);
+            rect = Some(match rect {
+                None => PixelRect { top: row, bottom: row, left: col, right: col },
+                Some(r) => PixelRect {
+                    top: r.top.min(row),
+                    bottom: r.bottom.max(row),
+                    left: r.left.min(col),
+                    right: r.right.max(col),
+                },
+            });
+        }
+        rect
+    }
+
+    /// [`pixel_rect`](Self::pixel_rect) normalized to `0..1`, the convention of `User::box`.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let rect = self.pixel_rect()?;
+        let (rows, cols) = (self.rows as f32, self.cols as f32);
+        Some(BoundingBox {
+            top: rect.top as f32 / rows,
+            bottom: (rect.bottom + 1) as f32 / rows,
+            left: rect.left as f32 / cols,
+            right: (rect.right + 1) as f32 / cols,
+        })
+    }
+
+    /// The outer contour of each 8-connected region, traced clockwise from the region's
+    /// top-left pixel. Regions are ordered by that pixel in raster order.
+    #[instrument(skip(self), fields(rows = self.rows, cols = self.cols))]
+    pub fn contours(&self) -> Vec<Vec<Pixel>> {
+        let labels = self.components();
+        let mut contours = Vec::new();
+        let mut traced = 0;
+        for (i, &label) in labels.iter().enumerate() {
+            if label <= traced {
+                continue;
+            }
+            traced = label;
+            let start = Pixel { row: i as i32 / self.cols, col: i as i32 % self.cols };
+            contours.push(self.trace(&labels, label, start));
+        }
+        contours
+    }
+
+    /// Connected-component labels (8-connectivity), 0 outside the mask. Labels are
+    /// assigned in raster order of each component's first pixel.
+    fn components(&self) -> Vec<u32> {
+        let mut labels = vec![0u32; self.data.len()];
+        let mut next = 0;
+        let mut queue = VecDeque::new();
+        for i in 0..self.data.len() {
+            if !self.data[i] || labels[i] != 0 {
+                continue;
+            }
+            next += 1;
+            labels[i] = next;
+            queue.push_back(i);
+            while let Some(j) = queue.pop_front() {
+                let (row, col) = (j as i32 / self.cols, j as i32 % self.cols);
+                for (dr, dc) in NEIGHBORS {
+                    let (r, c) = (row + dr, col + dc);
+                    if self.get(r, c) {
+                        let k = (r * self.cols + c) as usize;
+                        if labels[k] == 0 {
+                            labels[k] = next;
+                            queue.push_back(k);
+                        }
+                    }
+                }
+            }
+        }
+        labels
+    }
+
+    /// Moore-neighbor tracing with Jacob's stopping criterion.
+    fn trace(&self, labels: &[u32], label: u32, start: Pixel) -> Vec<Pixel> {
+        let inside = |r: i32, c: i32| {
+            (0..self.rows).contains(&r) && (0..self.cols).contains(&c) && labels[(r * self.cols + c) as usize] == label
+        };
+        let step = |p: Pixel, from: usize| {
+            (0..8).map(|k| (from + k) % 8).find(|&d| inside(p.row + NEIGHBORS[d].0, p.col + NEIGHBORS[d].1))
+        };
+        // The west neighbor of the first pixel in raster order is always outside.
+        let Some(first) = step(start, 0) else {
+            return vec![start];
+        };
+        let mut contour = vec![start];
+        let (mut current, mut direction) = (start, first);
+        loop {
+            current = Pixel { row: current.row + NEIGHBORS[direction].0, col: current.col + NEIGHBORS[direction].1 };
+            let next = step(current, (direction + 6) % 8).expect("a traced pixel has a neighbor");
+            if current == start && next == first {
+                return contour;
+            }
+            contour.push(current);
+            direction = next;
+        }
+    }
+
+    /// Grows the mask by `radius` pixels in every direction (square structuring element).
+    pub fn dilate(&self, radius: u32) -> Self {
+        if radius == 0 {
+            return self.clone();
+        }
+        let (rows, cols, r) = (self.rows as usize, self.cols as usize, radius as usize);
+        let mut horizontal = vec![false; self.data.len()];
+        for row in 0..rows {
+            let line = &self.data[row * cols..(row + 1) * cols];
+            Self::dilate_line(line.iter().copied(), r, &mut horizontal[row * cols..(row + 1) * cols]);
+        }
+        let mut data = vec![false; self.data.len()];
+        let mut column = vec![false; rows];
+        for col in 0..cols {
+            Self::dilate_line((0..rows).map(|row| horizontal[row * cols + col]), r, &mut column);
+            for (row, &set) in column.iter().enumerate() {
+                data[row * cols + col] = set;
+            }
+        }
+        Self { rows: self.rows, cols: self.cols, data }
+    }
+
+    /// Shrinks the mask by `radius` pixels. Pixels beyond the image border count as set,
+    /// so regions touching the border are not eroded from that side.
+    pub fn erode(&self, radius: u32) -> Self {
+        self.inverted().dilate(radius).inverted()
+    }
+
+    /// Sets `out[i]` if any input within `radius` of `i` is set, using a running count.
+    fn dilate_line(line: impl Iterator<Item = bool>, radius: usize, out: &mut [bool]) {
+        let n = out.len();
+        let mut prefix = Vec::with_capacity(n + 1);
+        prefix.push(0u32);
+        for set in line {
+            prefix.push(prefix.last().unwrap() + u32::from(set));
+        }
+        for (i, o) in out.iter_mut().enumerate() {
+            let (lo, hi) = (i.saturating_sub(radius), (i + radius + 1).min(n));
+            *o = prefix[hi] > prefix[lo];
+        }
+    }
+
+    /// Opacity per pixel: 255 inside the mask, 0 outside.
+    pub fn alpha(&self) -> Vec<u8> {
+        self.data.iter().map(|&set| if set { 255 } else { 0 }).collect()
+    }
+
+    /// Copies `color` where the mask is set and fills every other pixel with `background`.
+    ///
+    /// The result has `color`'s resolution; the mask is scaled to it, so `color` must be
+    /// aligned with the user frame (e.g. with the SDK's depth-to-color registration).
+    #[instrument(skip_all)]
+    pub fn cut_out(&self, color: &impl ColorFrameData, background: Color3) -> NuitrackResult<OwnedRGBFrame> {
+        let (rows, cols) = (color.rows()?, color.cols()?);
+        let pixels = color.data()?;
+        if pixels.len() != (rows * cols) as usize {
+            return Err(NuitrackError::OperationFailed("Color data does not match its dimensions".into()));
+        }
+        if self.rows == 0 || self.cols == 0 {
+            return Err(NuitrackError::OperationFailed("Cannot cut out with an empty mask".into()));
+        }
+        let mask_col: Vec<i32> = (0..cols).map(|c| c * self.cols / cols).collect();
+        let data = pixels
+            .iter()
+            .enumerate()
+            .map(|(i, &pixel)| {
+                let (row, col) = (i as i32 / cols, i as i32 % cols);
+                if self.data[(row * self.rows / rows * self.cols + mask_col[col as usize]) as usize] {
+                    pixel
+                } else {
+                    background
+                }
+            })
+            .collect();
+        Ok(OwnedRGBFrame { rows, cols, id: color.frame_id()?, timestamp: color.timestamp()?, data })
+    }
+}
+
+/// Number of pixels per user ID in `frame`, excluding the background.
+pub fn pixel_counts(frame: &impl UserFrameData) -> NuitrackResult<BTreeMap<i32, usize>> {
+    let mut counts = BTreeMap::new();
+    for &label in frame.data()?.iter().filter(|&&label| label != 0) {
+        *counts.entry(i32::from(label)).or_insert(0) += 1;
+    }
+    Ok(counts)
+}
+
+impl UserFrame {
+    /// The pixels labeled with `user_id`.
+    pub fn user_mask(&self, user_id: i32) -> NuitrackResult<UserMask> {
+        UserMask::from_frame(self, Some(user_id))
+    }
+
+    /// The pixels of every user.
+    pub fn foreground_mask(&self) -> NuitrackResult<UserMask> {
+        UserMask::from_frame(self, None)
+    }
+
+    /// Number of pixels per user ID, excluding the background.
+    pub fn pixel_counts(&self) -> NuitrackResult<BTreeMap<i32, usize>> {
+        pixel_counts(self)
+    }
+}
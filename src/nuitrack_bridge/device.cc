@@ -59,4 +59,97 @@ namespace nuitrack_bridge::device {
             throw std::runtime_error("Unknown exception during Nuitrack setDevice");
         }
     }
-}
\ No newline at end of file
+
+    ActivationStatus getActivationStatus(const std::shared_ptr<Device>& device_shared_ptr) {
+        if (!device_shared_ptr) {
+            throw std::runtime_error("Device (SharedPtrDevice) is null in getActivationStatus");
+        }
+        try {
+            return device_shared_ptr->getActivationStatus();
+        } catch (const tdv::nuitrack::Exception& e) {
+            throw std::runtime_error(std::string("Nuitrack getActivationStatus failed: ") + e.what());
+        } catch (const std::exception& e) {
+            throw;
+        } catch (...) {
+            throw std::runtime_error("Unknown exception during Nuitrack getActivationStatus");
+        }
+    }
+
+    void activate(const std::shared_ptr<Device>& device_shared_ptr, rust::Str activation_key) {
+        if (!device_shared_ptr) {
+            throw std::runtime_error("Device (SharedPtrDevice) is null in activate");
+        }
+        try {
+            device_shared_ptr->activate(std::string(activation_key));
+        } catch (const tdv::nuitrack::Exception& e) {
+            throw std::runtime_error(std::string("Nuitrack activate failed: ") + e.what());
+        } catch (const std::exception& e) {
+            throw;
+        } catch (...) {
+            throw std::runtime_error("Unknown exception during Nuitrack activate");
+        }
+    }
+
+    namespace {
+        VideoMode toBridgeVideoMode(const tdv::nuitrack::device::VideoMode& mode) {
+            VideoMode bridged;
+            bridged.width = mode.width;
+            bridged.height = mode.height;
+            bridged.fps = mode.fps;
+            return bridged;
+        }
+    }
+
+    rust::Vec<VideoMode> getAvailableVideoModes(const std::shared_ptr<Device>& device_shared_ptr, StreamType stream_type) {
+        if (!device_shared_ptr) {
+            throw std::runtime_error("Device (SharedPtrDevice) is null in getAvailableVideoModes");
+        }
+        try {
+            rust::Vec<VideoMode> modes;
+            for (const auto& mode : device_shared_ptr->getAvailableVideoModes(stream_type)) {
+                modes.push_back(toBridgeVideoMode(mode));
+            }
+            return modes;
+        } catch (const tdv::nuitrack::Exception& e) {
+            throw std::runtime_error(std::string("Nuitrack getAvailableVideoModes failed: ") + e.what());
+        } catch (const std::exception& e) {
+            throw;
+        } catch (...) {
+            throw std::runtime_error("Unknown exception during Nuitrack getAvailableVideoModes");
+        }
+    }
+
+    VideoMode getSelectedVideoMode(const std::shared_ptr<Device>& device_shared_ptr, StreamType stream_type) {
+        if (!device_shared_ptr) {
+            throw std::runtime_error("Device (SharedPtrDevice) is null in getSelectedVideoMode");
+        }
+        try {
+            return toBridgeVideoMode(device_shared_ptr->getSelectedVideoMode(stream_type));
+        } catch (const tdv::nuitrack::Exception& e) {
+            throw std::runtime_error(std::string("Nuitrack getSelectedVideoMode failed: ") + e.what());
+        } catch (const std::exception& e) {
+            throw;
+        } catch (...) {
+            throw std::runtime_error("Unknown exception during Nuitrack getSelectedVideoMode");
+        }
+    }
+
+    void setVideoMode(const std::shared_ptr<Device>& device_shared_ptr, StreamType stream_type, VideoMode mode) {
+        if (!device_shared_ptr) {
+            throw std::runtime_error("Device (SharedPtrDevice) is null in setVideoMode");
+        }
+        try {
+            tdv::nuitrack::device::VideoMode sdk_mode;
+            sdk_mode.width = mode.width;
+            sdk_mode.height = mode.height;
+            sdk_mode.fps = mode.fps;
+            device_shared_ptr->setVideoMode(stream_type, sdk_mode);
+        } catch (const tdv::nuitrack::Exception& e) {
+            throw std::runtime_error(std::string("Nuitrack setVideoMode failed: ") + e.what());
+        } catch (const std::exception& e) {
+            throw;
+        } catch (...) {
+            throw std::runtime_error("Unknown exception during Nuitrack setVideoMode");
+        }
+    }
+}
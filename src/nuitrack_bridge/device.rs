@@ -11,6 +11,26 @@ pub mod ffi {
         Count
     }
 
+    #[repr(i32)]
+    enum ActivationStatus {
+        NONE = 0,
+        TRIAL,
+        PRO,
+        UNKNOWN,
+        AI,
+    }
+
+    #[repr(i32)]
+    enum StreamType {
+        DEPTH = 0,
+        COLOR,
+    }
+
+    #[namespace = "nuitrack_bridge::video_mode"]
+    unsafe extern "C++" {
+        type VideoMode = crate::nuitrack_bridge::types::video_mode::ffi::VideoMode;
+    }
+
     unsafe extern "C++" {
         include!("nuitrack_bridge/device.h");
 
@@ -18,6 +38,10 @@ pub mod ffi {
         type Device;
         #[namespace = "tdv::nuitrack::device"]
         type DeviceInfoType;
+        #[namespace = "tdv::nuitrack::device"]
+        type ActivationStatus;
+        #[namespace = "tdv::nuitrack::device"]
+        type StreamType;
 
         type SharedPtrDevice;
 
@@ -32,6 +56,21 @@ pub mod ffi {
 
         #[cxx_name = "setDevice"]
         fn set_device(device: &SharedPtr<Device>) -> Result<()>;
+
+        #[cxx_name = "getActivationStatus"]
+        fn activation_status(device: &SharedPtr<Device>) -> Result<ActivationStatus>;
+
+        #[cxx_name = "activate"]
+        fn activate(device: &SharedPtr<Device>, activation_key: &str) -> Result<()>;
+
+        #[cxx_name = "getAvailableVideoModes"]
+        fn available_video_modes(device: &SharedPtr<Device>, stream_type: StreamType) -> Result<Vec<VideoMode>>;
+
+        #[cxx_name = "getSelectedVideoMode"]
+        fn selected_video_mode(device: &SharedPtr<Device>, stream_type: StreamType) -> Result<VideoMode>;
+
+        #[cxx_name = "setVideoMode"]
+        fn set_video_mode(device: &SharedPtr<Device>, stream_type: StreamType, mode: VideoMode) -> Result<()>;
        
     }
 }
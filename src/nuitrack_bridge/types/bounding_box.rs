@@ -13,3 +13,31 @@ pub mod ffi {
         pub right: f32,
     }
 }
+
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::ffi;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "ffi::BoundingBox", rename_all = "camelCase")]
+    struct BoundingBoxDef {
+        top: f32,
+        bottom: f32,
+        left: f32,
+        right: f32,
+    }
+
+    impl Serialize for ffi::BoundingBox {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            BoundingBoxDef::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ffi::BoundingBox {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            BoundingBoxDef::deserialize(deserializer)
+        }
+    }
+}
@@ -82,4 +82,84 @@ pub mod ffi {
 
     }
 
-}
\ No newline at end of file
+}
+
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::ffi;
+    use crate::nuitrack_bridge::types::serde_support::impl_serde_for_shared_enum;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    impl_serde_for_shared_enum!(ffi::GestureType {
+        0 => "waving",
+        1 => "swipeLeft",
+        2 => "swipeRight",
+        3 => "swipeUp",
+        4 => "swipeDown",
+        5 => "push",
+    });
+
+    impl_serde_for_shared_enum!(ffi::UserStateType {
+        0 => "isAbsent",
+        1 => "inScene",
+        2 => "active",
+    });
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "ffi::Gesture", rename_all = "camelCase")]
+    struct GestureDef {
+        user_id: i32,
+        gesture_type: ffi::GestureType,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "ffi::UserState", rename_all = "camelCase")]
+    struct UserStateDef {
+        user_id: i32,
+        state: ffi::UserStateType,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "ffi::GestureState", rename_all = "camelCase")]
+    struct GestureStateDef {
+        gesture_type: ffi::GestureType,
+        progress: i32,
+    }
+
+    impl Serialize for ffi::Gesture {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            GestureDef::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ffi::Gesture {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            GestureDef::deserialize(deserializer)
+        }
+    }
+
+    impl Serialize for ffi::UserState {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            UserStateDef::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ffi::UserState {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            UserStateDef::deserialize(deserializer)
+        }
+    }
+
+    impl Serialize for ffi::GestureState {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            GestureStateDef::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ffi::GestureState {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            GestureStateDef::deserialize(deserializer)
+        }
+    }
+}
@@ -31,4 +31,35 @@ pub mod ffi {
         #[cxx_name = "getUserHandsRightHand"] // Corrected
         pub fn user_hands_right_hand(user_hands: &UserHands) -> SharedPtr<Hand>;
     }
-}
\ No newline at end of file
+}
+
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::ffi;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "ffi::Hand", rename_all = "camelCase")]
+    struct HandDef {
+        x: f32,
+        y: f32,
+        click: bool,
+        pressure: i32,
+        x_real: f32,
+        y_real: f32,
+        z_real: f32,
+    }
+
+    impl Serialize for ffi::Hand {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            HandDef::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ffi::Hand {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            HandDef::deserialize(deserializer)
+        }
+    }
+}
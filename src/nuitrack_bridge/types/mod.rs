@@ -6,8 +6,11 @@ pub mod hand_data;
 pub mod hand;
 pub mod output_mode;
 pub mod rgb_frame;
+#[cfg(feature = "serde")]
+mod serde_support;
 pub mod skeleton_data;
 pub mod skeleton;
 pub mod user_frame;
 pub mod user;
-pub mod vector3;
\ No newline at end of file
+pub mod vector3;
+pub mod video_mode;
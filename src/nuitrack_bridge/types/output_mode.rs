@@ -21,4 +21,54 @@ pub mod ffi {
     unsafe extern "C++" {
         include!("nuitrack_bridge/types/output_mode.h");
     }
-}
\ No newline at end of file
+}
+
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::ffi;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "ffi::Intrinsics", rename_all = "camelCase")]
+    struct IntrinsicsDef {
+        fx: f32,
+        fy: f32,
+        cx: f32,
+        cy: f32,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "ffi::OutputMode", rename_all = "camelCase")]
+    struct OutputModeDef {
+        fps: i32,
+        xres: i32,
+        yres: i32,
+        hfov: f32,
+        intrinsics: ffi::Intrinsics,
+    }
+
+    impl Serialize for ffi::Intrinsics {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            IntrinsicsDef::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ffi::Intrinsics {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            IntrinsicsDef::deserialize(deserializer)
+        }
+    }
+
+    impl Serialize for ffi::OutputMode {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            OutputModeDef::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ffi::OutputMode {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            OutputModeDef::deserialize(deserializer)
+        }
+    }
+}
@@ -1,7 +1,7 @@
 #[cxx::bridge(namespace = "nuitrack_bridge::rgb_frame")]
 pub mod ffi {
     
-    #[derive{Debug, Clone, Copy, PartialEq}]
+    #[derive(Debug, Clone, Copy, PartialEq)]
     pub struct Color3 {
         pub blue: u8,
         pub green: u8,
@@ -36,4 +36,31 @@ pub mod ffi {
 }
 
 unsafe impl Send for ffi::RGBFrame {}
-unsafe impl Sync for ffi::RGBFrame {}
\ No newline at end of file
+unsafe impl Sync for ffi::RGBFrame {}
+
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::ffi;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "ffi::Color3", rename_all = "camelCase")]
+    struct Color3Def {
+        blue: u8,
+        green: u8,
+        red: u8,
+    }
+
+    impl Serialize for ffi::Color3 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Color3Def::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ffi::Color3 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Color3Def::deserialize(deserializer)
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! Serde support for types declared inside `cxx::bridge` modules.
+//!
+//! The bridge macro rejects `cfg_attr`, so shared types cannot carry feature-gated
+//! derives. Instead each bridge file has a `#[cfg(feature = "serde")] mod serde_impls`
+//! that implements the traits next to the type: structs through serde's remote
+//! derive, shared enums (which are `{ repr }` structs on the Rust side) through
+//! [`impl_serde_for_shared_enum!`].
+
+/// Implements `Serialize`/`Deserialize` for a cxx shared enum as camelCase strings.
+///
+/// Unknown discriminants serialize as `"unknown"`; unknown strings fail to deserialize.
+macro_rules! impl_serde_for_shared_enum {
+    ($ty:path { $($repr:literal => $name:literal),+ $(,)? }) => {
+        impl serde::Serialize for $ty {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                match self.repr {
+                    $($repr => serializer.serialize_str($name),)+
+                    _ => serializer.serialize_str("unknown"),
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $ty {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = <std::borrow::Cow<'de, str> as serde::Deserialize>::deserialize(deserializer)?;
+                match value.as_ref() {
+                    $($name => Ok(Self { repr: $repr }),)+
+                    other => Err(<D::Error as serde::de::Error>::unknown_variant(other, &[$($name),+])),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use impl_serde_for_shared_enum;
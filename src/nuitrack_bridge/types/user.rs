@@ -27,3 +27,34 @@ pub mod ffi {
         include!("nuitrack_bridge/types/user.h");
     }
 }
+
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::ffi;
+    use crate::nuitrack_bridge::types::bounding_box::ffi::BoundingBox;
+    use crate::nuitrack_bridge::types::vector3::ffi::Vector3;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "ffi::User", rename_all = "camelCase")]
+    struct UserDef {
+        id: i32,
+        proj: Vector3,
+        real: Vector3,
+        r#box: BoundingBox,
+        occlusion: f32,
+    }
+
+    impl Serialize for ffi::User {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            UserDef::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ffi::User {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            UserDef::deserialize(deserializer)
+        }
+    }
+}
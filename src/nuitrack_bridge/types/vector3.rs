@@ -1,4 +1,3 @@
-#[cfg(not(feature = "serde"))]
 #[cxx::bridge(namespace = "nuitrack_bridge::vector3")]
 pub mod ffi {
     /// A CXX-compatible struct representing a 3D vector.
@@ -14,14 +13,27 @@ pub mod ffi {
 }
 
 #[cfg(feature = "serde")]
-#[cxx::bridge(namespace = "nuitrack_bridge::vector3")]
-pub mod ffi {
+mod serde_impls {
+    use super::ffi;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-    #[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
-    #[serde(rename_all = "camelCase")]
-    pub struct Vector3 {
-        pub x: f32,
-        pub y: f32,
-        pub z: f32,
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "ffi::Vector3", rename_all = "camelCase")]
+    struct Vector3Def {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    impl Serialize for ffi::Vector3 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Vector3Def::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ffi::Vector3 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Vector3Def::deserialize(deserializer)
+        }
     }
 }
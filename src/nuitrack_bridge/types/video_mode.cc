@@ -0,0 +1,5 @@
+#include "nuitrack_bridge/types/video_mode.h"
+
+// This file is intentionally blank.
+// Its purpose is to provide a compilation unit for the CXX-generated code
+// associated with the video_mode.rs.h header, should any be produced
\ No newline at end of file
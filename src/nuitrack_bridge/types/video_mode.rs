@@ -0,0 +1,39 @@
+#[cxx::bridge(namespace = "nuitrack_bridge::video_mode")]
+pub mod ffi {
+    /// A resolution and frame rate a sensor stream can run at.
+    ///
+    /// Mirrors `tdv::nuitrack::device::VideoMode`; a zero field in a requested mode
+    /// means "any value".
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct VideoMode {
+        pub width: i32,
+        pub height: i32,
+        pub fps: i32,
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::ffi;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "ffi::VideoMode", rename_all = "camelCase")]
+    struct VideoModeDef {
+        width: i32,
+        height: i32,
+        fps: i32,
+    }
+
+    impl Serialize for ffi::VideoMode {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            VideoModeDef::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ffi::VideoMode {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            VideoModeDef::deserialize(deserializer)
+        }
+    }
+}
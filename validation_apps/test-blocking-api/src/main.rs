@@ -0,0 +1,95 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    backend::simulated::SimulatedBackend,
+    blocking_api::session::BlockingSessionBuilder,
+    shared_types::session_config::{DeviceConfig, DeviceSelector, ModuleType},
+};
+use tracing::{info, Level};
+
+/// This test validates the blocking API without any async runtime.
+/// 1. Tracker methods can be called synchronously.
+/// 2. Frames reach callbacks registered on the session during `update`.
+/// 3. `FrameIter::try_next` drains frames between updates, and `next_timeout`
+///    gives up when nothing arrives.
+/// 4. A blocking `FrameIter` on another thread is woken by the updating thread.
+/// 5. Iterators end once the session is closed.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Blocking API ---");
+
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_users(2)?.realtime(false))
+        .with_device_config(DeviceConfig::new(
+            DeviceSelector::BySerialNumber("SIM-0000".into()),
+            vec![ModuleType::DepthSensor, ModuleType::UserTracker, ModuleType::SkeletonTracker],
+        ))
+        .init_session()?;
+
+    // [1]
+    let (mode, mut depth_frames, mut user_frames, skeleton_frames) = {
+        let mut device = session.device("SIM-0000").expect("device is active");
+        let mut depth = device.depth_sensor().expect("depth sensor was requested");
+        let mode = depth.output_mode()?;
+        let depth_frames = depth.depth_frames()?;
+        let user_frames = device.user_tracker().expect("user tracker was requested").user_frames()?;
+        let skeleton_frames = device.skeleton_tracker().expect("skeleton tracker was requested").skeleton_frames()?;
+        (mode, depth_frames, user_frames, skeleton_frames)
+    };
+    info!(?mode, "SUCCESS: depth output mode read synchronously.");
+
+    // [2]
+    let skeleton_counts = Arc::new(Mutex::new(Vec::new()));
+    let counts = Arc::clone(&skeleton_counts);
+    session.on_frames(skeleton_frames, move |frame| {
+        let frame = frame.expect("skeleton frame");
+        counts.lock().unwrap().push(frame.skeletons().expect("skeletons").len());
+    });
+
+    session.start_processing()?;
+    ensure!(depth_frames.next_timeout(Duration::from_millis(50)).is_none(), "no frame before the first update");
+
+    // [3]
+    let mut depth_count = 0;
+    for _ in 0..20 {
+        session.update()?;
+        while let Some(frame) = depth_frames.try_next() {
+            let frame = frame?;
+            ensure!(frame.rows()? == mode.yres && frame.cols()? == mode.xres, "depth frame size mismatch");
+            depth_count += 1;
+        }
+    }
+    let skeleton_counts = std::mem::take(&mut *skeleton_counts.lock().unwrap());
+    ensure!(depth_count == 20, "expected 20 depth frames, got {}", depth_count);
+    ensure!(skeleton_counts.len() == 20, "expected 20 skeleton callbacks, got {}", skeleton_counts.len());
+    ensure!(skeleton_counts.iter().all(|&n| n == 2), "expected two skeletons per frame");
+    info!("SUCCESS: callbacks and try_next delivered every frame.");
+
+    // [4]
+    let consumer = thread::spawn(move || user_frames.by_ref().take(5).count());
+    while !consumer.is_finished() {
+        session.update()?;
+        thread::sleep(Duration::from_millis(5));
+    }
+    let received = consumer.join().expect("consumer thread panicked");
+    ensure!(received == 5, "consumer thread received {} user frames", received);
+    info!("SUCCESS: blocking iterator woken from another thread.");
+
+    // [5]
+    let mut events = session.events();
+    session.close()?;
+    ensure!(events.next().is_none(), "event iterator should end after close");
+    depth_frames.by_ref().for_each(drop);
+    ensure!(depth_frames.is_terminated(), "depth iterator should end after close");
+    info!("SUCCESS: iterators ended after close.");
+
+    info!("--- Blocking API test passed ---");
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::depth_sensor::Vector3,
+    backend::simulated::SimulatedBackend,
+    blocking_api::session::BlockingSessionBuilder,
+    shared_types::{
+        body_model::{BodyCalibrator, BodyModel, Rig},
+        kinematics::{Bone, Quaternion, BONES},
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+        skeleton::{Joint, JointType, Orientation, Skeleton},
+    },
+};
+use tracing::{info, Level};
+
+/// Offsets of a standing user from the torso, like the simulated backend's.
+const BODY: [(JointType, f32, f32); 18] = [
+    (JointType::Head, 0.0, 450.0),
+    (JointType::Neck, 0.0, 300.0),
+    (JointType::Torso, 0.0, 0.0),
+    (JointType::Waist, 0.0, -200.0),
+    (JointType::LeftCollar, 0.0, 250.0),
+    (JointType::LeftShoulder, -180.0, 250.0),
+    (JointType::LeftElbow, -200.0, 0.0),
+    (JointType::LeftWrist, -210.0, -220.0),
+    (JointType::LeftHand, -215.0, -280.0),
+    (JointType::RightShoulder, 180.0, 250.0),
+    (JointType::RightElbow, 200.0, 0.0),
+    (JointType::RightWrist, 210.0, -220.0),
+    (JointType::LeftHip, -100.0, -250.0),
+    (JointType::LeftKnee, -110.0, -700.0),
+    (JointType::LeftAnkle, -110.0, -1100.0),
+    (JointType::RightHip, 100.0, -250.0),
+    (JointType::RightKnee, 110.0, -700.0),
+    (JointType::RightAnkle, 110.0, -1100.0),
+];
+
+/// Deterministic noise in `-amplitude..amplitude`.
+struct Noise(u64);
+
+impl Noise {
+    fn next(&mut self, amplitude: f32) -> f32 {
+        // SplitMix64, whose consecutive outputs are uncorrelated unlike a plain LCG's.
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^= z >> 31;
+        ((z >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0) * amplitude
+    }
+}
+
+fn body(user_id: i32, noise: &mut Noise, amplitude: f32) -> Skeleton {
+    let joints = BODY
+        .iter()
+        .map(|&(joint_type, dx, dy)| Joint {
+            joint_type,
+            confidence: 0.75,
+            real: Vector3 { x: dx + noise.next(amplitude), y: dy + noise.next(amplitude), z: 2000.0 + noise.next(amplitude) },
+            proj: Vector3::default(),
+            orient: Orientation { matrix: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0] },
+        })
+        .collect();
+    Skeleton { user_id, joints }
+}
+
+fn close(a: f32, b: f32, tolerance: f32) -> bool {
+    (a - b).abs() < tolerance
+}
+
+/// This test validates body calibration and retargeting.
+/// 1. Calibration estimates median bone lengths and height from noisy frames.
+/// 2. Calibrated skeletons keep constant bone lengths and their bone directions; users
+///    missing from a frame are forgotten.
+/// 3. Retargeting poses a complete rig with rotations from the rest pose, and skips joints
+///    a partial rig does not reach.
+/// 4. Live simulated users are calibrated, including the waving arm.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Body Model ---");
+
+    let truth = BodyModel::from_skeletons([&body(1, &mut Noise(0), 0.0)]);
+    let forearm = Bone { parent: JointType::LeftElbow, child: JointType::LeftWrist };
+    let right_forearm = Bone { parent: JointType::RightElbow, child: JointType::RightWrist };
+    let upper_arm = Bone { parent: JointType::LeftShoulder, child: JointType::LeftElbow };
+
+    // [1]
+    let mut calibrator = BodyCalibrator::new(20);
+    let mut noise = Noise(3);
+    for _ in 0..10 {
+        calibrator.apply(&[body(1, &mut noise, 15.0)]);
+    }
+    ensure!(close(calibrator.progress(1), 0.5, 1e-6) && calibrator.model(1).is_none(), "half calibrated");
+    for _ in 0..10 {
+        calibrator.apply(&[body(1, &mut noise, 15.0)]);
+    }
+    let model = calibrator.model(1).expect("calibrated after 20 frames").clone();
+    ensure!(model.samples == 20 && calibrator.progress(1) == 1.0, "calibration complete");
+    ensure!(model.bone_lengths.len() == truth.bone_lengths.len(), "{} bones measured", model.bone_lengths.len());
+    for (bone, length) in &truth.bone_lengths {
+        let estimated = model.bone_length(*bone).unwrap();
+        ensure!(close(estimated, *length, 12.0), "{bone:?}: {estimated} vs {length}");
+    }
+    let height = model.height().expect("height");
+    ensure!(close(height, truth.height().unwrap(), 25.0) && close(truth.height().unwrap(), 1650.1, 0.5), "height {height}");
+    ensure!(model.bone_length(Bone { parent: JointType::RightWrist, child: JointType::RightHand }).is_none(), "unmeasured bone");
+    let symmetric = model.symmetrized();
+    ensure!(
+        symmetric.bone_length(forearm) == symmetric.bone_length(right_forearm),
+        "symmetrized forearms match"
+    );
+    ensure!(symmetric.bone_length(Bone { parent: JointType::RightWrist, child: JointType::RightHand }).is_some(), "missing side filled");
+    info!(height, "SUCCESS: calibration.");
+
+    // [2]
+    let mut lengths = Vec::new();
+    for _ in 0..10 {
+        let raw = body(1, &mut noise, 15.0);
+        let constrained = &calibrator.apply(std::slice::from_ref(&raw))[0];
+        for bone in BONES {
+            if let Some(expected) = model.bone_length(bone) {
+                let actual = constrained.bone_length(bone).unwrap();
+                ensure!(close(actual, expected, 1e-2), "{bone:?} is {actual}, model {expected}");
+            }
+        }
+        let (a, b) = (raw.bone_vector(upper_arm).unwrap(), constrained.bone_vector(upper_arm).unwrap());
+        let cos = (a.x * b.x + a.y * b.y + a.z * b.z) / raw.bone_length(upper_arm).unwrap() / constrained.bone_length(upper_arm).unwrap();
+        ensure!(cos > 0.99999, "bone directions are kept");
+        ensure!(constrained.position(JointType::Waist) == raw.position(JointType::Waist), "root stays in place");
+        lengths.push(constrained.bone_length(forearm).unwrap());
+    }
+    ensure!(lengths.iter().all(|&l| close(l, lengths[0], 1e-2)), "constant forearm length");
+    let both = calibrator.apply(&[body(1, &mut noise, 15.0), body(2, &mut noise, 15.0)]);
+    ensure!(calibrator.model(2).is_none() && !both[1].bone_length(forearm).is_some_and(|l| close(l, lengths[0], 1e-2)), "new users start uncalibrated");
+    calibrator.apply(&[body(2, &mut noise, 15.0)]);
+    ensure!(calibrator.model(1).is_none() && calibrator.progress(2) > 0.0, "users missing from a frame are forgotten");
+    calibrator.reset_user(2);
+    ensure!(calibrator.progress(2) == 0.0, "reset recalibrates");
+    info!("SUCCESS: lengths enforced.");
+
+    // [3]
+    let rig = Rig::standard();
+    let pose = rig.retarget(&body(1, &mut Noise(0), 0.0)).expect("waist tracked");
+    ensure!(pose.skeleton.joints.len() == BONES.len() + 1 && pose.rotations.len() == BONES.len(), "complete rig");
+    for rig_bone in &rig.bones {
+        let length = pose.skeleton.bone_length(rig_bone.bone);
+        let untracked = pose.skeleton.joint(rig_bone.bone.child).unwrap().confidence == 0.0;
+        ensure!(untracked || length.is_some_and(|l| close(l, rig_bone.length, 1e-2)), "{:?} has the rig length", rig_bone.bone);
+    }
+    let rotation = pose.rotation(upper_arm).unwrap();
+    let rest = rig.bone(upper_arm).unwrap().rest_direction;
+    let posed = rotation.rotate(rest);
+    ensure!(close(posed.y, -0.997, 1e-3), "T-pose arm rotated down: {posed:?}");
+    let hand = Bone { parent: JointType::RightWrist, child: JointType::RightHand };
+    ensure!(pose.rotation(hand).is_some_and(|q| q.angle_to(&Quaternion::IDENTITY) < 1e-6), "untracked bones stay at rest");
+    let local = pose.local_rotation(forearm).unwrap();
+    let expected = pose.rotation(upper_arm).unwrap().conjugate() * pose.rotation(forearm).unwrap();
+    ensure!(local.angle_to(&expected) < 1e-6, "local rotation is relative to the parent bone");
+    let fitted = Rig::from_model(&model);
+    ensure!(fitted.bone(forearm).unwrap().length == model.bone_length(forearm).unwrap(), "rig from model");
+    let mut no_waist = body(1, &mut Noise(0), 0.0);
+    no_waist.joints.retain(|j| j.joint_type != JointType::Waist);
+    ensure!(rig.retarget(&no_waist).is_none(), "retargeting needs the root");
+    let mut partial = rig.clone();
+    partial.bones.retain(|b| b.bone.child != JointType::Neck);
+    partial.bones.reverse();
+    let pose = partial.retarget(&body(1, &mut Noise(0), 0.0)).expect("waist tracked");
+    ensure!(pose.skeleton.joint(JointType::Head).is_none() && pose.skeleton.joint(JointType::Torso).is_some(), "unreachable joints skipped");
+    info!("SUCCESS: retargeting.");
+
+    // [4]
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_users(2)?.with_resolution(160, 120).realtime(false))
+        .with_device_config(DeviceConfig::new(
+            DeviceSelector::ByIndex(0),
+            vec![ModuleType::DepthSensor, ModuleType::SkeletonTracker],
+        ))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let camera = device.depth_sensor().expect("depth sensor").camera_model()?;
+    let mut frames = device.skeleton_tracker().expect("skeleton tracker").skeleton_frames()?;
+    session.start_processing()?;
+    let mut calibrator = BodyCalibrator::new(10).symmetric(true).with_camera_model(camera);
+    let right_upper_arm = Bone { parent: JointType::RightShoulder, child: JointType::RightElbow };
+    let mut constrained = 0;
+    for _ in 0..60 {
+        session.update()?;
+        while let Some(frame) = frames.try_next() {
+            let frame = calibrator.apply_frame(&frame?)?;
+            for s in frame.skeletons()? {
+                let Some(model) = calibrator.model(s.user_id) else {
+                    continue;
+                };
+                let expected = model.bone_length(right_upper_arm).unwrap();
+                ensure!(s.bone_length(right_upper_arm).is_some_and(|l| close(l, expected, 1e-2)), "waving arm keeps its length");
+                for joint in s.joints.iter().filter(|j| j.confidence > 0.0) {
+                    let proj = camera.proj_to_normalized(camera.real_to_proj(joint.real));
+                    ensure!(close(proj.x, joint.proj.x, 1e-4) && close(proj.y, joint.proj.y, 1e-4), "proj follows real");
+                }
+                constrained += 1;
+            }
+        }
+    }
+    ensure!(calibrator.models().count() == 2, "both users calibrated");
+    for (user_id, model) in calibrator.models() {
+        let height = model.height().unwrap();
+        ensure!(close(height, 1650.1, 1.0), "user {user_id} height {height}");
+    }
+    ensure!(constrained > 0, "no constrained skeletons");
+    session.close()?;
+    info!(constrained, "SUCCESS: live users calibrated.");
+
+    info!("--- Body model test passed ---");
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::depth_sensor::{Intrinsics, OutputMode, Vector3},
+    backend::simulated::SimulatedBackend,
+    blocking_api::session::BlockingSessionBuilder,
+    shared_types::{
+        camera_model::CameraModel,
+        depth_frame::OwnedDepthFrame,
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+    },
+};
+use tracing::{info, Level};
+
+fn distance(a: Vector3, b: Vector3) -> f32 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
+}
+
+fn grid(model: &CameraModel) -> Vec<Vector3> {
+    let mut points = Vec::new();
+    for v in (0..model.height).step_by(37) {
+        for u in (0..model.width).step_by(41) {
+            points.push(Vector3 { x: u as f32, y: v as f32, z: 500.0 + (u + v) as f32 * 3.0 });
+        }
+    }
+    points
+}
+
+/// This test validates the Rust-side camera model.
+/// 1. `proj_to_real` and `real_to_proj` are inverse, with and without mirroring.
+/// 2. Slice and whole-frame conversions agree with the per-point conversion.
+/// 3. Skeleton joints reproject onto their reported projective positions.
+/// 4. With `--sdk`, conversions match `DepthSensor::convertProjToReal`/`convertRealToProj`
+///    on the first connected device.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Camera Model ---");
+
+    // [1]
+    let mode = OutputMode {
+        fps: 30,
+        xres: 640,
+        yres: 480,
+        hfov: 1.0,
+        intrinsics: Intrinsics { fx: 520.0, fy: 515.0, cx: 318.5, cy: 243.0 },
+    };
+    let model = CameraModel::new(&mode)?;
+    for mirror in [false, true] {
+        let model = model.with_mirror(mirror);
+        for p in grid(&model) {
+            let back = model.real_to_proj(model.proj_to_real(p));
+            ensure!(distance(p, back) < 1e-3, "round trip of {p:?} (mirror {mirror}) gave {back:?}");
+        }
+    }
+    let center = model.proj_to_real(Vector3 { x: 318.5, y: 243.0, z: 1000.0 });
+    ensure!(center.x.abs() < 1e-3 && center.y.abs() < 1e-3, "principal point maps to the optical axis");
+    let up_left = model.proj_to_real(Vector3 { x: 0.0, y: 0.0, z: 1000.0 });
+    ensure!(up_left.x < 0.0 && up_left.y > 0.0, "image top-left is real-world left and up");
+    let mirrored = model.with_mirror(true).proj_to_real(Vector3 { x: 0.0, y: 0.0, z: 1000.0 });
+    ensure!(mirrored.x > 0.0, "mirroring flips the x axis");
+    let last_column = model.with_mirror(true).proj_to_real(Vector3 { x: 639.0, y: 0.0, z: 1000.0 });
+    ensure!(distance(last_column, up_left) < 1e-3, "mirroring maps the last column onto the first");
+    let fov_only = CameraModel::new(&OutputMode { intrinsics: Intrinsics { fx: 0.0, fy: 0.0, cx: 0.0, cy: 0.0 }, ..mode })?;
+    ensure!((fov_only.intrinsics.cx - 320.0).abs() < 1e-3, "field-of-view fallback");
+    info!("SUCCESS: conversions are inverse.");
+
+    // [2]
+    let mut points = grid(&model);
+    let expected: Vec<Vector3> = points.iter().map(|&p| model.proj_to_real(p)).collect();
+    model.proj_to_real_in_place(&mut points);
+    ensure!(points == expected, "slice conversion");
+    let half = model.scaled_to(320, 240);
+    let frame = OwnedDepthFrame { rows: 240, cols: 320, id: 0, timestamp: 0, data: (0..320 * 240).map(|i| (i % 3000) as u16).collect() };
+    let real = half.frame_to_real(&frame)?;
+    ensure!(real.len() == frame.data.len(), "one point per pixel");
+    let (v, u) = (100, 77);
+    let direct = half.proj_to_real(Vector3 { x: u as f32, y: v as f32, z: f32::from(frame.data[v * 320 + u]) });
+    ensure!(distance(real[v * 320 + u], direct) < 1e-2, "frame conversion {:?} vs {:?}", real[v * 320 + u], direct);
+    ensure!(model.frame_to_real(&frame)? == real, "frames smaller than the mode use the scaled model");
+    let mirrored = half.with_mirror(true);
+    let direct = mirrored.proj_to_real(Vector3 { x: u as f32, y: v as f32, z: f32::from(frame.data[v * 320 + u]) });
+    let real = mirrored.frame_to_real(&frame)?;
+    ensure!(distance(real[v * 320 + u], direct) < 1e-2, "mirrored frame conversion {:?} vs {:?}", real[v * 320 + u], direct);
+    info!("SUCCESS: slice and frame conversions agree.");
+
+    // [3]
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_resolution(320, 240).realtime(false))
+        .with_device_config(DeviceConfig::new(
+            DeviceSelector::ByIndex(0),
+            vec![ModuleType::DepthSensor, ModuleType::SkeletonTracker],
+        ))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let model = device.depth_sensor().expect("depth sensor").camera_model()?;
+    let mut skeletons = device.skeleton_tracker().expect("skeleton tracker").skeleton_frames()?;
+    session.start_processing()?;
+    session.update()?;
+    let frame = skeletons.try_next().expect("skeleton frame after update")?;
+    let mut checked = 0;
+    for skeleton in frame.skeletons()? {
+        for joint in skeleton.joints.iter().filter(|j| j.real.z > 0.0) {
+            let normalized = model.proj_to_normalized(model.real_to_proj(joint.real));
+            ensure!(distance(normalized, joint.proj) < 1e-3, "{:?}: {normalized:?} vs {:?}", joint.joint_type, joint.proj);
+            checked += 1;
+        }
+    }
+    ensure!(checked > 0, "no joints to check");
+    session.close()?;
+    info!(checked, "SUCCESS: joints reproject onto their projective positions.");
+
+    // [4]
+    if std::env::args().any(|arg| arg == "--sdk") {
+        let mut session = BlockingSessionBuilder::create_session_from_single_default_device(vec![ModuleType::DepthSensor])?;
+        session.start_processing()?;
+        let mut device = session.devices().next().expect("one active device");
+        let sensor = device.depth_sensor().expect("depth sensor");
+        let model = sensor.camera_model()?;
+        for p in grid(&model) {
+            let sdk_real = sensor.convert_proj_to_real(p)?;
+            ensure!(distance(model.proj_to_real(p), sdk_real) < 1.0, "proj_to_real({p:?}) vs SDK {sdk_real:?}");
+            let sdk_proj = sensor.convert_real_to_proj(sdk_real)?;
+            ensure!(distance(model.real_to_proj(sdk_real), sdk_proj) < 0.5, "real_to_proj({sdk_real:?}) vs SDK {sdk_proj:?}");
+        }
+        session.close()?;
+        info!("SUCCESS: conversions match the SDK.");
+    } else {
+        info!("Skipping SDK comparison; pass --sdk with a connected sensor.");
+    }
+
+    info!("--- Camera model test passed ---");
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::{depth_sensor::Vector3, processed_stream::ProcessedStream},
+    backend::simulated::SimulatedBackend,
+    blocking_api::{frame_iter::FrameIter, session::BlockingSessionBuilder},
+    shared_types::{
+        custom_gesture::{CustomGestureConfig, CustomGestureRecognizer, GestureRecorder, GestureTemplate},
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+        skeleton::{Joint, JointType, Orientation, Skeleton},
+    },
+};
+use std::f32::consts::PI;
+use std::time::Duration;
+use tracing::{info, Level};
+
+const FRAME_US: u64 = 33_333;
+const HAND: [JointType; 2] = [JointType::RightElbow, JointType::RightWrist];
+
+fn joint(joint_type: JointType, x: f32, y: f32, z: f32) -> Joint {
+    Joint {
+        joint_type,
+        confidence: 0.75,
+        real: Vector3 { x, y, z },
+        proj: Vector3::default(),
+        orient: Orientation { matrix: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0] },
+    }
+}
+
+/// A user of the given `size` (1 for a 300 mm torso) standing at `x`, with the right
+/// elbow and wrist at `elbow` and `wrist`, relative to the torso in unscaled millimeters.
+fn user(user_id: i32, x: f32, size: f32, elbow: (f32, f32), wrist: (f32, f32)) -> Skeleton {
+    let at = |dx: f32, dy: f32| (x + dx * size, dy * size);
+    let joints = [
+        (JointType::Torso, at(0.0, 0.0)),
+        (JointType::Neck, at(0.0, 300.0)),
+        (JointType::RightElbow, at(elbow.0, elbow.1)),
+        (JointType::RightWrist, at(wrist.0, wrist.1)),
+    ];
+    Skeleton { user_id, joints: joints.iter().map(|&(j, (x, y))| joint(j, x, y, 2000.0)).collect() }
+}
+
+/// The right arm at rest.
+fn idle(user_id: i32, x: f32, size: f32) -> Skeleton {
+    user(user_id, x, size, (200.0, 0.0), (210.0, -220.0))
+}
+
+/// The right arm `progress` (0 to 1) of the way through raising the hand overhead.
+fn raising(user_id: i32, x: f32, size: f32, progress: f32) -> Skeleton {
+    let angle = progress * PI;
+    user(user_id, x, size, (200.0, 0.0), (200.0 + 220.0 * angle.sin(), -220.0 * angle.cos()))
+}
+
+/// The right forearm `progress` of the way through a sweep across the body.
+fn swiping(user_id: i32, x: f32, size: f32, progress: f32) -> Skeleton {
+    user(user_id, x, size, (200.0, 0.0), (350.0 - 500.0 * progress, 50.0))
+}
+
+/// One simulated backend wave cycle: arm raised, wrist swinging 80 mm each way.
+fn waving(progress: f32) -> Skeleton {
+    user(1, 0.0, 1.0, (300.0, 300.0), (320.0 + 80.0 * (2.0 * PI * progress).sin(), 550.0))
+}
+
+fn template(name: &str, frames: u64, pose: impl Fn(f32) -> Skeleton) -> Result<GestureTemplate> {
+    let skeletons: Vec<_> = (0..frames).map(|i| (i * FRAME_US, pose(i as f32 / (frames - 1) as f32))).collect();
+    Ok(GestureTemplate::from_skeletons(name, HAND.to_vec(), skeletons.iter().map(|(t, s)| (*t, s)))?)
+}
+
+/// Feeds `skeletons` one per frame from `start` and returns `(frame, name)` of each event.
+fn run(recognizer: &mut CustomGestureRecognizer, start: u64, skeletons: &[Skeleton]) -> Vec<(u64, String, f32)> {
+    skeletons
+        .iter()
+        .enumerate()
+        .flat_map(|(i, s)| {
+            let frame = start + i as u64;
+            recognizer.apply(frame * FRAME_US, std::slice::from_ref(s)).into_iter().map(move |e| (frame, e.name, e.confidence))
+        })
+        .collect()
+}
+
+/// This test validates template-based gesture recognition.
+/// 1. Templates are recorded from skeletons, and too short recordings and malformed
+///    templates are rejected.
+/// 2. Gestures are recognized at other speeds, positions and body sizes, but not idle motion.
+/// 3. Multiple users, cooldowns and repeated gestures.
+/// 4. The stream adapter recognizes the simulated backend's wave.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Custom Gesture ---");
+
+    // [1]
+    let raise = template("raise", 30, |p| raising(1, 0.0, 1.0, p))?;
+    let swipe = template("swipe", 20, |p| swiping(1, 0.0, 1.0, p))?;
+    ensure!(raise.poses.len() == 30 && raise.duration == Duration::from_micros(29 * FRAME_US), "raise template");
+    let mut recorder = GestureRecorder::new(HAND.to_vec());
+    let mut incomplete = idle(1, 0.0, 1.0);
+    incomplete.joints.retain(|j| j.joint_type != JointType::RightWrist);
+    ensure!(!recorder.push(0, &incomplete) && recorder.push(0, &idle(1, 0.0, 1.0)), "untracked joints are skipped");
+    ensure!(recorder.finish("short").is_err(), "one frame is not a gesture");
+    let mut empty = raise.clone();
+    empty.poses.clear();
+    ensure!(CustomGestureRecognizer::default().add_template(empty).is_err(), "templates without poses are rejected");
+    let mut ragged = raise.clone();
+    ragged.poses[3].pop();
+    ensure!(CustomGestureRecognizer::default().add_template(ragged).is_err(), "poses must cover every joint");
+    info!("SUCCESS: templates recorded.");
+
+    // [2]
+    let mut recognizer = CustomGestureRecognizer::default().with_template(raise.clone())?.with_template(swipe.clone())?;
+    let mut motion: Vec<Skeleton> = (0..30).map(|_| idle(1, 500.0, 1.2)).collect();
+    motion.extend((0..20).map(|i| raising(1, 500.0, 1.2, i as f32 / 19.0)));
+    motion.extend((0..30).map(|_| raising(1, 500.0, 1.2, 1.0)));
+    let events = run(&mut recognizer, 0, &motion);
+    ensure!(events.len() == 1 && events[0].1 == "raise", "one raise at 1.5x speed: {events:?}");
+    ensure!((45..=55).contains(&events[0].0), "recognized as the raise completes: {events:?}");
+    // Each gesture is followed by a few frames holding its end pose, so it can be reported.
+    let slow: Vec<Skeleton> = (0..50).map(|i| raising(1, -300.0, 0.9, (i as f32 / 44.0).min(1.0))).collect();
+    let events = run(&mut recognizer, 100, &slow);
+    ensure!(events.len() == 1 && events[0].1 == "raise", "slow raise by a smaller user: {events:?}");
+    let sweep: Vec<Skeleton> = (0..20).map(|i| swiping(1, 0.0, 1.0, (i as f32 / 14.0).min(1.0))).collect();
+    let events = run(&mut recognizer, 200, &sweep);
+    ensure!(events.len() == 1 && events[0].1 == "swipe" && events[0].2 > 0.5, "swipe: {events:?}");
+    let too_fast: Vec<Skeleton> = (0..10).map(|i| raising(1, 0.0, 1.0, (i as f32 / 5.0).min(1.0))).collect();
+    ensure!(run(&mut recognizer, 300, &too_fast).is_empty(), "more than twice as fast does not match");
+    let still: Vec<Skeleton> = (0..120).map(|_| idle(1, 0.0, 1.0)).collect();
+    ensure!(run(&mut recognizer, 400, &still).is_empty(), "no gestures while idle");
+    info!("SUCCESS: gestures matched.");
+
+    // [3]
+    let config = CustomGestureConfig { cooldown: Duration::from_secs(2), ..Default::default() };
+    let mut recognizer = CustomGestureRecognizer::new(config).with_template(raise)?;
+    let mut events = Vec::new();
+    for i in 0..120u64 {
+        // User 1 raises twice in quick succession, user 2 once, late.
+        let p1 = (i % 30) as f32 / 29.0;
+        let s1 = if i < 60 { raising(1, -400.0, 1.0, p1) } else { idle(1, -400.0, 1.0) };
+        let s2 = if (80..110).contains(&i) { raising(2, 400.0, 1.0, (i - 80) as f32 / 29.0) } else { idle(2, 400.0, 1.0) };
+        events.extend(recognizer.apply(i * FRAME_US, &[s1, s2]));
+    }
+    let users: Vec<i32> = events.iter().map(|e| e.user_id).collect();
+    ensure!(users == [1, 2], "the second raise of user 1 falls in its cooldown: {events:?}");
+    ensure!(events[1].start_timestamp >= 79 * FRAME_US, "start of user 2's raise: {}", events[1].start_timestamp);
+    recognizer.remove_templates("raise");
+    ensure!(recognizer.templates().is_empty(), "templates removed");
+    info!("SUCCESS: users and cooldowns.");
+
+    // [4]
+    let wave = template("wave", 11, waving)?;
+    let config = CustomGestureConfig { cooldown: Duration::from_millis(400), ..Default::default() };
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_resolution(160, 120).realtime(false))
+        .with_device_config(DeviceConfig::new(DeviceSelector::ByIndex(0), vec![ModuleType::SkeletonTracker]))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let stream = device.skeleton_tracker().expect("skeleton tracker").as_async().skeleton_frames_stream()?;
+    let mut gestures = FrameIter::new(ProcessedStream::new(stream, CustomGestureRecognizer::new(config).with_template(wave)?));
+    session.start_processing()?;
+    let mut events = Vec::new();
+    for _ in 0..120 {
+        session.update()?;
+        while let Some(event) = gestures.try_next() {
+            events.push(event?);
+        }
+    }
+    session.close()?;
+    ensure!(events.len() >= 3, "{} waves recognized", events.len());
+    ensure!(events.iter().all(|e| e.name == "wave" && e.timestamp <= 2_100_000), "waves only while waving: {events:?}");
+    info!(waves = events.len(), "SUCCESS: live waves recognized.");
+
+    info!("--- Custom gesture test passed ---");
+    Ok(())
+}
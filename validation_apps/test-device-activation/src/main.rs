@@ -0,0 +1,61 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    backend::simulated::SimulatedBackend,
+    blocking_api::session::BlockingSessionBuilder,
+    shared_types::{error::NuitrackError, session_config::ActivationStatus},
+};
+use tracing::{info, warn, Level};
+
+/// This test validates device activation the way an installer would use it.
+/// 1. Virtual devices report `Unknown` and refuse activation with a backend error.
+/// 2. Every connected sensor reports its license state and trial time limit.
+/// 3. With `NUITRACK_ACTIVATION_KEY` set, sensors without a license are activated.
+///
+/// Steps 2 and 3 need the Nuitrack runtime and at least one sensor.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Device Activation ---");
+
+    // [1]
+    let mut discovery = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new())
+        .discover_devices_first()?;
+    let serial = discovery.list_devices()[0].serial_number.clone();
+    ensure!(discovery.list_devices()[0].activation == ActivationStatus::Unknown, "virtual device should report Unknown");
+    ensure!(discovery.refresh_activation(&serial)? == ActivationStatus::Unknown, "refresh should keep Unknown");
+    match discovery.activate_device(&serial, "any-key") {
+        Err(NuitrackError::Backend(_)) => info!("SUCCESS: virtual device refused activation."),
+        other => anyhow::bail!("unexpected activation result for a virtual device: {:?}", other.map_err(|e| e.to_string())),
+    }
+    ensure!(
+        matches!(discovery.activate_device("NO-SUCH-SERIAL", "any-key"), Err(NuitrackError::DeviceError(_))),
+        "unknown serial should be a device error"
+    );
+    drop(discovery);
+
+    // [2]
+    let mut discovery = BlockingSessionBuilder::new().discover_devices_first()?;
+    let devices: Vec<_> = discovery.list_devices().iter().map(|d| (d.serial_number.clone(), d.name.clone(), d.activation)).collect();
+    ensure!(!devices.is_empty(), "no sensor connected");
+    for (serial, name, activation) in &devices {
+        info!(%serial, %name, ?activation, time_limit = ?activation.time_limit(), "Sensor license.");
+    }
+
+    // [3]
+    match std::env::var("NUITRACK_ACTIVATION_KEY") {
+        Ok(key) => {
+            for (serial, _, activation) in devices.iter().filter(|(_, _, a)| !a.is_licensed()) {
+                let status = discovery.activate_device(serial, key.as_str())?;
+                info!(%serial, before = ?activation, after = ?status, "SUCCESS: sensor activated.");
+            }
+        }
+        Err(_) => warn!("NUITRACK_ACTIVATION_KEY is not set; skipping activation."),
+    }
+
+    info!("--- Device activation test passed ---");
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use anyhow::{ensure, Context, Result};
+use futures_util::StreamExt;
+use nuitrack_rs::nuitrack::{
+    async_api::{
+        health::{DeviceHealth, HealthConfig, SessionEvent},
+        session_builder::NuitrackSessionBuilder,
+    },
+    backend::simulated::SimulatedBackend,
+    shared_types::session_config::{DeviceConfig, DeviceSelector, ModuleType},
+};
+use tracing::{info, Level};
+
+/// This test validates device health monitoring with a simulated disconnect.
+/// 1. A device that stops delivering frames is reported as stalled, then lost.
+/// 2. Once it delivers frames again it is reported as recovered, and the stream
+///    handle taken before the dropout keeps working.
+/// 3. The healthy device produces no events, and closing the session ends the event stream.
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Device Health ---");
+
+    let backend = SimulatedBackend::new()
+        .with_devices(2)
+        .with_dropout(1, Duration::from_millis(500), Duration::from_millis(2000));
+    let mut session = NuitrackSessionBuilder::new()
+        .with_backend(backend)
+        .with_health_config(HealthConfig {
+            stall_timeout: Duration::from_millis(200),
+            lost_timeout: Duration::from_millis(600),
+            ..Default::default()
+        })
+        .with_device_config(DeviceConfig::new(DeviceSelector::ByIndex(0), vec![ModuleType::SkeletonTracker]))
+        .with_device_config(DeviceConfig::new(DeviceSelector::ByIndex(1), vec![ModuleType::SkeletonTracker]))
+        .init_session()
+        .await?;
+
+    let mut events = session.events_stream();
+    let mut skeletons = session.device_mut("SIM-0001").unwrap().skeleton_tracker.as_mut().unwrap().skeleton_frames_stream()?;
+    session.start_processing().await?;
+
+    // [1] and [2]
+    let mut received = Vec::new();
+    while !matches!(received.last(), Some(SessionEvent::DeviceRecovered { .. })) {
+        let event = tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .context("timed out waiting for session events")?
+            .context("event stream ended early")?;
+        if matches!(event, SessionEvent::DeviceLost { .. }) {
+            ensure!(session.device_health("SIM-0001") == Some(DeviceHealth::Lost), "health should report the loss");
+        }
+        received.push(event);
+    }
+    info!(?received, "Session events received.");
+    ensure!(received.len() == 3, "expected exactly stalled, lost and recovered");
+    ensure!(matches!(&received[0], SessionEvent::Stalled { serial_number, .. } if serial_number == "SIM-0001"), "first event should be a stall");
+    ensure!(matches!(&received[1], SessionEvent::DeviceLost { serial_number, .. } if serial_number == "SIM-0001"), "second event should be a loss");
+    ensure!(matches!(&received[2], SessionEvent::DeviceRecovered { serial_number } if serial_number == "SIM-0001"), "third event should be a recovery");
+    ensure!(session.device_health("SIM-0001") == Some(DeviceHealth::Healthy), "device should be healthy again");
+    info!("SUCCESS: stall, loss and recovery reported.");
+
+    // Drain what was buffered during the run, then check that new frames still arrive.
+    while tokio::time::timeout(Duration::from_millis(1), skeletons.next()).await.is_ok() {}
+    let frame = tokio::time::timeout(Duration::from_secs(1), skeletons.next())
+        .await
+        .context("no skeleton frames after recovery")?
+        .context("skeleton stream ended")??;
+    ensure!(frame.timestamp()? >= 2_000_000, "frame should be from after the dropout");
+    info!("SUCCESS: stream handle survived the dropout.");
+
+    // [3]
+    ensure!(session.device_health("SIM-0000") == Some(DeviceHealth::Healthy), "SIM-0000 should be unaffected");
+    drop(skeletons);
+    session.close().await?;
+    ensure!(events.next().await.is_none(), "event stream should end with the session");
+    info!("SUCCESS: event stream ended on close.");
+
+    info!("--- Test Complete ---");
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::depth_sensor::Vector3,
+    backend::simulated::SimulatedBackend,
+    blocking_api::session::BlockingSessionBuilder,
+    shared_types::{
+        floor_plane::{FloorPlane, FloorPlaneSmoother},
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+    },
+};
+use tracing::{info, Level};
+
+fn v(x: f32, y: f32, z: f32) -> Vector3 {
+    Vector3 { x, y, z }
+}
+
+fn near(a: f32, b: f32, tolerance: f32) -> bool {
+    (a - b).abs() <= tolerance
+}
+
+/// This test validates the floor plane model.
+/// 1. Distances, projections and sensor height for a level sensor, whichever way the normal points.
+/// 2. Pitch and roll of a tilted sensor.
+/// 3. Smoothing, missing floors and resets on large jumps.
+/// 4. Live floors, feet height and foot contact from skeletons.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Floor Plane ---");
+
+    // [1]
+    ensure!(FloorPlane::new(v(0.0, -1000.0, 0.0), v(0.0, 0.0, 0.0)).is_none(), "zero normal means no floor");
+    let level = FloorPlane::new(v(100.0, -1000.0, 3000.0), v(0.0, 2.0, 0.0)).expect("valid plane");
+    ensure!(near(level.sensor_height(), 1000.0, 1e-3), "sensor height {}", level.sensor_height());
+    ensure!(near(level.signed_distance(v(5.0, -400.0, 2000.0)), 600.0, 1e-3), "distance above");
+    ensure!(near(level.signed_distance(v(0.0, -1100.0, 0.0)), -100.0, 1e-3), "distance below");
+    let foot = level.project(v(250.0, 300.0, 1800.0));
+    ensure!(near(foot.y, -1000.0, 1e-3) && near(foot.x, 250.0, 1e-3) && near(foot.z, 1800.0, 1e-3), "projection {foot:?}");
+    ensure!(near(level.tilt(), 0.0, 1e-6) && near(level.origin().y, -1000.0, 1e-3), "level sensor");
+    let flipped = FloorPlane::new(v(100.0, -1000.0, 3000.0), v(0.0, -2.0, 0.0)).expect("valid plane");
+    ensure!(flipped == level, "a downward normal is flipped up: {flipped:?}");
+    info!("SUCCESS: level floor queries.");
+
+    // [2]
+    let angle = 20f32.to_radians();
+    // A sensor looking down by `angle` sees the up vector tilted towards -z.
+    let pitched = FloorPlane::new(v(0.0, -1200.0, 0.0), v(0.0, angle.cos(), -angle.sin())).expect("valid plane");
+    ensure!(near(pitched.pitch(), angle, 1e-5) && near(pitched.roll(), 0.0, 1e-5), "pitch {}", pitched.pitch());
+    ensure!(near(pitched.tilt(), angle, 1e-5), "tilt {}", pitched.tilt());
+    ensure!(near(pitched.sensor_height(), 1200.0 * angle.cos(), 1e-2), "pitched height {}", pitched.sensor_height());
+    let rolled = FloorPlane::new(v(0.0, -1200.0, 0.0), v(angle.sin(), angle.cos(), 0.0)).expect("valid plane");
+    ensure!(near(rolled.roll(), angle, 1e-5) && near(rolled.pitch(), 0.0, 1e-5), "roll {}", rolled.roll());
+    info!("SUCCESS: pitch and roll.");
+
+    // [3]
+    let mut smoother = FloorPlaneSmoother::new(0.5);
+    ensure!(smoother.update(None).is_none(), "nothing before the first floor");
+    smoother.update(Some(level));
+    let blended = smoother.update(FloorPlane::new(v(0.0, -1100.0, 0.0), v(0.0, 1.0, 0.0))).expect("smoothed floor");
+    ensure!(near(blended.sensor_height(), 1050.0, 1e-3), "blended height {}", blended.sensor_height());
+    ensure!(smoother.update(None) == Some(blended), "missing floor keeps the estimate");
+    let jumped = smoother.update(Some(pitched)).expect("smoothed floor");
+    ensure!(jumped == pitched, "a large jump replaces the estimate");
+    smoother.reset();
+    ensure!(smoother.current().is_none(), "reset");
+    info!("SUCCESS: smoothing.");
+
+    // [4]
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_resolution(160, 120).realtime(false))
+        .with_device_config(DeviceConfig::new(
+            DeviceSelector::ByIndex(0),
+            vec![ModuleType::UserTracker, ModuleType::SkeletonTracker],
+        ))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let mut user_frames = device.user_tracker().expect("user tracker").user_frames()?;
+    let mut skeleton_frames = device.skeleton_tracker().expect("skeleton tracker").skeleton_frames()?;
+    session.start_processing()?;
+    let mut smoother = FloorPlaneSmoother::default();
+    let mut checked = 0;
+    for _ in 0..5 {
+        session.update()?;
+        while let (Some(users), Some(skeletons)) = (user_frames.try_next(), skeleton_frames.try_next()) {
+            let floor = smoother.update_from_frame(&users?)?.expect("simulated floor");
+            ensure!(near(floor.sensor_height(), 1150.0, 1.0), "live sensor height {}", floor.sensor_height());
+            for skeleton in skeletons?.skeletons()? {
+                let feet = floor.feet_height(skeleton).expect("ankles are tracked");
+                ensure!(near(feet, 50.0, 1.0), "feet height {feet}");
+                ensure!(floor.has_foot_contact(skeleton, 100.0) && !floor.has_foot_contact(skeleton, 10.0), "foot contact");
+                checked += 1;
+            }
+        }
+    }
+    ensure!(checked > 0, "no skeletons checked");
+    session.close()?;
+    info!(checked, "SUCCESS: live floor and foot contact.");
+
+    info!("--- Floor plane test passed ---");
+    Ok(())
+}
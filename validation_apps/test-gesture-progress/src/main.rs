@@ -0,0 +1,162 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::gesture_event_stream::GestureEventStream,
+    backend::simulated::SimulatedBackend,
+    blocking_api::{frame_iter::FrameIter, session::BlockingSessionBuilder},
+    shared_types::{
+        gesture::{Gesture, GestureState, GestureType, UserGestures, UserStateType},
+        gesture_progress::{GestureEvent, GestureProgressConfig, GestureProgressTracker},
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+    },
+};
+use std::time::Duration;
+use tracing::{info, Level};
+
+const FRAME_US: u64 = 33_333;
+
+fn waving(user_id: i32, progress: i32) -> UserGestures {
+    UserGestures {
+        user_id,
+        user_state: UserStateType::Active,
+        gestures: vec![GestureState { gesture_type: GestureType::Waving, progress }],
+    }
+}
+
+fn wave_done(user_id: i32) -> Gesture {
+    Gesture { user_id, gesture_type: GestureType::Waving }
+}
+
+/// Short form of an event: kind, user, frame and progress (or -1).
+fn summary(event: &GestureEvent) -> (&'static str, i32, u64, i32) {
+    let frame = event.timestamp() / FRAME_US;
+    match *event {
+        GestureEvent::GestureStarted { user_id, progress, .. } => ("started", user_id, frame, progress),
+        GestureEvent::GestureProgress { user_id, progress, .. } => ("progress", user_id, frame, progress),
+        GestureEvent::GestureCompleted { user_id, .. } => ("completed", user_id, frame, -1),
+        GestureEvent::GestureCancelled { user_id, progress, .. } => ("cancelled", user_id, frame, progress),
+    }
+}
+
+/// Feeds one user list per frame from `start`, completing waves of the users listed
+/// for a frame in `completions`.
+fn run(
+    tracker: &mut GestureProgressTracker,
+    start: u64,
+    frames: &[Vec<UserGestures>],
+    completions: &[(u64, i32)],
+) -> Vec<(&'static str, i32, u64, i32)> {
+    let mut events = Vec::new();
+    for (i, users) in frames.iter().enumerate() {
+        let frame = start + i as u64;
+        events.extend(tracker.apply_update(frame * FRAME_US, users));
+        let done: Vec<Gesture> = completions.iter().filter(|c| c.0 == frame).map(|c| wave_done(c.1)).collect();
+        events.extend(tracker.apply_completed(frame * FRAME_US, &done));
+    }
+    events.iter().map(summary).collect()
+}
+
+/// This test validates gesture lifecycle events.
+/// 1. Gestures start after the debounce, report progress steps and complete.
+/// 2. Cooldowns suppress restarts and repeated completions, per gesture type.
+/// 3. Gestures falling back without completion, or whose user leaves, are cancelled.
+/// 4. The stream adapter merges the simulated backend's updates and completions.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Gesture Progress ---");
+
+    // [1]
+    let mut tracker = GestureProgressTracker::default();
+    let mut frames: Vec<Vec<UserGestures>> = (0..13).map(|i| vec![waving(1, i * 8)]).collect();
+    frames.push(vec![waving(1, 0)]);
+    let events = run(&mut tracker, 0, &frames, &[(13, 1)]);
+    let mut expected = vec![("started", 1, 6, 48)];
+    expected.extend((7..13).map(|f| ("progress", 1, f, f as i32 * 8)));
+    expected.push(("completed", 1, 13, -1));
+    ensure!(events == expected, "wave lifecycle: {events:?}");
+    ensure!(!tracker.is_active(1, GestureType::Waving), "completed gestures end");
+    let mut tracker = GestureProgressTracker::new(GestureProgressConfig::default().with_progress_step(20));
+    let steps = run(&mut tracker, 0, &frames[..13], &[]);
+    ensure!(steps.iter().filter(|e| e.0 == "progress").count() == 2, "larger progress steps: {steps:?}");
+    ensure!(tracker.is_active(1, GestureType::Waving), "wave under way");
+    info!("SUCCESS: gestures progress.");
+
+    // [2]
+    let mut tracker = GestureProgressTracker::default();
+    run(&mut tracker, 0, &frames, &[(13, 1)]);
+    let busy: Vec<Vec<UserGestures>> = (0..11).map(|_| vec![waving(1, 50)]).collect();
+    let events = run(&mut tracker, 14, &busy, &[(24, 1)]);
+    ensure!(events.is_empty(), "nothing within the 500 ms cooldown: {events:?}");
+    let events = run(&mut tracker, 30, &busy, &[]);
+    ensure!(events == [("started", 1, 34, 50)], "restarts after the cooldown: {events:?}");
+
+    let config = GestureProgressConfig::default()
+        .with_cooldown(Duration::from_secs(2))
+        .with_gesture_cooldown(GestureType::SwipeLeft, Duration::ZERO);
+    ensure!(config.cooldown_for(GestureType::SwipeLeft).is_zero(), "override");
+    let mut tracker = GestureProgressTracker::new(config);
+    let swipe = Gesture { user_id: 2, gesture_type: GestureType::SwipeLeft };
+    let mut completed = Vec::new();
+    for frame in 0..3u64 {
+        completed.extend(tracker.apply_completed(frame * FRAME_US, &[swipe, wave_done(2)]));
+    }
+    let kinds: Vec<GestureType> = completed.iter().map(|e| e.gesture_type()).collect();
+    ensure!(
+        kinds == [GestureType::SwipeLeft, GestureType::Waving, GestureType::SwipeLeft, GestureType::SwipeLeft],
+        "swipes have no cooldown, unstarted waves complete once: {kinds:?}"
+    );
+    info!("SUCCESS: cooldowns.");
+
+    // [3]
+    let mut tracker = GestureProgressTracker::default();
+    let mut frames: Vec<Vec<UserGestures>> = (0..6).map(|_| vec![waving(1, 50)]).collect();
+    frames.extend((0..10).map(|_| vec![waving(1, 0)]));
+    let events = run(&mut tracker, 0, &frames, &[]);
+    ensure!(events == [("started", 1, 4, 50), ("cancelled", 1, 13, 50)], "cancelled 200 ms after falling back: {events:?}");
+
+    let mut tracker = GestureProgressTracker::default();
+    let both: Vec<Vec<UserGestures>> = (0..5).map(|_| vec![waving(1, 40), waving(2, 40)]).collect();
+    run(&mut tracker, 0, &both, &[]);
+    ensure!(tracker.is_active(1, GestureType::Waving) && tracker.is_active(2, GestureType::Waving), "both waving");
+    let mut absent = waving(2, 40);
+    absent.user_state = UserStateType::IsAbsent;
+    let events = run(&mut tracker, 5, &[vec![absent]], &[]);
+    ensure!(events == [("cancelled", 1, 5, 40), ("cancelled", 2, 5, 40)], "lost and absent users cancel: {events:?}");
+    info!("SUCCESS: cancellations.");
+
+    // [4]
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_resolution(160, 120).realtime(false))
+        .with_device_config(DeviceConfig::new(DeviceSelector::ByIndex(0), vec![ModuleType::GestureRecognizer]))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let mut recognizer = device.gesture_recognizer().expect("gesture recognizer");
+    let recognizer = recognizer.as_async();
+    let stream = GestureEventStream::new(
+        recognizer.updates_stream()?,
+        recognizer.completed_gestures_frames_stream()?,
+        GestureProgressTracker::default(),
+    );
+    let mut gestures = FrameIter::new(stream);
+    session.start_processing()?;
+    let mut events = Vec::new();
+    for _ in 0..90 {
+        session.update()?;
+        while let Some(event) = gestures.try_next() {
+            events.push(summary(&event?));
+        }
+    }
+    session.close()?;
+    let kinds: Vec<&str> = events.iter().map(|e| e.0).filter(|k| *k != "progress").collect();
+    ensure!(kinds == ["started", "completed"], "one wave: {events:?}");
+    let progress: Vec<i32> = events.iter().filter(|e| e.0 == "progress").map(|e| e.3).collect();
+    ensure!(progress.len() > 5 && progress.windows(2).all(|w| w[1] > w[0]), "rising progress: {progress:?}");
+    ensure!((55..=61).contains(&events.last().unwrap().2), "completed after two seconds: {events:?}");
+    info!(events = events.len(), "SUCCESS: live waves tracked.");
+
+    info!("--- Gesture progress test passed ---");
+    Ok(())
+}
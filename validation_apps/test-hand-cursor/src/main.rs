@@ -0,0 +1,179 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::processed_stream::ProcessedStream,
+    backend::simulated::SimulatedBackend,
+    blocking_api::{frame_iter::FrameIter, session::BlockingSessionBuilder},
+    shared_types::{
+        hand::{Hand, UserHands},
+        hand_cursor::{ActiveRegion, CursorEventKind, CursorId, HandCursorConfig, HandCursorTracker},
+        kinematics::Side,
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+    },
+};
+use std::time::Duration;
+use tracing::{info, Level};
+
+const FRAME_US: u64 = 33_333;
+
+fn hand(x: f32, y: f32, click: bool) -> Hand {
+    Hand { x, y, click, pressure: if click { 100 } else { 0 }, x_real: 0.0, y_real: 0.0, z_real: 1500.0 }
+}
+
+fn right(user_id: i32, x: f32, y: f32, click: bool) -> UserHands {
+    UserHands { user_id, left_hand: None, right_hand: Some(hand(x, y, click)) }
+}
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-3
+}
+
+/// Feeds one hand list per frame from `start` and returns `(frame, kind)` of each event.
+fn run(tracker: &mut HandCursorTracker, start: u64, frames: &[Vec<UserHands>]) -> Vec<(u64, CursorEventKind)> {
+    frames
+        .iter()
+        .enumerate()
+        .flat_map(|(i, hands)| {
+            let frame = start + i as u64;
+            tracker.apply(frame * FRAME_US, hands).into_iter().map(move |e| (frame, e.kind))
+        })
+        .collect()
+}
+
+/// This test validates hand cursors.
+/// 1. Active regions are fitted, calibrated and map hands to the screen.
+/// 2. Cursors are smoothed and report entering, hovering and leaving.
+/// 3. Press and release follow `click` edges, and pressed cursors drag past a threshold.
+/// 4. Cursors held still dwell-click once until moved away.
+/// 5. The stream adapter drives cursors from the simulated backend's hands.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Hand Cursor ---");
+
+    let id = CursorId { user_id: 1, side: Side::Right };
+    let raw = HandCursorConfig::default().with_smoothing(1.0).with_hands([Side::Right]);
+
+    // [1]
+    let region = ActiveRegion::fit([(0.3, 0.2), (0.7, 0.6), (0.5, 0.4)], 0.05).expect("region");
+    ensure!(close(region.left, 0.25) && close(region.bottom, 0.65), "fitted region {region:?}");
+    ensure!(ActiveRegion::fit([(0.5, 0.5)], 0.0).is_none(), "a point is no region");
+    let (x, y) = region.map(0.5, 0.9);
+    ensure!(close(x, 0.5) && y == 1.0, "mapped and clamped: {x}, {y}");
+    let mut tracker = HandCursorTracker::new(raw.clone().with_screen(1920.0, 1080.0).with_dwell(None, 0.0));
+    tracker.start_calibration(id);
+    let sweep: Vec<Vec<UserHands>> = [(0.4, 0.3), (0.6, 0.3), (0.6, 0.5), (0.4, 0.5)].iter().map(|&(x, y)| vec![right(1, x, y, false)]).collect();
+    run(&mut tracker, 0, &sweep);
+    let calibrated = tracker.finish_calibration(id, 0.0).expect("calibrated");
+    ensure!(calibrated == tracker.region(id) && close(calibrated.right, 0.6), "region {calibrated:?}");
+    ensure!(tracker.region(CursorId { user_id: 2, side: Side::Right }) == ActiveRegion::default(), "others keep the default");
+    run(&mut tracker, 4, &[vec![right(1, 0.5, 0.35, false)]]);
+    let cursor = tracker.cursor_of(id).expect("tracked");
+    ensure!(close(cursor.x, 960.0) && close(cursor.y, 270.0), "screen position {cursor:?}");
+    info!("SUCCESS: regions calibrated.");
+
+    // [2]
+    let mut tracker = HandCursorTracker::new(HandCursorConfig::default().with_dwell(None, 0.0));
+    let frames = [
+        vec![right(1, 0.2, 0.2, false)],
+        vec![right(1, 0.6, 0.2, false)],
+        vec![right(1, 0.6, 0.2, false)],
+        vec![UserHands { user_id: 1, left_hand: Some(hand(0.1, 0.1, false)), right_hand: None }],
+    ];
+    let events = run(&mut tracker, 0, &frames[..2]);
+    ensure!(events == [(0, CursorEventKind::Entered), (1, CursorEventKind::Hover)], "{events:?}");
+    ensure!(close(tracker.cursor_of(id).unwrap().x, 0.4), "smoothed halfway");
+    run(&mut tracker, 2, &frames[2..3]);
+    ensure!(close(tracker.cursor_of(id).unwrap().x, 0.5), "smoothing converges");
+    let events = run(&mut tracker, 3, &frames[3..]);
+    let left = CursorId { user_id: 1, side: Side::Left };
+    ensure!(events == [(3, CursorEventKind::Entered), (3, CursorEventKind::Left)], "left hand appears, right lost: {events:?}");
+    ensure!(tracker.cursors().map(|c| c.id).eq([left]), "only the left cursor");
+    info!("SUCCESS: cursors hover.");
+
+    // [3]
+    let mut tracker = HandCursorTracker::new(raw.clone().with_dwell(None, 0.0));
+    let frames: Vec<Vec<UserHands>> = [
+        (0.5, false),
+        (0.5, true),
+        (0.52, true),
+        (0.56, true),
+        (0.6, true),
+        (0.6, false),
+        (0.7, true),
+        (0.8, true),
+    ]
+    .iter()
+    .map(|&(x, click)| vec![right(1, x, 0.5, click)])
+    .collect();
+    let events = run(&mut tracker, 0, &frames);
+    use CursorEventKind::*;
+    let expected = [
+        (0, Entered),
+        (1, Pressed),
+        (3, DragStarted),
+        (4, Dragged),
+        (5, DragEnded),
+        (5, Released),
+        (6, Hover),
+        (6, Pressed),
+        (7, DragStarted),
+    ];
+    ensure!(events == expected, "press, drag and release: {events:?}");
+    let cursor = tracker.cursor_of(id).unwrap();
+    ensure!(cursor.pressed && cursor.dragging && cursor.pressure == 100, "{cursor:?}");
+    let events = run(&mut tracker, 8, &[vec![]]);
+    ensure!(events == [(8, DragEnded), (8, Released), (8, Left)], "lost while dragging: {events:?}");
+    info!("SUCCESS: press and drag.");
+
+    // [4]
+    let mut tracker = HandCursorTracker::new(raw.clone());
+    let mut frames: Vec<Vec<UserHands>> = (0..60).map(|i| vec![right(1, 0.5 + 0.01 * (i % 2) as f32, 0.5, false)]).collect();
+    frames.extend((0..40).map(|_| vec![right(1, 0.8, 0.5, false)]));
+    let mut clicks = Vec::new();
+    for (i, hands) in frames.iter().enumerate() {
+        let events = tracker.apply(i as u64 * FRAME_US, hands);
+        clicks.extend(events.iter().filter(|e| e.kind == DwellClick).map(|_| i));
+        if i == 15 {
+            let progress = tracker.cursor_of(id).unwrap().dwell_progress;
+            ensure!(close(progress, 0.5), "dwell progress {progress}");
+        }
+    }
+    ensure!(clicks == [31, 91], "one dwell click per resting spot despite jitter: {clicks:?}");
+    let mut tracker = HandCursorTracker::new(raw.with_dwell(Some(Duration::from_millis(500)), 0.03));
+    let frames: Vec<Vec<UserHands>> = (0..40).map(|i| vec![right(1, 0.5, 0.5, (5..10).contains(&i))]).collect();
+    let events = run(&mut tracker, 0, &frames);
+    ensure!(!events.iter().any(|e| e.1 == DwellClick), "no dwell click where the hand pressed: {events:?}");
+    info!("SUCCESS: dwell clicks.");
+
+    // [5]
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_resolution(160, 120).realtime(false))
+        .with_device_config(DeviceConfig::new(DeviceSelector::ByIndex(0), vec![ModuleType::HandTracker]))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let stream = device.hand_tracker().expect("hand tracker").as_async().hand_frames_stream()?;
+    // Simulated users sway, so the resting hand needs a short dwell.
+    let config = HandCursorConfig::default().with_screen(1920.0, 1080.0).with_dwell(Some(Duration::from_millis(500)), 0.04);
+    let mut cursors = FrameIter::new(ProcessedStream::new(stream, HandCursorTracker::new(config)));
+    session.start_processing()?;
+    let mut events = Vec::new();
+    for _ in 0..60 {
+        session.update()?;
+        while let Some(event) = cursors.try_next() {
+            events.push(event?);
+        }
+    }
+    session.close()?;
+    let count = |side, kind| events.iter().filter(|e| e.id.side == side && e.kind == kind).count();
+    ensure!(count(Side::Left, Entered) == 1 && count(Side::Right, Entered) == 1, "both hands entered");
+    ensure!(count(Side::Left, DwellClick) >= 1, "the resting left hand dwell-clicks");
+    ensure!(count(Side::Right, Hover) > 20, "the waving right hand hovers");
+    ensure!(events.iter().all(|e| (0.0..=1920.0).contains(&e.x) && (0.0..=1080.0).contains(&e.y)), "cursors on screen");
+    info!(events = events.len(), "SUCCESS: live cursors.");
+
+    info!("--- Hand cursor test passed ---");
+    Ok(())
+}
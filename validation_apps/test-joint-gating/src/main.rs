@@ -0,0 +1,130 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::depth_sensor::Vector3,
+    backend::simulated::SimulatedBackend,
+    blocking_api::session::BlockingSessionBuilder,
+    shared_types::{
+        clean_skeleton::{GapFill, JointGate, JointGateConfig, JointValidity},
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+        skeleton::{Joint, JointType, Orientation, Skeleton},
+    },
+};
+use std::time::Duration;
+use tracing::{info, Level};
+
+const FRAME_US: u64 = 33_333;
+
+fn joint(joint_type: JointType, confidence: f32, x: f32) -> Joint {
+    Joint {
+        joint_type,
+        confidence,
+        real: Vector3 { x, y: 0.0, z: 2000.0 },
+        proj: Vector3 { x: 0.5, y: 0.5, z: 2000.0 },
+        orient: Orientation { matrix: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0] },
+    }
+}
+
+/// A skeleton whose head is at `x` with `confidence`, plus a tracked torso and an
+/// untracked fingertip.
+fn skeleton(confidence: f32, x: f32) -> Skeleton {
+    Skeleton {
+        user_id: 1,
+        joints: vec![
+            joint(JointType::Head, confidence, x),
+            joint(JointType::Torso, 0.75, 0.0),
+            joint(JointType::LeftFingertip, 0.0, 0.0),
+        ],
+    }
+}
+
+/// This test validates confidence gating.
+/// 1. Joints are classified as tracked, low confidence, missing or unused.
+/// 2. Short gaps are held or extrapolated; long gaps are not filled.
+/// 3. Per-joint thresholds and conversion back to a `Skeleton`.
+/// 4. Live simulated frames are gated with the SDK's unused joints marked.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Joint Gating ---");
+
+    // [1]
+    let mut gate = JointGate::new(JointGateConfig::default().with_gap_fill(GapFill::None, Duration::from_millis(250)));
+    let clean = &gate.apply(0, &[skeleton(0.2, 0.0)])[0];
+    ensure!(clean.joints.len() == JointType::ALL.len() - 1, "one clean joint per joint type");
+    let validity = |j: JointType| clean.joint(j).map(|j| j.validity);
+    ensure!(validity(JointType::Torso) == Some(JointValidity::Tracked), "torso tracked");
+    ensure!(validity(JointType::Head) == Some(JointValidity::LowConfidence), "head below threshold");
+    ensure!(validity(JointType::Neck) == Some(JointValidity::Missing), "neck not reported");
+    ensure!(validity(JointType::LeftFingertip) == Some(JointValidity::Unused), "fingertip unused");
+    ensure!(validity(JointType::RightFoot) == Some(JointValidity::Unused), "foot unused");
+    ensure!(clean.usable_joint(JointType::Head).is_none() && clean.usable_joints().count() == 1, "only the torso is usable");
+    info!("SUCCESS: joints classified.");
+
+    // [2]
+    let mut hold = JointGate::new(JointGateConfig::default());
+    let mut extrapolate =
+        JointGate::new(JointGateConfig::default().with_gap_fill(GapFill::Extrapolate, Duration::from_millis(250)));
+    for frame in 0..3u64 {
+        let s = skeleton(0.75, frame as f32 * 10.0);
+        hold.apply(frame * FRAME_US, std::slice::from_ref(&s));
+        extrapolate.apply(frame * FRAME_US, &[s]);
+    }
+    let gap = [skeleton(0.1, 999.0)];
+    let held = hold.apply(3 * FRAME_US, &gap)[0].joint(JointType::Head).copied().unwrap();
+    ensure!(held.validity == JointValidity::Held && held.real.x == 20.0, "held at last position: {:?}", held.real);
+    ensure!(held.confidence == 0.1, "measured confidence is kept");
+    let predicted = extrapolate.apply(3 * FRAME_US, &gap)[0].joint(JointType::Head).copied().unwrap();
+    ensure!(
+        predicted.validity == JointValidity::Extrapolated && (predicted.real.x - 30.0).abs() < 0.1,
+        "extrapolated along the motion: {:?}",
+        predicted.real
+    );
+    let expired = hold.apply(3 * FRAME_US + 300_000, &gap)[0].joint(JointType::Head).copied().unwrap();
+    ensure!(expired.validity == JointValidity::LowConfidence, "long gaps are not filled");
+    let after = hold.apply(3 * FRAME_US + 333_333, &gap)[0].joint(JointType::Head).copied().unwrap();
+    ensure!(after.validity == JointValidity::LowConfidence, "history is dropped after a long gap");
+    info!("SUCCESS: gaps filled.");
+
+    // [3]
+    let mut strict = JointGate::new(JointGateConfig::default().with_joint_threshold(JointType::Torso, 0.9));
+    let clean = &strict.apply(0, &[skeleton(0.75, 5.0)])[0];
+    ensure!(clean.usable_joint(JointType::Head).is_some(), "head passes the default threshold");
+    ensure!(clean.usable_joint(JointType::Torso).is_none(), "torso fails its own threshold");
+    let back = clean.to_skeleton();
+    ensure!(back.joints.len() == clean.joints.len(), "round trip keeps every joint");
+    ensure!(back.joint(JointType::Head).is_some_and(|j| j.confidence == 0.75), "usable joints keep confidence");
+    ensure!(back.joint(JointType::Torso).is_some_and(|j| j.confidence == 0.0), "unusable joints are zeroed");
+    info!("SUCCESS: thresholds and conversion.");
+
+    // [4]
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_resolution(160, 120).realtime(false))
+        .with_device_config(DeviceConfig::new(DeviceSelector::ByIndex(0), vec![ModuleType::SkeletonTracker]))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let mut frames_iter = device.skeleton_tracker().expect("skeleton tracker").skeleton_frames()?;
+    session.start_processing()?;
+    let mut gate = JointGate::default();
+    let mut frames = 0;
+    for _ in 0..5 {
+        session.update()?;
+        while let Some(frame) = frames_iter.try_next() {
+            for clean in gate.clean_frame(&frame?)? {
+                for j in &clean.joints {
+                    let expected = if j.joint_type.is_unused() { JointValidity::Unused } else { JointValidity::Tracked };
+                    ensure!(j.validity == expected, "{:?} is {:?}", j.joint_type, j.validity);
+                }
+            }
+            frames += 1;
+        }
+    }
+    ensure!(frames == 5, "{frames} gated frames");
+    session.close()?;
+    info!(frames, "SUCCESS: live frames gated.");
+
+    info!("--- Joint gating test passed ---");
+    Ok(())
+}
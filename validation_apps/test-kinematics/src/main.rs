@@ -0,0 +1,151 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::depth_sensor::Vector3,
+    backend::simulated::SimulatedBackend,
+    blocking_api::session::BlockingSessionBuilder,
+    shared_types::{
+        kinematics::{Bone, EulerAngles, Quaternion, Side, BONES},
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+        skeleton::{Joint, JointType, Orientation, Skeleton},
+    },
+};
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+use tracing::{info, Level};
+
+const UP: Vector3 = Vector3 { x: 0.0, y: 1.0, z: 0.0 };
+
+fn joint(joint_type: JointType, x: f32, y: f32, z: f32) -> Joint {
+    Joint {
+        joint_type,
+        confidence: 0.75,
+        real: Vector3 { x, y, z },
+        proj: Vector3::default(),
+        orient: Orientation { matrix: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0] },
+    }
+}
+
+/// A user with the left elbow bent to a right angle, the left thigh raised forward
+/// to horizontal with the shin hanging, and the trunk leaning 45° forward.
+fn posed() -> Skeleton {
+    Skeleton {
+        user_id: 1,
+        joints: vec![
+            joint(JointType::Waist, 0.0, 0.0, 2000.0),
+            joint(JointType::Neck, 0.0, 400.0, 1600.0),
+            joint(JointType::Head, 0.0, 500.0, 1500.0),
+            joint(JointType::LeftShoulder, -200.0, 400.0, 1600.0),
+            joint(JointType::LeftElbow, -200.0, 100.0, 1600.0),
+            joint(JointType::LeftWrist, -200.0, 100.0, 1350.0),
+            joint(JointType::LeftHip, -100.0, 0.0, 2000.0),
+            joint(JointType::LeftKnee, -100.0, 0.0, 1600.0),
+            joint(JointType::LeftAnkle, -100.0, -400.0, 1600.0),
+        ],
+    }
+}
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-3
+}
+
+/// This test validates the kinematics helpers.
+/// 1. The bone hierarchy is a tree rooted at the waist with parents listed first.
+/// 2. Bone lengths and flexion angles of a known pose.
+/// 3. Quaternion and Euler conversions round-trip and compose.
+/// 4. Live simulated skeletons report a standing, upright user.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Kinematics ---");
+
+    // [1]
+    ensure!(JointType::Waist.parent().is_none(), "the waist is the root");
+    for (i, bone) in BONES.iter().enumerate() {
+        ensure!(
+            bone.parent == JointType::Waist || BONES[..i].iter().any(|b| b.child == bone.parent),
+            "{:?} is listed before its parent",
+            bone.child
+        );
+    }
+    let covered = JointType::ALL.iter().filter(|&&j| j != JointType::None && j != JointType::Waist);
+    ensure!(covered.clone().all(|j| j.parent().is_some()), "every joint but the root has a parent");
+    ensure!(JointType::Waist.children().count() == 3, "torso and both hips hang off the waist");
+    info!("SUCCESS: bone hierarchy.");
+
+    // [2]
+    let s = posed();
+    let forearm = Bone { parent: JointType::LeftElbow, child: JointType::LeftWrist };
+    ensure!(s.bone_length(forearm).is_some_and(|l| close(l, 250.0)), "forearm length");
+    ensure!(s.bone_length(Bone { parent: JointType::Waist, child: JointType::Torso }).is_none(), "missing joints have no bone");
+    ensure!(s.bone_lengths().count() == 6, "{} measurable bones", s.bone_lengths().count());
+    ensure!(s.elbow_flexion(Side::Left).is_some_and(|a| close(a, FRAC_PI_2)), "elbow flexion {:?}", s.elbow_flexion(Side::Left));
+    ensure!(s.knee_flexion(Side::Left).is_some_and(|a| close(a, FRAC_PI_2)), "knee flexion {:?}", s.knee_flexion(Side::Left));
+    // The thigh points towards the sensor and the trunk leans 45° the same way.
+    ensure!(s.hip_flexion(Side::Left).is_some_and(|a| close(a, 3.0 * FRAC_PI_4)), "hip flexion {:?}", s.hip_flexion(Side::Left));
+    ensure!(s.trunk_lean(UP).is_some_and(|a| close(a, FRAC_PI_4)), "trunk lean {:?}", s.trunk_lean(UP));
+    ensure!(s.elbow_flexion(Side::Right).is_none(), "untracked side has no angle");
+    info!("SUCCESS: bones and angles.");
+
+    // [3]
+    let euler = EulerAngles { yaw: 0.4, pitch: -0.3, roll: 1.2 };
+    let q = euler.to_quaternion();
+    let back = q.to_euler();
+    ensure!(close(back.yaw, euler.yaw) && close(back.pitch, euler.pitch) && close(back.roll, euler.roll), "euler round trip: {back:?}");
+    let matrix = Orientation { matrix: euler.to_matrix() };
+    ensure!(matrix.to_quaternion().angle_to(&q) < 1e-3, "matrix and euler agree");
+    let from_matrix = Quaternion::from_matrix(&q.to_matrix());
+    ensure!(from_matrix.angle_to(&q) < 1e-3, "matrix round trip");
+    let yaw = Quaternion::from_axis_angle(UP, FRAC_PI_2);
+    let turned = yaw.rotate(Vector3 { x: 0.0, y: 0.0, z: 1.0 });
+    ensure!(close(turned.x, 1.0) && close(turned.z, 0.0), "yaw turns +z to +x: {turned:?}");
+    ensure!(close((yaw * yaw).angle_to(&Quaternion::IDENTITY), PI), "composition adds angles");
+    ensure!(close(Quaternion::IDENTITY.slerp(&yaw, 0.5).angle_to(&Quaternion::IDENTITY), FRAC_PI_4), "slerp halves the angle");
+    let (from, to) = (Vector3 { x: 1.0, y: 2.0, z: 0.5 }, Vector3 { x: -3.0, y: 0.0, z: 1.0 });
+    let aligned = Quaternion::between(from, to).rotate(from);
+    let scale = (aligned.x.powi(2) + aligned.y.powi(2) + aligned.z.powi(2)).sqrt() / 10f32.sqrt();
+    ensure!(close(aligned.x, to.x * scale) && close(aligned.y, 0.0) && close(aligned.z, to.z * scale), "between aligns: {aligned:?}");
+    let flipped = Quaternion::between(UP, Vector3 { x: 0.0, y: -1.0, z: 0.0 }).rotate(UP);
+    ensure!(close(flipped.y, -1.0), "between handles opposite directions");
+    let gimbal = EulerAngles { yaw: 0.7, pitch: FRAC_PI_2, roll: 0.0 }.to_quaternion().to_euler();
+    ensure!(close(gimbal.yaw, 0.7) && close(gimbal.roll, 0.0), "gimbal lock reports yaw: {gimbal:?}");
+    let mut relative = posed();
+    relative.joints[4].orient = Orientation::from_quaternion(&yaw);
+    let r = relative.relative_rotation(JointType::LeftElbow).expect("elbow has a parent");
+    ensure!(r.angle_to(&yaw) < 1e-3, "rotation relative to an unrotated parent");
+    info!("SUCCESS: orientation conversions.");
+
+    // [4]
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_resolution(160, 120).realtime(false))
+        .with_device_config(DeviceConfig::new(DeviceSelector::ByIndex(0), vec![ModuleType::SkeletonTracker]))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let mut frames = device.skeleton_tracker().expect("skeleton tracker").skeleton_frames()?;
+    session.start_processing()?;
+    let mut checked = 0;
+    for _ in 0..5 {
+        session.update()?;
+        while let Some(frame) = frames.try_next() {
+            for s in frame?.skeletons()? {
+                ensure!(s.trunk_lean(UP).is_some_and(|a| a < 0.01), "upright trunk");
+                for side in [Side::Left, Side::Right] {
+                    ensure!(s.knee_flexion(side).is_some_and(|a| a < 0.1), "straight knees");
+                    ensure!(s.hip_flexion(side).is_some_and(|a| a < 0.1), "standing hips");
+                }
+                ensure!(s.elbow_flexion(Side::Left).is_some_and(|a| a < 0.2), "hanging left arm");
+                let head = Bone { parent: JointType::Neck, child: JointType::Head };
+                ensure!(s.bone_length(head).is_some_and(|l| close(l, 150.0)), "neck to head");
+                ensure!(s.relative_rotation(JointType::Head).is_some_and(|q| q.angle_to(&Quaternion::IDENTITY) < 1e-3), "identity orientations");
+                checked += 1;
+            }
+        }
+    }
+    ensure!(checked > 0, "no skeletons checked");
+    session.close()?;
+    info!(checked, "SUCCESS: live skeletons measured.");
+
+    info!("--- Kinematics test passed ---");
+    Ok(())
+}
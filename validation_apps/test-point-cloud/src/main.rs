@@ -0,0 +1,134 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::depth_sensor::{Intrinsics, Vector3},
+    backend::simulated::SimulatedBackend,
+    blocking_api::session::BlockingSessionBuilder,
+    shared_types::{
+        camera_model::CameraModel,
+        depth_frame::{DepthFrame, OwnedDepthFrame},
+        point_cloud::{CloudEncoding, PointCloudBuilder},
+        rgb_frame::{Color3, OwnedRGBFrame},
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+        user_frame::OwnedUserFrame,
+    },
+};
+use tracing::{info, Level};
+
+const CAMERA: CameraModel = CameraModel {
+    width: 4,
+    height: 2,
+    intrinsics: Intrinsics { fx: 2.0, fy: 2.0, cx: 2.0, cy: 1.0 },
+    mirror: false,
+};
+
+fn close(a: Vector3, b: Vector3) -> bool {
+    (a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3 && (a.z - b.z).abs() < 1e-3
+}
+
+/// This test validates point cloud generation and export.
+/// 1. Deprojection matches the pinhole model, honors mirroring and skips pixels without depth.
+/// 2. Colors, user masks and decimation apply per pixel.
+/// 3. PLY and PCD export write the expected headers and payload sizes, and reject
+///    clouds with fewer colors than points.
+/// 4. Clouds build from live frames with the sensor's camera model.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Point Cloud ---");
+
+    // [1]
+    let (rows, cols) = (2, 4);
+    let mut data = vec![1000u16; 8];
+    data[3] = 0;
+    let depth = OwnedDepthFrame { rows, cols, id: 1, timestamp: 42, data };
+    let cloud = DepthFrame::from(depth.clone()).to_point_cloud(&CAMERA)?;
+    ensure!(cloud.len() == 7 && cloud.timestamp == 42 && cloud.colors.is_none(), "cloud of {} points", cloud.len());
+    ensure!(close(cloud.points[0], Vector3 { x: -1000.0, y: 500.0, z: 1000.0 }), "top-left point {:?}", cloud.points[0]);
+    ensure!(close(cloud.points[6], Vector3 { x: 500.0, y: 0.0, z: 1000.0 }), "bottom-right point {:?}", cloud.points[6]);
+    let mirrored = PointCloudBuilder::new(&depth, CAMERA.with_mirror(true)).build()?;
+    ensure!(close(mirrored.points[0], Vector3 { x: 500.0, y: 500.0, z: 1000.0 }), "mirrored top-left point {:?}", mirrored.points[0]);
+    info!("SUCCESS: depth deprojected with the pinhole model.");
+
+    // [2]
+    let color = OwnedRGBFrame {
+        rows: 4,
+        cols: 8,
+        id: 1,
+        timestamp: 42,
+        data: (0..32).map(|i| Color3 { blue: 0, green: (i / 8) as u8, red: (i % 8) as u8 }).collect(),
+    };
+    let users = OwnedUserFrame {
+        rows,
+        cols,
+        timestamp: 42,
+        data: vec![0, 1, 1, 2, 0, 2, 1, 0],
+        users: Vec::new(),
+        floor: Vector3 { x: 0.0, y: 0.0, z: 0.0 },
+        floor_normal: Vector3 { x: 0.0, y: 1.0, z: 0.0 },
+    };
+    let colored = PointCloudBuilder::new(&depth, CAMERA).with_color(&color).build()?;
+    let colors = colored.colors.as_ref().expect("colored cloud");
+    ensure!(colors.len() == colored.len(), "one color per point");
+    ensure!(colors[4] == Color3 { blue: 0, green: 2, red: 2 }, "scaled color sample {:?}", colors[4]);
+    let user_one = PointCloudBuilder::new(&depth, CAMERA).with_user_mask(&users, Some(1)).build()?;
+    let anyone = PointCloudBuilder::new(&depth, CAMERA).with_user_mask(&users, None).build()?;
+    ensure!(user_one.len() == 3, "user 1 has 3 points, got {}", user_one.len());
+    ensure!(anyone.len() == 4, "users cover 4 points with depth, got {}", anyone.len());
+    let decimated = PointCloudBuilder::new(&depth, CAMERA).with_step(2).build()?;
+    ensure!(decimated.len() == 2, "every second pixel, got {}", decimated.len());
+    info!("SUCCESS: color, mask and step applied.");
+
+    // [3]
+    let mut ply = Vec::new();
+    colored.write_ply(&mut ply, CloudEncoding::Binary)?;
+    let header_end = ply.windows(11).position(|w| w == b"end_header\n").expect("PLY header") + 11;
+    ensure!(std::str::from_utf8(&ply[..header_end])?.contains("element vertex 7"), "PLY vertex count");
+    ensure!(ply.len() - header_end == 7 * 15, "binary PLY payload of {} bytes", ply.len() - header_end);
+    let mut ascii = Vec::new();
+    cloud.write_pcd(&mut ascii, CloudEncoding::Ascii)?;
+    let text = String::from_utf8(ascii)?;
+    ensure!(text.contains("FIELDS x y z\n") && text.contains("POINTS 7\nDATA ascii\n"), "PCD header");
+    ensure!(text.lines().count() == 11 + 7, "PCD ascii lines");
+    let mut pcd = Vec::new();
+    colored.write_pcd(&mut pcd, CloudEncoding::Binary)?;
+    let data_at = pcd.windows(12).position(|w| w == b"DATA binary\n").expect("PCD header") + 12;
+    ensure!(pcd.len() - data_at == 7 * 16, "binary PCD payload of {} bytes", pcd.len() - data_at);
+    let path = std::env::temp_dir().join("nuitrack-rs-test-point-cloud.ply");
+    colored.save_ply(&path, CloudEncoding::Ascii)?;
+    ensure!(std::fs::read_to_string(&path)?.lines().count() == 10 + 7, "saved ASCII PLY");
+    std::fs::remove_file(&path)?;
+    let mut short = colored.clone();
+    short.colors.as_mut().unwrap().pop();
+    ensure!(short.write_ply(Vec::new(), CloudEncoding::Binary).is_err(), "PLY with a missing color");
+    ensure!(short.write_pcd(Vec::new(), CloudEncoding::Ascii).is_err(), "PCD with a missing color");
+    info!("SUCCESS: PLY and PCD export.");
+
+    // [4]
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_resolution(160, 120).realtime(false))
+        .with_device_config(DeviceConfig::new(
+            DeviceSelector::ByIndex(0),
+            vec![ModuleType::DepthSensor, ModuleType::UserTracker],
+        ))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let mut depth_sensor = device.depth_sensor().expect("depth sensor");
+    let camera = depth_sensor.camera_model()?;
+    let mut depth_frames = depth_sensor.depth_frames()?;
+    let mut user_frames = device.user_tracker().expect("user tracker").user_frames()?;
+    session.start_processing()?;
+    session.update()?;
+    let depth = depth_frames.try_next().expect("depth frame after update")?;
+    let users = user_frames.try_next().expect("user frame after update")?;
+    let full = depth.to_point_cloud(&camera)?;
+    let masked = depth.point_cloud(&camera).with_user_mask(&users, None).build()?;
+    ensure!(!full.is_empty() && !masked.is_empty() && masked.len() < full.len(), "full {} vs masked {}", full.len(), masked.len());
+    session.close()?;
+    info!(full = full.len(), masked = masked.len(), "SUCCESS: live clouds built.");
+
+    info!("--- Point cloud test passed ---");
+    Ok(())
+}
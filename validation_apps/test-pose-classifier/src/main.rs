@@ -0,0 +1,234 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::{depth_sensor::Vector3, processed_stream::ProcessedStream},
+    backend::simulated::SimulatedBackend,
+    blocking_api::{frame_iter::FrameIter, session::BlockingSessionBuilder},
+    shared_types::{
+        pose::{Axis, Measure, PoseClassifier, PoseCondition, PoseDefinition, PoseEvent},
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+        skeleton::{Joint, JointType, Orientation, Skeleton},
+    },
+};
+use std::time::Duration;
+use tracing::{info, Level};
+
+const FRAME_US: u64 = 33_333;
+
+/// Offsets of a standing user from the torso, like the simulated backend's.
+const STANDING: [(JointType, f32, f32); 17] = [
+    (JointType::Head, 0.0, 450.0),
+    (JointType::Neck, 0.0, 300.0),
+    (JointType::Torso, 0.0, 0.0),
+    (JointType::Waist, 0.0, -200.0),
+    (JointType::LeftShoulder, -180.0, 250.0),
+    (JointType::LeftElbow, -200.0, 0.0),
+    (JointType::LeftWrist, -210.0, -220.0),
+    (JointType::RightShoulder, 180.0, 250.0),
+    (JointType::RightElbow, 200.0, 0.0),
+    (JointType::RightWrist, 210.0, -220.0),
+    (JointType::LeftHip, -100.0, -250.0),
+    (JointType::LeftKnee, -110.0, -700.0),
+    (JointType::LeftAnkle, -110.0, -1100.0),
+    (JointType::RightHip, 100.0, -250.0),
+    (JointType::RightKnee, 110.0, -700.0),
+    (JointType::RightAnkle, 110.0, -1100.0),
+    (JointType::LeftCollar, 0.0, 250.0),
+];
+
+/// A standing user with some joints moved, relative to the torso.
+fn user(user_id: i32, moved: &[(JointType, f32, f32)]) -> Skeleton {
+    let joints = STANDING
+        .iter()
+        .map(|&(joint_type, x, y)| {
+            let (x, y) = moved.iter().find(|m| m.0 == joint_type).map_or((x, y), |m| (m.1, m.2));
+            Joint {
+                joint_type,
+                confidence: 0.75,
+                real: Vector3 { x, y, z: 2000.0 },
+                proj: Vector3::default(),
+                orient: Orientation { matrix: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0] },
+            }
+        })
+        .collect();
+    Skeleton { user_id, joints }
+}
+
+fn t_pose(user_id: i32) -> Skeleton {
+    user(
+        user_id,
+        &[
+            (JointType::LeftElbow, -450.0, 260.0),
+            (JointType::LeftWrist, -700.0, 270.0),
+            (JointType::RightElbow, 450.0, 240.0),
+            (JointType::RightWrist, 700.0, 230.0),
+        ],
+    )
+}
+
+fn arms_raised(user_id: i32) -> Skeleton {
+    user(
+        user_id,
+        &[
+            (JointType::LeftElbow, -220.0, 500.0),
+            (JointType::LeftWrist, -230.0, 740.0),
+            (JointType::RightElbow, 220.0, 500.0),
+            (JointType::RightWrist, 230.0, 740.0),
+        ],
+    )
+}
+
+fn hands_on_hips(user_id: i32) -> Skeleton {
+    user(
+        user_id,
+        &[
+            (JointType::LeftElbow, -380.0, 0.0),
+            (JointType::LeftWrist, -150.0, -200.0),
+            (JointType::RightElbow, 380.0, 0.0),
+            (JointType::RightWrist, 150.0, -200.0),
+        ],
+    )
+}
+
+fn squat(user_id: i32) -> Skeleton {
+    let mut skeleton = user(
+        user_id,
+        &[
+            (JointType::LeftKnee, -110.0, -300.0),
+            (JointType::LeftAnkle, -110.0, -700.0),
+            (JointType::RightKnee, 110.0, -300.0),
+            (JointType::RightAnkle, 110.0, -700.0),
+        ],
+    );
+    // Knees 400 mm forward of the hips and ankles.
+    for joint in &mut skeleton.joints {
+        if joint.joint_type == JointType::LeftKnee || joint.joint_type == JointType::RightKnee {
+            joint.real.z = 1600.0;
+        }
+    }
+    skeleton
+}
+
+/// Feeds one skeleton list per frame from `start` and returns `(frame, event)` pairs.
+fn run(classifier: &mut PoseClassifier, start: u64, frames: &[Vec<Skeleton>]) -> Vec<(u64, PoseEvent)> {
+    frames
+        .iter()
+        .enumerate()
+        .flat_map(|(i, skeletons)| {
+            let frame = start + i as u64;
+            classifier.apply(frame * FRAME_US, skeletons).into_iter().map(move |e| (frame, e))
+        })
+        .collect()
+}
+
+fn entered(event: &PoseEvent) -> bool {
+    matches!(event, PoseEvent::PoseEntered { .. })
+}
+
+/// This test validates static pose classification.
+/// 1. Measures and conditions evaluate on skeletons; untracked joints fail conditions.
+/// 2. Preset poses match only their own skeletons.
+/// 3. Hold and release durations, multiple users and users leaving while posed.
+/// 4. The stream adapter reports the simulated backend's raised hand while waving.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Pose Classifier ---");
+
+    // [1]
+    let standing = user(1, &[]);
+    let elbow = Measure::angle(JointType::LeftShoulder, JointType::LeftElbow, JointType::LeftWrist);
+    let angle = elbow.evaluate(&standing).unwrap();
+    ensure!((angle - 3.107).abs() < 0.01, "left elbow angle {angle}");
+    let drop = Measure::offset(JointType::LeftWrist, JointType::Head, Axis::Y).evaluate(&standing);
+    ensure!(drop == Some(-670.0), "wrist below head: {drop:?}");
+    let down = Measure::elevation(JointType::LeftHip, JointType::LeftKnee).evaluate(&standing).unwrap();
+    ensure!(down > 3.1, "thigh points down: {down}");
+    let across = Measure::distance(JointType::LeftShoulder, JointType::RightShoulder).evaluate(&standing);
+    ensure!(across == Some(360.0), "shoulder width {across:?}");
+    let straight = elbow.at_least(150f32.to_radians());
+    ensure!(straight.is_met(&standing) && !PoseCondition::not(straight.clone()).is_met(&standing), "not");
+    let mut armless = standing.clone();
+    armless.joints.retain(|j| j.joint_type != JointType::LeftWrist);
+    ensure!(!straight.is_met(&armless) && PoseCondition::not(straight.clone()).is_met(&armless), "untracked joints fail");
+    ensure!(PoseCondition::any([straight.clone(), elbow.at_most(0.0)]).is_met(&standing), "any");
+    ensure!(!PoseCondition::all([straight, elbow.at_most(0.0)]).is_met(&standing), "all");
+    info!("SUCCESS: conditions evaluated.");
+
+    // [2]
+    let presets = [PoseDefinition::t_pose(), PoseDefinition::arms_raised(), PoseDefinition::hands_on_hips(), PoseDefinition::squat()];
+    let skeletons = [t_pose(1), arms_raised(1), hands_on_hips(1), squat(1), standing.clone()];
+    for (i, skeleton) in skeletons.iter().enumerate() {
+        for (j, preset) in presets.iter().enumerate() {
+            ensure!(preset.condition.is_met(skeleton) == (i == j), "{} on skeleton {i}", preset.name);
+        }
+    }
+    info!("SUCCESS: presets matched.");
+
+    // [3]
+    let mut classifier = PoseClassifier::new(presets.clone());
+    let mut frames: Vec<Vec<Skeleton>> = (0..5).map(|_| vec![standing.clone()]).collect();
+    // A glimpse of a T-pose shorter than the hold is ignored.
+    frames.extend((0..5).map(|_| vec![t_pose(1)]));
+    frames.extend((0..5).map(|_| vec![standing.clone()]));
+    // Held T-pose with a single dropped frame, then relaxed.
+    frames.extend((0..15).map(|_| vec![t_pose(1)]));
+    frames.push(vec![standing.clone()]);
+    frames.extend((0..15).map(|_| vec![t_pose(1)]));
+    frames.extend((0..10).map(|_| vec![standing.clone()]));
+    let events = run(&mut classifier, 0, &frames);
+    ensure!(events.len() == 2, "one enter and exit: {events:?}");
+    ensure!(entered(&events[0].1) && events[0].1.name() == "t_pose" && events[0].0 == 25, "entered after 300 ms: {events:?}");
+    let PoseEvent::PoseExited { held, .. } = &events[1].1 else {
+        anyhow::bail!("expected an exit: {events:?}");
+    };
+    ensure!(events[1].0 == 50 && *held == Duration::from_micros(25 * FRAME_US), "exited after 100 ms: {events:?}");
+
+    let quick = PoseDefinition::arms_raised().with_hold(Duration::ZERO).with_release(Duration::ZERO);
+    let mut classifier = PoseClassifier::default().with_definition(quick).with_definition(PoseDefinition::squat());
+    let mut events = run(&mut classifier, 0, &[vec![arms_raised(1), squat(2)]]);
+    ensure!(events.len() == 1 && events[0].1.user_id() == 1, "zero hold enters immediately: {events:?}");
+    ensure!(classifier.active_poses(1).eq(["arms_raised"]) && classifier.active_poses(2).next().is_none(), "active poses");
+    // User 1 leaves while posed.
+    let frames: Vec<Vec<Skeleton>> = (0..12).map(|_| vec![squat(2)]).collect();
+    events = run(&mut classifier, 1, &frames);
+    let summary: Vec<(i32, &str, bool)> = events.iter().map(|(_, e)| (e.user_id(), e.name(), entered(e))).collect();
+    ensure!(summary == [(1, "arms_raised", false), (2, "squat", true)], "lost user exits, squat entered: {events:?}");
+    ensure!(events[0].0 == 1 && events[1].0 == 10, "exit on loss, squat after its hold: {events:?}");
+    classifier.reset();
+    ensure!(classifier.active_poses(2).next().is_none(), "reset");
+    info!("SUCCESS: poses held and released.");
+
+    // [4]
+    let hand_up = PoseDefinition::new(
+        "right_hand_up",
+        Measure::offset(JointType::RightWrist, JointType::Head, Axis::Y).at_least(0.0),
+    );
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_resolution(160, 120).realtime(false))
+        .with_device_config(DeviceConfig::new(DeviceSelector::ByIndex(0), vec![ModuleType::SkeletonTracker]))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let stream = device.skeleton_tracker().expect("skeleton tracker").as_async().skeleton_frames_stream()?;
+    let mut poses = FrameIter::new(ProcessedStream::new(stream, PoseClassifier::new([hand_up])));
+    session.start_processing()?;
+    let mut events = Vec::new();
+    for _ in 0..120 {
+        session.update()?;
+        while let Some(event) = poses.try_next() {
+            events.push(event?);
+        }
+    }
+    session.close()?;
+    ensure!(events.len() == 2 && entered(&events[0]) && !entered(&events[1]), "raised once: {events:?}");
+    let (PoseEvent::PoseEntered { timestamp: start, .. }, PoseEvent::PoseExited { timestamp: end, .. }) = (&events[0], &events[1]) else {
+        anyhow::bail!("unexpected events: {events:?}");
+    };
+    ensure!((250_000..400_000).contains(start) && (2_000_000..2_200_000).contains(end), "held while waving: {start}..{end}");
+    info!(start, end, "SUCCESS: live pose classified.");
+
+    info!("--- Pose classifier test passed ---");
+    Ok(())
+}
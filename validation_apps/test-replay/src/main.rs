@@ -0,0 +1,221 @@
+use anyhow::{ensure, Context, Result};
+use futures_util::{FutureExt, StreamExt};
+use nuitrack_rs::nuitrack::{
+    async_api::{health::SessionEvent, session_builder::NuitrackSessionBuilder},
+    backend::{replay::ReplayTiming, simulated::SimulatedBackend, BackendFrame},
+    recording::{format::RecordingReader, recorder::SessionRecorder},
+    shared_types::session_config::{DeviceConfig, DeviceSelector, ModuleType},
+};
+use std::{fs::File, io::BufReader, path::Path, time::Duration};
+use tracing::{info, Level};
+
+/// Depth payload and timestamp, plus the debug rendering of the skeletons, per frame.
+type Capture = (Vec<(u64, Vec<u16>)>, Vec<(u64, String)>);
+
+/// Timestamp and stream names of each update cycle, in playback order.
+type CycleOrder = Vec<(u64, Vec<&'static str>)>;
+
+fn device_config() -> DeviceConfig {
+    DeviceConfig::new(DeviceSelector::ByIndex(0), vec![ModuleType::DepthSensor, ModuleType::SkeletonTracker])
+}
+
+/// Reads the expected frames straight from the file.
+fn expected_from_file(path: &Path) -> Result<Capture> {
+    let mut reader = RecordingReader::new(BufReader::new(File::open(path)?))?;
+    let (mut depth, mut skeletons) = (Vec::new(), Vec::new());
+    while let Some(record) = reader.next_record()? {
+        match &record.frame {
+            BackendFrame::Depth(f) => depth.push((f.timestamp()?, f.data()?.to_vec())),
+            BackendFrame::Skeleton(f) => skeletons.push((f.timestamp()?, format!("{:?}", f.skeletons()?))),
+            _ => {}
+        }
+    }
+    Ok((depth, skeletons))
+}
+
+/// Groups the depth and skeleton records of the file into update cycles, in file order.
+fn expected_cycle_order(path: &Path) -> Result<CycleOrder> {
+    let mut reader = RecordingReader::new(BufReader::new(File::open(path)?))?;
+    let mut cycles: CycleOrder = Vec::new();
+    while let Some(record) = reader.next_record()? {
+        let name = match &record.frame {
+            BackendFrame::Depth(_) => "depth",
+            BackendFrame::Skeleton(_) => "skeleton",
+            _ => continue,
+        };
+        match cycles.last_mut() {
+            Some((timestamp, names)) if *timestamp == record.timestamp => names.push(name),
+            _ => cycles.push((record.timestamp, vec![name])),
+        }
+    }
+    cycles.iter_mut().for_each(|(_, names)| names.sort());
+    Ok(cycles)
+}
+
+/// This test validates replaying a recording through the regular async API.
+/// 1. A simulated session is recorded to disk.
+/// 2. An as-fast-as-possible replay yields byte-identical depth and skeleton frames, then ends the streams.
+/// 3. A stepped replay only advances on drive_update_cycle().
+/// 4. Original timing takes roughly as long as the recording itself.
+/// 5. Stepping through the replay delivers the streams cycle by cycle, in the recorded order.
+/// 6. A corrupt recording ends the streams and reports the failure as a session event.
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Replay Backend ---");
+    let path = std::env::temp_dir().join("nuitrack-rs-test-replay.ntrk");
+
+    // [1]
+    let session = NuitrackSessionBuilder::new()
+        .with_backend(SimulatedBackend::new())
+        .with_device_config(device_config())
+        .init_session()
+        .await?;
+    let recorder = SessionRecorder::start(&session, &path).await?;
+    session.start_processing().await?;
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    session.close().await?;
+    let summary = recorder.finish().await?;
+    let (expected_depth, expected_skeletons) = expected_from_file(&path)?;
+    info!(records = summary.records, depth = expected_depth.len(), skeletons = expected_skeletons.len(), "Recorded.");
+    ensure!(!expected_depth.is_empty() && !expected_skeletons.is_empty(), "recording is missing frames");
+
+    // [2]
+    let mut session = NuitrackSessionBuilder::new()
+        .with_recording(&path, ReplayTiming::AsFastAsPossible)
+        .with_device_config(device_config())
+        .init_session()
+        .await?;
+    let device = &mut session.active_devices[0];
+    let mut depth_stream = device.depth_sensor.as_mut().context("no depth sensor")?.depth_frames_stream()?;
+    let mut skeleton_stream = device.skeleton_tracker.as_mut().context("no skeleton tracker")?.skeleton_frames_stream()?;
+    session.start_processing().await?;
+
+    let (mut depth, mut skeletons) = (Vec::new(), Vec::new());
+    let collect = async {
+        while let Some(frame) = depth_stream.next().await {
+            let frame = frame?;
+            depth.push((frame.timestamp()?, frame.data()?.to_vec()));
+        }
+        while let Some(frame) = skeleton_stream.next().await {
+            let frame = frame?;
+            skeletons.push((frame.timestamp()?, format!("{:?}", frame.skeletons()?)));
+        }
+        anyhow::Ok(())
+    };
+    tokio::time::timeout(Duration::from_secs(10), collect).await.context("replay streams never ended")??;
+    ensure!(depth == expected_depth, "replayed depth frames differ from the recording");
+    ensure!(skeletons == expected_skeletons, "replayed skeleton frames differ from the recording");
+    session.close().await?;
+    info!(depth = depth.len(), skeletons = skeletons.len(), "SUCCESS: replay is byte-identical.");
+
+    // [3]
+    let mut session = NuitrackSessionBuilder::new()
+        .with_recording(&path, ReplayTiming::Stepped)
+        .with_device_config(device_config())
+        .init_session()
+        .await?;
+    let mut depth_stream = session.active_devices[0].depth_sensor.as_mut().unwrap().depth_frames_stream()?;
+    session.start_processing().await?;
+    let idle = tokio::time::timeout(Duration::from_millis(300), depth_stream.next()).await;
+    ensure!(idle.is_err(), "stepped replay advanced without drive_update_cycle()");
+    let mut first = None;
+    while first.is_none() {
+        session.drive_update_cycle().await?;
+        if let Ok(Some(frame)) = tokio::time::timeout(Duration::from_millis(10), depth_stream.next()).await {
+            first = Some(frame?.timestamp()?);
+        }
+    }
+    ensure!(first == Some(expected_depth[0].0), "stepped replay did not start at the first depth frame");
+    session.close().await?;
+    info!("SUCCESS: stepped replay advances on demand.");
+
+    // [4]
+    let mut session = NuitrackSessionBuilder::new()
+        .with_recording(&path, ReplayTiming::Original)
+        .with_device_config(device_config())
+        .init_session()
+        .await?;
+    let mut depth_stream = session.active_devices[0].depth_sensor.as_mut().unwrap().depth_frames_stream()?;
+    let started = std::time::Instant::now();
+    session.start_processing().await?;
+    while depth_stream.next().await.is_some() {}
+    let elapsed = started.elapsed();
+    let recorded = Duration::from_micros(expected_depth.last().unwrap().0 - expected_depth[0].0);
+    ensure!(elapsed + Duration::from_millis(100) >= recorded, "original-timing replay ran too fast ({:?} < {:?})", elapsed, recorded);
+    session.close().await?;
+    info!(?elapsed, ?recorded, "SUCCESS: original timing is honored.");
+
+    // [5]
+    let expected_order = expected_cycle_order(&path)?;
+    let mut session = NuitrackSessionBuilder::new()
+        .with_recording(&path, ReplayTiming::Stepped)
+        .with_device_config(device_config())
+        .init_session()
+        .await?;
+    let device = &mut session.active_devices[0];
+    let mut depth_stream = device.depth_sensor.as_mut().context("no depth sensor")?.depth_frames_stream()?;
+    let mut skeleton_stream = device.skeleton_tracker.as_mut().context("no skeleton tracker")?.skeleton_frames_stream()?;
+    session.start_processing().await?;
+    let mut replayed_order: CycleOrder = Vec::new();
+    let (mut depth_done, mut skeletons_done) = (false, false);
+    while !(depth_done && skeletons_done) {
+        session.drive_update_cycle().await?;
+        // A stepped backend publishes the whole batch before drive_update_cycle() returns.
+        let mut cycle = Vec::new();
+        loop {
+            match depth_stream.next().now_or_never() {
+                Some(Some(frame)) => cycle.push((frame?.timestamp()?, "depth")),
+                Some(None) => { depth_done = true; break; }
+                None => break,
+            }
+        }
+        loop {
+            match skeleton_stream.next().now_or_never() {
+                Some(Some(frame)) => cycle.push((frame?.timestamp()?, "skeleton")),
+                Some(None) => { skeletons_done = true; break; }
+                None => break,
+            }
+        }
+        let Some(&(timestamp, _)) = cycle.first() else { continue };
+        ensure!(cycle.iter().all(|(ts, _)| *ts == timestamp), "one step delivered frames of different cycles: {:?}", cycle);
+        let mut names: Vec<_> = cycle.into_iter().map(|(_, name)| name).collect();
+        names.sort();
+        replayed_order.push((timestamp, names));
+    }
+    ensure!(replayed_order == expected_order, "replayed cycle order differs from the recording");
+    session.close().await?;
+    info!(cycles = replayed_order.len(), "SUCCESS: streams replay in the recorded order.");
+
+    // [6]
+    let corrupt = std::env::temp_dir().join("nuitrack-rs-test-replay-corrupt.ntrk");
+    let last_chunk = RecordingReader::new(BufReader::new(File::open(&path)?))?.chunks().last().context("recording has no chunks")?.offset as usize;
+    let mut bytes = std::fs::read(&path)?;
+    // The last chunk claims to extend past the end of the file.
+    bytes[last_chunk + 4..last_chunk + 8].copy_from_slice(&u32::MAX.to_le_bytes());
+    std::fs::write(&corrupt, bytes)?;
+    let mut session = NuitrackSessionBuilder::new()
+        .with_recording(&corrupt, ReplayTiming::AsFastAsPossible)
+        .with_device_config(device_config())
+        .init_session()
+        .await?;
+    let mut events = session.events_stream();
+    let mut depth_stream = session.active_devices[0].depth_sensor.as_mut().unwrap().depth_frames_stream()?;
+    session.start_processing().await?;
+    let drain = async { while depth_stream.next().await.is_some() {} };
+    tokio::time::timeout(Duration::from_secs(10), drain).await.context("streams did not end after the backend failed")?;
+    let event = tokio::time::timeout(Duration::from_secs(1), events.next()).await.context("no session event")?;
+    ensure!(matches!(event, Some(SessionEvent::BackendFailed { .. })), "expected a backend failure, got {:?}", event);
+    ensure!(events.next().await.is_none(), "event stream did not end after the backend failed");
+    session.close().await?;
+    std::fs::remove_file(&corrupt)?;
+    info!(?event, "SUCCESS: a failing backend ends the session's streams.");
+
+    std::fs::remove_file(&path)?;
+    info!("--- Test Complete ---");
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::frame_bundle::FrameBundleStream,
+    backend::simulated::SimulatedBackend,
+    blocking_api::{frame_iter::FrameIter, session::BlockingSessionBuilder},
+    shared_types::{
+        depth_frame::OwnedDepthFrame,
+        rgb_frame::{Color3, OwnedRGBFrame},
+        rgbd_frame::{Extrinsics, Registration},
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+    },
+};
+use nuitrack_rs::nuitrack::async_api::depth_sensor::{Intrinsics, OutputMode};
+use tracing::{info, Level};
+
+fn mode(xres: i32, yres: i32) -> OutputMode {
+    let f = xres as f32;
+    OutputMode {
+        fps: 30,
+        xres,
+        yres,
+        hfov: 90f32.to_radians(),
+        intrinsics: Intrinsics { fx: f, fy: f, cx: xres as f32 / 2.0, cy: yres as f32 / 2.0 },
+    }
+}
+
+/// Encodes a pixel's coordinates in its color, so samples can be traced back.
+fn pixel_color(row: i32, col: i32) -> Color3 {
+    Color3 { blue: 0, green: row as u8, red: col as u8 }
+}
+
+/// This test validates depth-to-color registration.
+/// 1. With identity extrinsics, each depth pixel samples the color pixel at the scaled position.
+/// 2. Pixels without depth, or projecting outside the color image, get no color.
+/// 3. The builder toggle and `registration()` work with a session, and bundles convert to `RgbdFrame`s.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: RGB-D Registration ---");
+
+    // [1]
+    let (rows, cols) = (4, 4);
+    let mut depth = OwnedDepthFrame { rows, cols, id: 1, timestamp: 1000, data: vec![1000; 16] };
+    depth.data[5] = 0;
+    let color = OwnedRGBFrame {
+        rows: 8,
+        cols: 8,
+        id: 1,
+        timestamp: 1000,
+        data: (0..64).map(|i| pixel_color(i / 8, i % 8)).collect(),
+    };
+    let registration = Registration::new(mode(4, 4), mode(8, 8));
+    let rgbd = registration.align(&depth, &color)?;
+    ensure!((rgbd.rows, rgbd.cols, rgbd.timestamp) == (rows, cols, 1000), "frame header");
+    for row in 0..rows {
+        for col in 0..cols {
+            if (row, col) == (1, 1) {
+                continue;
+            }
+            ensure!(rgbd.color_at(row, col) == Some(pixel_color(row * 2, col * 2)), "sample at ({row}, {col})");
+        }
+    }
+    info!("SUCCESS: identity registration samples the scaled position.");
+
+    // [2]
+    ensure!(rgbd.depth_at(1, 1) == Some(0) && rgbd.color_at(1, 1).is_none(), "pixel without depth");
+    ensure!((rgbd.coverage() - 15.0 / 16.0).abs() < 1e-6, "coverage {}", rgbd.coverage());
+    let shifted = registration.with_extrinsics(Extrinsics::from_translation(10_000.0, 0.0, 0.0)).align(&depth, &color)?;
+    ensure!(shifted.coverage() == 0.0, "points shifted out of view keep no color");
+    info!("SUCCESS: missing depth and out-of-view points have no color.");
+
+    // [3]
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_resolution(160, 120).realtime(false))
+        .with_depth_to_color_registration(true)
+        .with_device_config(DeviceConfig::new(
+            DeviceSelector::ByIndex(0),
+            vec![ModuleType::DepthSensor, ModuleType::ColorSensor],
+        ))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let registration = device.registration()?.expect("device has depth and color");
+    let mut bundles = FrameIter::new(FrameBundleStream::for_device(device.as_async())?.build()?);
+    session.start_processing()?;
+    let mut converted = 0;
+    for _ in 0..5 {
+        session.update()?;
+        while let Some(bundle) = bundles.try_next() {
+            let rgbd = bundle?.to_rgbd(&registration)?.expect("bundle has depth and color");
+            ensure!((rgbd.rows, rgbd.cols) == (120, 160), "registered frame size");
+            ensure!(rgbd.coverage() > 0.5, "coverage {}", rgbd.coverage());
+            converted += 1;
+        }
+    }
+    ensure!(converted > 0, "no bundles were registered");
+    session.close()?;
+    info!(converted, "SUCCESS: session bundles registered to RGB-D.");
+
+    info!("--- RGB-D registration test passed ---");
+    Ok(())
+}
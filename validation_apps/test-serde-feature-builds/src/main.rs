@@ -1,22 +1,97 @@
 // File: /validation_apps/test-serde-feature-builds/src/main.rs
 
-// We need to import one of the types that has the conditional serde derive.
-// Vector3 is a perfect, simple choice.
-use nuitrack_rs::nuitrack::async_api::depth_sensor::Vector3;
+use nuitrack_rs::nuitrack::async_api::depth_sensor::{Intrinsics, OutputMode, Vector3};
+use nuitrack_rs::nuitrack::shared_types::{
+    depth_frame::OwnedDepthFrame,
+    gesture::{Gesture, GestureState, GestureType, UserGestures, UserState, UserStateType},
+    gesture_frame::{OwnedGestureFrame, OwnedUserGesturesFrame, OwnedUserStateFrame},
+    hand::{Hand, UserHands},
+    hand_frame::OwnedHandFrame,
+    rgb_frame::{Color3, OwnedRGBFrame},
+    skeleton::{Joint, JointType, Orientation, Skeleton},
+    skeleton_frame::OwnedSkeletonFrame,
+    user::User,
+    user_frame::OwnedUserFrame,
+    user_mask::UserMask,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Serializes `value` to JSON, parses it back and checks that re-serializing yields the same JSON.
+fn round_trip<T: Serialize + DeserializeOwned>(name: &str, value: &T) -> String {
+    let json = serde_json::to_string(value).expect("serialization failed");
+    let parsed: T = serde_json::from_str(&json).unwrap_or_else(|e| panic!("{name}: {e}\n{json}"));
+    let again = serde_json::to_string(&parsed).expect("re-serialization failed");
+    assert_eq!(json, again, "{name} did not survive a JSON round trip");
+    println!("{name}: {json}");
+    json
+}
 
 fn main() {
-    // The purpose of this test is to ensure that the project compiles
-    // when the "serde" feature is enabled. We don't need complex logic.
-
-    // Just creating an instance of the struct is enough for the compiler
-    // to check that the `Serialize` and `Deserialize` traits are correctly derived.
-    let _vector = Vector3 {
-        x: 1.0,
-        y: 2.0,
-        z: 3.0,
+    // The purpose of this test is to ensure that the project compiles with the
+    // "serde" feature enabled and that every public data type round-trips through JSON.
+    let vector = Vector3 { x: 1.0, y: 2.0, z: 3.0 };
+    round_trip("Vector3", &vector);
+
+    let user = User { id: 1, proj: vector, real: vector, ..Default::default() };
+    let json = round_trip("User", &user);
+    assert!(json.contains("\"box\""), "bounding box should serialize as `box`");
+
+    let hand = Hand { x: 0.5, y: 0.5, click: true, pressure: 80, x_real: 1.0, y_real: 2.0, z_real: 3.0 };
+    let user_hands = UserHands { user_id: 1, left_hand: None, right_hand: Some(hand) };
+    round_trip("UserHands", &user_hands);
+
+    let joint = Joint {
+        joint_type: JointType::RightHand,
+        confidence: 0.75,
+        real: vector,
+        proj: vector,
+        orient: Orientation { matrix: [0.0; 9] },
     };
+    let json = round_trip("Joint", &joint);
+    assert!(json.contains("\"rightHand\""), "joint types should serialize as camelCase strings");
+
+    let gesture = Gesture { user_id: 1, gesture_type: GestureType::SwipeLeft };
+    round_trip("Gesture", &gesture);
+    let user_gestures = UserGestures {
+        user_id: 1,
+        user_state: UserStateType::Active,
+        gestures: vec![GestureState { gesture_type: GestureType::Waving, progress: 40 }],
+    };
+    round_trip("UserGestures", &user_gestures);
+
+    round_trip("OutputMode", &OutputMode {
+        fps: 30, xres: 640, yres: 480, hfov: 1.0,
+        intrinsics: Intrinsics { fx: 554.0, fy: 554.0, cx: 320.0, cy: 240.0 },
+    });
+
+    round_trip("OwnedDepthFrame", &OwnedDepthFrame { rows: 1, cols: 2, id: 3, timestamp: 4, data: vec![500, 600] });
+    round_trip("OwnedRGBFrame", &OwnedRGBFrame {
+        rows: 1, cols: 1, id: 3, timestamp: 4,
+        data: vec![Color3 { red: 255, green: 0, blue: 0 }],
+    });
+    round_trip("OwnedUserFrame", &OwnedUserFrame {
+        rows: 1, cols: 1, timestamp: 4, data: vec![1], users: vec![user],
+        floor: Vector3::default(), floor_normal: Vector3 { x: 0.0, y: 1.0, z: 0.0 },
+    });
+    round_trip("OwnedSkeletonFrame", &OwnedSkeletonFrame {
+        timestamp: 4,
+        skeletons: vec![Skeleton { user_id: 1, joints: vec![joint] }],
+    });
+    round_trip("OwnedHandFrame", &OwnedHandFrame { timestamp: 4, users_hands: vec![user_hands] });
+    round_trip("OwnedGestureFrame", &OwnedGestureFrame { timestamp: 4, gestures: vec![gesture] });
+    round_trip("OwnedUserStateFrame", &OwnedUserStateFrame {
+        timestamp: 4,
+        user_states: vec![UserState { user_id: 1, state: UserStateType::Active }],
+    });
+    round_trip("OwnedUserGesturesFrame", &OwnedUserGesturesFrame { timestamp: 4, users: vec![user_gestures] });
+
+    round_trip("UserMask", &UserMask::new(1, 2, vec![true, false]).unwrap());
+    assert!(
+        serde_json::from_str::<UserMask>(r#"{"rows":2,"cols":2,"data":[true]}"#).is_err(),
+        "masks whose data does not match their dimensions should not deserialize"
+    );
 
     // A print statement confirms that the build and run were successful.
     println!("Successfully compiled and ran the serde feature validation app!");
     println!("This confirms that `nuitrack-rs` builds correctly with the 'serde' feature enabled.");
-}
\ No newline at end of file
+}
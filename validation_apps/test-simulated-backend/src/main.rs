@@ -0,0 +1,89 @@
+use anyhow::{ensure, Result};
+use futures_util::StreamExt;
+use nuitrack_rs::nuitrack::{
+    async_api::session_builder::NuitrackSessionBuilder,
+    backend::simulated::{SimulatedBackend, MAX_USERS},
+    shared_types::session_config::{DeviceConfig, DeviceSelector, ModuleType},
+};
+use tracing::{info, Level};
+
+/// This test validates that a session driven by the simulated backend behaves
+/// like a hardware session, without a sensor or the Nuitrack runtime.
+/// 1. Every stream delivers frames with consistent timestamps.
+/// 2. New-user events arrive before the first skeletons.
+/// 3. A completed waving gesture is eventually reported.
+/// 4. Two simulated sessions can coexist, since no runtime guard is held.
+/// 5. Scenes with more users than Nuitrack tracks are rejected.
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Simulated Backend ---");
+
+    let mut session = NuitrackSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_users(2)?.realtime(false))
+        .with_device_config(DeviceConfig::new(
+            DeviceSelector::BySerialNumber("SIM-0000".into()),
+            vec![
+                ModuleType::DepthSensor,
+                ModuleType::UserTracker,
+                ModuleType::SkeletonTracker,
+                ModuleType::GestureRecognizer,
+            ],
+        ))
+        .init_session()
+        .await?;
+
+    // A second backend-driven session must not conflict with the first.
+    let other_session = NuitrackSessionBuilder::new()
+        .with_backend(SimulatedBackend::new())
+        .init_session()
+        .await?;
+    other_session.close().await?;
+
+    let device = &mut session.active_devices[0];
+    let mode = device.depth_sensor.as_ref().unwrap().output_mode().await?;
+    info!(?mode, "Depth output mode.");
+    let mut depth_stream = device.depth_sensor.as_mut().unwrap().depth_frames_stream()?;
+    let mut user_stream = device.user_tracker.as_mut().unwrap().user_frames_stream()?;
+    let mut new_user_stream = device.skeleton_tracker.as_mut().unwrap().new_user_events_stream()?;
+    let mut skeleton_stream = device.skeleton_tracker.as_mut().unwrap().skeleton_frames_stream()?;
+    let mut gesture_stream = device.gesture_recognizer.as_mut().unwrap().completed_gestures_frames_stream()?;
+
+    session.start_processing().await?;
+
+    // [1] and [2]
+    for expected_id in 1..=2 {
+        let user_id = new_user_stream.next().await.unwrap()?;
+        ensure!(user_id == expected_id, "unexpected new user {}", user_id);
+    }
+    for _ in 0..30 {
+        let (depth, users, skeletons) = (
+            depth_stream.next().await.unwrap()?,
+            user_stream.next().await.unwrap()?,
+            skeleton_stream.next().await.unwrap()?,
+        );
+        ensure!(depth.rows()? == mode.yres && depth.cols()? == mode.xres, "depth frame size mismatch");
+        ensure!(depth.timestamp()? == skeletons.timestamp()?, "depth/skeleton timestamps differ");
+        ensure!(users.users()?.len() == 2, "expected two users");
+        ensure!(skeletons.skeletons()?.len() == 2, "expected two skeletons");
+    }
+    info!("SUCCESS: depth, user and skeleton streams are consistent.");
+
+    // [3] The simulated wave completes within a few simulated seconds.
+    let gestures = gesture_stream.next().await.unwrap()?;
+    info!(gestures = ?gestures.gestures()?, "SUCCESS: completed gesture received.");
+
+    drop((depth_stream, user_stream, new_user_stream, skeleton_stream, gesture_stream));
+    session.close().await?;
+
+    // [5]
+    ensure!(SimulatedBackend::new().with_users(MAX_USERS).is_ok(), "a full scene is accepted");
+    ensure!(SimulatedBackend::new().with_users(MAX_USERS + 1).is_err(), "too many users are accepted");
+    info!("SUCCESS: user count validated.");
+    info!("--- Test Complete ---");
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::{depth_sensor::Vector3, filtered_skeleton_stream::FilteredSkeletonStream},
+    backend::simulated::SimulatedBackend,
+    blocking_api::{frame_iter::FrameIter, session::BlockingSessionBuilder},
+    shared_types::{
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+        skeleton::{Joint, JointType, Orientation, Skeleton},
+        skeleton_filter::{FilterConfig, SkeletonFilter, SkeletonFilterConfig},
+    },
+};
+use std::time::Duration;
+use tracing::{info, Level};
+
+const FRAME_US: u64 = 33_333;
+
+fn joint(joint_type: JointType, real: Vector3) -> Joint {
+    Joint {
+        joint_type,
+        confidence: 0.75,
+        real,
+        proj: Vector3 { x: 0.5, y: 0.5, z: real.z },
+        orient: Orientation { matrix: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0] },
+    }
+}
+
+fn skeleton(user_id: i32, head: Vector3, torso: Vector3) -> Skeleton {
+    Skeleton { user_id, joints: vec![joint(JointType::Head, head), joint(JointType::Torso, torso)] }
+}
+
+/// Deterministic noise in `-amplitude..amplitude`.
+struct Noise(u64);
+
+impl Noise {
+    fn next(&mut self, amplitude: f32) -> f32 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0) * amplitude
+    }
+}
+
+/// Runs `config` over a head moving along `x` at `speed` mm/s with noise, and returns
+/// (mean squared jitter of raw input, of the output) over the last second, measured
+/// against the true position.
+fn run(config: FilterConfig, speed: f32) -> (f32, f32) {
+    let mut filter = SkeletonFilter::new(SkeletonFilterConfig::new(config));
+    let mut noise = Noise(7);
+    let (mut raw_error, mut filtered_error, mut samples) = (0.0, 0.0, 0.0);
+    for frame in 0..90u64 {
+        let t = frame as f32 * FRAME_US as f32 / 1e6;
+        let truth = Vector3 { x: speed * t, y: 300.0, z: 2000.0 };
+        let measured = Vector3 { x: truth.x + noise.next(20.0), y: truth.y + noise.next(20.0), z: truth.z + noise.next(20.0) };
+        let out = filter.apply(frame * FRAME_US, &[skeleton(1, measured, truth)]);
+        if frame >= 60 {
+            let head = out[0].joints[0].real;
+            raw_error += (measured.x - truth.x).powi(2) + (measured.y - truth.y).powi(2);
+            filtered_error += (head.x - truth.x).powi(2) + (head.y - truth.y).powi(2);
+            samples += 1.0;
+        }
+    }
+    (raw_error / samples, filtered_error / samples)
+}
+
+/// Steps the head from 0 to 100 mm along `x` and returns its filtered position 200 ms
+/// later, with frames `frame_us` apart.
+fn step_response(config: FilterConfig, frame_us: u64) -> f32 {
+    let mut filter = SkeletonFilter::new(SkeletonFilterConfig::new(config));
+    let at = |x: f32| Vector3 { x, y: 0.0, z: 2000.0 };
+    let mut out = filter.apply(0, &[skeleton(1, at(0.0), at(0.0))]);
+    for frame in 1..=(200_000 + frame_us / 2) / frame_us {
+        out = filter.apply(frame * frame_us, &[skeleton(1, at(100.0), at(100.0))]);
+    }
+    out[0].joints[0].real.x
+}
+
+/// This test validates skeleton smoothing.
+/// 1. Every filter reduces jitter on a static joint.
+/// 2. Every filter keeps up with a joint moving at constant speed, and double exponential
+///    smoothing responds alike at 30 and 60 FPS.
+/// 3. Per-joint configuration, duplicate timestamps, untracked joints and state expiry.
+/// 4. The stream adapter smooths live frames and recomputes `proj` with the camera model.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Skeleton Filter ---");
+
+    let filters = [
+        ("one euro", FilterConfig::one_euro()),
+        ("double exponential", FilterConfig::double_exponential()),
+        ("kalman", FilterConfig::kalman()),
+    ];
+
+    // [1]
+    for (name, config) in filters {
+        let (raw, filtered) = run(config, 0.0);
+        ensure!(filtered < raw * 0.5, "{name}: static jitter {filtered:.1} vs raw {raw:.1}");
+        info!(name, raw, filtered, "Static joint smoothed.");
+    }
+    info!("SUCCESS: jitter reduced.");
+
+    // [2]
+    for (name, config) in filters {
+        let (raw, filtered) = run(config, 800.0);
+        ensure!(filtered < raw * 4.0 && filtered.sqrt() < 60.0, "{name}: moving error {:.1} mm", filtered.sqrt());
+        info!(name, rms_mm = filtered.sqrt(), "Moving joint tracked.");
+    }
+    let at_30 = step_response(FilterConfig::double_exponential(), FRAME_US);
+    let at_60 = step_response(FilterConfig::double_exponential(), FRAME_US / 2);
+    ensure!((at_30 - at_60).abs() < 4.0, "double exponential step response {at_30:.1} at 30 FPS vs {at_60:.1} at 60 FPS");
+    info!("SUCCESS: moving joints tracked.");
+
+    // [3]
+    let config = SkeletonFilterConfig::new(FilterConfig::kalman())
+        .with_joint(JointType::Torso, FilterConfig::None)
+        .with_reset_after(Some(Duration::from_millis(200)));
+    let mut filter = SkeletonFilter::new(config);
+    let at = |x: f32| Vector3 { x, y: 0.0, z: 2000.0 };
+    filter.apply(0, &[skeleton(1, at(0.0), at(0.0))]);
+    let moved = filter.apply(FRAME_US, &[skeleton(1, at(100.0), at(100.0))]);
+    ensure!(moved[0].joints[1].real.x == 100.0, "unfiltered torso passes through");
+    ensure!(moved[0].joints[0].real.x < 100.0, "filtered head lags the jump");
+    let repeated = filter.apply(FRAME_US, &[skeleton(1, at(500.0), at(500.0))]);
+    ensure!(repeated[0].joints[0].real.x == moved[0].joints[0].real.x, "duplicate timestamps keep the last output");
+    let mut untracked = skeleton(1, at(900.0), at(900.0));
+    untracked.joints[0].confidence = 0.0;
+    ensure!(filter.apply(2 * FRAME_US, &[untracked])[0].joints[0].real.x == 900.0, "untracked joints pass through");
+    filter.apply(3 * FRAME_US, &[skeleton(1, at(0.0), at(0.0))]);
+    let after_gap = filter.apply(3 * FRAME_US + 300_000, &[skeleton(1, at(700.0), at(700.0))]);
+    ensure!(after_gap[0].joints[0].real.x == 700.0, "state expires after reset_after");
+    let two_users = filter.apply(4 * FRAME_US + 300_000, &[skeleton(1, at(700.0), at(0.0)), skeleton(2, at(-50.0), at(0.0))]);
+    ensure!(two_users.len() == 2 && two_users[1].joints[0].real.x == -50.0, "new users start from their first frame");
+    info!("SUCCESS: configuration and state handling.");
+
+    // [4]
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_users(2)?.with_resolution(160, 120).realtime(false))
+        .with_device_config(DeviceConfig::new(
+            DeviceSelector::ByIndex(0),
+            vec![ModuleType::DepthSensor, ModuleType::SkeletonTracker],
+        ))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let camera = device.depth_sensor().expect("depth sensor").camera_model()?;
+    let stream = device.skeleton_tracker().expect("skeleton tracker").as_async().skeleton_frames_stream()?;
+    let filter = SkeletonFilter::new(SkeletonFilterConfig::new(FilterConfig::one_euro())).with_camera_model(camera);
+    let mut smoothed = FrameIter::new(FilteredSkeletonStream::new(stream, filter));
+    session.start_processing()?;
+    let mut frames = 0;
+    for _ in 0..10 {
+        session.update()?;
+        while let Some(frame) = smoothed.try_next() {
+            let frame = frame?;
+            ensure!(frame.skeletons()?.len() == 2, "both users smoothed");
+            for joint in frame.skeletons()?.iter().flat_map(|s| &s.joints).filter(|j| j.confidence > 0.0) {
+                let expected = camera.proj_to_normalized(camera.real_to_proj(joint.real));
+                ensure!((expected.x - joint.proj.x).abs() < 1e-4 && (expected.y - joint.proj.y).abs() < 1e-4, "proj follows real");
+            }
+            frames += 1;
+        }
+    }
+    ensure!(frames == 10, "{frames} smoothed frames");
+    session.close()?;
+    info!(frames, "SUCCESS: stream adapter smooths live frames.");
+
+    info!("--- Skeleton filter test passed ---");
+    Ok(())
+}
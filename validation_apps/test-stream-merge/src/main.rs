@@ -1,6 +1,9 @@
 use futures_util::StreamExt;
 use nuitrack_rs::{
-    nuitrack::shared_types::error::NuitrackError,
+    nuitrack::{
+        async_api::frame_bundle::{FrameBundleStream, MissingFramePolicy},
+        shared_types::error::NuitrackError,
+    },
     setup_nuitrack_streams
 };
 use tracing::{error, info, warn, Level};
@@ -21,49 +24,52 @@ async fn main() -> anyhow::Result<()> {
     })?;
     info!("Nuitrack processing and internal update loop started.");
 
-    // 5. Process frames from the stream
-    for i in 0..1000 { // Let's try to get a few frames
-        info!("Waiting for synchronized frame set #{}...", i + 1);
+    // 5. Combine the streams into timestamp-aligned bundles. Only complete bundles
+    //    are yielded, so a frame dropped by one stream no longer derails the others.
+    let mut bundles = FrameBundleStream::builder()
+        .depth(depth_stream)
+        .user(user_stream)
+        .skeleton(skeleton_stream)
+        .hand(hand_stream)
+        .missing_frame_policy(MissingFramePolicy::Skip)
+        .build()?;
 
-        // 3. Use `tokio::join!` to await the next frame from each stream
-        let (depth_result, user_result, skeleton_result, hand_result) = tokio::join!(
-            depth_stream.next(),
-            user_stream.next(),
-            skeleton_stream.next(),
-            hand_stream.next()
-        );
+    for i in 0..1000 {
+        info!("Waiting for synchronized frame set #{}...", i + 1);
+        let bundle = match bundles.next().await {
+            Some(Ok(bundle)) => bundle,
+            Some(Err(e)) => {
+                warn!(error = %e, "A stream produced an error. Exiting loop.");
+                break;
+            }
+            None => {
+                warn!("All streams ended. Exiting loop.");
+                break;
+            }
+        };
 
-        // 4. Match on the tuple of results to get the synchronized frames
-        match (depth_result, user_result, skeleton_result, hand_result) {
-            (
-                Some(Ok(depth_frame)),
-                Some(Ok(user_frame)),
-                Some(Ok(skeleton_frame)),
-                Some(Ok(hand_frame)),
-            ) => {
-                // All frames received successfully!
-                let ts = depth_frame.timestamp()?;
+        let (Some(depth_frame), Some(user_frame), Some(skeleton_frame), Some(hand_frame)) =
+            (&bundle.depth, &bundle.user, &bundle.skeleton, &bundle.hand)
+        else {
+            anyhow::bail!("MissingFramePolicy::Skip yielded an incomplete bundle");
+        };
 
-                // Prove that all timestamps are identical
-                assert_eq!(ts, user_frame.timestamp()?);
-                assert_eq!(ts, skeleton_frame.timestamp()?);
-                assert_eq!(ts, hand_frame.timestamp()?);
+        // Prove that all timestamps are identical
+        let ts = bundle.timestamp;
+        assert_eq!(ts, depth_frame.timestamp()?);
+        assert_eq!(ts, user_frame.timestamp()?);
+        assert_eq!(ts, skeleton_frame.timestamp()?);
+        assert_eq!(ts, hand_frame.timestamp()?);
 
-                info!(
-                    "--> SYNCHRONIZED FRAME BUNDLE RECEIVED | Timestamp: {}",
-                    ts
-                );
+        info!(
+            "--> SYNCHRONIZED FRAME BUNDLE RECEIVED | Timestamp: {}",
+            ts
+        );
 
-                // Now you can process them together, e.g., find the skeleton for a specific user
-                // let skeletons = skeleton_frame.skeletons()?;
-                // let users = user_frame.users()?;
-                // ... your application logic here ...
-            }
-            _ => {
-                warn!("A stream ended or produced an error. Exiting loop.");
-                break;
-            }
-        }
+        // Now you can process them together, e.g., find the skeleton for a specific user
+        // let skeletons = skeleton_frame.skeletons()?;
+        // let users = user_frame.users()?;
+        // ... your application logic here ...
     }
     info!("Finished processing frames or example duration ended.");
 
@@ -0,0 +1,126 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::{depth_sensor::Vector3, user_event_stream::UserEventStream},
+    backend::simulated::SimulatedBackend,
+    blocking_api::{frame_iter::FrameIter, session::BlockingSessionBuilder},
+    shared_types::{
+        gesture::{UserState, UserStateType},
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+        skeleton::{Joint, JointType, Orientation, Skeleton},
+        user::User,
+        user_lifecycle::{UserEvent, UserEventKind, UserLifecycle},
+    },
+};
+use tracing::{info, Level};
+use UserEventKind::*;
+
+fn user(id: i32, x: f32) -> User {
+    User { id, real: Vector3 { x, y: 0.0, z: 2000.0 }, ..Default::default() }
+}
+
+fn skeleton(user_id: i32, confidence: f32) -> Skeleton {
+    let joint = Joint {
+        joint_type: JointType::Torso,
+        confidence,
+        real: Vector3::default(),
+        proj: Vector3::default(),
+        orient: Orientation { matrix: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0] },
+    };
+    Skeleton { user_id, joints: vec![joint] }
+}
+
+fn state(user_id: i32, state: UserStateType) -> UserState {
+    UserState { user_id, state }
+}
+
+fn kinds(events: &[UserEvent]) -> Vec<(i32, UserEventKind)> {
+    events.iter().map(|e| (e.user_id, e.kind)).collect()
+}
+
+/// This test validates the user lifecycle.
+/// 1. Every source reports a user's appearance once, followed by engagement and skeleton.
+/// 2. Skeletons and engagement come and go while the user stays.
+/// 3. Users leave through lost events, user frames, absent states or, with skeletons as
+///    the only source, lost skeletons, in order.
+/// 4. The device stream reports the simulated backend's users.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: User Events ---");
+
+    // [1]
+    let mut lifecycle = UserLifecycle::new();
+    let events = lifecycle.user_appeared(1);
+    ensure!(kinds(&events) == [(1, Appeared)] && events[0].user.is_none(), "new user event: {events:?}");
+    let events = lifecycle.apply_user_states(100, &[state(1, UserStateType::Active), state(2, UserStateType::InScene)]);
+    ensure!(kinds(&events) == [(1, Engaged), (2, Appeared)], "states: {events:?}");
+    let events = lifecycle.apply_users(100, &[user(1, 10.0), user(2, 20.0)]);
+    ensure!(events.is_empty(), "known users do not reappear: {events:?}");
+    let events = lifecycle.apply_skeletons(100, &[skeleton(1, 0.75), skeleton(2, 0.0), skeleton(3, 0.5)]);
+    ensure!(kinds(&events) == [(1, SkeletonAcquired), (3, Appeared), (3, SkeletonAcquired)], "skeletons: {events:?}");
+    ensure!(events[0].user == Some(user(1, 10.0)) && events[0].timestamp == 100, "user data attached");
+    ensure!(lifecycle.users().count() == 3 && lifecycle.status(3).is_some_and(|s| s.has_skeleton && s.since == 100), "statuses");
+    info!("SUCCESS: users appear.");
+
+    // [2]
+    let events = lifecycle.apply_skeletons(200, &[skeleton(1, 0.0), skeleton(3, 0.5)]);
+    ensure!(kinds(&events) == [(1, SkeletonLost)], "untracked skeleton lost: {events:?}");
+    let events = lifecycle.apply_skeletons(300, &[skeleton(1, 0.75)]);
+    ensure!(kinds(&events) == [(1, SkeletonAcquired), (3, SkeletonLost)], "reacquired, missing skeleton lost: {events:?}");
+    let events = lifecycle.apply_user_states(300, &[state(1, UserStateType::InScene)]);
+    ensure!(kinds(&events) == [(1, Disengaged)], "disengaged: {events:?}");
+    let events = lifecycle.apply_user_states(300, &[state(1, UserStateType::Active)]);
+    ensure!(kinds(&events) == [(1, Engaged)], "engaged again: {events:?}");
+    info!("SUCCESS: skeletons and engagement change.");
+
+    // [3]
+    let events = lifecycle.apply_users(400, &[user(1, 15.0), user(2, 20.0), user(3, 30.0)]);
+    ensure!(events.is_empty(), "{events:?}");
+    let events = lifecycle.user_lost(1);
+    ensure!(kinds(&events) == [(1, SkeletonLost), (1, Disengaged), (1, Left)], "lost user winds down: {events:?}");
+    ensure!(events.iter().all(|e| e.timestamp == 400 && e.user == Some(user(1, 15.0))), "latest timestamp and last user data");
+    ensure!(lifecycle.user_lost(1).is_empty(), "lost once");
+    let events = lifecycle.apply_users(500, &[user(3, 30.0)]);
+    ensure!(kinds(&events) == [(2, Left)], "missing from the user frame: {events:?}");
+    let events = lifecycle.apply_user_states(600, &[state(3, UserStateType::IsAbsent)]);
+    ensure!(kinds(&events) == [(3, Left)] && lifecycle.users().count() == 0, "absent: {events:?}");
+    let mut skeletons_only = UserLifecycle::new();
+    skeletons_only.set_skeletons_only(true);
+    skeletons_only.apply_skeletons(700, &[skeleton(4, 0.5)]);
+    let events = skeletons_only.apply_skeletons(800, &[]);
+    ensure!(kinds(&events) == [(4, SkeletonLost), (4, Left)] && skeletons_only.users().count() == 0, "skeleton-only: {events:?}");
+    info!("SUCCESS: users leave.");
+
+    // [4]
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_users(2)?.with_resolution(160, 120).realtime(false))
+        .with_device_config(DeviceConfig::new(
+            DeviceSelector::ByIndex(0),
+            vec![ModuleType::UserTracker, ModuleType::SkeletonTracker, ModuleType::GestureRecognizer],
+        ))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let mut events = FrameIter::new(UserEventStream::for_device(device.as_async())?);
+    session.start_processing()?;
+    let mut received = Vec::new();
+    for _ in 0..30 {
+        session.update()?;
+        while let Some(event) = events.try_next() {
+            received.push(event?);
+        }
+    }
+    session.close()?;
+    for user_id in [1, 2] {
+        let own: Vec<UserEventKind> = received.iter().filter(|e| e.user_id == user_id).map(|e| e.kind).collect();
+        ensure!(own == [Appeared, Engaged, SkeletonAcquired], "user {user_id}: {own:?}");
+    }
+    let acquired = received.iter().filter(|e| e.kind == SkeletonAcquired);
+    ensure!(acquired.clone().all(|e| e.user.is_some_and(|u| u.id == e.user_id)), "user data from the user frame");
+    info!(events = received.len(), "SUCCESS: live users.");
+
+    info!("--- User events test passed ---");
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    async_api::depth_sensor::Vector3,
+    backend::simulated::SimulatedBackend,
+    blocking_api::session::BlockingSessionBuilder,
+    shared_types::{
+        rgb_frame::{Color3, OwnedRGBFrame},
+        session_config::{DeviceConfig, DeviceSelector, ModuleType},
+        user_frame::{OwnedUserFrame, UserFrame},
+        user_mask::{pixel_counts, Pixel, PixelRect, UserMask},
+    },
+};
+use tracing::{info, Level};
+
+const ORIGIN: Vector3 = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
+
+/// An 8x8 label map: user 1 is a 3x3 square at rows 1..=3, cols 1..=3; user 2 is a
+/// single pixel at (6, 6).
+fn labels() -> OwnedUserFrame {
+    let mut data = vec![0u16; 64];
+    for row in 1..=3 {
+        for col in 1..=3 {
+            data[row * 8 + col] = 1;
+        }
+    }
+    data[6 * 8 + 6] = 2;
+    OwnedUserFrame { rows: 8, cols: 8, timestamp: 7, data, users: Vec::new(), floor: ORIGIN, floor_normal: ORIGIN }
+}
+
+/// This test validates the user mask utilities.
+/// 1. Masks and pixel counts per user; data must match the dimensions.
+/// 2. Pixel rectangles and normalized bounding boxes.
+/// 3. Contours of each region.
+/// 4. Dilation and erosion.
+/// 5. Cut-outs from a color frame at another resolution.
+/// 6. Masks of live user frames agree with the pixel counts.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: User Mask ---");
+
+    // [1]
+    let frame = UserFrame::from(labels());
+    let counts = frame.pixel_counts()?;
+    ensure!(counts.len() == 2 && counts[&1] == 9 && counts[&2] == 1, "pixel counts {counts:?}");
+    let user_one = frame.user_mask(1)?;
+    let foreground = frame.foreground_mask()?;
+    ensure!(user_one.count() == 9 && foreground.count() == 10, "mask sizes");
+    ensure!(user_one.get(2, 2) && !user_one.get(6, 6) && foreground.get(6, 6), "mask membership");
+    ensure!(frame.user_mask(3)?.is_empty(), "unknown user has an empty mask");
+    ensure!(foreground.inverted().count() == 54, "inverted mask");
+    ensure!(foreground.rows() == 8 && foreground.cols() == 8 && foreground.data().len() == 64, "mask dimensions");
+    ensure!(UserMask::new(2, 2, vec![true; 3]).is_err() && UserMask::new(-1, -4, vec![true; 4]).is_err(), "mismatched data");
+    info!("SUCCESS: masks and counts.");
+
+    // [2]
+    ensure!(user_one.pixel_rect() == Some(PixelRect { top: 1, bottom: 3, left: 1, right: 3 }), "user 1 rectangle");
+    ensure!(foreground.pixel_rect().map(|r| (r.width(), r.height())) == Some((6, 6)), "foreground rectangle");
+    let bbox = user_one.bounding_box().expect("non-empty mask");
+    ensure!((bbox.left, bbox.right, bbox.top, bbox.bottom) == (0.125, 0.5, 0.125, 0.5), "normalized box {bbox:?}");
+    ensure!(frame.user_mask(3)?.bounding_box().is_none(), "empty mask has no box");
+    info!("SUCCESS: rectangles and boxes.");
+
+    // [3]
+    let contours = foreground.contours();
+    ensure!(contours.len() == 2, "{} contours", contours.len());
+    let square: Vec<Pixel> = [(1, 1), (1, 2), (1, 3), (2, 3), (3, 3), (3, 2), (3, 1), (2, 1)]
+        .into_iter()
+        .map(|(row, col)| Pixel { row, col })
+        .collect();
+    ensure!(contours[0] == square, "square contour {:?}", contours[0]);
+    ensure!(contours[1] == [Pixel { row: 6, col: 6 }], "single-pixel contour {:?}", contours[1]);
+    info!("SUCCESS: contours traced.");
+
+    // [4]
+    let grown = frame.user_mask(2)?.dilate(1);
+    ensure!(grown.count() == 9 && grown.pixel_rect() == Some(PixelRect { top: 5, bottom: 7, left: 5, right: 7 }), "dilated pixel");
+    let shrunk = user_one.erode(1);
+    ensure!(shrunk.count() == 1 && shrunk.get(2, 2), "eroded square keeps its center");
+    ensure!(shrunk.dilate(1) == user_one, "opening a square restores it");
+    ensure!(user_one.dilate(0) == user_one, "zero radius is the identity");
+    info!("SUCCESS: dilation and erosion.");
+
+    // [5]
+    let color = OwnedRGBFrame {
+        rows: 16,
+        cols: 16,
+        id: 3,
+        timestamp: 7,
+        data: vec![Color3 { blue: 10, green: 20, red: 30 }; 256],
+    };
+    let background = Color3 { blue: 0, green: 255, red: 0 };
+    let cut = user_one.cut_out(&color, background)?;
+    ensure!((cut.rows, cut.cols, cut.id) == (16, 16, 3), "cut-out keeps the color resolution");
+    ensure!(cut.data.iter().filter(|&&c| c != background).count() == 9 * 4, "each mask pixel covers 2x2 color pixels");
+    ensure!(cut.data[4 * 16 + 4] == color.data[0] && cut.data[0] == background, "foreground copied, background filled");
+    ensure!(user_one.alpha().iter().filter(|&&a| a == 255).count() == 9, "alpha channel");
+    info!("SUCCESS: cut-out composited.");
+
+    // [6]
+    let mut session = BlockingSessionBuilder::new()
+        .with_backend(SimulatedBackend::new().with_users(2)?.with_resolution(160, 120).realtime(false))
+        .with_device_config(DeviceConfig::new(DeviceSelector::ByIndex(0), vec![ModuleType::UserTracker]))
+        .init_session()?;
+    let mut device = session.devices().next().expect("one active device");
+    let mut user_frames = device.user_tracker().expect("user tracker").user_frames()?;
+    session.start_processing()?;
+    session.update()?;
+    let live = user_frames.try_next().expect("user frame after update")?;
+    let counts = pixel_counts(&live)?;
+    ensure!(!counts.is_empty(), "simulated users are segmented");
+    ensure!(live.foreground_mask()?.count() == counts.values().sum::<usize>(), "foreground equals all users");
+    for (&id, &count) in &counts {
+        let mask = UserMask::from_frame(&live, Some(id))?;
+        ensure!(mask.count() == count, "user {id} mask size");
+        ensure!(!mask.contours().is_empty(), "user {id} has a contour");
+    }
+    session.close()?;
+    info!(?counts, "SUCCESS: live masks agree with the counts.");
+
+    info!("--- User mask test passed ---");
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anyhow::{ensure, Result};
+use nuitrack_rs::nuitrack::{
+    backend::simulated::SimulatedBackend,
+    blocking_api::session::BlockingSessionBuilder,
+    shared_types::session_config::{
+        DeviceConfig, DeviceSelector, ModuleType, VideoMode, VideoModeRequest, VideoStream,
+    },
+};
+use tracing::{info, Level};
+
+fn mode(width: i32, height: i32, fps: i32) -> VideoMode {
+    VideoMode { width, height, fps }
+}
+
+/// This test validates video mode enumeration and selection.
+/// 1. `VideoModeRequest` resolves exact, wildcard and best-match requests.
+/// 2. Discovered devices list their depth and color modes.
+/// 3. A satisfiable request is recorded on the active device and matches the sensor's output mode.
+/// 4. An exact request the device cannot meet fails session creation.
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_target(false)
+        .init();
+
+    info!("--- Test: Video Modes ---");
+
+    // [1]
+    let available = [mode(320, 240, 30), mode(640, 480, 30), mode(640, 480, 60), mode(1280, 720, 30)];
+    let resolve = |request: VideoModeRequest| request.resolve(&available);
+    ensure!(resolve(VideoModeRequest::Exact(mode(640, 480, 60))) == Some(mode(640, 480, 60)), "exact match");
+    ensure!(resolve(VideoModeRequest::Exact(mode(800, 600, 30))).is_none(), "exact request without a match");
+    ensure!(resolve(VideoModeRequest::Exact(mode(640, 480, 0))) == Some(mode(640, 480, 60)), "wildcard FPS prefers higher FPS");
+    ensure!(resolve(VideoModeRequest::Exact(mode(0, 0, 30))) == Some(mode(1280, 720, 30)), "wildcard size prefers larger size");
+    ensure!(resolve(VideoModeRequest::BestMatch(mode(1280, 800, 30))) == Some(mode(1280, 720, 30)), "closest resolution");
+    ensure!(resolve(VideoModeRequest::BestMatch(mode(600, 450, 50))) == Some(mode(640, 480, 60)), "closest FPS among equal sizes");
+    ensure!(VideoModeRequest::BestMatch(mode(640, 480, 30)).resolve(&[]).is_none(), "no modes available");
+    info!("SUCCESS: requests resolve as documented.");
+
+    // [2]
+    let backend = || SimulatedBackend::new().with_resolution(320, 240).with_fps(15).realtime(false);
+    let discovery = BlockingSessionBuilder::new().with_backend(backend()).discover_devices_first()?;
+    let device = &discovery.list_devices()[0];
+    ensure!(device.video_modes(VideoStream::Depth) == [mode(320, 240, 15)], "depth modes: {:?}", device.depth_modes);
+    ensure!(device.video_modes(VideoStream::Color) == [mode(320, 240, 15)], "color modes: {:?}", device.color_modes);
+    info!(depth = ?device.depth_modes, color = ?device.color_modes, "SUCCESS: modes listed at discovery.");
+
+    // [3]
+    let mut session = discovery.finalize_session(vec![
+        DeviceConfig::new(DeviceSelector::ByIndex(0), vec![ModuleType::DepthSensor])
+            .with_depth_mode(VideoModeRequest::BestMatch(mode(640, 480, 30))),
+    ])?;
+    let mut device = session.devices().next().expect("one active device");
+    ensure!(device.as_async().video_mode(VideoStream::Depth) == Some(mode(320, 240, 15)), "selected depth mode");
+    ensure!(device.as_async().video_mode(VideoStream::Color).is_none(), "no color mode was requested");
+    let output = device.depth_sensor().expect("depth sensor").output_mode()?;
+    ensure!((output.xres, output.yres, output.fps) == (320, 240, 15), "output mode {:?}", output);
+    session.close()?;
+    info!("SUCCESS: best-match request applied.");
+
+    // [4]
+    let result = BlockingSessionBuilder::new()
+        .with_backend(backend())
+        .with_device_config(
+            DeviceConfig::new(DeviceSelector::ByIndex(0), vec![ModuleType::ColorSensor])
+                .with_color_mode(VideoModeRequest::Exact(mode(1920, 1080, 30))),
+        )
+        .init_session();
+    match result {
+        Err(e) => info!(error = %e, "SUCCESS: unsatisfiable exact request rejected."),
+        Ok(_) => anyhow::bail!("an unsatisfiable exact request should fail"),
+    }
+
+    info!("--- Video modes test passed ---");
+    Ok(())
+}